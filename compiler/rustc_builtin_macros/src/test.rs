@@ -7,6 +7,7 @@
 use rustc_ast_pretty::pprust;
 use rustc_errors::Applicability;
 use rustc_expand::base::*;
+use rustc_session::Session;
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::{ErrorGuaranteed, FileNameDisplayPreference, Span};
 use std::iter;
@@ -363,7 +364,12 @@ pub fn expand_test_or_bench(
     });
 
     // extern crate test
-    let test_extern = cx.item(sp, test_id, ast::AttrVec::new(), ast::ItemKind::ExternCrate(None));
+    let test_extern = cx.item(
+        sp,
+        test_id,
+        ast::AttrVec::new(),
+        ast::ItemKind::ExternCrate(test_crate_rename(cx.sess)),
+    );
 
     debug!("synthetic test item:\n{}\n", pprust::item_to_string(&test_const));
 
@@ -388,6 +394,14 @@ pub fn expand_test_or_bench(
     }
 }
 
+/// Returns the name of the crate providing the test harness, if it isn't `test`.
+///
+/// With `-Z semihosting-tests` the `no_std` harness in `test_semihosting` is used instead, and it
+/// is imported as `test` so that the rest of the expansion is the same.
+pub(crate) fn test_crate_rename(sess: &Session) -> Option<Symbol> {
+    sess.opts.unstable_opts.semihosting_tests.then_some(sym::test_semihosting)
+}
+
 fn not_testable_error(cx: &ExtCtxt<'_>, attr_sp: Span, item: Option<&ast::Item>) {
     let diag = &cx.sess.parse_sess.span_diagnostic;
     let msg = "the `#[test]` attribute may only be used on a non-associated function";
@@ -22,6 +22,7 @@
 use std::{iter, mem};
 
 use crate::errors;
+use crate::test::test_crate_rename;
 
 #[derive(Clone)]
 struct Test {
@@ -316,7 +317,12 @@ fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
     // extern crate test
     let test_extern_stmt = ecx.stmt_item(
         sp,
-        ecx.item(sp, test_id, ast::AttrVec::new(), ast::ItemKind::ExternCrate(None)),
+        ecx.item(
+            sp,
+            test_id,
+            ast::AttrVec::new(),
+            ast::ItemKind::ExternCrate(test_crate_rename(ecx.sess)),
+        ),
     );
 
     // #[rustc_main]
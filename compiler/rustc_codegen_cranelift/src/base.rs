@@ -323,7 +323,11 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
                 crate::abi::codegen_return(fx);
             }
             TerminatorKind::Assert { cond, expected, msg, target, unwind: _ } => {
-                if !fx.tcx.sess.overflow_checks() && msg.is_optional_overflow_check() {
+                let check_overflow = fx
+                    .tcx
+                    .codegen_fn_attrs(fx.instance.def_id())
+                    .keeps_overflow_checks(fx.tcx.sess.overflow_checks());
+                if !check_overflow && msg.is_optional_overflow_check() {
                     let target = fx.get_block(*target);
                     fx.bcx.ins().jump(target, &[]);
                     continue;
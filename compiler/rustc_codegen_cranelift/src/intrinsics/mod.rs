@@ -659,6 +659,7 @@ fn codegen_regular_intrinsic_call<'tcx>(
 
         sym::pref_align_of
         | sym::needs_drop
+        | sym::debug_assertions
         | sym::type_id
         | sym::type_name
         | sym::variant_count => {
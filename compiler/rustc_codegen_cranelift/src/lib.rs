@@ -42,6 +42,7 @@
 use rustc_middle::dep_graph::{WorkProduct, WorkProductId};
 use rustc_session::config::OutputFilenames;
 use rustc_session::Session;
+use rustc_target::spec::StackLimit;
 use rustc_span::Symbol;
 
 pub use crate::config::*;
@@ -179,6 +180,9 @@ fn init(&self, sess: &Session) {
             Lto::No | Lto::ThinLocal => {}
             Lto::Thin | Lto::Fat => sess.warn("LTO is not supported. You may get a linker error."),
         }
+        if *sess.stack_limit() != StackLimit::None {
+            sess.fatal("stack limit not supported by this backend");
+        }
 
         let mut config = self.config.borrow_mut();
         if config.is_none() {
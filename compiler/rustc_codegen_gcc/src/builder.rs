@@ -48,7 +48,7 @@
     TargetDataLayout,
     WrappingRange,
 };
-use rustc_target::spec::{HasTargetSpec, Target};
+use rustc_target::spec::{HasTargetSpec, StackLimit, Target};
 
 use crate::common::{SignType, TypeReflection, type_is_pointer};
 use crate::context::CodegenCx;
@@ -1425,6 +1425,11 @@ fn apply_attrs_to_cleanup_callsite(&mut self, _llret: RValue<'gcc>) {
         // FIXME(bjorn3): implement
     }
 
+    fn check_stack_limit(&mut self, _limit: &StackLimit) {
+        // Stack limits are rejected when the backend is initialized.
+        unreachable!("stack limit checks are not supported by the GCC backend");
+    }
+
     fn set_span(&mut self, _span: Span) {}
 
     fn from_immediate(&mut self, val: Self::Value) -> Self::Value {
@@ -88,7 +88,7 @@
 use gccjit::TargetInfo;
 #[cfg(not(feature="master"))]
 use gccjit::CType;
-use errors::LTONotSupported;
+use errors::{LTONotSupported, StackLimitNotSupported};
 use rustc_ast::expand::allocator::AllocatorKind;
 use rustc_codegen_ssa::{CodegenResults, CompiledModule, ModuleCodegen};
 use rustc_codegen_ssa::base::codegen_crate;
@@ -106,6 +106,7 @@
 use rustc_middle::ty::TyCtxt;
 use rustc_session::config::{Lto, OptLevel, OutputFilenames};
 use rustc_session::Session;
+use rustc_target::spec::StackLimit;
 use rustc_span::Symbol;
 use rustc_span::fatal_error::FatalError;
 use tempfile::TempDir;
@@ -192,6 +193,9 @@ fn init(&self, sess: &Session) {
         if sess.lto() == Lto::Thin {
             sess.emit_warning(LTONotSupported {});
         }
+        if *sess.stack_limit() != StackLimit::None {
+            sess.emit_fatal(StackLimitNotSupported);
+        }
 
         #[cfg(not(feature="master"))]
         {
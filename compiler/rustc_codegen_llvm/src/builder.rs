@@ -24,7 +24,7 @@
 use rustc_span::Span;
 use rustc_symbol_mangling::typeid::{kcfi_typeid_for_fnabi, typeid_for_fnabi, TypeIdOptions};
 use rustc_target::abi::{self, call::FnAbi, Align, Size, WrappingRange};
-use rustc_target::spec::{HasTargetSpec, SanitizerSet, Target};
+use rustc_target::spec::{HasTargetSpec, SanitizerSet, StackLimit, Target};
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::ffi::CStr;
@@ -1237,6 +1237,49 @@ fn apply_attrs_to_cleanup_callsite(&mut self, llret: &'ll Value) {
             attributes::apply_to_callsite(llret, llvm::AttributePlace::Function, &[cold_inline]);
         }
     }
+
+    fn check_stack_limit(&mut self, limit: &StackLimit) {
+        let isize_ty = self.type_isize();
+        let stack_pointer = self.call_intrinsic("llvm.stacksave", &[]);
+        let stack_pointer = self.ptrtoint(stack_pointer, isize_ty);
+        let limit = match limit {
+            StackLimit::None => return,
+            // The address of the symbol is the limit, it is usually defined by the linker script.
+            StackLimit::Symbol(name) => {
+                let limit = self.declare_global(name, self.type_i8());
+                self.ptrtoint(limit, isize_ty)
+            }
+            StackLimit::Register(name) => {
+                let name = unsafe {
+                    let len = name.len() as c_uint;
+                    let name = llvm::LLVMMDStringInContext(self.llcx, name.as_ptr().cast(), len);
+                    llvm::LLVMMDNodeInContext(self.llcx, &name, 1)
+                };
+                let bits = self.tcx.data_layout.pointer_size.bits();
+                self.call_intrinsic(&format!("llvm.read_register.i{bits}"), &[name])
+            }
+        };
+        let reserve = self.const_usize(StackLimit::PROLOGUE_RESERVE);
+        let limit = self.add(limit, reserve);
+        let exceeded = self.icmp(IntPredicate::IntULT, stack_pointer, limit);
+        let exceeded = self.expect(exceeded, false);
+
+        let overflow_llbb = self.append_sibling_block("stack_overflow");
+        let start_llbb = self.append_sibling_block("stack_ok");
+        self.cond_br(exceeded, overflow_llbb, start_llbb);
+
+        self.switch_to_block(overflow_llbb);
+        let handler_ty = self.type_func(&[], self.type_void());
+        let handler = self.get_declared_value(StackLimit::HANDLER_SYMBOL).unwrap_or_else(|| {
+            self.declare_cfn(StackLimit::HANDLER_SYMBOL, llvm::UnnamedAddr::No, handler_ty)
+        });
+        let call = self.call(handler_ty, None, None, handler, &[], None);
+        let cold = llvm::AttributeKind::Cold.create_attr(self.llcx);
+        attributes::apply_to_callsite(call, llvm::AttributePlace::Function, &[cold]);
+        self.unreachable();
+
+        self.switch_to_block(start_llbb);
+    }
 }
 
 impl<'ll> StaticBuilderMethods for Builder<'_, 'll, '_> {
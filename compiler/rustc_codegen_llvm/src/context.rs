@@ -929,6 +929,10 @@ macro_rules! mk_struct {
 
         ifn!("llvm.ptrmask", fn(ptr, t_isize) -> ptr);
 
+        ifn!("llvm.stacksave", fn() -> ptr);
+        ifn!("llvm.read_register.i32", fn(t_metadata) -> t_i32);
+        ifn!("llvm.read_register.i64", fn(t_metadata) -> t_i64);
+
         None
     }
 
@@ -157,6 +157,15 @@ codegen_ssa_link_script_write_failure = failed to write link script to {$path}:
 
 codegen_ssa_linker_file_stem = couldn't extract file stem from specified linker
 
+codegen_ssa_linker_map_baseline_failure = failed to read the baseline linker map report `{$path}`: {$error}
+
+codegen_ssa_linker_map_missing = the linker did not write the map file `{$path}` requested by `-Z linker-map-report`
+    .note = map files are only requested from GNU ld, gold and LLD
+
+codegen_ssa_linker_map_read_failure = failed to read the linker map file `{$path}`: {$error}
+
+codegen_ssa_linker_map_report_failure = failed to write the linker map report `{$path}`: {$error}
+
 codegen_ssa_linker_not_found = linker `{$linker_path}` not found
     .note = {$error}
 
@@ -190,6 +199,21 @@ codegen_ssa_no_module_named =
 
 codegen_ssa_no_natvis_directory = error enumerating natvis directory: {$error}
 
+codegen_ssa_no_panic_path = reached through {$path}
+
+codegen_ssa_no_panic_read_object = failed to read object file `{$path}` to check `#[no_panic]` functions: {$error}
+
+codegen_ssa_no_panic_site = the operation at {$location} may panic
+
+codegen_ssa_no_panic_unavailable_code = cannot check whether `{$fn_path}`, marked `#[no_panic]`, can panic
+    .defined_at = `{$fn_path}` is defined at {$location}
+    .note = the generated code for this function can reach `{$callee}`, whose code is not available
+    .help = provide the object code of all the dependencies, which is not available when they are dylibs or when only their metadata is provided
+
+codegen_ssa_no_panic_violated = `{$fn_path}`, marked `#[no_panic]`, can panic after optimization
+    .defined_at = `{$fn_path}` is defined at {$location}
+    .note = the generated code for this function can reach `{$panic_fn}`, the `{$lang_item}` lang item
+
 codegen_ssa_processing_dymutil_failed = processing debug info with `dsymutil` failed: {$status}
     .note = {$output}
 
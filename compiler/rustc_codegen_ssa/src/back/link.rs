@@ -29,7 +29,9 @@
 use super::archive::{ArchiveBuilder, ArchiveBuilderBuilder};
 use super::command::Command;
 use super::linker::{self, Linker};
+use super::linker_map;
 use super::metadata::{create_wrapper_file, MetadataPosition};
+use super::no_panic;
 use super::rpath::{self, RPathConfig};
 use crate::{
     errors, looks_like_rust_object_file, CodegenResults, CompiledModule, CrateInfo, NativeLib,
@@ -67,6 +69,9 @@ pub fn link_binary<'a>(
     outputs: &OutputFilenames,
 ) -> Result<(), ErrorGuaranteed> {
     let _timer = sess.timer("link_binary");
+    sess.time("link_binary_check_no_panic_fns", || {
+        no_panic::check_no_panic_fns(sess, codegen_results)
+    })?;
     let output_metadata = sess.opts.output_types.contains_key(&OutputType::Metadata);
     let mut tempfiles_for_stdout_output: Vec<PathBuf> = Vec::new();
     for &crate_type in &codegen_results.crate_info.crate_types {
@@ -986,6 +991,12 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
         }
     }
 
+    if let Some(report_path) = &sess.opts.unstable_opts.linker_map_report {
+        sess.time("link_binary_write_linker_map_report", || {
+            linker_map::write_report(sess, out_filename, report_path)
+        });
+    }
+
     match sess.split_debuginfo() {
         // If split debug information is disabled or located in individual files
         // there's nothing to do here.
@@ -2330,6 +2341,10 @@ fn add_order_independent_options(
 
     cmd.output_filename(out_filename);
 
+    if sess.opts.unstable_opts.linker_map_report.is_some() {
+        cmd.map_file(&linker_map::map_path(out_filename));
+    }
+
     if crate_type == CrateType::Executable && sess.target.is_like_windows {
         if let Some(ref s) = codegen_results.crate_info.windows_subsystem {
             cmd.subsystem(s);
@@ -194,6 +194,7 @@ pub trait Linker {
     fn add_eh_frame_header(&mut self) {}
     fn add_no_exec(&mut self) {}
     fn add_as_needed(&mut self) {}
+    fn map_file(&mut self, _path: &Path) {}
     fn reset_per_library_state(&mut self) {}
 }
 
@@ -782,6 +783,17 @@ fn add_as_needed(&mut self) {
             self.linker_args(&["-z", "ignore"]);
         }
     }
+
+    fn map_file(&mut self, path: &Path) {
+        // The map files of other linkers are in a format `linker_map` doesn't read.
+        if self.is_gnu {
+            let mut arg = OsString::from("-Map=");
+            arg.push(path);
+            // The symbols are demangled by `linker_map` itself, which needs the mangled names of
+            // the generic instances to tell them apart.
+            self.linker_args(&[arg, OsString::from("--no-demangle")]);
+        }
+    }
 }
 
 pub struct MsvcLinker<'a> {
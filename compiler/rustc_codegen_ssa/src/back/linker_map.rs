@@ -0,0 +1,688 @@
+//! Report of the size of the code and data linked in the output, written by
+//! `-Zlinker-map-report`.
+//!
+//! The linker is asked for a map file, which lists every input section placed in the output along
+//! with its address, its size and the object file it comes from. Rust code is emitted with one
+//! section per function and per static, so the size of each input section is attributed to the
+//! symbols it defines, or to the symbol its name was derived from. The demangled names of these
+//! symbols are then used to add up the sizes per crate, module and generic item. The object files
+//! are only used to find the crate of the symbols which can't be demangled, as with LTO all the
+//! code is in the object files of the final crate.
+//!
+//! Both the map files of GNU ld and gold, and the ones of LLD are understood.
+
+use crate::errors;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_session::Session;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Returns the path of the map file written by the linker next to `out_filename`.
+pub fn map_path(out_filename: &Path) -> PathBuf {
+    let mut path = OsString::from(out_filename);
+    path.push(".map");
+    PathBuf::from(path)
+}
+
+/// Reads the map file written by the linker for `out_filename` and writes the report to `path`.
+pub fn write_report(sess: &Session, out_filename: &Path, path: &Path) {
+    let map_path = map_path(out_filename);
+    let map = match fs::read(&map_path) {
+        Ok(map) => map,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            sess.emit_warning(errors::LinkerMapMissing { path: map_path });
+            return;
+        }
+        Err(error) => {
+            sess.emit_err(errors::LinkerMapReadFailure { path: map_path, error });
+            return;
+        }
+    };
+    let map = parse_map(&String::from_utf8_lossy(&map));
+    let mut report = Report::new(out_filename, &map);
+
+    if let Some(baseline_path) = &sess.opts.unstable_opts.linker_map_baseline {
+        let baseline =
+            fs::read(baseline_path).map_err(|error| error.to_string()).and_then(|baseline| {
+                serde_json::from_slice(&baseline).map_err(|error| error.to_string())
+            });
+        match baseline {
+            Ok(baseline) => report.diff = Some(Diff::new(baseline_path, &baseline, &report)),
+            Err(error) => {
+                let path = baseline_path.clone();
+                sess.emit_err(errors::LinkerMapBaselineFailure { path, error });
+            }
+        }
+    }
+
+    let result = File::create(path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, &report)?;
+        writer.flush()
+    });
+    if let Err(error) = result {
+        sess.emit_err(errors::LinkerMapReportFailure { path, error });
+    }
+}
+
+/// The contents of a map file that matter for the report.
+#[derive(Debug, Default, PartialEq)]
+struct LinkerMap {
+    /// The name and size of each output section.
+    output_sections: Vec<(String, u64)>,
+    input_sections: Vec<InputSection>,
+}
+
+#[derive(Debug, PartialEq)]
+struct InputSection {
+    output_section: String,
+    name: String,
+    /// The object file the section comes from, as `archive(member)` for archive members.
+    file: String,
+    address: u64,
+    size: u64,
+    /// The address and name of the symbols defined in the section, as listed by the linker.
+    symbols: Vec<(u64, String)>,
+}
+
+fn parse_map(map: &str) -> LinkerMap {
+    let mut lines = map.lines();
+    for line in lines.by_ref() {
+        if line.trim_start().starts_with("VMA ") {
+            return parse_lld_map(line, lines);
+        }
+        if line.starts_with("Linker script and memory map") || line.starts_with("Memory map") {
+            return parse_gnu_map(lines);
+        }
+    }
+    LinkerMap::default()
+}
+
+fn parse_hex(s: &str) -> Option<u64> {
+    u64::from_str_radix(s.strip_prefix("0x")?, 16).ok()
+}
+
+/// Parses the memory map of GNU ld and gold, where sections are listed as follows, the name of a
+/// section being on its own line when it is too long:
+///
+/// ```text
+/// .text           0x0000000000001000      0x4c2
+///  .text._ZN4main4main17h0123456789abcdefE
+///                 0x0000000000001000       0x2a /tmp/main.main.a1b2c3-cgu.0.rcgu.o
+///                 0x0000000000001000                _ZN4main4main17h0123456789abcdefE
+/// ```
+fn parse_gnu_map<'a>(lines: impl Iterator<Item = &'a str>) -> LinkerMap {
+    let mut map = LinkerMap::default();
+    // The name of the section being listed, when it was on its own line.
+    let mut pending_output = None;
+    let mut pending_input = None;
+    let mut output_section = String::new();
+
+    for line in lines {
+        let indent = line.len() - line.trim_start().len();
+        let mut tokens = line.split_whitespace();
+        let Some(first) = tokens.next() else { continue };
+
+        if indent == 0 {
+            pending_input = None;
+            if !first.starts_with('.') {
+                pending_output = None;
+                continue;
+            }
+            match (tokens.next().and_then(parse_hex), tokens.next().and_then(parse_hex)) {
+                (Some(_), Some(size)) => {
+                    output_section = first.to_string();
+                    map.output_sections.push((output_section.clone(), size));
+                    pending_output = None;
+                }
+                _ => pending_output = Some(first),
+            }
+            continue;
+        }
+
+        if indent == 1 {
+            pending_output = None;
+            pending_input = None;
+            // Skip the input section patterns of the linker script and the padding.
+            if first.starts_with('*') {
+                continue;
+            }
+            let fields = line.trim_start()[first.len()..].trim_start();
+            if fields.is_empty() {
+                pending_input = Some(first);
+            } else {
+                push_gnu_input(&mut map, &output_section, first, fields);
+            }
+            continue;
+        }
+
+        let second = tokens.next();
+        let third = tokens.next();
+        if let Some(name) = pending_output.take() {
+            if let (Some(_), Some(size)) = (parse_hex(first), second.and_then(parse_hex)) {
+                output_section = name.to_string();
+                map.output_sections.push((output_section.clone(), size));
+            }
+        } else if let Some(name) = pending_input.take() {
+            push_gnu_input(&mut map, &output_section, name, line.trim_start());
+        } else if let (Some(address), Some(symbol), None) = (parse_hex(first), second, third) {
+            // A symbol defined in the last input section.
+            if let Some(section) = map.input_sections.last_mut()
+                && section.output_section == output_section
+                && parse_hex(symbol).is_none()
+            {
+                section.symbols.push((address, symbol.to_string()));
+            }
+        }
+    }
+    map
+}
+
+/// Adds the input section `name`, whose address, size and file are listed in `fields`.
+fn push_gnu_input(map: &mut LinkerMap, output_section: &str, name: &str, fields: &str) {
+    let Some((address, fields)) = fields.split_once(char::is_whitespace) else { return };
+    let fields = fields.trim_start();
+    let (size, file) = fields.split_once(char::is_whitespace).unwrap_or((fields, ""));
+    let (Some(address), Some(size)) = (parse_hex(address), parse_hex(size)) else { return };
+    let file = file.trim();
+    if size == 0 || file.is_empty() {
+        return;
+    }
+    map.input_sections.push(InputSection {
+        output_section: output_section.to_string(),
+        name: name.to_string(),
+        file: file.to_string(),
+        address,
+        size,
+        symbols: Vec::new(),
+    });
+}
+
+/// Parses the map of LLD, where the columns of the numbers are given by `header` and the
+/// indentation of the last column tells output sections, input sections and symbols apart:
+///
+/// ```text
+///              VMA              LMA     Size Align Out     In      Symbol
+///             1000             1000      4c2    16 .text
+///             1000             1000       2a    16         main.o:(.text.main)
+///             1000             1000        0     1                 main
+/// ```
+fn parse_lld_map<'a>(header: &str, lines: impl Iterator<Item = &'a str>) -> LinkerMap {
+    let mut map = LinkerMap::default();
+    let columns: Vec<&str> = header.split_whitespace().collect();
+    let (
+        Some(numbers),
+        Some(address_column),
+        Some(size_column),
+        Some(in_start),
+        Some(symbol_start),
+    ) = (
+        columns.iter().position(|&c| c == "Out"),
+        columns.iter().position(|&c| c == "VMA"),
+        columns.iter().position(|&c| c == "Size"),
+        header.find(" In ").map(|i| i + 1),
+        header.find(" Symbol").map(|i| i + 1),
+    )
+    else {
+        return map;
+    };
+    let mut output_section = String::new();
+
+    for line in lines {
+        let mut rest = line;
+        let mut values = Vec::with_capacity(numbers);
+        for _ in 0..numbers {
+            rest = rest.trim_start();
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            values.push(u64::from_str_radix(&rest[..end], 16).ok());
+            rest = &rest[end..];
+        }
+        let (Some(address), Some(size)) = (values[address_column], values[size_column]) else {
+            continue;
+        };
+        let column = line.len() - rest.trim_start().len();
+        let text = rest.trim();
+        // Skip the assignments of the linker script.
+        if text.is_empty() || text.contains(" = ") {
+            continue;
+        }
+
+        if column < in_start {
+            output_section = text.to_string();
+            map.output_sections.push((output_section.clone(), size));
+        } else if column < symbol_start {
+            let Some((file, name)) = text.rsplit_once(":(") else { continue };
+            if size == 0 {
+                continue;
+            }
+            map.input_sections.push(InputSection {
+                output_section: output_section.clone(),
+                name: name.trim_end_matches(')').to_string(),
+                file: file.to_string(),
+                address,
+                size,
+                symbols: Vec::new(),
+            });
+        } else if let Some(section) = map.input_sections.last_mut()
+            && section.output_section == output_section
+        {
+            section.symbols.push((address, text.to_string()));
+        }
+    }
+    map
+}
+
+/// Whether the output section holds metadata which is not loaded, such as debuginfo.
+fn is_metadata_section(name: &str) -> bool {
+    name.starts_with(".debug")
+        || name.starts_with(".stab")
+        || name.ends_with(".attributes")
+        || matches!(name, ".comment" | ".symtab" | ".strtab" | ".shstrtab" | "/DISCARD/")
+}
+
+/// A piece of an input section attributed to a single symbol.
+struct Piece<'a> {
+    output_section: &'a str,
+    /// The crate which produced the object file of the piece.
+    crate_name: String,
+    symbol: Option<&'a str>,
+    /// The name of the input section, for the pieces which are not attributed to a symbol.
+    section: &'a str,
+    size: u64,
+}
+
+fn pieces(map: &LinkerMap) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    for section in &map.input_sections {
+        if is_metadata_section(&section.output_section) {
+            continue;
+        }
+        let crate_name = crate_of_file(&section.file);
+        let piece = |symbol, size| Piece {
+            output_section: &section.output_section,
+            crate_name: crate_name.clone(),
+            symbol,
+            section: &section.name,
+            size,
+        };
+
+        let end = section.address + section.size;
+        let mut symbols: Vec<_> = section
+            .symbols
+            .iter()
+            .filter(|&&(address, _)| address >= section.address && address < end)
+            .collect();
+        symbols.sort_by_key(|symbol| symbol.0);
+        symbols.dedup_by_key(|symbol| symbol.0);
+        if symbols.is_empty() {
+            // GNU ld only lists the global symbols, so derive the symbol of the other sections
+            // from their name, as in `.text._ZN4main4main17h0123456789abcdefE`.
+            let symbol = ["_ZN", "_R"].iter().find_map(|prefix| {
+                section.name.find(&format!(".{prefix}")).map(|start| &section.name[start + 1..])
+            });
+            pieces.push(piece(symbol, section.size));
+            continue;
+        }
+        for (i, &&(address, ref symbol)) in symbols.iter().enumerate() {
+            // The bytes preceding the first symbol, if any, belong to it.
+            let start = if i == 0 { section.address } else { address };
+            let next = symbols.get(i + 1).map_or(end, |&&(next, _)| next);
+            pieces.push(piece(Some(symbol.as_str()), next - start));
+        }
+    }
+    pieces
+}
+
+/// Returns the name of the crate which produced `file`. The object files of Rust code are named
+/// after their codegen unit, whose name starts with the crate, as in
+/// `libcore-<hash>.rlib(core-<hash>.core.a1b2c3-cgu.0.rcgu.o)`. Other object files are named
+/// after their archive, or after their own file name.
+fn crate_of_file(file: &str) -> String {
+    let (archive, member) = match file.strip_suffix(')').and_then(|f| f.split_once('(')) {
+        Some((archive, member)) => (Some(archive), member),
+        None => (None, file),
+    };
+    let file_name = |path: &str| -> String {
+        Path::new(path).file_name().map_or(path.into(), |name| name.to_string_lossy().into_owned())
+    };
+    // Strips the `-C metadata` hash appended by Cargo.
+    let without_hash = |name: &str| -> String {
+        match name.rsplit_once('-') {
+            Some((name, hash))
+                if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) =>
+            {
+                name.into()
+            }
+            _ => name.into(),
+        }
+    };
+
+    let member = file_name(member);
+    if member.ends_with(".rcgu.o") {
+        let components: Vec<_> = member.split('.').collect();
+        if let Some(cgu) = components.iter().position(|component| component.ends_with("-cgu"))
+            && cgu > 0
+        {
+            return components[cgu - 1].to_string();
+        }
+        return without_hash(components[0]);
+    }
+    match archive {
+        Some(archive) => {
+            let archive = file_name(archive);
+            let stem = archive.split('.').next().unwrap();
+            without_hash(stem.strip_prefix("lib").unwrap_or(stem))
+        }
+        None => member,
+    }
+}
+
+/// Removes the generic arguments from a demangled path, e.g. turns
+/// `<alloc::vec::Vec<u8> as core::clone::Clone>::clone` into
+/// `<alloc::vec::Vec as core::clone::Clone>::clone` and `core::mem::drop::<u8>` into
+/// `core::mem::drop`.
+fn strip_generic_args(path: &str) -> String {
+    let mut stripped = String::with_capacity(path.len());
+    let mut depth = 0;
+    let mut prev = ' ';
+    for c in path.chars() {
+        if depth > 0 {
+            match c {
+                '<' => depth += 1,
+                '>' if prev != '-' => depth -= 1,
+                _ => {}
+            }
+        } else if c == '<' && stripped.ends_with("::") {
+            stripped.truncate(stripped.len() - 2);
+            depth = 1;
+        } else if c == '<' && (prev.is_alphanumeric() || prev == '_') {
+            depth = 1;
+        } else {
+            stripped.push(c);
+        }
+        prev = c;
+    }
+    stripped
+}
+
+/// Splits a path on the `::` which are not nested in `<>`.
+fn split_path(path: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut prev = ' ';
+    for (i, c) in path.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' if prev != '-' => depth -= 1,
+            ':' if depth == 0 && path[i..].starts_with("::") => {
+                segments.push(&path[start..i]);
+                start = i + 2;
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    segments.push(&path[start..]);
+    segments.retain(|segment| !segment.is_empty());
+    segments
+}
+
+/// Returns the crate of an item path without generic arguments, which is the first segment of its
+/// module as returned by [`module_of`].
+fn crate_of_item(item: &str) -> Option<String> {
+    split_path(&module_of(item)?).first().map(|name| name.to_string())
+}
+
+/// Returns the module of an item path without generic arguments. The items of an impl are placed
+/// in the module of the self type, or in the one of the trait for primitive types, and closures
+/// and shims in the module of the item they belong to.
+fn module_of(item: &str) -> Option<String> {
+    let mut segments = split_path(item);
+    while segments.last().is_some_and(|segment| segment.starts_with('{')) {
+        segments.pop();
+    }
+    let first = *segments.first()?;
+    if let Some(qualified) = first.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+        let (self_ty, trait_) = match qualified.split_once(" as ") {
+            Some((self_ty, trait_)) => (self_ty, Some(trait_)),
+            None => (qualified, None),
+        };
+        let self_ty = self_ty.trim_start_matches(['&', '*']);
+        let self_ty = ["mut ", "const ", "dyn "]
+            .iter()
+            .fold(self_ty, |ty, prefix| ty.strip_prefix(prefix).unwrap_or(ty));
+        let parent = |path: &str| {
+            // Arrays, slices, tuples and the like have no module.
+            if !path.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+                return None;
+            }
+            let segments = split_path(path);
+            (segments.len() > 1).then(|| segments[..segments.len() - 1].join("::"))
+        };
+        return parent(self_ty).or_else(|| trait_.and_then(parent));
+    }
+    (segments.len() > 1).then(|| segments[..segments.len() - 1].join("::"))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Report {
+    output: PathBuf,
+    /// The total size of the sections loaded in memory.
+    size: u64,
+    sections: Vec<Entry>,
+    crates: Vec<Entry>,
+    modules: Vec<Entry>,
+    items: Vec<Item>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    diff: Option<Diff>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    name: String,
+    size: u64,
+    /// The size of the entry in each output section.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    sections: BTreeMap<String, u64>,
+}
+
+/// An item with all its monomorphizations, or a symbol which is not from Rust code.
+#[derive(Debug, Serialize, Deserialize)]
+struct Item {
+    name: String,
+    size: u64,
+    monomorphizations: usize,
+    instances: Vec<Instance>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Instance {
+    name: String,
+    /// The crate the instance was codegened in.
+    #[serde(rename = "crate")]
+    crate_name: String,
+    size: u64,
+}
+
+impl Report {
+    fn new(output: &Path, map: &LinkerMap) -> Report {
+        fn entries(sizes: FxHashMap<String, BTreeMap<String, u64>>) -> Vec<Entry> {
+            let mut entries: Vec<_> = sizes
+                .into_iter()
+                .map(|(name, sections)| Entry { name, size: sections.values().sum(), sections })
+                .collect();
+            entries.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+            entries
+        }
+
+        let mut crates: FxHashMap<String, BTreeMap<String, u64>> = FxHashMap::default();
+        let mut modules: FxHashMap<String, BTreeMap<String, u64>> = FxHashMap::default();
+        // The item, name and size of the instances, by symbol and crate. The legacy symbol names
+        // don't tell the monomorphizations of an item apart once demangled.
+        let mut instances: FxHashMap<(&str, String), (String, String, u64)> = FxHashMap::default();
+        for piece in pieces(map) {
+            let section = piece.output_section.to_string();
+            let demangled =
+                piece.symbol.and_then(|symbol| rustc_demangle::try_demangle(symbol).ok());
+            let is_rust = demangled.is_some();
+            let (item, instance) = match (demangled, piece.symbol) {
+                (Some(demangled), _) => {
+                    let instance = format!("{demangled:#}");
+                    (strip_generic_args(&instance), instance)
+                }
+                (None, Some(symbol)) => (symbol.to_string(), symbol.to_string()),
+                (None, None) => {
+                    // LLD names the pieces of the synthetic sections after their offset, as in
+                    // `.eh_frame+0x154`.
+                    let section = piece.section.split_once('+').map_or(piece.section, |s| s.0);
+                    let name = format!("[{section}]");
+                    (name.clone(), name)
+                }
+            };
+
+            // With LTO, all the code is in the object files of the final crate, so the code is
+            // attributed to the crate of its symbol instead, when it can be demangled.
+            let crate_name = is_rust
+                .then(|| crate_of_item(&item))
+                .flatten()
+                .unwrap_or_else(|| piece.crate_name.clone());
+            *crates.entry(crate_name).or_default().entry(section.clone()).or_default() +=
+                piece.size;
+
+            if piece.symbol.is_some()
+                && let Some(module) = module_of(&item)
+            {
+                *modules.entry(module).or_default().entry(section).or_default() += piece.size;
+            }
+            let key = (piece.symbol.unwrap_or(piece.section), piece.crate_name);
+            instances.entry(key).or_insert((item, instance, 0)).2 += piece.size;
+        }
+
+        let mut items: FxHashMap<String, Item> = FxHashMap::default();
+        for ((_, crate_name), (item, name, size)) in instances {
+            let item = items.entry(item).or_insert_with_key(|item| Item {
+                name: item.clone(),
+                size: 0,
+                monomorphizations: 0,
+                instances: Vec::new(),
+            });
+            item.size += size;
+            item.monomorphizations += 1;
+            item.instances.push(Instance { name, crate_name, size });
+        }
+        let mut items: Vec<_> = items.into_values().collect();
+        for item in &mut items {
+            item.instances.sort_by(|a, b| {
+                b.size
+                    .cmp(&a.size)
+                    .then_with(|| (&a.name, &a.crate_name).cmp(&(&b.name, &b.crate_name)))
+            });
+        }
+        items.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        let mut sections: Vec<_> = map
+            .output_sections
+            .iter()
+            .filter(|(name, size)| *size > 0 && !is_metadata_section(name))
+            .map(|(name, size)| Entry {
+                name: name.clone(),
+                size: *size,
+                sections: BTreeMap::new(),
+            })
+            .collect();
+        sections.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        Report {
+            output: output.to_path_buf(),
+            size: sections.iter().map(|section| section.size).sum(),
+            sections,
+            crates: entries(crates),
+            modules: entries(modules),
+            items,
+            diff: None,
+        }
+    }
+}
+
+/// The changes from the report of a previous build.
+#[derive(Debug, Serialize, Deserialize)]
+struct Diff {
+    baseline: PathBuf,
+    old_size: u64,
+    new_size: u64,
+    sections: Vec<Change>,
+    crates: Vec<Change>,
+    modules: Vec<Change>,
+    items: Vec<Change>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Change {
+    name: String,
+    old_size: u64,
+    new_size: u64,
+    delta: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    old_monomorphizations: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    new_monomorphizations: Option<usize>,
+}
+
+impl Diff {
+    fn new(baseline_path: &Path, baseline: &Report, report: &Report) -> Diff {
+        fn entries(entries: &[Entry]) -> impl Iterator<Item = (&str, u64, Option<usize>)> {
+            entries.iter().map(|entry| (entry.name.as_str(), entry.size, None))
+        }
+        fn items(items: &[Item]) -> impl Iterator<Item = (&str, u64, Option<usize>)> {
+            items.iter().map(|item| (item.name.as_str(), item.size, Some(item.monomorphizations)))
+        }
+        fn changes<'a>(
+            old: impl Iterator<Item = (&'a str, u64, Option<usize>)>,
+            new: impl Iterator<Item = (&'a str, u64, Option<usize>)>,
+        ) -> Vec<Change> {
+            // The size and number of monomorphizations of each entry, before and after.
+            type Size = (u64, Option<usize>);
+            let mut sizes: BTreeMap<&str, (Size, Size)> = BTreeMap::new();
+            for (name, size, monomorphizations) in old {
+                sizes.entry(name).or_default().0 = (size, monomorphizations);
+            }
+            for (name, size, monomorphizations) in new {
+                sizes.entry(name).or_default().1 = (size, monomorphizations);
+            }
+            let mut changes: Vec<_> = sizes
+                .into_iter()
+                .filter(|(_, (old, new))| old != new)
+                .map(|(name, ((old_size, old_mono), (new_size, new_mono)))| Change {
+                    name: name.to_string(),
+                    old_size,
+                    new_size,
+                    delta: new_size as i64 - old_size as i64,
+                    old_monomorphizations: old_mono,
+                    new_monomorphizations: new_mono,
+                })
+                .collect();
+            changes.sort_by(|a, b| b.delta.abs().cmp(&a.delta.abs()).then(a.name.cmp(&b.name)));
+            changes
+        }
+
+        Diff {
+            baseline: baseline_path.to_path_buf(),
+            old_size: baseline.size,
+            new_size: report.size,
+            sections: changes(entries(&baseline.sections), entries(&report.sections)),
+            crates: changes(entries(&baseline.crates), entries(&report.crates)),
+            modules: changes(entries(&baseline.modules), entries(&report.modules)),
+            items: changes(items(&baseline.items), items(&report.items)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
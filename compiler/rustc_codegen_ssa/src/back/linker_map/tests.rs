@@ -0,0 +1,244 @@
+// ignore-tidy-linelength
+
+use super::*;
+
+const GNU_MAP: &str = "\
+Archive member included to satisfy reference by file (symbol)
+
+Discarded input sections
+
+ .text._ZN4main6unused17h0123456789abcdefE
+                0x0000000000000000       0x10 /tmp/main.main.a1b2c3-cgu.0.rcgu.o
+
+Memory Configuration
+
+Name             Origin             Length             Attributes
+*default*        0x0000000000000000 0xffffffffffffffff
+
+Linker script and memory map
+
+LOAD /tmp/main.main.a1b2c3-cgu.0.rcgu.o
+LOAD /lib/libcore-0123456789abcdef.rlib
+
+.text           0x0000000000001000       0x80
+ *(.text .text.*)
+ .text._ZN4core3ptr23drop_in_place$LT$u8$GT$17h0123456789abcdefE
+                0x0000000000001000       0x10 /lib/libcore-0123456789abcdef.rlib(core-0123456789abcdef.core.d4e5f6-cgu.0.rcgu.o)
+ .text._ZN4core3ptr24drop_in_place$LT$u16$GT$17h0123456789abcdefE
+                0x0000000000001010       0x20 /tmp/main.main.a1b2c3-cgu.0.rcgu.o
+ .text.main     0x0000000000001030       0x30 /tmp/main.main.a1b2c3-cgu.0.rcgu.o
+                0x0000000000001030                main
+                0x0000000000001040                other
+ *fill*         0x0000000000001060       0x10
+ .text          0x0000000000001070       0x10 /lib/crt1.o
+                0x0000000000001070                _start
+
+.rodata         0x0000000000002000        0x8
+ .rodata..L__unnamed_1
+                0x0000000000002000        0x8 /tmp/main.main.a1b2c3-cgu.0.rcgu.o
+
+.debug_info     0x0000000000000000      0x100
+ .debug_info    0x0000000000000000      0x100 /tmp/main.main.a1b2c3-cgu.0.rcgu.o
+OUTPUT(main elf64-x86-64)
+";
+
+// The header is indented like the columns, so the string can't start with a line continuation.
+const LLD_MAP: &str = "
+             VMA              LMA     Size Align Out     In      Symbol
+            1000             1000       40    16 .text
+            1000             1000       10    16         /lib/libcore-0123456789abcdef.rlib(core-0123456789abcdef.core.d4e5f6-cgu.0.rcgu.o):(.text._ZN4core3ptr23drop_in_place$LT$u8$GT$17h0123456789abcdefE)
+            1000             1000        0     1                 _ZN4core3ptr23drop_in_place$LT$u8$GT$17h0123456789abcdefE
+            1010             1010       30    16         /tmp/main.main.a1b2c3-cgu.0.rcgu.o:(.text.main)
+            1010             1010        0     1                 main
+            1020             1020        0     1                 other
+            1040             1040        0     1 __text_end = .
+               0                0       20     1 .comment
+               0                0       20     1         <internal>:(.comment)
+";
+
+fn input(output_section: &str, name: &str, file: &str, address: u64, size: u64) -> InputSection {
+    InputSection {
+        output_section: output_section.into(),
+        name: name.into(),
+        file: file.into(),
+        address,
+        size,
+        symbols: Vec::new(),
+    }
+}
+
+#[test]
+fn test_parse_gnu_map() {
+    let map = parse_map(GNU_MAP);
+    assert_eq!(
+        map.output_sections,
+        [(".text".into(), 0x80), (".rodata".into(), 0x8), (".debug_info".into(), 0x100)]
+    );
+    let core = "/lib/libcore-0123456789abcdef.rlib(core-0123456789abcdef.core.d4e5f6-cgu.0.rcgu.o)";
+    let main = "/tmp/main.main.a1b2c3-cgu.0.rcgu.o";
+    assert_eq!(
+        map.input_sections,
+        [
+            input(
+                ".text",
+                ".text._ZN4core3ptr23drop_in_place$LT$u8$GT$17h0123456789abcdefE",
+                core,
+                0x1000,
+                0x10
+            ),
+            input(
+                ".text",
+                ".text._ZN4core3ptr24drop_in_place$LT$u16$GT$17h0123456789abcdefE",
+                main,
+                0x1010,
+                0x20
+            ),
+            InputSection {
+                symbols: vec![(0x1030, "main".into()), (0x1040, "other".into())],
+                ..input(".text", ".text.main", main, 0x1030, 0x30)
+            },
+            InputSection {
+                symbols: vec![(0x1070, "_start".into())],
+                ..input(".text", ".text", "/lib/crt1.o", 0x1070, 0x10)
+            },
+            input(".rodata", ".rodata..L__unnamed_1", main, 0x2000, 0x8),
+            input(".debug_info", ".debug_info", main, 0, 0x100),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_lld_map() {
+    let map = parse_map(LLD_MAP);
+    assert_eq!(map.output_sections, [(".text".into(), 0x40), (".comment".into(), 0x20)]);
+    let core = "/lib/libcore-0123456789abcdef.rlib(core-0123456789abcdef.core.d4e5f6-cgu.0.rcgu.o)";
+    let symbol = "_ZN4core3ptr23drop_in_place$LT$u8$GT$17h0123456789abcdefE";
+    assert_eq!(
+        map.input_sections,
+        [
+            InputSection {
+                symbols: vec![(0x1000, symbol.into())],
+                ..input(".text", &format!(".text.{symbol}"), core, 0x1000, 0x10)
+            },
+            InputSection {
+                symbols: vec![(0x1010, "main".into()), (0x1020, "other".into())],
+                ..input(".text", ".text.main", "/tmp/main.main.a1b2c3-cgu.0.rcgu.o", 0x1010, 0x30)
+            },
+            input(".comment", ".comment", "<internal>", 0, 0x20),
+        ]
+    );
+}
+
+#[test]
+fn test_crate_of_file() {
+    assert_eq!(
+        crate_of_file(
+            "/lib/libcore-0123456789abcdef.rlib(core-0123456789abcdef.core.d4e5f6-cgu.0.rcgu.o)"
+        ),
+        "core"
+    );
+    assert_eq!(crate_of_file("/tmp/main.main.a1b2c3-cgu.0.rcgu.o"), "main");
+    assert_eq!(crate_of_file("/tmp/app.main.a1b2c3-cgu.0.rcgu.o"), "main");
+    assert_eq!(crate_of_file("/lib/libc.a(printf.o)"), "c");
+    assert_eq!(crate_of_file("/lib/libfoo-0123456789abcdef.rlib(foo.o)"), "foo");
+    assert_eq!(crate_of_file("/lib/crt1.o"), "crt1.o");
+}
+
+#[test]
+fn test_strip_generic_args() {
+    assert_eq!(strip_generic_args("core::ptr::drop_in_place<u8>"), "core::ptr::drop_in_place");
+    assert_eq!(strip_generic_args("core::mem::drop::<u8>"), "core::mem::drop");
+    assert_eq!(
+        strip_generic_args("<alloc::vec::Vec<fn() -> u8> as core::clone::Clone>::clone"),
+        "<alloc::vec::Vec as core::clone::Clone>::clone"
+    );
+    assert_eq!(
+        strip_generic_args("<[u8; 4] as core::fmt::Debug>::fmt"),
+        "<[u8; 4] as core::fmt::Debug>::fmt"
+    );
+}
+
+#[test]
+fn test_module_of() {
+    assert_eq!(module_of("core::fmt::write").as_deref(), Some("core::fmt"));
+    assert_eq!(module_of("main::main::{closure#0}").as_deref(), Some("main"));
+    assert_eq!(
+        module_of("<alloc::vec::Vec as core::clone::Clone>::clone").as_deref(),
+        Some("alloc::vec")
+    );
+    assert_eq!(module_of("<&mut dyn core::any::Any>::is").as_deref(), Some("core::any"));
+    assert_eq!(module_of("<[u8] as core::fmt::Debug>::fmt").as_deref(), Some("core::fmt"));
+    assert_eq!(module_of("main"), None);
+}
+
+#[test]
+fn test_crate_of_item() {
+    assert_eq!(crate_of_item("core::fmt::write").as_deref(), Some("core"));
+    assert_eq!(crate_of_item("main::main::{closure#0}").as_deref(), Some("main"));
+    assert_eq!(
+        crate_of_item("<alloc::vec::Vec as core::clone::Clone>::clone").as_deref(),
+        Some("alloc")
+    );
+    assert_eq!(crate_of_item("<[u8] as core::fmt::Debug>::fmt").as_deref(), Some("core"));
+    assert_eq!(crate_of_item("main"), None);
+}
+
+#[test]
+fn test_report() {
+    let report = Report::new(Path::new("main"), &parse_map(GNU_MAP));
+    assert_eq!(report.size, 0x88);
+
+    let names = |entries: &[Entry]| -> Vec<(String, u64)> {
+        entries.iter().map(|entry| (entry.name.clone(), entry.size)).collect()
+    };
+    assert_eq!(names(&report.sections), [(".text".into(), 0x80), (".rodata".into(), 0x8)]);
+    // The padding is not attributed to any crate, and `drop_in_place<u16>` is attributed to `core`
+    // even though it was instantiated in `main`.
+    assert_eq!(
+        names(&report.crates),
+        [("main".into(), 0x38), ("core".into(), 0x30), ("crt1.o".into(), 0x10)]
+    );
+    assert_eq!(names(&report.modules), [("core::ptr".into(), 0x30)]);
+
+    let drop_in_place = &report.items[0];
+    assert_eq!(drop_in_place.name, "core::ptr::drop_in_place");
+    assert_eq!(drop_in_place.size, 0x30);
+    assert_eq!(drop_in_place.monomorphizations, 2);
+    assert_eq!(drop_in_place.instances[0].name, "core::ptr::drop_in_place<u16>");
+    assert_eq!(drop_in_place.instances[0].crate_name, "main");
+    assert_eq!(drop_in_place.instances[1].name, "core::ptr::drop_in_place<u8>");
+    assert_eq!(drop_in_place.instances[1].crate_name, "core");
+
+    let items: Vec<_> = report.items.iter().map(|item| (item.name.as_str(), item.size)).collect();
+    assert_eq!(
+        items,
+        [
+            ("core::ptr::drop_in_place", 0x30),
+            ("other", 0x20),
+            ("_start", 0x10),
+            ("main", 0x10),
+            ("[.rodata..L__unnamed_1]", 0x8),
+        ]
+    );
+}
+
+#[test]
+fn test_diff() {
+    let old = Report::new(Path::new("main"), &parse_map(LLD_MAP));
+    let new = Report::new(Path::new("main"), &parse_map(GNU_MAP));
+    let diff = Diff::new(Path::new("old.json"), &old, &new);
+    assert_eq!((diff.old_size, diff.new_size), (0x40, 0x88));
+
+    let drop_in_place = &diff.items[0];
+    assert_eq!(drop_in_place.name, "core::ptr::drop_in_place");
+    assert_eq!(
+        (drop_in_place.old_size, drop_in_place.new_size, drop_in_place.delta),
+        (0x10, 0x30, 0x20)
+    );
+    assert_eq!(
+        (drop_in_place.old_monomorphizations, drop_in_place.new_monomorphizations),
+        (Some(1), Some(2))
+    );
+    // `main` and `other` did not change.
+    assert!(diff.items.iter().all(|item| item.name != "main" && item.name != "other"));
+}
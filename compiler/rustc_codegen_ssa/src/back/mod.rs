@@ -2,8 +2,10 @@
 pub mod command;
 pub mod link;
 pub mod linker;
+pub mod linker_map;
 pub mod lto;
 pub mod metadata;
+pub mod no_panic;
 pub mod rpath;
 pub mod symbol_export;
 pub mod write;
@@ -0,0 +1,384 @@
+//! Enforcement of the `#[no_panic]` attribute.
+//!
+//! Whether a function can panic is only known once LLVM had the chance to optimize away the
+//! panicking branches, so the check is performed on the object code. A call graph is built from
+//! the relocations within every symbol of the object files produced by codegen and, when needed,
+//! of the object files in the upstream rlibs, and no function marked `#[no_panic]` may reach one
+//! of the panic lang items or the panic handler (the `panic_impl` lang item) in it. This also
+//! catches panics raised by the non-inlined helpers of the standard library, like
+//! `core::result::unwrap_failed`, and by any other function called by the marked function.
+//!
+//! References to data are followed too, so that the functions referenced by vtables and function
+//! pointers are checked as well. Reaching a Rust function whose code is not available (because it
+//! is defined in a dylib, or only the metadata of its crate was provided) is an error, as there is
+//! no way to prove it doesn't panic.
+
+use crate::errors;
+use crate::{CodegenResults, NoPanicFn};
+
+use object::read::archive::ArchiveFile;
+use object::{
+    macho, Architecture, BinaryFormat, Object, ObjectSection, ObjectSymbol, Relocation,
+    RelocationKind, RelocationTarget, SectionIndex, SymbolKind,
+};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::ErrorGuaranteed;
+use rustc_metadata::fs::METADATA_FILENAME;
+use rustc_session::Session;
+
+use std::borrow::Borrow;
+use std::collections::{BTreeSet, VecDeque};
+use std::fs;
+use std::hash::Hash;
+use std::iter;
+use std::path::Path;
+
+pub fn check_no_panic_fns(
+    sess: &Session,
+    codegen_results: &CodegenResults,
+) -> Result<(), ErrorGuaranteed> {
+    let crate_info = &codegen_results.crate_info;
+    if crate_info.no_panic_fns.is_empty() {
+        return Ok(());
+    }
+
+    let mut graph = CallGraph::default();
+    let mut result = Ok(());
+    let local_objects = codegen_results
+        .modules
+        .iter()
+        .chain(&codegen_results.allocator_module)
+        .filter_map(|m| m.object.as_ref());
+    for path in local_objects {
+        if let Err(guar) = graph.add_object_file(sess, path) {
+            result = Err(guar);
+        }
+    }
+    result?;
+
+    // Reading the upstream rlibs is expensive, and their code is only needed when the functions
+    // marked `#[no_panic]` call into them.
+    let panic_symbols = &crate_info.panic_symbols;
+    let needs_upstream = crate_info
+        .no_panic_fns
+        .iter()
+        .any(|no_panic_fn| graph.reaches_undefined(&no_panic_fn.symbol_name, panic_symbols));
+    if needs_upstream {
+        for cnum in &crate_info.used_crates {
+            if let Some((path, _)) = &crate_info.used_crate_source[cnum].rlib {
+                if let Err(guar) = graph.add_rlib(sess, path) {
+                    result = Err(guar);
+                }
+            }
+        }
+        result?;
+    }
+
+    for no_panic_fn in &crate_info.no_panic_fns {
+        if let Err(guar) = check_fn(sess, &graph, no_panic_fn, panic_symbols) {
+            result = Err(guar);
+        }
+    }
+    result
+}
+
+/// The symbols referenced by each symbol defined in the object files.
+#[derive(Default)]
+struct CallGraph {
+    references: FxHashMap<String, BTreeSet<String>>,
+    /// Number of object files added, used to name the nodes of their sections.
+    objects: usize,
+}
+
+impl CallGraph {
+    fn add_object_file(&mut self, sess: &Session, path: &Path) -> Result<(), ErrorGuaranteed> {
+        let data = fs::read(path).map_err(|error| read_error(sess, path, error))?;
+        let file = object::File::parse(&*data).map_err(|error| read_error(sess, path, error))?;
+        self.add_object(&file);
+        Ok(())
+    }
+
+    fn add_rlib(&mut self, sess: &Session, path: &Path) -> Result<(), ErrorGuaranteed> {
+        let data = fs::read(path).map_err(|error| read_error(sess, path, error))?;
+        let archive = ArchiveFile::parse(&*data).map_err(|error| read_error(sess, path, error))?;
+        for member in archive.members() {
+            let member = member.map_err(|error| read_error(sess, path, error))?;
+            if member.name() == METADATA_FILENAME.as_bytes() {
+                continue;
+            }
+            let member_data = member.data(&*data).map_err(|error| read_error(sess, path, error))?;
+            // Rlibs only contain object files besides the metadata, but be lenient with the
+            // members of bundled native libraries.
+            if let Ok(file) = object::File::parse(member_data) {
+                self.add_object(&file);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_object(&mut self, file: &object::File<'_>) {
+        // Symbols without a size (as emitted for Mach-O) extend up to the next symbol in the same
+        // section, so every relocation is attributed to the last symbols starting before it.
+        let mut symbols: FxHashMap<SectionIndex, Vec<(u64, String)>> = FxHashMap::default();
+        for symbol in file.symbols() {
+            if !symbol.is_definition() {
+                continue;
+            }
+            let (Some(index), Ok(name)) = (symbol.section_index(), symbol.name()) else {
+                continue;
+            };
+            let Ok(section) = file.section_by_index(index) else { continue };
+            symbols
+                .entry(index)
+                .or_default()
+                .push((symbol.address() - section.address(), name.into()));
+            self.references.entry(name.into()).or_default();
+        }
+        for symbols in symbols.values_mut() {
+            symbols.sort_unstable();
+        }
+
+        // Private data (like vtables) may have no symbol, and be referenced through its section.
+        // Mach-O places many functions and objects in each section, so such a reference is a
+        // reference to the symbols containing the address it targets. The other formats place
+        // every function and object in its own section, so references to a section (and the ones
+        // that aren't understood) are conservatively treated as references to all the symbols in
+        // it. Sections are nodes of the graph too, for the data before their first symbol.
+        let object = self.objects;
+        self.objects += 1;
+        let section_refs = |index: SectionIndex, target_offset: Option<u64>| {
+            let symbols = symbols.get(&index).map(|symbols| &symbols[..]).unwrap_or(&[]);
+            match target_offset.map(|target_offset| owners_at(symbols, target_offset)) {
+                Some(owners) if !owners.is_empty() => {
+                    owners.iter().map(|(_, name)| name.clone()).collect()
+                }
+                Some(_) => vec![section_node(object, index)],
+                None => iter::once(section_node(object, index))
+                    .chain(symbols.iter().map(|(_, name)| name.clone()))
+                    .collect::<Vec<_>>(),
+            }
+        };
+
+        for section in file.sections() {
+            let owners = symbols.get(&section.index()).map(|owners| &owners[..]).unwrap_or(&[]);
+            for (offset, relocation) in section.relocations() {
+                let target_section = match relocation.target() {
+                    RelocationTarget::Symbol(index) => match file.symbol_by_index(index) {
+                        Ok(target) if target.kind() == SymbolKind::Section => {
+                            target.section_index()
+                        }
+                        Ok(target) => {
+                            let Ok(name) = target.name() else { continue };
+                            self.add_reference(object, section.index(), owners, offset, name);
+                            continue;
+                        }
+                        Err(_) => continue,
+                    },
+                    RelocationTarget::Section(index) => Some(index),
+                    _ => continue,
+                };
+                let Some(target_section) = target_section else { continue };
+                let target_offset =
+                    macho_target_offset(file, &section, offset, &relocation, target_section);
+                for target in section_refs(target_section, target_offset) {
+                    self.add_reference(object, section.index(), owners, offset, &target);
+                }
+            }
+        }
+    }
+
+    /// Records a reference from the symbols containing the given offset of a section, or from the
+    /// section itself if no symbol contains it.
+    fn add_reference(
+        &mut self,
+        object: usize,
+        section: SectionIndex,
+        symbols: &[(u64, String)],
+        offset: u64,
+        target: &str,
+    ) {
+        let owners = owners_at(symbols, offset);
+        if owners.is_empty() {
+            let owner = section_node(object, section);
+            self.references.entry(owner).or_default().insert(target.into());
+        }
+        for (_, owner) in owners {
+            self.references.get_mut(owner.as_str()).unwrap().insert(target.into());
+        }
+    }
+
+    /// Looks up a symbol defined in the graph, accounting for the leading underscore added to the
+    /// symbol names on some platforms.
+    fn defined(&self, name: &str) -> Option<&str> {
+        self.references
+            .get_key_value(name)
+            .or_else(|| self.references.get_key_value(&format!("_{name}")))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Whether a symbol can reach a symbol that is neither defined in the graph nor a panic.
+    fn reaches_undefined(
+        &self,
+        root: &str,
+        panic_symbols: &FxHashMap<String, (String, String)>,
+    ) -> bool {
+        let Some(root) = self.defined(root) else { return false };
+        let mut visited = BTreeSet::from([root]);
+        let mut queue = VecDeque::from([root]);
+        while let Some(symbol) = queue.pop_front() {
+            for target in &self.references[symbol] {
+                let target = target.as_str();
+                if !visited.insert(target) || lookup(panic_symbols, target).is_some() {
+                    continue;
+                }
+                if !self.references.contains_key(target) {
+                    return true;
+                }
+                queue.push_back(target);
+            }
+        }
+        false
+    }
+}
+
+/// The symbols containing the given offset of a section, that is the last symbols (the aliases
+/// of each other) starting at or before it.
+fn owners_at(symbols: &[(u64, String)], offset: u64) -> &[(u64, String)] {
+    let end = symbols.partition_point(|(start, _)| *start <= offset);
+    let Some(&(owner_start, _)) = end.checked_sub(1).map(|last| &symbols[last]) else {
+        return &[];
+    };
+    let start = symbols[..end].partition_point(|(start, _)| *start < owner_start);
+    &symbols[start..end]
+}
+
+/// Computes the offset in the target section of the address referenced by a Mach-O relocation
+/// against a section, whose addend is stored in the relocated field. Returns `None` for the other
+/// formats and for the relocations that aren't understood.
+fn macho_target_offset(
+    file: &object::File<'_>,
+    section: &object::Section<'_, '_>,
+    offset: u64,
+    relocation: &Relocation,
+    target_section: SectionIndex,
+) -> Option<u64> {
+    if file.format() != BinaryFormat::MachO || !relocation.has_implicit_addend() {
+        return None;
+    }
+    let data = section.data().ok()?;
+    let field = data.get(usize::try_from(offset).ok()?..)?;
+    // All the targets supported by Mach-O are little-endian.
+    let value = match relocation.size() {
+        32 => i64::from(i32::from_le_bytes(field.get(..4)?.try_into().ok()?)),
+        64 => i64::from_le_bytes(field.get(..8)?.try_into().ok()?),
+        _ => return None,
+    };
+    let address = match relocation.kind() {
+        RelocationKind::Absolute => value,
+        // The displacements of x86-64 instructions are relative to the end of the instruction,
+        // which may have an immediate operand of 1, 2 or 4 bytes after the displacement.
+        kind if file.architecture() == Architecture::X86_64 && relocation.size() == 32 => {
+            let immediate = match kind {
+                RelocationKind::Relative => 0,
+                RelocationKind::MachO { value: macho::X86_64_RELOC_SIGNED_1, .. } => 1,
+                RelocationKind::MachO { value: macho::X86_64_RELOC_SIGNED_2, .. } => 2,
+                RelocationKind::MachO { value: macho::X86_64_RELOC_SIGNED_4, .. } => 4,
+                _ => return None,
+            };
+            let end = section.address().checked_add(offset + 4 + immediate)?;
+            value.checked_add(i64::try_from(end).ok()?)?
+        }
+        _ => return None,
+    };
+    let target_section = file.section_by_index(target_section).ok()?;
+    let target_offset = u64::try_from(address).ok()?.checked_sub(target_section.address())?;
+    (target_offset < target_section.size()).then_some(target_offset)
+}
+
+/// Searches the shortest path from a function marked `#[no_panic]` to a panic, and reports it.
+fn check_fn(
+    sess: &Session,
+    graph: &CallGraph,
+    no_panic_fn: &NoPanicFn,
+    panic_symbols: &FxHashMap<String, (String, String)>,
+) -> Result<(), ErrorGuaranteed> {
+    // Functions without any code (because LLVM removed them after inlining them in all their
+    // callers) can't panic by themselves.
+    let Some(root) = graph.defined(&no_panic_fn.symbol_name) else { return Ok(()) };
+
+    let mut parents: FxHashMap<&str, &str> = FxHashMap::default();
+    let mut queue = VecDeque::from([root]);
+    while let Some(symbol) = queue.pop_front() {
+        for target in &graph.references[symbol] {
+            let target = target.as_str();
+            if target == root || parents.contains_key(target) {
+                continue;
+            }
+            parents.insert(target, symbol);
+
+            if let Some((lang_item, panic_fn)) = lookup(panic_symbols, target) {
+                return Err(sess.emit_err(errors::NoPanicViolated {
+                    fn_path: &no_panic_fn.def_path,
+                    location: &no_panic_fn.location,
+                    panic_fn: panic_fn.clone(),
+                    lang_item: lang_item.clone(),
+                    path: path_to(&parents, root, target),
+                    sites: no_panic_fn
+                        .panic_locations
+                        .iter()
+                        .map(|location| errors::NoPanicSite { location })
+                        .collect(),
+                }));
+            }
+            if graph.references.contains_key(target) {
+                queue.push_back(target);
+            } else if rustc_demangle::try_demangle(target).is_ok() {
+                return Err(sess.emit_err(errors::NoPanicUnavailableCode {
+                    fn_path: &no_panic_fn.def_path,
+                    location: &no_panic_fn.location,
+                    callee: format!("{:#}", rustc_demangle::demangle(target)),
+                    path: path_to(&parents, root, target),
+                }));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Describes the functions between the root and the target, if any.
+fn path_to(
+    parents: &FxHashMap<&str, &str>,
+    root: &str,
+    target: &str,
+) -> Option<errors::NoPanicPath> {
+    let mut path = Vec::new();
+    let mut symbol = parents[target];
+    while symbol != root {
+        if !symbol.starts_with('\0') {
+            path.push(format!("`{:#}`", rustc_demangle::demangle(symbol)));
+        }
+        symbol = parents[symbol];
+    }
+    if path.is_empty() {
+        return None;
+    }
+    path.reverse();
+    Some(errors::NoPanicPath { path: path.join(" -> ") })
+}
+
+/// Name of the node of a section without symbols, which can't clash with the symbols.
+fn section_node(object: usize, section: SectionIndex) -> String {
+    format!("\0{object}:{}", section.0)
+}
+
+fn read_error(sess: &Session, path: &Path, error: impl ToString) -> ErrorGuaranteed {
+    sess.emit_err(errors::NoPanicReadObject { path: path.to_path_buf(), error: error.to_string() })
+}
+
+/// Looks up a symbol name, ignoring the leading underscore added on some platforms.
+fn lookup<'a, K, V>(map: &'a FxHashMap<K, V>, name: &str) -> Option<&'a V>
+where
+    K: Borrow<str> + Eq + Hash,
+{
+    map.get(name).or_else(|| map.get(name.strip_prefix('_')?))
+}
@@ -12,7 +12,9 @@
 use crate::mir::operand::OperandValue;
 use crate::mir::place::PlaceRef;
 use crate::traits::*;
-use crate::{CachedModuleCodegen, CompiledModule, CrateInfo, MemFlags, ModuleCodegen, ModuleKind};
+use crate::{
+    CachedModuleCodegen, CompiledModule, CrateInfo, MemFlags, ModuleCodegen, ModuleKind, NoPanicFn,
+};
 
 use rustc_ast::expand::allocator::{global_fn_name, AllocatorKind, ALLOCATOR_METHODS};
 use rustc_attr as attr;
@@ -23,19 +25,21 @@
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_hir::lang_items::LangItem;
 use rustc_metadata::EncodedMetadata;
-use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrs;
+use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
 use rustc_middle::middle::debugger_visualizer::{DebuggerVisualizerFile, DebuggerVisualizerType};
 use rustc_middle::middle::exported_symbols;
 use rustc_middle::middle::exported_symbols::SymbolExportKind;
 use rustc_middle::middle::lang_items;
 use rustc_middle::mir::mono::{CodegenUnit, CodegenUnitNameBuilder, MonoItem};
+use rustc_middle::mir::TerminatorKind;
 use rustc_middle::query::Providers;
 use rustc_middle::ty::layout::{HasTyCtxt, LayoutOf, TyAndLayout};
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_session::config::{self, CrateType, EntryFnType, OutputType};
 use rustc_session::Session;
 use rustc_span::symbol::sym;
-use rustc_span::Symbol;
+use rustc_span::{Span, Symbol};
 use rustc_target::abi::{Align, FIRST_VARIANT};
 
 use std::cmp;
@@ -863,7 +867,13 @@ pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
             dependency_formats: tcx.dependency_formats(()).clone(),
             windows_subsystem,
             natvis_debugger_visualizers: Default::default(),
+            no_panic_fns: collect_no_panic_fns(tcx),
+            panic_symbols: Default::default(),
         };
+
+        if !info.no_panic_fns.is_empty() {
+            info.panic_symbols = panic_lang_item_symbols(tcx);
+        }
         let crates = tcx.crates(());
 
         let n_crates = crates.len();
@@ -962,6 +972,93 @@ pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
     }
 }
 
+/// Collects the functions marked `#[no_panic]` that are codegened in this crate, along with the
+/// locations of the MIR terminators that may lead to a panic, so that the check performed before
+/// linking can point at them.
+fn collect_no_panic_fns(tcx: TyCtxt<'_>) -> Vec<NoPanicFn> {
+    let panic_lang_items: Vec<DefId> =
+        PANIC_LANG_ITEMS.iter().filter_map(|&item| tcx.lang_items().get(item)).collect();
+
+    let mut no_panic_fns = Vec::new();
+    for cgu in tcx.collect_and_partition_mono_items(()).1 {
+        for (item, _) in cgu.items_in_deterministic_order(tcx) {
+            let MonoItem::Fn(instance) = item else { continue };
+            let def_id = instance.def_id();
+            if !tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::NO_PANIC) {
+                continue;
+            }
+
+            let body = tcx.instance_mir(instance.def);
+            let panic_locations = body
+                .basic_blocks
+                .iter()
+                .map(|block| block.terminator())
+                .filter(|terminator| match &terminator.kind {
+                    TerminatorKind::Assert { .. } => true,
+                    // `#[track_caller]` functions (like `Option::unwrap`) are called where the
+                    // panics they raise should be reported.
+                    TerminatorKind::Call { func, .. } => {
+                        func.const_fn_def().is_some_and(|(callee, _)| {
+                            panic_lang_items.contains(&callee)
+                                || tcx
+                                    .codegen_fn_attrs(callee)
+                                    .flags
+                                    .contains(CodegenFnAttrFlags::TRACK_CALLER)
+                        })
+                    }
+                    _ => false,
+                })
+                .map(|terminator| source_location(tcx, terminator.source_info.span))
+                .collect();
+
+            no_panic_fns.push(NoPanicFn {
+                symbol_name: tcx.symbol_name(instance).name.to_string(),
+                // The paths are computed even if no error is reported, so they can't be trimmed.
+                def_path: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+                location: source_location(tcx, tcx.def_span(def_id)),
+                panic_locations,
+            });
+        }
+    }
+    no_panic_fns
+}
+
+/// Formats the start of a span as `file:line:column`, with the file name remapped as requested.
+fn source_location(tcx: TyCtxt<'_>, span: Span) -> String {
+    let loc = tcx.sess.source_map().lookup_char_pos(span.lo());
+    format!("{}:{}:{}", loc.file.name.prefer_remapped(), loc.line, loc.col_display + 1)
+}
+
+/// The lang items that functions marked `#[no_panic]` must not reach.
+const PANIC_LANG_ITEMS: [LangItem; 4] =
+    [LangItem::Panic, LangItem::PanicBoundsCheck, LangItem::PanicFmt, LangItem::PanicNounwind];
+
+/// Maps the symbol names of the panic lang items to the names of the lang items and their paths.
+fn panic_lang_item_symbols(tcx: TyCtxt<'_>) -> FxHashMap<String, (String, String)> {
+    let mut symbols: FxHashMap<_, _> = PANIC_LANG_ITEMS
+        .iter()
+        .filter_map(|&item| {
+            let def_id = tcx.lang_items().get(item)?;
+            let symbol_name = tcx.symbol_name(Instance::mono(tcx, def_id)).name.to_string();
+            let path = with_no_trimmed_paths!(tcx.def_path_str(def_id));
+            Some((symbol_name, (item.name().to_string(), path)))
+        })
+        .collect();
+
+    // Every panic ends up in the panic handler, which is thus reached even when the other lang
+    // items are inlined or renamed. It is only defined once the final artifact is linked, so
+    // refer to it with its link name.
+    let item = LangItem::PanicImpl;
+    if let Some(link_name) = item.link_name() {
+        let path = match tcx.lang_items().get(item) {
+            Some(def_id) => with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+            None => link_name.to_string(),
+        };
+        symbols.insert(link_name.to_string(), (item.name().to_string(), path));
+    }
+    symbols
+}
+
 pub fn provide(providers: &mut Providers) {
     providers.backend_optimization_level = |tcx, cratenum| {
         let for_speed = match tcx.sess.opts.optimize {
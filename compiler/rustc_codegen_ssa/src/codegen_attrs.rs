@@ -11,8 +11,8 @@
 use rustc_middle::ty::{self as ty, TyCtxt};
 use rustc_session::{lint, parse::feature_err};
 use rustc_span::symbol::Ident;
-use rustc_span::{sym, Span};
-use rustc_target::spec::{abi, SanitizerSet};
+use rustc_span::{sym, Span, Symbol};
+use rustc_target::spec::{abi, SanitizerSet, StackLimit};
 
 use crate::errors;
 use crate::target_features::from_target_feature;
@@ -71,6 +71,13 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
         codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_BUILTINS;
     }
 
+    // `#[overflow_checks]` applies to all the functions nested in the item it is written on.
+    match tcx.checks_attr_setting(did, sym::overflow_checks) {
+        Some(true) => codegen_fn_attrs.flags |= CodegenFnAttrFlags::OVERFLOW_CHECKS_ON,
+        Some(false) => codegen_fn_attrs.flags |= CodegenFnAttrFlags::OVERFLOW_CHECKS_OFF,
+        None => {}
+    }
+
     let supported_target_features = tcx.supported_target_features(LOCAL_CRATE);
 
     let mut inline_span = None;
@@ -115,6 +122,11 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
                 codegen_fn_attrs.flags |= CodegenFnAttrFlags::ALLOCATOR_ZEROED
             }
             sym::naked => codegen_fn_attrs.flags |= CodegenFnAttrFlags::NAKED,
+            sym::no_panic => codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_PANIC,
+            sym::stack_overflow_handler => {
+                codegen_fn_attrs.flags |= CodegenFnAttrFlags::STACK_OVERFLOW_HANDLER;
+                codegen_fn_attrs.export_name = Some(Symbol::intern(StackLimit::HANDLER_SYMBOL));
+            }
             sym::no_mangle => {
                 if tcx.opt_item_name(did.to_def_id()).is_some() {
                     codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_MANGLE
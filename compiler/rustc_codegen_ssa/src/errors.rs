@@ -7,7 +7,7 @@
     DiagnosticArgValue, DiagnosticBuilder, ErrorGuaranteed, Handler, IntoDiagnostic,
     IntoDiagnosticArg,
 };
-use rustc_macros::Diagnostic;
+use rustc_macros::{Diagnostic, Subdiagnostic};
 use rustc_middle::ty::layout::LayoutError;
 use rustc_middle::ty::Ty;
 use rustc_span::{Span, Symbol};
@@ -69,6 +69,81 @@ pub struct NoModuleNamed<'a> {
     pub cgu_names: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_no_panic_violated)]
+#[note(codegen_ssa_defined_at)]
+#[note]
+pub struct NoPanicViolated<'a> {
+    pub fn_path: &'a str,
+    pub location: &'a str,
+    pub panic_fn: String,
+    pub lang_item: String,
+    #[subdiagnostic]
+    pub path: Option<NoPanicPath>,
+    #[subdiagnostic]
+    pub sites: Vec<NoPanicSite<'a>>,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_no_panic_unavailable_code)]
+#[note(codegen_ssa_defined_at)]
+#[note]
+#[help]
+pub struct NoPanicUnavailableCode<'a> {
+    pub fn_path: &'a str,
+    pub location: &'a str,
+    pub callee: String,
+    #[subdiagnostic]
+    pub path: Option<NoPanicPath>,
+}
+
+#[derive(Subdiagnostic)]
+#[note(codegen_ssa_no_panic_path)]
+pub struct NoPanicPath {
+    pub path: String,
+}
+
+#[derive(Subdiagnostic)]
+#[note(codegen_ssa_no_panic_site)]
+pub struct NoPanicSite<'a> {
+    pub location: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_no_panic_read_object)]
+pub struct NoPanicReadObject {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_map_missing)]
+#[note]
+pub struct LinkerMapMissing {
+    pub path: PathBuf,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_map_read_failure)]
+pub struct LinkerMapReadFailure {
+    pub path: PathBuf,
+    pub error: Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_map_baseline_failure)]
+pub struct LinkerMapBaselineFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_map_report_failure)]
+pub struct LinkerMapReportFailure<'a> {
+    pub path: &'a Path,
+    pub error: Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_field_associated_value_expected)]
 pub struct FieldAssociatedValueExpected {
@@ -612,7 +687,7 @@ pub struct ReadFileError {
 #[diag(codegen_ssa_archive_build_failure)]
 // Public for rustc_codegen_llvm::back::archive
 pub struct ArchiveBuildFailure {
-    pub error: std::io::Error,
+    pub error: Error,
 }
 
 #[derive(Diagnostic)]
@@ -1097,5 +1172,5 @@ pub struct FailedToGetLayout<'tcx> {
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_error_creating_remark_dir)]
 pub struct ErrorCreatingRemarkDir {
-    pub error: std::io::Error,
+    pub error: Error,
 }
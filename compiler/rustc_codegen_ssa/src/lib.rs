@@ -168,6 +168,24 @@ pub struct CrateInfo {
     pub dependency_formats: Lrc<Dependencies>,
     pub windows_subsystem: Option<String>,
     pub natvis_debugger_visualizers: BTreeSet<DebuggerVisualizerFile>,
+    pub no_panic_fns: Vec<NoPanicFn>,
+    /// Maps the symbol names of the panic lang items (including the panic handler) to the lang item
+    /// names and their paths.
+    pub panic_symbols: FxHashMap<String, (String, String)>,
+}
+
+/// A function marked `#[no_panic]`, whose object code is checked before linking to ensure it
+/// can't reach the panic lang items.
+///
+/// The check may run in another session (with `-Z link-only`), so the source locations are
+/// stored as `file:line:column` strings rather than spans.
+#[derive(Debug, Encodable, Decodable)]
+pub struct NoPanicFn {
+    pub symbol_name: String,
+    pub def_path: String,
+    pub location: String,
+    /// Locations of the MIR terminators that may panic, reported if the check fails.
+    pub panic_locations: Vec<String>,
 }
 
 #[derive(Encodable, Decodable)]
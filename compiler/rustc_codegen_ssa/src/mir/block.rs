@@ -579,8 +579,13 @@ fn codegen_assert_terminator(
         // This case can currently arise only from functions marked
         // with #[rustc_inherit_overflow_checks] and inlined from
         // another crate (mostly core::num generic/#[inline] fns),
-        // while the current crate doesn't use overflow checks.
-        if !bx.cx().check_overflow() && msg.is_optional_overflow_check() {
+        // while the current crate doesn't use overflow checks,
+        // or from functions where `#[overflow_checks]` overrides it.
+        let check_overflow = bx
+            .tcx()
+            .codegen_fn_attrs(self.instance.def_id())
+            .keeps_overflow_checks(bx.cx().check_overflow());
+        if !check_overflow && msg.is_optional_overflow_check() {
             const_cond = Some(expected);
         }
 
@@ -128,6 +128,7 @@ pub fn codegen_intrinsic_call(
             }
             sym::pref_align_of
             | sym::needs_drop
+            | sym::debug_assertions
             | sym::type_id
             | sym::type_name
             | sym::variant_count => {
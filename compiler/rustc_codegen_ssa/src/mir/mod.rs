@@ -2,12 +2,14 @@
 use crate::traits::*;
 use rustc_index::bit_set::BitSet;
 use rustc_index::IndexVec;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir;
 use rustc_middle::mir::traversal;
 use rustc_middle::mir::UnwindTerminateReason;
 use rustc_middle::ty::layout::{FnAbiOf, HasTyCtxt, TyAndLayout};
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt, TypeFoldable, TypeVisitableExt};
 use rustc_target::abi::call::{FnAbi, PassMode};
+use rustc_target::spec::StackLimit;
 
 use std::iter;
 
@@ -209,6 +211,19 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
         caller_location: None,
     };
 
+    // Check the stack limit before anything is stored in the new frame. The handler can't be
+    // checked itself, and naked functions can't contain anything but their assembly.
+    let stack_limit = cx.tcx().sess.stack_limit();
+    let unchecked = CodegenFnAttrFlags::NAKED | CodegenFnAttrFlags::STACK_OVERFLOW_HANDLER;
+    if *stack_limit != StackLimit::None
+        && !cx.tcx().codegen_fn_attrs(instance.def_id()).flags.intersects(unchecked)
+    {
+        fx.set_debug_loc(&mut start_bx, mir::SourceInfo::outermost(mir.span));
+        start_bx.check_stack_limit(stack_limit);
+        // The body of the function now starts in the block following the check.
+        fx.cached_llbbs[mir::START_BLOCK] = CachedLlbb::Some(start_bx.llbb());
+    }
+
     // It may seem like we should iterate over `required_consts` to ensure they all successfully
     // evaluate; however, the `MirUsedCollector` already did that during the collection phase of
     // monomorphization so we don't have to do it again.
@@ -20,7 +20,7 @@
 use rustc_span::Span;
 use rustc_target::abi::call::FnAbi;
 use rustc_target::abi::{Abi, Align, Scalar, Size, WrappingRange};
-use rustc_target::spec::HasTargetSpec;
+use rustc_target::spec::{HasTargetSpec, StackLimit};
 
 #[derive(Copy, Clone)]
 pub enum OverflowOp {
@@ -333,4 +333,8 @@ fn call(
     fn zext(&mut self, val: Self::Value, dest_ty: Self::Type) -> Self::Value;
 
     fn apply_attrs_to_cleanup_callsite(&mut self, llret: Self::Value);
+
+    /// Compares the stack pointer with `limit` and calls the stack overflow handler if it is
+    /// below the limit. The builder is left in a new block, where the function continues.
+    fn check_stack_limit(&mut self, limit: &StackLimit);
 }
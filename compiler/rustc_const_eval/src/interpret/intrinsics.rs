@@ -56,8 +56,13 @@ pub(crate) fn eval_nullary_intrinsic<'tcx>(
     def_id: DefId,
     args: GenericArgsRef<'tcx>,
 ) -> InterpResult<'tcx, ConstValue<'tcx>> {
-    let tp_ty = args.type_at(0);
     let name = tcx.item_name(def_id);
+    if name == sym::debug_assertions {
+        // Calls are replaced when building MIR with the setting of the calling function, which
+        // can override the crate-wide one. Any other call falls back to the crate-wide setting.
+        return Ok(ConstValue::from_bool(tcx.sess.opts.debug_assertions));
+    }
+    let tp_ty = args.type_at(0);
     Ok(match name {
         sym::type_name => {
             ensure_monomorphic_enough(tcx, tp_ty)?;
@@ -153,13 +158,14 @@ pub fn emulate_intrinsic(
 
             sym::pref_align_of
             | sym::needs_drop
+            | sym::debug_assertions
             | sym::type_id
             | sym::type_name
             | sym::variant_count => {
                 let gid = GlobalId { instance, promoted: None };
                 let ty = match intrinsic_name {
                     sym::pref_align_of | sym::variant_count => self.tcx.types.usize,
-                    sym::needs_drop => self.tcx.types.bool,
+                    sym::needs_drop | sym::debug_assertions => self.tcx.types.bool,
                     sym::type_id => self.tcx.types.u128,
                     sym::type_name => Ty::new_static_str(self.tcx.tcx),
                     _ => bug!(),
@@ -449,6 +449,14 @@ fn run_compiler(
 
             queries.global_ctxt()?.enter(|tcx| tcx.analysis(()))?;
 
+            // Written outside of any query, as the file must be written again even when the
+            // results of the analysis are reused from the incremental cache.
+            if let Some(dir) = &sess.opts.unstable_opts.certified_calls {
+                queries.global_ctxt()?.enter(|tcx| rustc_lint::write_certified_calls(tcx, dir));
+            }
+            // Same for the lint inventory, which also needs all lints to have been emitted.
+            queries.global_ctxt()?.enter(|tcx| rustc_lint::check_lint_levels(tcx));
+
             if callbacks.after_analysis(compiler, queries) == Compilation::Stop {
                 return early_exit();
             }
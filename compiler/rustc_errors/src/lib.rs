@@ -71,6 +71,7 @@
 mod lock;
 pub mod markdown;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 #[cfg(test)]
@@ -1467,7 +1468,7 @@ fn print_error_count(&mut self, registry: &Registry) {
             1 => Cow::from("aborting due to previous error"),
             count => Cow::from(format!("aborting due to {count} previous errors")),
         };
-        if self.treat_err_as_bug() {
+        if self.treat_err_as_bug() || !self.emitter.should_show_error_count() {
             return;
         }
 
@@ -0,0 +1,485 @@
+//! A SARIF emitter for errors.
+//!
+//! Unlike the JSON emitter, which emits a JSON object for each diagnostic, SARIF requires all the
+//! results of an analysis to be part of the same document. Diagnostics are thus collected as they
+//! are emitted, and the SARIF 2.1.0 log is written when the emitter is dropped. A log without
+//! results is written if no diagnostic was emitted, as consumers close the results of the previous
+//! logs which are missing from the new one.
+//!
+//! Error codes and lint names are reported as the rules of the results, primary spans as their
+//! locations, and secondary spans and child diagnostics as their related locations. Each
+//! alternative of a suggestion is reported as a separate fix.
+
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::translation::{to_fluent_args, Translate};
+use crate::DiagnosticId;
+use crate::{CodeSuggestion, FluentBundle, LazyFallbackBundle, Level, SubDiagnostic};
+use rustc_lint_defs::Applicability;
+
+use rustc_data_structures::sync::{IntoDynSyncSend, Lrc};
+use rustc_error_messages::FluentArgs;
+use rustc_span::Span;
+use std::collections::BTreeMap;
+use std::error::Report;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+#[cfg(test)]
+mod tests;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub struct SarifEmitter {
+    dst: IntoDynSyncSend<Box<dyn Write + Send>>,
+    registry: Option<Registry>,
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    fallback_bundle: LazyFallbackBundle,
+    rules: Vec<Rule>,
+    results: Vec<SarifResult>,
+    /// Whether to write the log even if no diagnostic was emitted.
+    write_empty_log: bool,
+}
+
+impl SarifEmitter {
+    pub fn stderr(
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter::new(
+            Box::new(io::BufWriter::new(io::stderr())),
+            registry,
+            source_map,
+            fluent_bundle,
+            fallback_bundle,
+        )
+    }
+
+    pub fn basic(
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        let file_path_mapping = FilePathMapping::empty();
+        let mut emitter = SarifEmitter::stderr(
+            None,
+            Lrc::new(SourceMap::new(file_path_mapping)),
+            fluent_bundle,
+            fallback_bundle,
+        );
+        // Emitters for early errors are created alongside the one of the session, which writes
+        // the log of the compilation when there are no errors.
+        emitter.write_empty_log = false;
+        emitter
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst: IntoDynSyncSend(dst),
+            registry,
+            sm: source_map,
+            fluent_bundle,
+            fallback_bundle,
+            rules: Vec::new(),
+            results: Vec::new(),
+            write_empty_log: true,
+        }
+    }
+
+    /// Returns the index of the rule of a diagnostic code, adding the rule if it's the first
+    /// diagnostic with that code.
+    fn rule_index(&mut self, code: &DiagnosticId) -> usize {
+        let id = match code {
+            DiagnosticId::Error(code) => code,
+            DiagnosticId::Lint { name, .. } => name,
+        };
+        if let Some(index) = self.rules.iter().position(|rule| rule.id == *id) {
+            return index;
+        }
+
+        let rule = match code {
+            DiagnosticId::Error(code) => Rule {
+                id: code.clone(),
+                full_description: self
+                    .registry
+                    .as_ref()
+                    .and_then(|registry| registry.try_find_description(code).ok())
+                    .map(|description| Message { text: description.trim().to_string() }),
+                help_uri: Some(format!("https://doc.rust-lang.org/error_codes/{code}.html")),
+            },
+            DiagnosticId::Lint { name, .. } => {
+                Rule { id: name.clone(), full_description: None, help_uri: None }
+            }
+        };
+        self.rules.push(rule);
+        self.rules.len() - 1
+    }
+
+    fn location(&self, span: Span, message: Option<String>) -> Option<Location> {
+        if span.is_dummy() {
+            return None;
+        }
+        Some(Location {
+            physical_location: Some(PhysicalLocation {
+                artifact_location: self.artifact_location(span),
+                region: self.region(span),
+            }),
+            message: message.map(|text| Message { text }),
+        })
+    }
+
+    /// Returns the locations of a child diagnostic, or a location containing only the message of
+    /// the child if it has no span.
+    fn child_locations(&self, child: &SubDiagnostic, args: &FluentArgs<'_>) -> Vec<Location> {
+        let message =
+            format!("{}: {}", child.level.to_str(), self.translate_messages(&child.message, args));
+        let span = child.render_span.as_ref().unwrap_or(&child.span);
+        let locations = span
+            .primary_spans()
+            .iter()
+            .filter_map(|span| self.location(*span, Some(message.clone())))
+            .collect::<Vec<_>>();
+        if locations.is_empty() {
+            vec![Location { physical_location: None, message: Some(Message { text: message }) }]
+        } else {
+            locations
+        }
+    }
+
+    fn fixes(&self, suggestion: &CodeSuggestion, args: &FluentArgs<'_>) -> Vec<Fix> {
+        let description =
+            self.translate_message(&suggestion.msg, args).map_err(Report::new).unwrap();
+        suggestion
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                let mut changes = BTreeMap::<String, Vec<Replacement>>::new();
+                for part in &substitution.parts {
+                    changes.entry(self.artifact_location(part.span).uri).or_default().push(
+                        Replacement {
+                            deleted_region: self.region(part.span),
+                            inserted_content: Content { text: part.snippet.clone() },
+                        },
+                    );
+                }
+                Fix {
+                    description: Message { text: description.to_string() },
+                    artifact_changes: changes
+                        .into_iter()
+                        .map(|(uri, replacements)| ArtifactChange {
+                            artifact_location: ArtifactLocation::from_uri(uri),
+                            replacements,
+                        })
+                        .collect(),
+                    properties: FixProperties { applicability: suggestion.applicability },
+                }
+            })
+            .collect()
+    }
+
+    fn artifact_location(&self, span: Span) -> ArtifactLocation {
+        let file = self.sm.lookup_source_file(span.lo());
+        ArtifactLocation::from_uri(path_to_uri(
+            &self.sm.filename_for_diagnostics(&file.name).to_string(),
+        ))
+    }
+
+    fn region(&self, span: Span) -> Region {
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        Region {
+            start_line: start.line,
+            start_column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+            byte_offset: byte_start,
+            byte_length: byte_end - byte_start,
+        }
+    }
+}
+
+impl Translate for SarifEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        // Failure notes, like the hint to use `rustc --explain`, are not results of the analysis.
+        if diag.level == Level::FailureNote {
+            return;
+        }
+
+        let args = to_fluent_args(diag.args());
+        let rule_index = diag.code.as_ref().map(|code| self.rule_index(code));
+
+        let mut locations = Vec::new();
+        let mut related_locations = Vec::new();
+        for span_label in diag.span.span_labels() {
+            let label = span_label
+                .label
+                .as_ref()
+                .map(|label| self.translate_message(label, &args).unwrap().to_string());
+            let Some(location) = self.location(span_label.span, label) else { continue };
+            if span_label.is_primary {
+                locations.push(location);
+            } else {
+                related_locations.push(location);
+            }
+        }
+        for child in &diag.children {
+            related_locations.extend(self.child_locations(child, &args));
+        }
+
+        let result = SarifResult {
+            rule_id: rule_index.map(|index| self.rules[index].id.clone()),
+            rule_index,
+            level: if diag.is_error() {
+                "error"
+            } else if matches!(diag.level, Level::Warning(_)) {
+                "warning"
+            } else {
+                "note"
+            },
+            message: Message { text: self.translate_messages(&diag.message, &args).to_string() },
+            locations,
+            related_locations,
+            fixes: diag
+                .suggestions
+                .iter()
+                .flatten()
+                .flat_map(|suggestion| self.fixes(suggestion, &args))
+                .collect(),
+        };
+        self.results.push(result);
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+
+    // The summary of the compilation is not a result of the analysis.
+    fn should_show_error_count(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        if self.results.is_empty() && !self.write_empty_log {
+            return;
+        }
+        let log = Log {
+            schema: SCHEMA,
+            version: "2.1.0",
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "rustc",
+                        information_uri: "https://www.rust-lang.org/",
+                        rules: &self.rules,
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results: &self.results,
+            }],
+        };
+        let result = writeln!(&mut self.dst, "{}", serde_json::to_string_pretty(&log).unwrap())
+            .and_then(|_| self.dst.flush());
+        // Panicking while the compiler is already unwinding would abort the process.
+        if let Err(e) = result {
+            eprintln!("failed to print diagnostics: {e:?}");
+        }
+    }
+}
+
+/// Converts the name of a source file to the URI of the SARIF artifact. Relative paths are kept
+/// relative to the `%SRCROOT%` base, as the directory rustc was invoked in is not known by the
+/// consumers of the log.
+fn path_to_uri(path: &str) -> String {
+    let mut uri = String::new();
+    if Path::new(path).is_absolute() {
+        uri.push_str("file://");
+        if !path.starts_with('/') {
+            // Windows paths like `C:\foo` are written as `file:///C:/foo`.
+            uri.push('/');
+        }
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'\\' => uri.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+// The following data types are provided just for serialisation.
+
+#[derive(Serialize)]
+struct Log<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run<'a> {
+    tool: Tool<'a>,
+    /// Columns are counted in characters, like in the human readable and JSON output.
+    column_kind: &'static str,
+    results: &'a [SarifResult],
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    driver: Driver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver<'a> {
+    name: &'static str,
+    information_uri: &'static str,
+    rules: &'a [Rule],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    /// The error code or the name of the lint.
+    id: String,
+    /// The explanation of the error code, as shown by `rustc --explain`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    /// "error", "warning" or "note".
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<PhysicalLocation>,
+    /// The label of the span, or the message of the child diagnostic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+impl ArtifactLocation {
+    fn from_uri(uri: String) -> ArtifactLocation {
+        let uri_base_id = if uri.starts_with("file://") { None } else { Some("%SRCROOT%") };
+        ArtifactLocation { uri, uri_base_id }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    /// 1-based, character offset of the character after the end of the region.
+    end_column: usize,
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+    properties: FixProperties,
+}
+
+#[derive(Serialize)]
+struct FixProperties {
+    applicability: Applicability,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: Content,
+}
+
+#[derive(Serialize)]
+struct Content {
+    text: String,
+}
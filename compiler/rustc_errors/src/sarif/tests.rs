@@ -0,0 +1,148 @@
+use super::*;
+
+use crate::sarif::SarifEmitter;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+
+use crate::Handler;
+use rustc_span::{BytePos, Span};
+
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Emits the diagnostics of `f` with the SARIF emitter, returning the SARIF log.
+fn emit(code: &str, f: impl FnOnce(&Handler)) -> Value {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("src/lib.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm,
+            None,
+            fallback_bundle,
+        );
+
+        let handler = Handler::with_emitter(Box::new(se));
+        f(&handler);
+        drop(handler);
+
+        let bytes = output.lock().unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn no_diagnostics() {
+    // An empty log is still written, so that consumers can close the results of previous logs.
+    let log = emit("fn main() {}", |_| {});
+    assert_eq!(json!([]), log["runs"][0]["tool"]["driver"]["rules"]);
+    assert_eq!(json!([]), log["runs"][0]["results"]);
+}
+
+#[test]
+fn error_with_suggestion() {
+    let log = emit("fn main() {\n    let x = 1;\n}\n", |handler| {
+        handler
+            .struct_span_err_with_code(span(20, 21), "foo", DiagnosticId::Error("E0001".into()))
+            .span_label(span(16, 19), "bar")
+            .note("baz")
+            .span_suggestion(span(20, 21), "qux", "_x", Applicability::MachineApplicable)
+            .emit();
+        handler
+            .struct_span_warn(span(3, 7), "unused")
+            .code(DiagnosticId::Lint {
+                name: "dead_code".into(),
+                has_future_breakage: false,
+                is_force_warn: false,
+            })
+            .emit();
+    });
+
+    assert_eq!("2.1.0", log["version"]);
+    let run = &log["runs"][0];
+    assert_eq!(
+        json!([
+            { "id": "E0001", "helpUri": "https://doc.rust-lang.org/error_codes/E0001.html" },
+            { "id": "dead_code" },
+        ]),
+        run["tool"]["driver"]["rules"]
+    );
+
+    let location = |start_line, start_column, end_column, byte_offset, byte_length| {
+        json!({
+            "artifactLocation": { "uri": "src/lib.rs", "uriBaseId": "%SRCROOT%" },
+            "region": {
+                "startLine": start_line,
+                "startColumn": start_column,
+                "endLine": start_line,
+                "endColumn": end_column,
+                "byteOffset": byte_offset,
+                "byteLength": byte_length,
+            },
+        })
+    };
+    assert_eq!(
+        json!([
+            {
+                "ruleId": "E0001",
+                "ruleIndex": 0,
+                "level": "error",
+                "message": { "text": "foo" },
+                "locations": [{ "physicalLocation": location(2, 9, 10, 20, 1) }],
+                "relatedLocations": [
+                    { "physicalLocation": location(2, 5, 8, 16, 3), "message": { "text": "bar" } },
+                    { "message": { "text": "note: baz" } },
+                ],
+                "fixes": [{
+                    "description": { "text": "qux" },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": "src/lib.rs", "uriBaseId": "%SRCROOT%" },
+                        "replacements": [{
+                            "deletedRegion": location(2, 9, 10, 20, 1)["region"],
+                            "insertedContent": { "text": "_x" },
+                        }],
+                    }],
+                    "properties": { "applicability": "MachineApplicable" },
+                }],
+            },
+            {
+                "ruleId": "dead_code",
+                "ruleIndex": 1,
+                "level": "warning",
+                "message": { "text": "unused" },
+                "locations": [{ "physicalLocation": location(1, 4, 8, 3, 4) }],
+            },
+        ]),
+        run["results"]
+    );
+}
+
+#[test]
+fn uris() {
+    assert_eq!("src/lib.rs", path_to_uri("src/lib.rs"));
+    assert_eq!("src/my%20file.rs", path_to_uri("src/my file.rs"));
+    assert_eq!("file:///home/user/src/lib.rs", path_to_uri("/home/user/src/lib.rs"));
+    assert_eq!("src/a/b.rs", path_to_uri("src\\a\\b.rs"));
+}
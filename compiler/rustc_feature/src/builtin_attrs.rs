@@ -470,6 +470,19 @@ pub struct BuiltinAttribute {
         cmse_nonsecure_entry, Normal, template!(Word), WarnFollowing,
         experimental!(cmse_nonsecure_entry)
     ),
+    gated!(no_panic, Normal, template!(Word), WarnFollowing, experimental!(no_panic)),
+    gated!(
+        stack_overflow_handler, Normal, template!(Word), ErrorFollowing,
+        experimental!(stack_overflow_handler)
+    ),
+    gated!(
+        overflow_checks, Normal, template!(List: "on|off"), ErrorFollowing, item_level_checks,
+        experimental!(overflow_checks)
+    ),
+    gated!(
+        debug_assertions, Normal, template!(List: "on|off"), ErrorFollowing, item_level_checks,
+        experimental!(debug_assertions)
+    ),
     // RFC 2632
     gated!(
         const_trait, Normal, template!(Word), WarnFollowing, const_trait_impl,
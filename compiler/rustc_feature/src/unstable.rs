@@ -428,6 +428,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, doc_cfg_hide, "1.57.0", Some(43781), None),
     /// Allows `#[doc(masked)]`.
     (unstable, doc_masked, "1.21.0", Some(44027), None),
+    /// Allows `#[doc(requirement = "...")]`.
+    (unstable, doc_requirement, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows `dyn* Trait` objects.
     (incomplete, dyn_star, "1.65.0", Some(102425), None),
     // Uses generic effect parameters for ~const bounds
@@ -443,6 +445,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, extended_varargs_abi_support, "1.65.0", Some(100189), None),
     /// Allows defining `extern type`s.
     (unstable, extern_types, "1.23.0", Some(43467), None),
+    /// Allows marking items with `#[ferrocene::certified]`, and the `ferrocene::uncertified` lint.
+    (unstable, ferrocene_certified, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows the use of `#[ffi_const]` on foreign functions.
     (unstable, ffi_const, "1.45.0", Some(58328), None),
     /// Allows the use of `#[ffi_pure]` on foreign functions.
@@ -481,6 +485,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (incomplete, inline_const_pat, "1.58.0", Some(76001), None),
     /// Allows using `pointer` and `reference` in intra-doc links
     (unstable, intra_doc_pointers, "1.51.0", Some(80896), None),
+    /// Allows `#[overflow_checks(on|off)]` and `#[debug_assertions(on|off)]` on items.
+    (unstable, item_level_checks, "CURRENT_RUSTC_VERSION", None, None),
     // Allows setting the threshold for the `large_assignments` lint.
     (unstable, large_assignments, "1.52.0", Some(83518), None),
     /// Allow to have type alias types for inter-crate use.
@@ -513,6 +519,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, never_type_fallback, "1.41.0", Some(65992), None),
     /// Allows `#![no_core]`.
     (unstable, no_core, "1.3.0", Some(29639), None),
+    /// Allows using `#[no_panic]` to reject functions that can still panic after optimization.
+    (unstable, no_panic, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows the use of `no_sanitize` attribute.
     (unstable, no_sanitize, "1.42.0", Some(39699), None),
     /// Allows using the `non_exhaustive_omitted_patterns` lint.
@@ -547,6 +555,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, simd_ffi, "1.0.0", Some(27731), None),
     /// Allows specialization of implementations (RFC 1210).
     (incomplete, specialization, "1.7.0", Some(31844), None),
+    /// Allows defining the handler called by `-Zstack-limit` checks with `#[stack_overflow_handler]`.
+    (unstable, stack_overflow_handler, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows attributes on expressions and non-item statements.
     (unstable, stmt_expr_attributes, "1.6.0", Some(15701), None),
     /// Allows lints part of the strict provenance effort.
@@ -102,6 +102,7 @@ pub fn intrinsic_operation_unsafety(tcx: TyCtxt<'_>, intrinsic_id: DefId) -> hir
         | sym::type_id
         | sym::likely
         | sym::unlikely
+        | sym::debug_assertions
         | sym::ptr_guaranteed_cmp
         | sym::minnumf32
         | sym::minnumf64
@@ -394,6 +395,7 @@ pub fn check_intrinsic_type(tcx: TyCtxt<'_>, it: &hir::ForeignItem<'_>) {
             sym::assume => (0, vec![tcx.types.bool], Ty::new_unit(tcx)),
             sym::likely => (0, vec![tcx.types.bool], tcx.types.bool),
             sym::unlikely => (0, vec![tcx.types.bool], tcx.types.bool),
+            sym::debug_assertions => (0, vec![], tcx.types.bool),
 
             sym::read_via_copy => (1, vec![Ty::new_imm_ptr(tcx, param(0))], param(0)),
             sym::write_via_move => {
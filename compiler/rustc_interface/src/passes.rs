@@ -798,6 +798,12 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
         }
     });
 
+    if let Some(path) = &sess.opts.unstable_opts.unsafe_inventory {
+        sess.time("write_unsafe_inventory", || {
+            rustc_mir_build::unsafe_inventory::write_unsafe_inventory(tcx, path)
+        });
+    }
+
     tcx.hir().par_body_owners(|def_id| {
         if let rustc_hir::def::DefKind::Generator = tcx.def_kind(def_id) {
             tcx.ensure().mir_generator_witnesses(def_id);
@@ -168,6 +168,10 @@ lint_confusable_identifier_pair = found both `{$existing_sym}` and `{$sym}` as i
     .current_use = this identifier can be confused with `{$existing_sym}`
     .other_use = other identifier used here
 
+lint_couldnt_write_certified_calls = failed to write the certified calls to `{$path}`: {$error}
+
+lint_couldnt_write_lint_inventory = failed to write the lint inventory to `{$path}`: {$error}
+
 lint_cstring_ptr = getting the inner pointer of a temporary `CString`
     .as_ptr_label = this pointer will be invalid
     .unwrap_label = this `CString` is deallocated at the end of the statement, bind it to a variable to extend its lifetime
@@ -337,6 +341,10 @@ lint_map_unit_fn = `Iterator::map` call that discard the iterator's values
     .map_label = after this call to map, the resulting iterator is `impl Iterator<Item = ()>`, which means the only information carried by the iterator is the number of items
     .suggestion = you might have meant to use `Iterator::for_each`
 
+lint_missing_lint_reason = `{$lint_level}({$lint_name})` has no reason
+    .help = add `reason = "..."` at the end of the attribute, explaining why the lint does not apply here
+    .note = a reason is required on the attributes allowing, expecting or downgrading lints because of `-Z require-lint-reasons`
+
 lint_mixed_script_confusables =
     the usage of Script Group `{$set}` in this crate consists solely of mixed script confusables
     .includes_note = the usage includes {$includes}
@@ -514,6 +522,9 @@ lint_tykind = usage of `ty::TyKind`
 lint_tykind_kind = usage of `ty::TyKind::<kind>`
     .suggestion = try using `ty::<kind>` directly
 
+lint_uncertified_call = call to `{$callee}`, which is not part of the certified subset of the standard library
+    .note = only functions marked with `#[ferrocene::certified]` are covered by the safety manual
+
 lint_undropped_manually_drops = calls to `std::mem::drop` with `std::mem::ManuallyDrop` instead of the inner value does nothing
     .label = argument has type `{$arg_ty}`
     .suggestion = use `std::mem::ManuallyDrop::into_inner` to get the inner value
@@ -3,6 +3,7 @@
 use rustc_macros::{Diagnostic, Subdiagnostic};
 use rustc_session::lint::Level;
 use rustc_span::{Span, Symbol};
+use std::path::Path;
 
 #[derive(Diagnostic)]
 #[diag(lint_overruled_attribute, code = "E0453")]
@@ -107,3 +108,28 @@ pub struct CheckNameUnknownTool<'a> {
     #[subdiagnostic]
     pub sub: RequestedLevel<'a>,
 }
+
+#[derive(Diagnostic)]
+#[diag(lint_missing_lint_reason)]
+#[help]
+#[note]
+pub struct MissingLintReason<'a> {
+    #[primary_span]
+    pub span: Span,
+    pub lint_level: &'a str,
+    pub lint_name: Symbol,
+}
+
+#[derive(Diagnostic)]
+#[diag(lint_couldnt_write_certified_calls)]
+pub struct CouldntWriteCertifiedCalls<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(lint_couldnt_write_lint_inventory)]
+pub struct CouldntWriteLintInventory<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
@@ -17,6 +17,9 @@ fn check_expectations(tcx: TyCtxt<'_>, tool_filter: Option<Symbol>) {
 
     let lint_expectations = tcx.lint_expectations(());
     let fulfilled_expectations = tcx.sess.diagnostic().steal_fulfilled_expectation_ids();
+    if tcx.sess.opts.unstable_opts.lint_inventory.is_some() {
+        tcx.sess.fulfilled_lint_expectations.lock().extend(fulfilled_expectations.iter().copied());
+    }
 
     tracing::debug!(?lint_expectations, ?fulfilled_expectations);
 
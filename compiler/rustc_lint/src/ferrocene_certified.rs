@@ -0,0 +1,228 @@
+//! Lint for calls to functions of the standard library outside of the certified subset, and
+//! recording of the certified functions called by each test for `-Z certified-calls`.
+
+use crate::errors::CouldntWriteCertifiedCalls;
+use crate::lints::UncertifiedCallDiag;
+use crate::{LateContext, LateLintPass, LintContext};
+use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, LocalDefId, LOCAL_CRATE};
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_hir::{Expr, ExprKind};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+declare_tool_lint! {
+    /// The `uncertified` lint detects calls to functions of `core` and `alloc` that are not
+    /// part of the certified subset, that is not marked with `#[ferrocene::certified]`.
+    ///
+    /// Crates that must only rely on the certified subset of the standard library can opt in
+    /// with `#![feature(ferrocene_certified)]` and `#![deny(ferrocene::uncertified)]`.
+    pub ferrocene::UNCERTIFIED,
+    Allow,
+    "detects calls to functions of the standard library outside of the certified subset",
+    report_in_external_macro: true
+}
+
+declare_lint_pass!(UncertifiedCalls => [UNCERTIFIED]);
+
+impl<'tcx> LateLintPass<'tcx> for UncertifiedCalls {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        // Both calls and references to functions (like `map(Option::is_some)`) are paths, method
+        // calls or overloaded operators, as the callee of a call expression is visited as an
+        // expression too.
+        let Some(def_id) = referenced_fn(cx.tcx, cx.param_env, cx.typeck_results(), expr) else {
+            return;
+        };
+        if is_library_fn(cx.tcx, def_id) && !is_certified(cx.tcx, def_id) {
+            let callee = with_no_trimmed_paths!(cx.tcx.def_path_str(def_id));
+            cx.emit_spanned_lint(UNCERTIFIED, expr.span, UncertifiedCallDiag { callee });
+        }
+    }
+}
+
+/// Returns the function an expression calls or refers to, if any, including the trait methods
+/// called by the overloaded operators.
+///
+/// Trait methods are resolved to the implementation they call when it's known, so that calling
+/// `clone` on a `Vec` is treated as a call to `<Vec<T> as Clone>::clone`.
+fn referenced_fn<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    typeck_results: &ty::TypeckResults<'tcx>,
+    expr: &Expr<'_>,
+) -> Option<DefId> {
+    let def_id = match expr.kind {
+        ExprKind::Path(ref qpath) => typeck_results.qpath_res(qpath, expr.hir_id).opt_def_id()?,
+        // The operators only have a type dependent definition when they are overloaded.
+        ExprKind::MethodCall(..)
+        | ExprKind::Binary(..)
+        | ExprKind::AssignOp(..)
+        | ExprKind::Unary(..)
+        | ExprKind::Index(..) => typeck_results.type_dependent_def_id(expr.hir_id)?,
+        _ => return None,
+    };
+    if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) {
+        return None;
+    }
+    if tcx.trait_of_item(def_id).is_some()
+        && let Ok(args) =
+            tcx.try_normalize_erasing_regions(param_env, typeck_results.node_args(expr.hir_id))
+        && let Ok(Some(instance)) = ty::Instance::resolve(tcx, param_env, def_id, args)
+    {
+        return Some(instance.def_id());
+    }
+    Some(def_id)
+}
+
+fn is_library_fn(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    matches!(tcx.crate_name(def_id.krate), sym::core | sym::alloc)
+}
+
+fn is_certified(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    tcx.get_attrs_by_path(def_id, &[sym::ferrocene, sym::certified]).next().is_some()
+}
+
+#[derive(Serialize)]
+struct CertifiedCalls {
+    crate_name: String,
+    tests: Vec<TestCalls>,
+}
+
+#[derive(Serialize)]
+struct TestCalls {
+    /// Name of the test, as reported by the test harness.
+    name: String,
+    calls: Vec<CertifiedCall>,
+}
+
+#[derive(Serialize)]
+struct CertifiedCall {
+    /// Path of the called function, like `core::mem::swap`.
+    path: String,
+    /// Location of the definition of the called function.
+    file: String,
+    line: usize,
+}
+
+/// Writes the functions of the certified subset called by each test of the crate to
+/// `<dir>/<crate name>.json`, for `-Z certified-calls`.
+///
+/// A test calls a function when the function is reachable from the test through the functions
+/// of the crate, and references to functions count as calls.
+pub fn write_certified_calls(tcx: TyCtxt<'_>, dir: &Path) {
+    // The call graph of the crate, from each body to the functions it calls or refers to, and
+    // to the closures and inline constants it contains.
+    let mut graph = FxIndexMap::default();
+    let mut tests = Vec::new();
+    for owner in tcx.hir().body_owners() {
+        let mut collector = CallCollector {
+            tcx,
+            param_env: tcx.param_env(owner),
+            typeck_results: tcx.typeck(owner),
+            callees: FxIndexSet::default(),
+        };
+        collector.visit_body(tcx.hir().body(tcx.hir().body_owned_by(owner)));
+        graph.insert(owner, collector.callees);
+
+        // The test harness marks the constant describing each test with its name.
+        if let Some(name) =
+            tcx.get_attr(owner, sym::rustc_test_marker).and_then(|attr| attr.value_str())
+        {
+            tests.push((name.to_string(), owner));
+        }
+    }
+    if tests.is_empty() {
+        return;
+    }
+
+    let source_map = tcx.sess.source_map();
+    let tests = tests
+        .into_iter()
+        .map(|(name, test)| {
+            let calls = certified_callees(tcx, &graph, test)
+                .into_iter()
+                .map(|def_id| {
+                    let loc = source_map.lookup_char_pos(tcx.def_span(def_id).lo());
+                    CertifiedCall {
+                        path: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+                        file: source_map.filename_for_diagnostics(&loc.file.name).to_string(),
+                        line: loc.line,
+                    }
+                })
+                .collect();
+            TestCalls { name, calls }
+        })
+        .collect();
+
+    let crate_name = tcx.crate_name(LOCAL_CRATE).to_string();
+    let path = dir.join(format!("{crate_name}.json"));
+    let result = File::create(&path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, &CertifiedCalls { crate_name, tests })?;
+        writer.flush()
+    });
+    if let Err(error) = result {
+        tcx.sess.emit_err(CouldntWriteCertifiedCalls { path: &path, error });
+    }
+}
+
+/// Returns the certified functions reachable from `root` through the functions of the crate.
+fn certified_callees(
+    tcx: TyCtxt<'_>,
+    graph: &FxIndexMap<LocalDefId, FxIndexSet<DefId>>,
+    root: LocalDefId,
+) -> Vec<DefId> {
+    let mut certified = FxIndexSet::default();
+    let mut visited = FxIndexSet::default();
+    let mut queue = vec![root];
+    while let Some(caller) = queue.pop() {
+        if !visited.insert(caller) {
+            continue;
+        }
+        for &callee in graph.get(&caller).into_iter().flatten() {
+            // The functions called by the certified ones are not followed, as they are tested
+            // through the certified function and not by the test itself.
+            if is_library_fn(tcx, callee) && is_certified(tcx, callee) {
+                certified.insert(callee);
+            } else if let Some(callee) = callee.as_local() {
+                queue.push(callee);
+            }
+        }
+    }
+    let mut certified: Vec<_> = certified.into_iter().collect();
+    certified.sort_by_cached_key(|&def_id| tcx.def_path_hash(def_id));
+    certified
+}
+
+struct CallCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    typeck_results: &'tcx ty::TypeckResults<'tcx>,
+    callees: FxIndexSet<DefId>,
+}
+
+impl<'tcx> Visitor<'tcx> for CallCollector<'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        match expr.kind {
+            // Closures and inline constants have their own bodies, which are not visited here.
+            ExprKind::Closure(closure) => {
+                self.callees.insert(closure.def_id.to_def_id());
+            }
+            ExprKind::ConstBlock(ref block) => {
+                self.callees.insert(block.def_id.to_def_id());
+            }
+            _ => {
+                let callee = referenced_fn(self.tcx, self.param_env, self.typeck_results, expr);
+                self.callees.extend(callee);
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
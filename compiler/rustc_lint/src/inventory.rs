@@ -0,0 +1,253 @@
+//! Inventory of the places where lint levels are changed, for `-Z lint-inventory` and
+//! `-Z require-lint-reasons`.
+//!
+//! The inventory lists every lint level attribute (`#[allow]`, `#[expect]`, `#[warn]`, `#[deny]`,
+//! `#[forbid]`) of the crate along with the lints it affects and its reason, and every lint flag
+//! passed on the command line. It is built by the driver after the analysis, outside of any query,
+//! so that it can record whether each expectation was fulfilled, and so that it's written again
+//! when the analysis is reused from the incremental cache.
+
+use crate::errors::{CouldntWriteLintInventory, MissingLintReason};
+use crate::late::unerased_lint_store;
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir as hir;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::lint::{in_external_macro, LintLevelSource};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::lint::{Level, LintExpectationId};
+use rustc_span::{sym, Span, Symbol};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+#[derive(Serialize)]
+struct Inventory {
+    crate_name: String,
+    /// Level set with `--cap-lints`, if any.
+    cap_lints: Option<&'static str>,
+    command_line: Vec<CommandLineEntry>,
+    attributes: Vec<AttributeEntry>,
+}
+
+#[derive(Serialize)]
+struct CommandLineEntry {
+    level: &'static str,
+    /// Lint or lint group, as written in the flag.
+    name: String,
+    lints: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct AttributeEntry {
+    level: &'static str,
+    /// Lint or lint group, as written in the attribute.
+    name: String,
+    lints: Vec<String>,
+    reason: Option<String>,
+    location: Location,
+    /// Location of the outermost macro call, if the attribute comes from a macro expansion.
+    macro_call: Option<Location>,
+    /// Whether the attribute was generated by a macro defined in another crate.
+    external_macro: bool,
+    /// Whether the attribute allows, expects or downgrades a lint denied by default.
+    suppression: bool,
+    /// Whether the expectation was fulfilled, for `#[expect]` attributes.
+    fulfilled: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct Location {
+    file: String,
+    line: usize,
+    column: usize,
+}
+
+/// Writes the inventory for `-Z lint-inventory`, and requires reasons on the attributes
+/// suppressing lints for `-Z require-lint-reasons`.
+pub fn check_lint_levels(tcx: TyCtxt<'_>) {
+    let opts = &tcx.sess.opts.unstable_opts;
+    if opts.lint_inventory.is_none() && !opts.require_lint_reasons {
+        return;
+    }
+
+    let attributes = collect_attributes(tcx, &tcx.sess.fulfilled_lint_expectations.lock());
+
+    if opts.require_lint_reasons {
+        for attr in &attributes {
+            if attr.suppression && attr.reason.is_none() && !attr.external_macro {
+                tcx.sess.emit_err(MissingLintReason {
+                    span: attr.span,
+                    lint_level: attr.level.as_str(),
+                    lint_name: attr.name,
+                });
+            }
+        }
+    }
+
+    if let Some(path) = &opts.lint_inventory {
+        let inventory = Inventory {
+            crate_name: tcx.crate_name(LOCAL_CRATE).to_string(),
+            cap_lints: tcx.sess.opts.lint_cap.map(|level| level.as_str()),
+            command_line: collect_command_line(tcx),
+            attributes: attributes.iter().map(|attr| attr.to_entry(tcx)).collect(),
+        };
+        let result = File::create(path).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer_pretty(&mut writer, &inventory)?;
+            writer.flush()
+        });
+        if let Err(error) = result {
+            tcx.sess.emit_err(CouldntWriteLintInventory { path, error });
+        }
+    }
+}
+
+/// Lint level attribute, gathered from the levels set for each of the lints it affects.
+struct Attribute {
+    level: Level,
+    name: Symbol,
+    span: Span,
+    reason: Option<Symbol>,
+    lints: Vec<String>,
+    external_macro: bool,
+    suppression: bool,
+    fulfilled: Option<bool>,
+}
+
+impl Attribute {
+    fn to_entry(&self, tcx: TyCtxt<'_>) -> AttributeEntry {
+        let mut lints = self.lints.clone();
+        lints.sort();
+        lints.dedup();
+        AttributeEntry {
+            level: self.level.as_str(),
+            name: self.name.to_string(),
+            lints,
+            reason: self.reason.map(|reason| reason.to_string()),
+            location: location(tcx, self.span),
+            macro_call: self
+                .span
+                .from_expansion()
+                .then(|| location(tcx, self.span.source_callsite())),
+            external_macro: self.external_macro,
+            suppression: self.suppression,
+            fulfilled: self.fulfilled,
+        }
+    }
+}
+
+fn collect_attributes(
+    tcx: TyCtxt<'_>,
+    fulfilled_expectations: &FxHashSet<LintExpectationId>,
+) -> Vec<Attribute> {
+    let edition = tcx.sess.edition();
+    let owners = std::iter::once(hir::CRATE_OWNER_ID).chain(tcx.hir_crate_items(()).owners());
+
+    let mut attributes = FxHashMap::default();
+    for owner in owners {
+        for (_, specs) in tcx.shallow_lint_levels_on(owner).specs.iter() {
+            for (lint, &(level, source)) in specs {
+                // Levels set on the command line are listed separately, and the ones set by the
+                // compiler itself (for example on derived code) are not relevant.
+                let LintLevelSource::Node { name, span, reason } = source else { continue };
+
+                let attr = attributes.entry((span, name)).or_insert_with(|| Attribute {
+                    level,
+                    name,
+                    span,
+                    reason,
+                    lints: Vec::new(),
+                    external_macro: in_external_macro(tcx.sess, span),
+                    suppression: false,
+                    fulfilled: None,
+                });
+                attr.lints.push(lint.lint.name_lower());
+                attr.suppression |= match level {
+                    Level::Allow | Level::Expect(_) => true,
+                    Level::Warn | Level::ForceWarn(_) => {
+                        matches!(lint.lint.default_level(edition), Level::Deny | Level::Forbid)
+                    }
+                    Level::Deny | Level::Forbid => false,
+                };
+                if let Level::Expect(id) = level {
+                    attr.fulfilled = Some(fulfilled_expectations.contains(&id));
+                }
+            }
+        }
+    }
+
+    collect_tool_attributes(tcx, &mut attributes);
+
+    let mut attributes: Vec<_> = attributes.into_values().collect();
+    attributes.sort_by_key(|attr| attr.span.lo());
+    attributes
+}
+
+/// Adds the attributes changing the level of lints from tools not running in this compilation
+/// (like `clippy::` lints outside of Clippy), which are unknown to the lint store and thus don't
+/// set any lint level. Their lint groups can't be expanded, and whether their expectations are
+/// fulfilled is only known by the tool.
+fn collect_tool_attributes(tcx: TyCtxt<'_>, attributes: &mut FxHashMap<(Span, Symbol), Attribute>) {
+    let known_spans: FxHashSet<Span> = attributes.keys().map(|&(span, _)| span).collect();
+    let owners = std::iter::once(hir::CRATE_OWNER_ID).chain(tcx.hir_crate_items(()).owners());
+    for owner in owners {
+        for attr in tcx.hir_attrs(owner).map.values().flat_map(|attrs| attrs.iter()) {
+            let Some(level) = Level::from_attr(attr) else { continue };
+            let Some(metas) = attr.meta_item_list() else { continue };
+            let reason = metas
+                .iter()
+                .filter_map(|meta| meta.meta_item())
+                .find(|item| item.path == sym::reason)
+                .and_then(|item| item.value_str());
+
+            for meta in &metas {
+                let Some(item) = meta.meta_item() else { continue };
+                let span = meta.span();
+                if !item.is_word() || item.path.segments.len() < 2 || known_spans.contains(&span) {
+                    continue;
+                }
+                let name = Symbol::intern(&pprust::path_to_string(&item.path));
+                attributes.entry((span, name)).or_insert_with(|| Attribute {
+                    level,
+                    name,
+                    span,
+                    reason,
+                    lints: vec![name.to_string()],
+                    external_macro: in_external_macro(tcx.sess, span),
+                    suppression: matches!(level, Level::Allow | Level::Expect(_)),
+                    fulfilled: None,
+                });
+            }
+        }
+    }
+}
+
+fn collect_command_line(tcx: TyCtxt<'_>) -> Vec<CommandLineEntry> {
+    let store = unerased_lint_store(tcx);
+    tcx.sess
+        .opts
+        .lint_opts
+        .iter()
+        .map(|(name, level)| {
+            let mut lints: Vec<_> = store
+                .find_lints(name)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|lint| lint.lint.name_lower())
+                .collect();
+            lints.sort();
+            CommandLineEntry { level: level.as_str(), name: name.clone(), lints }
+        })
+        .collect()
+}
+
+fn location(tcx: TyCtxt<'_>, span: Span) -> Location {
+    let source_map = tcx.sess.source_map();
+    let loc = source_map.lookup_char_pos(span.lo());
+    Location {
+        file: source_map.filename_for_diagnostics(&loc.file.name).to_string(),
+        line: loc.line,
+        column: loc.col.0 + 1,
+    }
+}
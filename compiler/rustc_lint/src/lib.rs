@@ -61,10 +61,12 @@
 mod enum_intrinsics_non_enums;
 mod errors;
 mod expect;
+mod ferrocene_certified;
 mod for_loops_over_fallibles;
 mod foreign_modules;
 pub mod hidden_unicode_codepoints;
 mod internal;
+mod inventory;
 mod invalid_from_utf8;
 mod late;
 mod let_underscore;
@@ -104,6 +106,7 @@
 use deref_into_dyn_supertrait::*;
 use drop_forget_useless::*;
 use enum_intrinsics_non_enums::EnumIntrinsicsNonEnums;
+use ferrocene_certified::UncertifiedCalls;
 use for_loops_over_fallibles::*;
 use hidden_unicode_codepoints::*;
 use internal::*;
@@ -130,6 +133,8 @@
 pub use context::{CheckLintNameResult, FindLintError, LintStore};
 pub use context::{EarlyContext, LateContext, LintContext};
 pub use early::{check_ast_node, EarlyCheckNode};
+pub use ferrocene_certified::write_certified_calls;
+pub use inventory::check_lint_levels;
 pub use late::{check_crate, late_lint_mod, unerased_lint_store};
 pub use passes::{EarlyLintPass, LateLintPass};
 pub use rustc_session::lint::Level::{self, *};
@@ -239,6 +244,7 @@ fn lint_mod(tcx: TyCtxt<'_>, module_def_id: LocalModDefId) {
             MissingDebugImplementations: MissingDebugImplementations,
             MissingDoc: MissingDoc,
             AsyncFnInTrait: AsyncFnInTrait,
+            UncertifiedCalls: UncertifiedCalls,
         ]
     ]
 );
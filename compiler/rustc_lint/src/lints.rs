@@ -617,6 +617,14 @@ pub struct ExpectationNote {
     pub rationale: Symbol,
 }
 
+// ferrocene_certified.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_uncertified_call)]
+#[note]
+pub struct UncertifiedCallDiag {
+    pub callee: String,
+}
+
 // ptr_nulls.rs
 #[derive(LintDiagnostic)]
 pub enum PtrNullChecksDiag<'a> {
@@ -102,6 +102,18 @@ pub struct CodegenFnAttrFlags: u32 {
         const ALLOCATOR_ZEROED          = 1 << 19;
         /// `#[no_builtins]`: indicates that disable implicit builtin knowledge of functions for the function.
         const NO_BUILTINS               = 1 << 20;
+        /// `#[no_panic]`: indicates that the generated code of the function must not be able to
+        /// reach any of the panic lang items, which is checked after optimization.
+        const NO_PANIC                  = 1 << 21;
+        /// `#[overflow_checks(on)]`, on the function or an enclosing item: indicates that the
+        /// overflow checks of the function are kept even if the crate disables them.
+        const OVERFLOW_CHECKS_ON        = 1 << 22;
+        /// `#[overflow_checks(off)]`, on the function or an enclosing item: indicates that the
+        /// overflow checks of the function are removed even if the crate enables them.
+        const OVERFLOW_CHECKS_OFF       = 1 << 23;
+        /// `#[stack_overflow_handler]`: indicates that the function is called when a stack limit
+        /// check fails, so it must not be checked itself.
+        const STACK_OVERFLOW_HANDLER    = 1 << 24;
     }
 }
 
@@ -149,4 +161,16 @@ pub fn contains_extern_indicator(&self) -> bool {
                 Some(_) => true,
             }
     }
+
+    /// Returns `true` if the optional overflow checks of the function are kept by codegen, given
+    /// whether the crate it is codegened in enables overflow checks.
+    pub fn keeps_overflow_checks(&self, crate_overflow_checks: bool) -> bool {
+        if self.flags.contains(CodegenFnAttrFlags::OVERFLOW_CHECKS_ON) {
+            true
+        } else if self.flags.contains(CodegenFnAttrFlags::OVERFLOW_CHECKS_OFF) {
+            false
+        } else {
+            crate_overflow_checks
+        }
+    }
 }
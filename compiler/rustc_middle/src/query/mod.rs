@@ -887,6 +887,13 @@
         cache_on_disk_if { true }
     }
 
+    /// Lists the operations requiring `unsafe` in this `LocalDefId`, including the ones of its
+    /// closures and inline constants, as found by THIR unsafeck. This is used by
+    /// `-Zunsafe-inventory`, regardless of whether `-Zthir-unsafeck` is enabled.
+    query thir_unsafe_operations(key: LocalDefId) -> &'tcx [thir::UnsafeOperation] {
+        desc { |tcx| "collecting the unsafe operations of `{}`", tcx.def_path_str(key) }
+    }
+
     /// Returns the types assumed to be well formed while "inside" of the given item.
     ///
     /// Note that we've liberated the late bound regions of function signatures, so
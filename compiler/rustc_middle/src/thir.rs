@@ -188,6 +188,19 @@ pub enum BlockSafety {
     ExplicitUnsafe(hir::HirId),
 }
 
+/// An operation requiring `unsafe`, found by THIR unsafeck. Used by `-Zunsafe-inventory`.
+#[derive(Copy, Clone, Debug, HashStable)]
+pub struct UnsafeOperation {
+    pub span: Span,
+    /// What the operation does, e.g. "dereference of raw pointer".
+    pub description: &'static str,
+    /// The function called by the operation, if any.
+    pub callee: Option<DefId>,
+    /// The `unsafe` block allowing the operation, or `None` if it is allowed by the enclosing
+    /// `unsafe fn`.
+    pub block: Option<hir::HirId>,
+}
+
 #[derive(Clone, Debug, HashStable)]
 pub struct Stmt<'tcx> {
     pub kind: StmtKind<'tcx>,
@@ -18,7 +18,7 @@
 use rustc_index::bit_set::GrowableBitSet;
 use rustc_macros::HashStable;
 use rustc_session::Limit;
-use rustc_span::sym;
+use rustc_span::{sym, Symbol};
 use rustc_target::abi::{Integer, IntegerType, Size};
 use rustc_target::spec::abi::Abi;
 use smallvec::SmallVec;
@@ -560,6 +560,26 @@ pub fn is_typeck_child(self, def_id: DefId) -> bool {
         )
     }
 
+    /// Returns the setting of the `#[overflow_checks(on|off)]` or `#[debug_assertions(on|off)]`
+    /// attribute (`attr`) that applies to `def_id`, as `Some(true)` for `on`. These attributes
+    /// apply to everything nested in the item they are written on, so the closest enclosing
+    /// item carrying `attr` decides. Returns `None` when there is none, in which case the
+    /// crate-wide `-C overflow-checks` or `-C debug-assertions` setting applies.
+    pub fn checks_attr_setting(self, def_id: LocalDefId, attr: Symbol) -> Option<bool> {
+        let mut def_id = def_id;
+        loop {
+            if let Some(found) = self.get_attr(def_id, attr) {
+                // Malformed attributes are reported by `check_attr` and ignored here.
+                match found.meta_item_list().as_deref() {
+                    Some([item]) if item.has_name(sym::on) => return Some(true),
+                    Some([item]) if item.has_name(sym::off) => return Some(false),
+                    _ => {}
+                }
+            }
+            def_id = self.opt_local_parent(def_id)?;
+        }
+    }
+
     /// Returns `true` if `def_id` refers to a trait (i.e., `trait Foo { ... }`).
     pub fn is_trait(self, def_id: DefId) -> bool {
         self.def_kind(def_id) == DefKind::Trait
@@ -55,8 +55,11 @@ pub(crate) fn closure_saved_names_of_captured_variables<'tcx>(
 /// Construct the MIR for a given `DefId`.
 fn mir_build(tcx: TyCtxt<'_>, def: LocalDefId) -> Body<'_> {
     // Ensure unsafeck and abstract const building is ran before we steal the THIR.
-    tcx.ensure_with_value()
-        .thir_check_unsafety(tcx.typeck_root_def_id(def.to_def_id()).expect_local());
+    let typeck_root = tcx.typeck_root_def_id(def.to_def_id()).expect_local();
+    tcx.ensure_with_value().thir_check_unsafety(typeck_root);
+    if tcx.sess.opts.unstable_opts.unsafe_inventory.is_some() {
+        tcx.ensure_with_value().thir_unsafe_operations(typeck_root);
+    }
     tcx.ensure_with_value().thir_abstract_const(def);
     if let Err(e) = tcx.check_match(def) {
         return construct_error(tcx, def, e);
@@ -695,8 +698,10 @@ fn new(
         // however, they may not get codegen'd, depending on
         // the settings for the crate they are codegened in.
         let mut check_overflow = attr::contains_name(attrs, sym::rustc_inherit_overflow_checks);
-        // Respect -C overflow-checks.
-        check_overflow |= tcx.sess.overflow_checks();
+        // Respect `#[overflow_checks(on|off)]` on the enclosing items, then -C overflow-checks.
+        check_overflow |= tcx
+            .checks_attr_setting(def, sym::overflow_checks)
+            .unwrap_or_else(|| tcx.sess.overflow_checks());
         // Constants always need overflow checks.
         check_overflow |= matches!(
             tcx.hir().body_owner_kind(def),
@@ -34,6 +34,11 @@ struct UnsafetyVisitor<'a, 'tcx> {
     in_union_destructure: bool,
     param_env: ParamEnv<'tcx>,
     inside_adt: bool,
+    /// Whether to report errors and lints, which is not the case when only collecting the
+    /// unsafe operations for `-Zunsafe-inventory`.
+    emit_diagnostics: bool,
+    /// The operations allowed by an `unsafe` block or by the enclosing `unsafe fn`.
+    operations: Vec<UnsafeOperation>,
 }
 
 impl<'tcx> UnsafetyVisitor<'_, 'tcx> {
@@ -78,18 +83,22 @@ fn requires_unsafe(&mut self, span: Span, kind: UnsafeOpKind) {
         let unsafe_op_in_unsafe_fn_allowed = self.unsafe_op_in_unsafe_fn_allowed();
         match self.safety_context {
             SafetyContext::BuiltinUnsafeBlock => {}
-            SafetyContext::UnsafeBlock { ref mut used, .. } => {
+            SafetyContext::UnsafeBlock { ref mut used, hir_id, .. } => {
                 // Mark this block as useful (even inside `unsafe fn`, where it is technically
                 // redundant -- but we want to eventually enable `unsafe_op_in_unsafe_fn` by
                 // default which will require those blocks:
                 // https://github.com/rust-lang/rust/issues/71668#issuecomment-1203075594).
                 *used = true;
+                self.operations.push(kind.to_operation(span, Some(hir_id)));
             }
-            SafetyContext::UnsafeFn if unsafe_op_in_unsafe_fn_allowed => {}
             SafetyContext::UnsafeFn => {
-                // unsafe_op_in_unsafe_fn is disallowed
-                kind.emit_unsafe_op_in_unsafe_fn_lint(self.tcx, self.hir_context, span);
+                self.operations.push(kind.to_operation(span, None));
+                if !unsafe_op_in_unsafe_fn_allowed && self.emit_diagnostics {
+                    // unsafe_op_in_unsafe_fn is disallowed
+                    kind.emit_unsafe_op_in_unsafe_fn_lint(self.tcx, self.hir_context, span);
+                }
             }
+            SafetyContext::Safe if !self.emit_diagnostics => {}
             SafetyContext::Safe => {
                 kind.emit_requires_unsafe_err(
                     self.tcx,
@@ -107,6 +116,9 @@ fn warn_unused_unsafe(
         block_span: Span,
         enclosing_unsafe: Option<UnusedUnsafeEnclosing>,
     ) {
+        if !self.emit_diagnostics {
+            return;
+        }
         let block_span = self.tcx.sess.source_map().guess_head_span(block_span);
         self.tcx.emit_spanned_lint(
             UNUSED_UNSAFE,
@@ -126,10 +138,13 @@ fn visit_inner_body(&mut self, def: LocalDefId) {
         if let Ok((inner_thir, expr)) = self.tcx.thir_body(def) {
             let inner_thir = &inner_thir.borrow();
             let hir_context = self.tcx.hir().local_def_id_to_hir_id(def);
-            let mut inner_visitor = UnsafetyVisitor { thir: inner_thir, hir_context, ..*self };
+            let operations = std::mem::take(&mut self.operations);
+            let mut inner_visitor =
+                UnsafetyVisitor { thir: inner_thir, hir_context, operations, ..*self };
             inner_visitor.visit_expr(&inner_thir[expr]);
             // Unsafe blocks can be used in the inner body, make sure to take it into account
             self.safety_context = inner_visitor.safety_context;
+            self.operations = inner_visitor.operations;
         }
     }
 }
@@ -524,6 +539,26 @@ enum UnsafeOpKind {
 use UnsafeOpKind::*;
 
 impl UnsafeOpKind {
+    fn to_operation(self, span: Span, block: Option<hir::HirId>) -> UnsafeOperation {
+        let (description, callee) = match self {
+            CallToUnsafeFunction(did) => ("call to unsafe function", did),
+            UseOfInlineAssembly => ("use of inline assembly", None),
+            InitializingTypeWith => {
+                ("initializing type with `rustc_layout_scalar_valid_range` attr", None)
+            }
+            UseOfMutableStatic => ("use of mutable static", None),
+            UseOfExternStatic => ("use of extern static", None),
+            DerefOfRawPointer => ("dereference of raw pointer", None),
+            AccessToUnionField => ("access to union field", None),
+            MutationOfLayoutConstrainedField => ("mutation of layout constrained field", None),
+            BorrowOfLayoutConstrainedField => {
+                ("borrow of layout constrained field with interior mutability", None)
+            }
+            CallToFunctionWith(did) => ("call to function with `#[target_feature]`", Some(did)),
+        };
+        UnsafeOperation { span, description, callee, block }
+    }
+
     pub fn emit_unsafe_op_in_unsafe_fn_lint(
         &self,
         tcx: TyCtxt<'_>,
@@ -782,16 +817,25 @@ pub fn thir_check_unsafety(tcx: TyCtxt<'_>, def: LocalDefId) {
         return;
     }
 
+    check_body(tcx, def, true);
+}
+
+pub fn thir_unsafe_operations(tcx: TyCtxt<'_>, def: LocalDefId) -> &[UnsafeOperation] {
+    tcx.arena.alloc_from_iter(check_body(tcx, def, false))
+}
+
+/// Visits the body of `def`, returning the operations allowed by `unsafe` blocks and functions.
+fn check_body(tcx: TyCtxt<'_>, def: LocalDefId, emit_diagnostics: bool) -> Vec<UnsafeOperation> {
     // Closures and inline consts are handled by their owner, if it has a body
     if tcx.is_typeck_child(def.to_def_id()) {
-        return;
+        return Vec::new();
     }
 
-    let Ok((thir, expr)) = tcx.thir_body(def) else { return };
+    let Ok((thir, expr)) = tcx.thir_body(def) else { return Vec::new() };
     let thir = &thir.borrow();
     // If `thir` is empty, a type error occurred, skip this body.
     if thir.exprs.is_empty() {
-        return;
+        return Vec::new();
     }
 
     let hir_id = tcx.hir().local_def_id_to_hir_id(def);
@@ -816,6 +860,9 @@ pub fn thir_check_unsafety(tcx: TyCtxt<'_>, def: LocalDefId) {
         in_union_destructure: false,
         param_env: tcx.param_env(def),
         inside_adt: false,
+        emit_diagnostics,
+        operations: Vec::new(),
     };
     visitor.visit_expr(&thir[expr]);
+    visitor.operations
 }
@@ -11,6 +11,7 @@
 use rustc_middle::ty::{self, Ty};
 use rustc_span::symbol::Symbol;
 use rustc_span::Span;
+use std::path::Path;
 
 #[derive(LintDiagnostic)]
 #[diag(mir_build_unconditional_recursion)]
@@ -534,6 +535,13 @@ pub struct CouldNotEvalConstPattern {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(mir_build_could_not_write_unsafe_inventory)]
+pub struct CouldNotWriteUnsafeInventory<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(mir_build_lower_range_bound_must_be_less_than_or_equal_to_upper, code = "E0030")]
 pub struct LowerRangeBoundMustBeLessThanOrEqualToUpper {
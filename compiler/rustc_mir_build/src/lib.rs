@@ -21,6 +21,7 @@
 mod errors;
 pub mod lints;
 pub mod thir;
+pub mod unsafe_inventory;
 
 use rustc_middle::query::Providers;
 
@@ -36,6 +37,7 @@ pub fn provide(providers: &mut Providers) {
     providers.closure_saved_names_of_captured_variables =
         build::closure_saved_names_of_captured_variables;
     providers.thir_check_unsafety = check_unsafety::thir_check_unsafety;
+    providers.thir_unsafe_operations = check_unsafety::thir_unsafe_operations;
     providers.thir_body = thir::cx::thir_body;
     providers.thir_tree = thir::print::thir_tree;
     providers.thir_flat = thir::print::thir_flat;
@@ -347,6 +347,27 @@ fn make_mirror_unadjusted(&mut self, expr: &'tcx hir::Expr<'tcx>) -> Expr<'tcx>
                         }
                     }
 
+                    // `debug_assert!` calls the `debug_assertions` intrinsic, which is replaced
+                    // here by whether debug assertions are enabled for this body, so that
+                    // `#[debug_assertions(on|off)]` on the enclosing items is honored.
+                    let fun_ty = self.typeck_results().node_type(fun.hir_id);
+                    if let ty::FnDef(def_id, _) = *fun_ty.kind()
+                        && tcx.is_intrinsic(def_id)
+                        && tcx.item_name(def_id) == sym::debug_assertions
+                    {
+                        let body_owner = self.body_owner.expect_local();
+                        let enabled = tcx
+                            .checks_attr_setting(body_owner, sym::debug_assertions)
+                            .unwrap_or(tcx.sess.opts.debug_assertions);
+                        let lit = ScalarInt::from(enabled);
+                        return Expr {
+                            temp_lifetime,
+                            ty: expr_ty,
+                            span: expr.span,
+                            kind: ExprKind::NonHirLiteral { lit, user_ty: None },
+                        };
+                    }
+
                     // Tuple-like ADTs are represented as ExprKind::Call. We convert them here.
                     let adt_data = if let hir::ExprKind::Path(ref qpath) = fun.kind
                         && let Some(adt_def) = expr_ty.ty_adt_def()
@@ -0,0 +1,226 @@
+//! Inventory of the `unsafe` code of the crate, written by `-Zunsafe-inventory`.
+//!
+//! Every `unsafe` block, `unsafe fn`, `unsafe impl` and foreign function or static is listed along
+//! with the `// SAFETY:` comment preceding it. `unsafe` blocks and functions also list the
+//! operations they allow, as found by THIR unsafeck (see [`UnsafeOperation`]).
+
+use crate::errors::CouldNotWriteUnsafeInventory;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
+use rustc_hir::def_id::{LocalDefId, LOCAL_CRATE};
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::thir::UnsafeOperation;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{SourceFileAndLine, Span};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[derive(Serialize)]
+struct Inventory {
+    crate_name: String,
+    items: Vec<Item>,
+}
+
+#[derive(Serialize)]
+struct Item {
+    kind: ItemKind,
+    /// Path of the item, or of the body containing the `unsafe` block.
+    item: String,
+    location: Location,
+    /// Text of the `// SAFETY:` comment preceding the item, without the comment markers.
+    safety_comment: Option<String>,
+    operations: Vec<Operation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ItemKind {
+    UnsafeBlock,
+    UnsafeFn,
+    UnsafeImpl,
+    ExternFn,
+    ExternStatic,
+}
+
+#[derive(Serialize)]
+struct Operation {
+    description: &'static str,
+    callee: Option<String>,
+    location: Location,
+}
+
+#[derive(Serialize)]
+struct Location {
+    file: String,
+    line: usize,
+    column: usize,
+}
+
+pub fn write_unsafe_inventory(tcx: TyCtxt<'_>, path: &Path) {
+    let mut collector = Collector { tcx, items: Vec::new(), blocks: FxHashMap::default() };
+    tcx.hir().walk_toplevel_module(&mut collector);
+    let Collector { mut items, blocks, .. } = collector;
+
+    // Operations in `unsafe` blocks go to the block, the other ones are allowed by the
+    // `unsafe fn` they are in.
+    let fns: FxHashMap<_, _> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (_, def))| def.map(|def| (def, index)))
+        .collect();
+    for body_owner in tcx.hir().body_owners() {
+        for operation in tcx.thir_unsafe_operations(body_owner) {
+            let index = match operation.block {
+                Some(block) => blocks.get(&block),
+                None => fns.get(&body_owner),
+            };
+            if let Some(&index) = index {
+                items[index].0.operations.push(to_operation(tcx, operation));
+            }
+        }
+    }
+
+    let inventory = Inventory {
+        crate_name: tcx.crate_name(LOCAL_CRATE).to_string(),
+        items: items.into_iter().map(|(item, _)| item).collect(),
+    };
+    let result = File::create(path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, &inventory)?;
+        writer.flush()
+    });
+    if let Err(error) = result {
+        tcx.sess.emit_err(CouldNotWriteUnsafeInventory { path, error });
+    }
+}
+
+struct Collector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The items in source order, with the definition of the `unsafe fn` ones.
+    items: Vec<(Item, Option<LocalDefId>)>,
+    /// Index in `items` of each `unsafe` block.
+    blocks: FxHashMap<hir::HirId, usize>,
+}
+
+impl<'tcx> Collector<'tcx> {
+    fn push(&mut self, kind: ItemKind, def: LocalDefId, span: Span) -> Option<usize> {
+        if in_external_macro(self.tcx.sess, span) {
+            return None;
+        }
+        let unsafe_fn = matches!(kind, ItemKind::UnsafeFn).then_some(def);
+        let item = Item {
+            kind,
+            item: self.tcx.def_path_str(def),
+            location: location(self.tcx, span),
+            safety_comment: safety_comment(self.tcx, span),
+            operations: Vec::new(),
+        };
+        self.items.push((item, unsafe_fn));
+        Some(self.items.len() - 1)
+    }
+
+    fn push_fn(&mut self, sig: &hir::FnSig<'_>, def: LocalDefId, span: Span) {
+        if sig.header.unsafety == hir::Unsafety::Unsafe {
+            self.push(ItemKind::UnsafeFn, def, span);
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for Collector<'tcx> {
+    type NestedFilter = nested_filter::All;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_block(&mut self, block: &'tcx hir::Block<'tcx>) {
+        if let hir::BlockCheckMode::UnsafeBlock(hir::UnsafeSource::UserProvided) = block.rules {
+            let body_owner = self.tcx.hir().enclosing_body_owner(block.hir_id);
+            if let Some(index) = self.push(ItemKind::UnsafeBlock, body_owner, block.span) {
+                self.blocks.insert(block.hir_id, index);
+            }
+        }
+        intravisit::walk_block(self, block);
+    }
+
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        match item.kind {
+            hir::ItemKind::Fn(ref sig, ..) => self.push_fn(sig, item.owner_id.def_id, item.span),
+            hir::ItemKind::Impl(impl_) if impl_.unsafety == hir::Unsafety::Unsafe => {
+                self.push(ItemKind::UnsafeImpl, item.owner_id.def_id, item.span);
+            }
+            _ => {}
+        }
+        intravisit::walk_item(self, item);
+    }
+
+    fn visit_trait_item(&mut self, item: &'tcx hir::TraitItem<'tcx>) {
+        if let hir::TraitItemKind::Fn(ref sig, _) = item.kind {
+            self.push_fn(sig, item.owner_id.def_id, item.span);
+        }
+        intravisit::walk_trait_item(self, item);
+    }
+
+    fn visit_impl_item(&mut self, item: &'tcx hir::ImplItem<'tcx>) {
+        if let hir::ImplItemKind::Fn(ref sig, _) = item.kind {
+            self.push_fn(sig, item.owner_id.def_id, item.span);
+        }
+        intravisit::walk_impl_item(self, item);
+    }
+
+    fn visit_foreign_item(&mut self, item: &'tcx hir::ForeignItem<'tcx>) {
+        let kind = match item.kind {
+            hir::ForeignItemKind::Fn(..) => Some(ItemKind::ExternFn),
+            hir::ForeignItemKind::Static(..) => Some(ItemKind::ExternStatic),
+            hir::ForeignItemKind::Type => None,
+        };
+        if let Some(kind) = kind {
+            self.push(kind, item.owner_id.def_id, item.span);
+        }
+        intravisit::walk_foreign_item(self, item);
+    }
+}
+
+fn to_operation(tcx: TyCtxt<'_>, operation: &UnsafeOperation) -> Operation {
+    Operation {
+        description: operation.description,
+        callee: operation.callee.map(|callee| tcx.def_path_str(callee)),
+        location: location(tcx, operation.span),
+    }
+}
+
+fn location(tcx: TyCtxt<'_>, span: Span) -> Location {
+    let source_map = tcx.sess.source_map();
+    let loc = source_map.lookup_char_pos(span.lo());
+    Location {
+        file: source_map.filename_for_diagnostics(&loc.file.name).to_string(),
+        line: loc.line,
+        column: loc.col.0 + 1,
+    }
+}
+
+/// Returns the text following `SAFETY:` in the line comments right above `span`. Attributes and
+/// doc comments between the comment and `span` are skipped.
+fn safety_comment(tcx: TyCtxt<'_>, span: Span) -> Option<String> {
+    let SourceFileAndLine { sf, line } = tcx.sess.source_map().lookup_line(span.lo()).ok()?;
+
+    let mut comment = Vec::new();
+    for line in (0..line).rev() {
+        let text = sf.get_line(line)?;
+        let text = text.trim();
+        if text.starts_with("#[") || text.starts_with("///") || text.starts_with("//!") {
+            continue;
+        }
+        let Some(text) = text.strip_prefix("//") else { break };
+        comment.push(text.strip_prefix(' ').unwrap_or(text).to_string());
+    }
+    comment.reverse();
+
+    let start = comment.iter().position(|line| line.starts_with("SAFETY:"))?;
+    let text = comment[start..].join("\n");
+    Some(text["SAFETY:".len()..].trim().to_string())
+}
@@ -11,6 +11,7 @@
 use rustc_middle::ty::TypeVisitableExt;
 use rustc_middle::ty::{self, Instance, InstanceDef, ParamEnv, Ty, TyCtxt};
 use rustc_session::config::OptLevel;
+use rustc_span::sym;
 use rustc_target::abi::FieldIdx;
 use rustc_target::spec::abi::Abi;
 
@@ -430,6 +431,21 @@ fn check_codegen_attributes(
             return Err("incompatible sanitizer set");
         }
 
+        // Codegen keeps or removes the overflow checks of the inlined body according to the
+        // setting of the caller, so the callee must have the same one. Functions inheriting the
+        // overflow checks of their caller are fine, unless they have their own setting.
+        let crate_overflow_checks = self.tcx.sess.overflow_checks();
+        let own_overflow_checks =
+            CodegenFnAttrFlags::OVERFLOW_CHECKS_ON | CodegenFnAttrFlags::OVERFLOW_CHECKS_OFF;
+        let inherits_overflow_checks = !callee_attrs.flags.intersects(own_overflow_checks)
+            && self.tcx.has_attr(callsite.callee.def_id(), sym::rustc_inherit_overflow_checks);
+        if !inherits_overflow_checks
+            && callee_attrs.keeps_overflow_checks(crate_overflow_checks)
+                != self.codegen_fn_attrs.keeps_overflow_checks(crate_overflow_checks)
+        {
+            return Err("incompatible overflow checks");
+        }
+
         // Two functions are compatible if the callee has no attribute (meaning
         // that it's codegen agnostic), or sets an attribute that is identical
         // to this function's attribute.
@@ -92,6 +92,14 @@ passes_change_fields_to_be_of_unit_type =
      *[other] fields
     }
 
+passes_checks_attr_malformed =
+    malformed `#[{$attr_name}]` attribute
+    .help = use `#[{$attr_name}(on)]` or `#[{$attr_name}(off)]`
+
+passes_checks_attr_not_applicable =
+    `#[{$attr_name}]` should be applied to a function, closure, impl, trait or module
+    .label = not a function, closure, impl, trait or module
+
 passes_cold =
     {passes_should_be_applied_to_fn}
     .warn = {-passes_previously_accepted}
@@ -237,6 +245,9 @@ passes_doc_masked_only_extern_crate =
     .not_an_extern_crate_label = not an `extern crate` item
     .note = read <https://doc.rust-lang.org/unstable-book/language-features/doc-masked.html> for more information
 
+passes_doc_requirement_invalid =
+    `{$requirement}` is not a valid requirement ID, as it contains whitespace
+
 passes_doc_test_literal = `#![doc(test(...)]` does not take a literal
 
 passes_doc_test_takes_list =
@@ -682,6 +693,10 @@ passes_skipping_const_checks = skipping const checks
 passes_stability_promotable =
     attribute cannot be applied to an expression
 
+passes_stack_overflow_handler_signature =
+    `#[stack_overflow_handler]` function must have the signature `extern "C" fn() -> !`
+    .label = incorrect signature
+
 passes_string_interpolation_only_works = string interpolation only works in `format!` invocations
 
 passes_target_feature_on_statement =
@@ -147,7 +147,7 @@ fn check_attributes(
                     self.check_rustc_std_internal_symbol(&attr, span, target)
                 }
                 sym::naked => self.check_naked(hir_id, attr, span, target),
-                sym::rustc_never_returns_null_ptr => {
+                sym::rustc_never_returns_null_ptr | sym::no_panic => {
                     self.check_applied_to_fn_or_method(hir_id, attr, span, target)
                 }
                 sym::rustc_legacy_const_generics => {
@@ -175,6 +175,12 @@ fn check_attributes(
                     self.check_cmse_nonsecure_entry(hir_id, attr, span, target)
                 }
                 sym::collapse_debuginfo => self.check_collapse_debuginfo(attr, span, target),
+                sym::overflow_checks | sym::debug_assertions => {
+                    self.check_checks_attr(attr, span, target)
+                }
+                sym::stack_overflow_handler => {
+                    self.check_stack_overflow_handler(hir_id, attr, span, target)
+                }
                 sym::must_not_suspend => self.check_must_not_suspend(&attr, span, target),
                 sym::must_use => self.check_must_use(hir_id, &attr, target),
                 sym::rustc_pass_by_value => self.check_pass_by_value(&attr, span, target),
@@ -501,6 +507,75 @@ fn check_collapse_debuginfo(&self, attr: &Attribute, span: Span, target: Target)
         }
     }
 
+    /// Checks if `#[overflow_checks]` or `#[debug_assertions]` is set to `on` or `off`, and applied
+    /// to an item which can contain code. Returns `true` if valid.
+    fn check_checks_attr(&self, attr: &Attribute, span: Span, target: Target) -> bool {
+        let attr_name = attr.name_or_empty();
+        let valid_target = match target {
+            Target::Fn
+            | Target::Closure
+            | Target::Method(
+                MethodKind::Trait { body: true } | MethodKind::Inherent | MethodKind::TraitImpl,
+            )
+            | Target::Impl
+            | Target::Trait
+            | Target::Mod => true,
+            _ => {
+                self.tcx.sess.emit_err(errors::ChecksAttrNotApplicable {
+                    attr_span: attr.span,
+                    defn_span: span,
+                    attr_name,
+                });
+                false
+            }
+        };
+
+        let valid_setting = match attr.meta_item_list().as_deref() {
+            Some([item]) => item.has_name(sym::on) || item.has_name(sym::off),
+            // Other forms are rejected by the attribute template.
+            None => true,
+            _ => false,
+        };
+        if !valid_setting {
+            self.tcx.sess.emit_err(errors::ChecksAttrMalformed { attr_span: attr.span, attr_name });
+        }
+
+        valid_target && valid_setting
+    }
+
+    /// Checks if `#[stack_overflow_handler]` is applied to a non-generic `extern "C" fn() -> !`.
+    /// Returns `true` if valid.
+    fn check_stack_overflow_handler(
+        &self,
+        hir_id: HirId,
+        attr: &Attribute,
+        span: Span,
+        target: Target,
+    ) -> bool {
+        if target != Target::Fn {
+            self.tcx.sess.emit_err(errors::AttrShouldBeAppliedToFn {
+                attr_span: attr.span,
+                defn_span: span,
+                on_crate: hir_id == CRATE_HIR_ID,
+            });
+            return false;
+        }
+
+        let def_id = hir_id.expect_owner().def_id;
+        let sig = self.tcx.fn_sig(def_id).instantiate_identity().skip_binder();
+        if sig.abi != (Abi::C { unwind: false })
+            || !sig.inputs().is_empty()
+            || !sig.output().is_never()
+            || self.tcx.generics_of(def_id).count() != 0
+        {
+            self.tcx
+                .sess
+                .emit_err(errors::StackOverflowHandlerSignature { attr_span: attr.span, span });
+            return false;
+        }
+        true
+    }
+
     /// Checks if a `#[track_caller]` is applied to a non-naked function. Returns `true` if valid.
     fn check_track_caller(
         &self,
@@ -846,6 +921,22 @@ fn check_doc_keyword(&self, meta: &NestedMetaItem, hir_id: HirId) -> bool {
         true
     }
 
+    fn check_doc_requirement(&self, meta: &NestedMetaItem) -> bool {
+        let requirement = meta.value_str().unwrap_or(kw::Empty);
+        if requirement == kw::Empty {
+            self.doc_attr_str_error(meta, "requirement");
+            return false;
+        }
+        if requirement.as_str().contains(char::is_whitespace) {
+            self.tcx.sess.emit_err(errors::DocRequirementInvalid {
+                span: meta.name_value_literal_span().unwrap_or_else(|| meta.span()),
+                requirement,
+            });
+            return false;
+        }
+        true
+    }
+
     fn check_doc_fake_variadic(&self, meta: &NestedMetaItem, hir_id: HirId) -> bool {
         match self.tcx.hir().find(hir_id).and_then(|node| match node {
             hir::Node::Item(item) => Some(&item.kind),
@@ -1139,6 +1230,10 @@ fn check_doc_attrs(
                             is_valid = false;
                         }
 
+                        sym::requirement if !self.check_doc_requirement(meta) => {
+                            is_valid = false;
+                        }
+
                         // no_default_passes: deprecated
                         // passes: deprecated
                         // plugins: removed, but rustdoc warns about it itself
@@ -1160,6 +1255,7 @@ fn check_doc_attrs(
                         | sym::notable_trait
                         | sym::passes
                         | sym::plugins
+                        | sym::requirement
                         | sym::fake_variadic => {}
 
                         sym::rust_logo => {
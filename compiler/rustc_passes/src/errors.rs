@@ -227,6 +227,14 @@ pub struct DocKeywordNotMod {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_doc_requirement_invalid)]
+pub struct DocRequirementInvalid {
+    #[primary_span]
+    pub span: Span,
+    pub requirement: Symbol,
+}
+
 #[derive(Diagnostic)]
 #[diag(passes_doc_keyword_invalid_ident)]
 pub struct DocKeywordInvalidIdent {
@@ -790,6 +798,34 @@ pub struct CollapseDebuginfo {
     pub defn_span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_checks_attr_not_applicable)]
+pub struct ChecksAttrNotApplicable {
+    #[primary_span]
+    pub attr_span: Span,
+    #[label]
+    pub defn_span: Span,
+    pub attr_name: Symbol,
+}
+
+#[derive(Diagnostic)]
+#[diag(passes_checks_attr_malformed)]
+#[help]
+pub struct ChecksAttrMalformed {
+    #[primary_span]
+    pub attr_span: Span,
+    pub attr_name: Symbol,
+}
+
+#[derive(Diagnostic)]
+#[diag(passes_stack_overflow_handler_signature)]
+pub struct StackOverflowHandlerSignature {
+    #[primary_span]
+    pub attr_span: Span,
+    #[label]
+    pub span: Span,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(passes_deprecated_annotation_has_no_effect)]
 pub struct DeprecatedAnnotationHasNoEffect {
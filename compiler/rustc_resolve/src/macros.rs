@@ -147,6 +147,11 @@ pub(crate) fn registered_tools(tcx: TyCtxt<'_>, (): ()) -> RegisteredTools {
     // but it's not an error to register them explicitly.
     let predefined_tools = [sym::clippy, sym::rustfmt, sym::diagnostic];
     registered_tools.extend(predefined_tools.iter().cloned().map(Ident::with_dummy_span));
+    // The `ferrocene` tool (`#[ferrocene::certified]` and the `ferrocene::uncertified` lint) is
+    // only known to crates opting into the unstable feature.
+    if tcx.features().ferrocene_certified {
+        registered_tools.insert(Ident::with_dummy_span(sym::ferrocene));
+    }
     registered_tools
 }
 
@@ -543,6 +543,8 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A SARIF 2.1.0 log, consumed by static analysis tools.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -1786,7 +1788,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "",
             "error-format",
             "How errors and other messages are produced",
-            "human|json|short",
+            "human|json|short|sarif",
         ),
         opt::multi_s("", "json", "Configure the JSON output of the compiler", "CONFIG"),
         opt::opt_s(
@@ -1958,6 +1960,7 @@ pub fn parse_error_format(
             }
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
+            Some("sarif") => ErrorOutputType::Sarif,
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
 
             Some(arg) => {
@@ -1965,8 +1968,8 @@ pub fn parse_error_format(
                     HumanReadableErrorType::Default(color),
                 ));
                 handler.early_error(format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{arg}`)"
+                    "argument for `--error-format` must be `human`, `json`, \
+                     `short` or `sarif` (instead was `{arg}`)"
                 ))
             }
         }
@@ -2039,6 +2042,13 @@ fn check_error_format_stability(
             });
             handler.early_error("`--error-format=human-annotate-rs` is unstable");
         }
+        if let ErrorOutputType::Sarif = error_format {
+            handler.abort_if_error_and_set_error_format(ErrorOutputType::Json {
+                pretty: false,
+                json_rendered,
+            });
+            handler.early_error("`--error-format=sarif` is unstable");
+        }
     }
 }
 
@@ -3186,7 +3196,8 @@ pub(crate) mod dep_tracking {
     use rustc_span::RealFileName;
     use rustc_target::spec::{CodeModel, MergeFunctions, PanicStrategy, RelocModel};
     use rustc_target::spec::{
-        RelroLevel, SanitizerSet, SplitDebuginfo, StackProtector, TargetTriple, TlsModel,
+        RelroLevel, SanitizerSet, SplitDebuginfo, StackLimit, StackProtector, TargetTriple,
+        TlsModel,
     };
     use std::collections::hash_map::DefaultHasher;
     use std::collections::BTreeMap;
@@ -3265,6 +3276,7 @@ fn hash(
         ResolveDocLinks,
         SplitDebuginfo,
         SplitDwarfKind,
+        StackLimit,
         StackProtector,
         SwitchWithOptPath,
         SymbolManglingVersion,
@@ -9,7 +9,7 @@
 use rustc_errors::{LanguageIdentifier, TerminalUrl};
 use rustc_target::spec::{CodeModel, LinkerFlavorCli, MergeFunctions, PanicStrategy, SanitizerSet};
 use rustc_target::spec::{
-    RelocModel, RelroLevel, SplitDebuginfo, StackProtector, TargetTriple, TlsModel,
+    RelocModel, RelroLevel, SplitDebuginfo, StackLimit, StackProtector, TargetTriple, TlsModel,
 };
 
 use rustc_feature::UnstableFeatures;
@@ -420,6 +420,7 @@ mod desc {
     pub const parse_link_self_contained: &str = "one of: `y`, `yes`, `on`, `n`, `no`, `off`, or a list of enabled (`+` prefix) and disabled (`-` prefix) \
         components: `crto`, `libc`, `unwind`, `linker`, `sanitizers`, `mingw`";
     pub const parse_polonius: &str = "either no value or `legacy` (the default), or `next`";
+    pub const parse_stack_limit: &str = "one of `none`, `symbol:<name>`, or `register:<name>`";
     pub const parse_stack_protector: &str =
         "one of (`none` (default), `basic`, `strong`, or `all`)";
     pub const parse_branch_protection: &str =
@@ -1222,6 +1223,14 @@ pub(crate) fn parse_split_dwarf_kind(slot: &mut SplitDwarfKind, v: Option<&str>)
         true
     }
 
+    pub(crate) fn parse_stack_limit(slot: &mut Option<StackLimit>, v: Option<&str>) -> bool {
+        match v.and_then(|s| StackLimit::from_str(s).ok()) {
+            Some(limit) => *slot = Some(limit),
+            _ => return false,
+        }
+        true
+    }
+
     pub(crate) fn parse_stack_protector(slot: &mut StackProtector, v: Option<&str>) -> bool {
         match v.and_then(|s| StackProtector::from_str(s).ok()) {
             Some(ssp) => *slot = ssp,
@@ -1444,6 +1453,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "emit noalias metadata for box (default: yes)"),
     branch_protection: Option<BranchProtection> = (None, parse_branch_protection, [TRACKED],
         "set options for branch target identification and pointer authentication on AArch64"),
+    certified_calls: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the functions of the certified subset of the standard library called by each \
+        test to a JSON file in the given directory"),
     cf_protection: CFProtection = (CFProtection::None, parse_cfprotection, [TRACKED],
         "instrument control-flow architecture protection"),
     codegen_backend: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1595,6 +1607,14 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    linker_map_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "compare the `-Z linker-map-report` with the report of a previous build at the given path"),
+    linker_map_report: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a JSON report of the size of each crate, module and generic item linked in the \
+        output to the given path, from the map file produced by the linker"),
+    lint_inventory: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a JSON inventory of the lint level attributes and command-line lint flags \
+        to the given path"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1736,6 +1756,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
 written to standard error output)"),
     report_delayed_bugs: bool = (false, parse_bool, [TRACKED],
         "immediately print bugs registered with `delay_span_bug` (default: no)"),
+    require_lint_reasons: bool = (false, parse_bool, [TRACKED],
+        "require a `reason` on the attributes allowing, expecting or downgrading lints \
+        (default: no)"),
     sanitizer: SanitizerSet = (SanitizerSet::empty(), parse_sanitizers, [TRACKED],
         "use a sanitizer"),
     sanitizer_cfi_canonical_jump_tables: Option<bool> = (Some(true), parse_opt_bool, [TRACKED],
@@ -1766,6 +1789,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         for example: `-Z self-profile-events=default,query-keys`
         all options: none, all, default, generic-activity, query-provider, query-cache-hit
                      query-blocked, incr-cache-load, incr-result-hashing, query-keys, function-args, args, llvm, artifact-sizes"),
+    semihosting_tests: bool = (false, parse_bool, [TRACKED],
+        "compile tests with the `no_std` test harness reporting over Arm semihosting \
+        (default: no)"),
     share_generics: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "make the current crate share its generic instantiations"),
     show_span: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1793,6 +1819,10 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "enable LTO unit splitting (default: no)"),
     src_hash_algorithm: Option<SourceFileHashAlgorithm> = (None, parse_src_file_hash, [TRACKED],
         "hash algorithm of source files in debug info (`md5`, `sha1`, or `sha256`)"),
+    #[rustc_lint_opt_deny_field_access("use `Session::stack_limit` instead of this field")]
+    stack_limit: Option<StackLimit> = (None, parse_stack_limit, [TRACKED],
+        "check the stack pointer against a limit in function prologues, overriding the target \
+        (`none`, `symbol:<name>`, or `register:<name>`)"),
     #[rustc_lint_opt_deny_field_access("use `Session::stack_protector` instead of this field")]
     stack_protector: StackProtector = (StackProtector::None, parse_stack_protector, [TRACKED],
         "control stack smash protection strategy (`rustc --print stack-protector-strategies` for details)"),
@@ -1881,6 +1911,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         `hir,typed` (HIR with types for each node),
         `hir-tree` (dump the raw HIR),
         `mir` (the MIR), or `mir-cfg` (graphviz formatted MIR)"),
+    unsafe_inventory: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a JSON inventory of the `unsafe` blocks, functions, impls and extern items, \
+        with the operations they allow and their `SAFETY` comments, to the given path"),
     unsound_mir_opts: bool = (false, parse_bool, [TRACKED],
         "enable unsound and buggy MIR optimizations (default: no)"),
     /// This name is kind of confusing: Most unstable options enable something themselves, while
@@ -12,7 +12,7 @@
 pub use rustc_ast::attr::MarkedAttrs;
 pub use rustc_ast::Attribute;
 use rustc_data_structures::flock;
-use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexSet};
 use rustc_data_structures::jobserver::{self, Client};
 use rustc_data_structures::profiling::{duration_to_secs_str, SelfProfiler, SelfProfilerRef};
 use rustc_data_structures::sync::{
@@ -22,6 +22,7 @@
 use rustc_errors::emitter::{DynEmitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
     error_code, fallback_fluent_bundle, DiagnosticBuilder, DiagnosticId, DiagnosticMessage,
     ErrorGuaranteed, FluentBundle, Handler, IntoDiagnostic, LazyFallbackBundle, MultiSpan, Noted,
@@ -35,7 +36,8 @@
 use rustc_target::asm::InlineAsmArch;
 use rustc_target::spec::{CodeModel, PanicStrategy, RelocModel, RelroLevel};
 use rustc_target::spec::{
-    DebuginfoKind, SanitizerSet, SplitDebuginfo, StackProtector, Target, TargetTriple, TlsModel,
+    DebuginfoKind, SanitizerSet, SplitDebuginfo, StackLimit, StackProtector, Target, TargetTriple,
+    TlsModel,
 };
 
 use std::cell::{self, RefCell};
@@ -206,6 +208,10 @@ pub struct Session {
     /// This is mainly useful for other tools that reads that debuginfo to figure out
     /// how to call the compiler with the same arguments.
     pub expanded_args: Vec<String>,
+
+    /// Lint expectations fulfilled during the analysis, copied for `-Z lint-inventory` before the
+    /// diagnostic handler gives them up to check the expectations.
+    pub fulfilled_lint_expectations: Lock<FxHashSet<lint::LintExpectationId>>,
 }
 
 pub struct PerfStats {
@@ -1128,6 +1134,12 @@ pub fn stack_protector(&self) -> StackProtector {
         }
     }
 
+    /// The software stack limit check to emit in function prologues, set by `-Z stack-limit` or
+    /// by the target.
+    pub fn stack_limit(&self) -> &StackLimit {
+        self.opts.unstable_opts.stack_limit.as_ref().unwrap_or(&self.target.stack_limit)
+    }
+
     pub fn must_emit_unwind_tables(&self) -> bool {
         // This is used to control the emission of the `uwtable` attribute on
         // LLVM functions.
@@ -1316,6 +1328,9 @@ fn default_emitter(
                 sopts.unstable_opts.ignore_directory_in_diagnostics_source_blocks.clone(),
             ),
         ),
+        config::ErrorOutputType::Sarif => {
+            Box::new(SarifEmitter::stderr(Some(registry), source_map, bundle, fallback_bundle))
+        }
     }
 }
 
@@ -1470,6 +1485,7 @@ pub fn build_session(
         unstable_target_features: Default::default(),
         cfg_version,
         expanded_args,
+        fulfilled_lint_expectations: Default::default(),
     };
 
     validate_commandline_args_with_session_available(&sess);
@@ -1749,6 +1765,7 @@ fn mk_emitter(output: ErrorOutputType) -> Box<DynEmitter> {
             false,
             TerminalUrl::No,
         )),
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::basic(None, fallback_bundle)),
     };
     emitter
 }
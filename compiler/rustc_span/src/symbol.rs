@@ -483,6 +483,7 @@
         cdylib,
         ceilf32,
         ceilf64,
+        certified,
         cfg,
         cfg_accessible,
         cfg_attr,
@@ -674,6 +675,7 @@
         doc_masked,
         doc_notable_trait,
         doc_primitive,
+        doc_requirement,
         doc_spotlight,
         doctest,
         document_private_items,
@@ -752,6 +754,8 @@
         feature,
         fence,
         ferris: "🦀",
+        ferrocene,
+        ferrocene_certified,
         fetch_update,
         ffi,
         ffi_const,
@@ -903,6 +907,7 @@
         issue_5723_bootstrap,
         issue_tracker_base_url,
         item,
+        item_level_checks,
         item_like_imports,
         iter,
         iter_mut,
@@ -1079,6 +1084,7 @@
         no_link,
         no_main,
         no_mangle,
+        no_panic,
         no_sanitize,
         no_stack_check,
         no_start,
@@ -1294,6 +1300,7 @@
         repr_simd,
         repr_transparent,
         require,
+        requirement,
         residual,
         result,
         resume,
@@ -1539,6 +1546,7 @@
         sse,
         sse4a_target_feature,
         stable,
+        stack_overflow_handler,
         staged_api,
         start,
         state,
@@ -1605,6 +1613,7 @@
         test_case,
         test_removed_feature,
         test_runner,
+        test_semihosting,
         test_unstable_lint,
         thread,
         thread_local,
@@ -1119,6 +1119,92 @@ fn to_json(&self) -> Json {
     }
 }
 
+/// Software check of the stack pointer against a limit in the prologue of every function, for
+/// targets without a guard page to catch stack overflows.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StackLimit {
+    /// Don't check the stack pointer.
+    None,
+    /// Compare the stack pointer with the address of the given symbol, usually defined by the
+    /// linker script.
+    Symbol(StaticCow<str>),
+    /// Compare the stack pointer with the value of the given register, which must be reserved.
+    Register(StaticCow<str>),
+}
+
+impl StackLimit {
+    /// Symbol of the function called when the stack limit is exceeded, which is defined with
+    /// `#[stack_overflow_handler]`.
+    pub const HANDLER_SYMBOL: &'static str = "__rust_stack_overflow";
+
+    /// Stack that must remain above the limit once a function allocated its frame, in bytes.
+    ///
+    /// The check can only run after the prologue, which already saved registers in the new frame.
+    /// Keeping this much stack available for the prologues of the callees ensures that they never
+    /// write below the limit before their own check. It covers the largest area written by the
+    /// prologues of the supported architectures before the frame is complete, which is 512 bytes
+    /// on AArch64 when the saved registers and the frame are allocated at once.
+    pub const PROLOGUE_RESERVE: u64 = 512;
+
+    fn from_json(json: &Json) -> Result<Self, String> {
+        let object = json.as_object().ok_or_else(|| "expected a JSON object")?;
+        let kind = object
+            .get("kind")
+            .and_then(|o| o.as_str())
+            .ok_or_else(|| "expected `kind` to be a string")?;
+        let name = || {
+            object
+                .get("name")
+                .and_then(|o| o.as_str())
+                .map(|name| name.to_string().into())
+                .ok_or_else(|| String::from("expected `name` to be a string"))
+        };
+        match kind {
+            "none" => Ok(StackLimit::None),
+            "symbol" => Ok(StackLimit::Symbol(name()?)),
+            "register" => Ok(StackLimit::Register(name()?)),
+            _ => Err(String::from("`kind` expected to be one of `none`, `symbol` or `register`")),
+        }
+    }
+}
+
+impl FromStr for StackLimit {
+    type Err = ();
+
+    /// Parses `none`, `symbol:<name>` or `register:<name>`.
+    fn from_str(s: &str) -> Result<StackLimit, ()> {
+        match s.split_once(':') {
+            None if s == "none" => Ok(StackLimit::None),
+            Some(("symbol", name)) if !name.is_empty() => {
+                Ok(StackLimit::Symbol(name.to_string().into()))
+            }
+            Some(("register", name)) if !name.is_empty() => {
+                Ok(StackLimit::Register(name.to_string().into()))
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+impl ToJson for StackLimit {
+    fn to_json(&self) -> Json {
+        Json::Object(match self {
+            StackLimit::None => [(String::from("kind"), "none".to_json())].into_iter().collect(),
+            StackLimit::Symbol(name) => {
+                [(String::from("kind"), "symbol".to_json()), (String::from("name"), name.to_json())]
+                    .into_iter()
+                    .collect()
+            }
+            StackLimit::Register(name) => [
+                (String::from("kind"), "register".to_json()),
+                (String::from("name"), name.to_json()),
+            ]
+            .into_iter()
+            .collect(),
+        })
+    }
+}
+
 bitflags::bitflags! {
     #[derive(Default, Encodable, Decodable)]
     pub struct SanitizerSet: u16 {
@@ -1964,6 +2050,9 @@ pub struct TargetOptions {
     /// The implementation of stack probes to use.
     pub stack_probes: StackProbeType,
 
+    /// The software stack limit check to emit in function prologues.
+    pub stack_limit: StackLimit,
+
     /// The minimum alignment for global symbols.
     pub min_global_align: Option<u64>,
 
@@ -2274,6 +2363,7 @@ fn default() -> TargetOptions {
             crt_static_default: false,
             crt_static_respected: false,
             stack_probes: StackProbeType::None,
+            stack_limit: StackLimit::None,
             min_global_align: None,
             default_codegen_units: None,
             trap_unreachable: true,
@@ -2700,6 +2790,18 @@ macro_rules! key {
                     )),
                 }).unwrap_or(Ok(()))
             } );
+            ($key_name:ident, StackLimit) => ( {
+                let name = (stringify!($key_name)).replace("_", "-");
+                obj.remove(&name).and_then(|o| match StackLimit::from_json(&o) {
+                    Ok(v) => {
+                        base.$key_name = v;
+                        Some(Ok(()))
+                    },
+                    Err(s) => Some(Err(
+                        format!("`{:?}` is not a valid value for `{}`: {}", o, name, s)
+                    )),
+                }).unwrap_or(Ok(()))
+            } );
             ($key_name:ident, SanitizerSet) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
                 if let Some(o) = obj.remove(&name) {
@@ -2939,6 +3041,7 @@ macro_rules! key {
         key!(crt_static_default, bool);
         key!(crt_static_respected, bool);
         key!(stack_probes, StackProbeType)?;
+        key!(stack_limit, StackLimit)?;
         key!(min_global_align, Option<u64>);
         key!(default_codegen_units, Option<u64>);
         key!(trap_unreachable, bool);
@@ -3196,6 +3299,7 @@ macro_rules! target_option_val {
         target_option_val!(crt_static_default);
         target_option_val!(crt_static_respected);
         target_option_val!(stack_probes);
+        target_option_val!(stack_limit);
         target_option_val!(min_global_align);
         target_option_val!(default_codegen_units);
         target_option_val!(trap_unreachable);
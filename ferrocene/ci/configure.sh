@@ -245,6 +245,12 @@ add --set ferrocene.traceability-matrix-mode=ci
 # signed, and will not be compatible with criticalup.
 add --set ferrocene.tarball-signing-kms-key-arn="arn:aws:kms:us-east-1:886866542769:key/cfbd0673-04d8-4368-b09f-56998ede9b96"
 
+# Generate the SPDX and CycloneDX SBOMs of the packages produced by CI.
+#
+# If this configuration is missing the release will not include SBOMs, which
+# are required by customers for every tool in their safety lifecycle.
+add --set ferrocene.sbom=true
+
 # Download the correct version of the OxidOS source code from our mirrors bucket.
 #
 # If this configuration is missing, building OxidOS will fail as the source
@@ -62,6 +62,46 @@ class IgnoredTestsDirective(SphinxDirective):
             return [note]
 
 
+class PassedOnRetryTestsDirective(SphinxDirective):
+    has_content = False
+
+    def run(self):
+        outcomes = self.env.ferrocene_test_outcomes
+        if outcomes is None:
+            return error(
+                "The ",
+                literal("ferrocene.test_outcomes_dir"),
+                " setting in ",
+                literal("config.toml"),
+                " was not set. The list of tests passed on retry won't be populated",
+                " until you add the setting and store the relevant build metrics",
+                " files in the directory.",
+            )
+
+        table = RenderTable(2)
+        table.add_row(
+            paragraph("Test name"),
+            paragraph("Attempts"),
+            head=True,
+        )
+        for suite in outcomes.suites.values():
+            for test, attempts in suite.passed_on_retry.items():
+                table.add_row(paragraph(literal(test)), paragraph(str(attempts)))
+
+        if len(table.body.children):
+            return [
+                paragraph(
+                    f"{len(table.body.children)} tests only passed after being "
+                    "retried:"
+                ),
+                table.finalize(),
+            ]
+        else:
+            note = nodes.note()
+            note += paragraph("No tests were retried as part of this qualification.")
+            return [note]
+
 
 def setup(app):
     app.add_directive("ignored-tests", IgnoredTestsDirective)
+    app.add_directive("passed-on-retry-tests", PassedOnRetryTestsDirective)
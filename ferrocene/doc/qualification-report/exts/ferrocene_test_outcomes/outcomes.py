@@ -57,6 +57,9 @@ class Outcomes:
         self.suites = OrderedDict(sorted(self.suites.items(), key=lambda kv: kv[0]))
         for suite in self.suites.values():
             suite.invocations.sort(key=lambda invocation: invocation.debug_repr)
+            suite.passed_on_retry = OrderedDict(
+                sorted(suite.passed_on_retry.items(), key=lambda kv: kv[0])
+            )
             suite.ignored_tests = OrderedDict(
                 sorted(suite.ignored_tests.items(), key=lambda kv: kv[0])
             )
@@ -99,6 +102,10 @@ class InvocationCollector:
                     elif outcome == "passed":
                         invocation.passed_tests += 1
                         self._suite.ignored_tests[test["name"]] = TEST_EXECUTED
+                        # Flaky tests that were retried have every attempt recorded.
+                        attempts = test.get("attempts", [])
+                        if len(attempts) > 1:
+                            self._suite.passed_on_retry[test["name"]] = len(attempts)
                     else:
                         raise RuntimeError(f"unexpected outcome: {outcome}")
 
@@ -149,6 +156,9 @@ class Suite:
     ignored_tests: OrderedDict[str, Union[str, object]] = field(
         default_factory=OrderedDict
     )
+    # Tests that failed before passing on retry, along with the number of
+    # attempts it took for them to pass.
+    passed_on_retry: OrderedDict[str, int] = field(default_factory=OrderedDict)
 
 
 def builder_inited(app):
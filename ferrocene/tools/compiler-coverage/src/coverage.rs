@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+use anyhow::{Context, Error};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Subset of the JSON document emitted by `llvm-cov export -summary-only`.
+#[derive(Deserialize)]
+struct Export {
+    data: Vec<ExportData>,
+}
+
+#[derive(Deserialize)]
+struct ExportData {
+    files: Vec<ExportFile>,
+}
+
+#[derive(Deserialize)]
+struct ExportFile {
+    filename: String,
+    summary: Summary,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Counts {
+    pub(crate) count: u64,
+    pub(crate) covered: u64,
+}
+
+impl Counts {
+    pub(crate) fn percentage(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.covered as f64 * 100.0 / self.count as f64
+        }
+    }
+
+    fn add(&mut self, other: &Counts) {
+        self.count += other.count;
+        self.covered += other.covered;
+    }
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Summary {
+    pub(crate) lines: Counts,
+    pub(crate) functions: Counts,
+    pub(crate) regions: Counts,
+}
+
+impl Summary {
+    pub(crate) fn add(&mut self, other: &Summary) {
+        self.lines.add(&other.lines);
+        self.functions.add(&other.functions);
+        self.regions.add(&other.regions);
+    }
+}
+
+/// Coverage of each crate in `compiler/`, keyed by crate name.
+pub(crate) type CrateCoverage = BTreeMap<String, Summary>;
+
+/// Parses the output of `llvm-cov export -summary-only`, grouping the files by the compiler crate
+/// they belong to. Files outside of `compiler/`, like the ones of the standard library or of
+/// external dependencies, are ignored.
+pub(crate) fn parse_export(json: &[u8], src_base: &Path) -> Result<CrateCoverage, Error> {
+    let export: Export =
+        serde_json::from_slice(json).context("failed to parse the llvm-cov export")?;
+
+    let mut crates = CrateCoverage::new();
+    for file in export.data.iter().flat_map(|data| &data.files) {
+        let Some(krate) = compiler_crate(&file.filename, src_base) else { continue };
+        crates.entry(krate.into()).or_default().add(&file.summary);
+    }
+    Ok(crates)
+}
+
+/// Returns the name of the compiler crate a source file belongs to. The paths recorded in the
+/// coverage mapping are either absolute or, when `rust.remap-debuginfo` is enabled, relative to
+/// `/rustc/<commit>/`.
+fn compiler_crate<'a>(filename: &'a str, src_base: &Path) -> Option<&'a str> {
+    let relative = match Path::new(filename).strip_prefix(src_base) {
+        Ok(relative) => relative,
+        Err(_) => {
+            let mut components = Path::new(filename).strip_prefix("/rustc").ok()?.iter();
+            components.next()?;
+            components.as_path()
+        }
+    };
+    let mut components = relative.iter();
+    if components.next()? != "compiler" {
+        return None;
+    }
+    components.next()?.to_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(filename: &str, lines: (u64, u64)) -> serde_json::Value {
+        let counts = |(count, covered)| serde_json::json!({ "count": count, "covered": covered });
+        serde_json::json!({
+            "filename": filename,
+            "summary": {
+                "lines": counts(lines),
+                "functions": counts((2, 1)),
+                "regions": counts((4, 3)),
+            },
+        })
+    }
+
+    #[test]
+    fn test_parse_export() -> Result<(), Error> {
+        let export = serde_json::json!({
+            "type": "llvm.coverage.json.export",
+            "version": "2.0.1",
+            "data": [{
+                "files": [
+                    file("/src/compiler/rustc_parse/src/lib.rs", (100, 50)),
+                    file("/src/compiler/rustc_parse/src/parser/mod.rs", (300, 250)),
+                    file("/rustc/0123456789/compiler/rustc_lexer/src/lib.rs", (10, 10)),
+                    file("/rustc/0123456789/library/core/src/option.rs", (10, 1)),
+                    file("/home/user/.cargo/registry/src/index/smallvec/src/lib.rs", (10, 0)),
+                ],
+            }],
+        });
+        let crates = parse_export(&serde_json::to_vec(&export)?, Path::new("/src"))?;
+
+        assert_eq!(vec!["rustc_lexer", "rustc_parse"], crates.keys().collect::<Vec<_>>());
+        let parse = &crates["rustc_parse"];
+        assert_eq!(Counts { count: 400, covered: 300 }, parse.lines);
+        assert_eq!(Counts { count: 4, covered: 2 }, parse.functions);
+        assert_eq!(Counts { count: 8, covered: 6 }, parse.regions);
+        assert_eq!(75.0, parse.lines.percentage());
+        assert_eq!(Counts { count: 10, covered: 10 }, crates["rustc_lexer"].lines);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentage_of_nothing() {
+        assert_eq!(0.0, Counts::default().percentage());
+    }
+}
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Coverage report of the compiler itself, built from the profiles an instrumented compiler wrote
+//! while executing the test suites.
+//!
+//! The profiles of each suite are merged and exported with the LLVM tools, and the coverage of
+//! each crate in `compiler/` is reported for every suite and for all the suites combined.
+
+mod coverage;
+mod report;
+
+use crate::coverage::CrateCoverage;
+use anyhow::{Context, Error};
+use askama::Template;
+use clap::Parser;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Parser)]
+struct Cli {
+    /// Directory containing a subdirectory of `.profraw` files for each test suite.
+    #[arg(long)]
+    profiles: PathBuf,
+    /// Directory containing the `llvm-profdata` and `llvm-cov` binaries.
+    #[arg(long)]
+    llvm_bin_dir: PathBuf,
+    /// Root of the source code the compiler was built from.
+    #[arg(long)]
+    src_base: PathBuf,
+    /// Directory the merged profiles are stored in.
+    #[arg(long)]
+    work_dir: PathBuf,
+    #[arg(long)]
+    html_out: PathBuf,
+    #[arg(long)]
+    json_out: PathBuf,
+    /// Instrumented executables and libraries of the compiler.
+    #[arg(required = true)]
+    objects: Vec<PathBuf>,
+}
+
+fn main() -> Result<(), Error> {
+    let cli = Cli::parse();
+    std::fs::create_dir_all(&cli.work_dir)?;
+
+    let mut profiles = Vec::new();
+    for (suite, profraws) in find_profiles(&cli.profiles)? {
+        eprintln!("merging {} coverage profiles of the {suite} suite", profraws.len());
+        let profdata = cli.work_dir.join(format!("{suite}.profdata"));
+        merge(&cli, &profraws, &profdata)?;
+        profiles.push((suite, profdata));
+    }
+    if profiles.is_empty() {
+        anyhow::bail!("no coverage profiles found in {}", cli.profiles.display());
+    }
+
+    let combined_profdata = cli.work_dir.join("combined.profdata");
+    let suite_profdata = profiles.iter().map(|(_, profdata)| profdata.clone()).collect::<Vec<_>>();
+    merge(&cli, &suite_profdata, &combined_profdata)?;
+
+    let mut suites = BTreeMap::new();
+    for (suite, profdata) in &profiles {
+        suites.insert(suite.clone(), export(&cli, profdata)?);
+    }
+    let combined = export(&cli, &combined_profdata)?;
+
+    let report = report::Report { suites, combined };
+    std::fs::write(&cli.json_out, serde_json::to_vec_pretty(&report)?)?;
+    std::fs::write(&cli.html_out, report.render()?.as_bytes())?;
+
+    // The file:// link is hopefully clickable in terminals.
+    eprintln!("Full report: file://{}", std::fs::canonicalize(&cli.html_out)?.display());
+    Ok(())
+}
+
+/// Returns the `.profraw` files of each test suite, keyed by the name of the suite.
+fn find_profiles(path: &Path) -> Result<BTreeMap<String, Vec<PathBuf>>, Error> {
+    let mut suites = BTreeMap::new();
+    let entries = std::fs::read_dir(path)
+        .with_context(|| format!("failed to read the coverage profiles in {}", path.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let mut profraws = Vec::new();
+        for file in std::fs::read_dir(entry.path())? {
+            let file = file?.path();
+            if file.extension().and_then(|e| e.to_str()) == Some("profraw") {
+                profraws.push(file);
+            }
+        }
+        if !profraws.is_empty() {
+            profraws.sort();
+            suites.insert(entry.file_name().to_string_lossy().into_owned(), profraws);
+        }
+    }
+    Ok(suites)
+}
+
+fn merge(cli: &Cli, inputs: &[PathBuf], output: &Path) -> Result<(), Error> {
+    // Test suites produce a lot of profiles, so they are passed through a file rather than as
+    // arguments to avoid exceeding the command line length limit.
+    let inputs_list = output.with_extension("inputs");
+    let mut contents = String::new();
+    for input in inputs {
+        contents.push_str(input.to_str().context("non UTF-8 path of a coverage profile")?);
+        contents.push('\n');
+    }
+    std::fs::write(&inputs_list, contents)?;
+
+    run(Command::new(cli.llvm_bin_dir.join("llvm-profdata"))
+        .arg("merge")
+        .arg("-sparse")
+        .arg("--input-files")
+        .arg(&inputs_list)
+        .arg("-o")
+        .arg(output))?;
+    Ok(())
+}
+
+fn export(cli: &Cli, profdata: &Path) -> Result<CrateCoverage, Error> {
+    let mut cmd = Command::new(cli.llvm_bin_dir.join("llvm-cov"));
+    cmd.arg("export").arg("-summary-only").arg("-instr-profile").arg(profdata);
+    for (i, object) in cli.objects.iter().enumerate() {
+        if i > 0 {
+            cmd.arg("-object");
+        }
+        cmd.arg(object);
+    }
+    coverage::parse_export(&run(&mut cmd)?, &cli.src_base)
+}
+
+fn run(cmd: &mut Command) -> Result<Vec<u8>, Error> {
+    let output = cmd.output().with_context(|| format!("failed to execute {cmd:?}"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{cmd:?} failed with {}:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(output.stdout)
+}
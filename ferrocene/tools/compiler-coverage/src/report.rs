@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+use crate::coverage::{CrateCoverage, Summary};
+use askama::Template;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Template)]
+#[template(path = "report.html")]
+pub(crate) struct Report {
+    /// Coverage of the compiler crates exercised by each test suite.
+    pub(crate) suites: BTreeMap<String, CrateCoverage>,
+    /// Coverage of the compiler crates exercised by all the test suites together.
+    pub(crate) combined: CrateCoverage,
+}
+
+struct Row<'a> {
+    krate: &'a str,
+    suites: Vec<Option<&'a Summary>>,
+    combined: &'a Summary,
+}
+
+impl Report {
+    fn rows(&self) -> Vec<Row<'_>> {
+        self.combined
+            .iter()
+            .map(|(krate, combined)| Row {
+                krate,
+                suites: self.suites.values().map(|crates| crates.get(krate)).collect(),
+                combined,
+            })
+            .collect()
+    }
+
+    fn total(&self) -> Summary {
+        let mut total = Summary::default();
+        for summary in self.combined.values() {
+            total.add(summary);
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coverage::Counts;
+
+    #[test]
+    fn test_rows() {
+        let summary = |covered| Summary {
+            lines: Counts { count: 10, covered },
+            functions: Counts { count: 2, covered: 1 },
+            regions: Counts { count: 4, covered: 1 },
+        };
+        let crates = |entries: &[(&str, u64)]| {
+            entries.iter().map(|(krate, covered)| (krate.to_string(), summary(*covered))).collect()
+        };
+        let report = Report {
+            suites: [
+                ("codegen".to_string(), crates(&[("rustc_codegen_llvm", 5)])),
+                ("ui".to_string(), crates(&[("rustc_codegen_llvm", 2), ("rustc_parse", 8)])),
+            ]
+            .into_iter()
+            .collect(),
+            combined: crates(&[("rustc_codegen_llvm", 6), ("rustc_parse", 8)]),
+        };
+
+        let rows = report.rows();
+        assert_eq!(
+            vec!["rustc_codegen_llvm", "rustc_parse"],
+            rows.iter().map(|r| r.krate).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![Some(&summary(5)), Some(&summary(2))], rows[0].suites);
+        assert_eq!(vec![None, Some(&summary(8))], rows[1].suites);
+        assert_eq!(Counts { count: 20, covered: 14 }, report.total().lines);
+        assert!(report.render().unwrap().contains("rustc_parse"));
+    }
+}
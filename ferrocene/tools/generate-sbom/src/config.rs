@@ -0,0 +1,186 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+use anyhow::{anyhow, bail, Context, Error};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use yaml_rust::YamlLoader;
+
+/// Subset of `ferrocene/packages.toml` needed to know which packages are released.
+#[derive(Deserialize)]
+pub(crate) struct Packages {
+    groups: BTreeMap<String, Group>,
+}
+
+#[derive(Deserialize)]
+struct Group {
+    targets: Vec<String>,
+    packages: Vec<GroupPackage>,
+}
+
+#[derive(Deserialize)]
+struct GroupPackage {
+    name: String,
+}
+
+impl Packages {
+    pub(crate) fn load(src: &Path) -> Result<Self, Error> {
+        load_toml(&src.join("ferrocene").join("packages.toml"))
+    }
+
+    /// Whether the package is released for the target, or for all targets when the package is
+    /// not specific to a target.
+    pub(crate) fn is_released(&self, package: &str, target: Option<&str>) -> bool {
+        self.groups.values().any(|group| {
+            group.packages.iter().any(|p| p.name == package)
+                && group.targets.iter().any(|t| t == "*" || Some(t.as_str()) == target)
+        })
+    }
+
+    fn contains(&self, package: &str) -> bool {
+        self.groups.values().any(|group| group.packages.iter().any(|p| p.name == package))
+    }
+}
+
+/// Contents of `ferrocene/sbom.toml`.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct SbomConfig {
+    pub(crate) llvm: LlvmConfig,
+    #[serde(default)]
+    pub(crate) packages: BTreeMap<String, PackageConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct LlvmConfig {
+    pub(crate) path: PathBuf,
+    pub(crate) repository: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct PackageConfig {
+    #[serde(default)]
+    pub(crate) crates: Vec<CratesConfig>,
+    #[serde(default)]
+    pub(crate) llvm: bool,
+    #[serde(default)]
+    pub(crate) sources: Vec<PathBuf>,
+    pub(crate) sources_in: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct CratesConfig {
+    pub(crate) lockfile: PathBuf,
+    pub(crate) roots: Option<Vec<String>>,
+}
+
+impl SbomConfig {
+    pub(crate) fn load(src: &Path, packages: &Packages) -> Result<Self, Error> {
+        let path = src.join("ferrocene").join("sbom.toml");
+        let config: SbomConfig = load_toml(&path)?;
+        for name in config.packages.keys() {
+            if !packages.contains(name) {
+                bail!("package {name} in {} is not present in packages.toml", path.display());
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Subtree managed by `ferrocene/tools/pull-subtrees`.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Subtree {
+    pub(crate) path: PathBuf,
+    pub(crate) repo: String,
+    pub(crate) ref_: String,
+}
+
+pub(crate) fn load_subtrees(src: &Path) -> Result<Vec<Subtree>, Error> {
+    let path = src.join("ferrocene").join("tools").join("pull-subtrees").join("subtrees.yml");
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    parse_subtrees(&contents).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn parse_subtrees(contents: &str) -> Result<Vec<Subtree>, Error> {
+    let documents = YamlLoader::load_from_str(contents)?;
+    let Some(entries) = documents.first().and_then(|document| document.as_vec()) else {
+        bail!("the file should contain a list of subtrees");
+    };
+    entries
+        .iter()
+        .map(|entry| {
+            let field = |name: &str| {
+                entry[name].as_str().ok_or_else(|| anyhow!("missing {name} in subtree definition"))
+            };
+            Ok(Subtree {
+                path: field("path")?.into(),
+                repo: field("repo")?.into(),
+                ref_: field("ref")?.into(),
+            })
+        })
+        .collect()
+}
+
+fn load_toml<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, Error> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    toml::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_released() {
+        let packages: Packages = toml::from_str(
+            r#"
+                manifest-version = 2
+
+                [groups.hosts]
+                targets = ["x86_64-unknown-linux-gnu"]
+                [[groups.hosts.packages]]
+                name = "rustc"
+                subset = "default"
+
+                [groups.any-platform]
+                targets = ["*"]
+                [[groups.any-platform.packages]]
+                name = "rust-src"
+                subset = "default"
+            "#,
+        )
+        .unwrap();
+
+        assert!(packages.is_released("rustc", Some("x86_64-unknown-linux-gnu")));
+        assert!(!packages.is_released("rustc", Some("aarch64-unknown-none")));
+        assert!(!packages.is_released("rustc", None));
+        assert!(packages.is_released("rust-src", None));
+        assert!(!packages.is_released("cargo", Some("x86_64-unknown-linux-gnu")));
+    }
+
+    #[test]
+    fn test_parse_subtrees() {
+        let subtrees = parse_subtrees(
+            "---\n\
+             - path: ferrocene/library/libc\n  repo: rust-lang/libc\n  ref: main\n  after:\n    \
+             - update-cargo-lock\n",
+        )
+        .unwrap();
+        assert_eq!(
+            subtrees,
+            [Subtree {
+                path: "ferrocene/library/libc".into(),
+                repo: "rust-lang/libc".into(),
+                ref_: "main".into(),
+            }]
+        );
+
+        assert!(parse_subtrees("- path: ferrocene/library/libc\n").is_err());
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Crates shipped in a package, from the `Cargo.lock` of the workspace building it.
+//!
+//! The lockfile doesn't distinguish between the dependencies only used by tests or build scripts
+//! and the ones linked into the final artifacts, so the list is a superset of the crates actually
+//! shipped. Path dependencies are not listed, as they're part of the Ferrocene source code.
+
+use anyhow::{anyhow, bail, Context, Error};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+const CRATES_IO: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+#[derive(Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct LockedPackage {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) source: Option<String>,
+    pub(crate) checksum: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+impl LockedPackage {
+    pub(crate) fn purl(&self) -> Option<String> {
+        match self.source.as_deref() {
+            Some(CRATES_IO) => Some(format!("pkg:cargo/{}@{}", self.name, self.version)),
+            _ => None,
+        }
+    }
+
+    /// Location the crate was downloaded from, in the format of SPDX download locations.
+    pub(crate) fn download_location(&self) -> Option<String> {
+        match self.source.as_deref()? {
+            CRATES_IO => Some(format!(
+                "https://crates.io/api/v1/crates/{}/{}/download",
+                self.name, self.version
+            )),
+            // Git sources are written as `git+<url>?<ref>#<commit>`.
+            source if source.starts_with("git+") => {
+                let (url, commit) = source.split_once('#')?;
+                let url = url.split_once('?').map(|(url, _)| url).unwrap_or(url);
+                Some(format!("{url}@{commit}"))
+            }
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn load(path: &Path, roots: Option<&[String]>) -> Result<Vec<LockedPackage>, Error> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    collect(&contents, roots).with_context(|| format!("failed to process {}", path.display()))
+}
+
+/// Returns the crates of the lockfile that are dependencies of the roots, or all the crates of the
+/// lockfile if no roots are provided.
+fn collect(lockfile: &str, roots: Option<&[String]>) -> Result<Vec<LockedPackage>, Error> {
+    let lockfile: Lockfile = toml::from_str(lockfile)?;
+    let packages = &lockfile.package;
+
+    let mut reachable = BTreeSet::new();
+    let mut queue = match roots {
+        Some(roots) => {
+            roots.iter().map(|root| resolve(packages, root)).collect::<Result<Vec<_>, _>>()?
+        }
+        None => (0..packages.len()).collect(),
+    };
+    while let Some(idx) = queue.pop() {
+        if reachable.insert(idx) {
+            for dependency in &packages[idx].dependencies {
+                queue.push(resolve(packages, dependency)?);
+            }
+        }
+    }
+
+    let mut crates: Vec<_> = reachable
+        .into_iter()
+        .map(|idx| packages[idx].clone())
+        .filter(|package| package.source.is_some())
+        .collect();
+    crates.sort();
+    Ok(crates)
+}
+
+/// Resolves a dependency of the lockfile, which is written as `name`, `name version` or
+/// `name version (source)` depending on how many crates with that name are in the lockfile.
+fn resolve(packages: &[LockedPackage], dependency: &str) -> Result<usize, Error> {
+    let mut parts = dependency.splitn(3, ' ');
+    let name = parts.next().unwrap();
+    let version = parts.next();
+    let source = parts.next().map(|source| source.trim_start_matches('(').trim_end_matches(')'));
+
+    let mut candidates = packages.iter().enumerate().filter(|(_, package)| {
+        package.name == name
+            && version.map_or(true, |version| package.version == version)
+            && source.map_or(true, |source| package.source.as_deref() == Some(source))
+    });
+    match (candidates.next(), candidates.next()) {
+        (Some((idx, _)), None) => Ok(idx),
+        (Some(_), Some(_)) => bail!("crate {dependency} is ambiguous in the lockfile"),
+        (None, _) => Err(anyhow!("crate {dependency} is not present in the lockfile")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCKFILE: &str = r#"
+        version = 3
+
+        [[package]]
+        name = "app"
+        version = "0.1.0"
+        dependencies = ["bitflags 2.4.0", "internal", "serde"]
+
+        [[package]]
+        name = "bitflags"
+        version = "1.3.2"
+        source = "registry+https://github.com/rust-lang/crates.io-index"
+        checksum = "bef38d45163c2f1dde094a7dfd33ccf595c92905c8f8f4fdc18d06fb1037718a"
+
+        [[package]]
+        name = "bitflags"
+        version = "2.4.0"
+        source = "registry+https://github.com/rust-lang/crates.io-index"
+        checksum = "b4682ae6287fcf752ecaabbfcc7b6f9b72aa33933dc23a554d853aea8eea8635"
+
+        [[package]]
+        name = "internal"
+        version = "0.0.0"
+        dependencies = ["dep-from-git"]
+
+        [[package]]
+        name = "dep-from-git"
+        version = "0.2.0"
+        source = "git+https://github.com/example/dep?branch=main#0123456789abcdef"
+
+        [[package]]
+        name = "serde"
+        version = "1.0.188"
+        source = "registry+https://github.com/rust-lang/crates.io-index"
+        checksum = "cf9e0fcba69a370eed61bcf2b728575f726b50b55cba78064753d708ddc7549e"
+
+        [[package]]
+        name = "unrelated"
+        version = "0.1.0"
+        dependencies = ["bitflags 1.3.2"]
+    "#;
+
+    fn names(crates: &[LockedPackage]) -> Vec<String> {
+        crates.iter().map(|c| format!("{} {}", c.name, c.version)).collect()
+    }
+
+    #[test]
+    fn test_collect_from_roots() {
+        let crates = collect(LOCKFILE, Some(&["app".into()])).unwrap();
+        assert_eq!(names(&crates), ["bitflags 2.4.0", "dep-from-git 0.2.0", "serde 1.0.188"]);
+    }
+
+    #[test]
+    fn test_collect_all() {
+        let crates = collect(LOCKFILE, None).unwrap();
+        assert_eq!(
+            names(&crates),
+            ["bitflags 1.3.2", "bitflags 2.4.0", "dep-from-git 0.2.0", "serde 1.0.188"]
+        );
+    }
+
+    #[test]
+    fn test_missing_and_ambiguous_roots() {
+        assert!(collect(LOCKFILE, Some(&["missing".into()])).is_err());
+        assert!(collect(LOCKFILE, Some(&["bitflags".into()])).is_err());
+    }
+
+    #[test]
+    fn test_locations() {
+        let crates = collect(LOCKFILE, Some(&["app".into()])).unwrap();
+        assert_eq!(crates[0].purl().as_deref(), Some("pkg:cargo/bitflags@2.4.0"));
+        assert_eq!(
+            crates[0].download_location().as_deref(),
+            Some("https://crates.io/api/v1/crates/bitflags/2.4.0/download")
+        );
+        assert_eq!(crates[1].purl(), None);
+        assert_eq!(
+            crates[1].download_location().as_deref(),
+            Some("git+https://github.com/example/dep@0123456789abcdef")
+        );
+    }
+}
@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! CycloneDX 1.5 documents, in the JSON format: https://cyclonedx.org/docs/1.5/json/
+
+use crate::files::hex;
+use crate::licenses::License;
+use crate::sbom::{ComponentKind, Sbom};
+use anyhow::Error;
+use serde::Serialize;
+
+const PACKAGE_REF: &str = "package";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    serial_number: String,
+    version: u32,
+    metadata: Metadata,
+    components: Vec<Component>,
+    dependencies: Vec<Dependency>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    timestamp: String,
+    tools: Tools,
+    component: Component,
+    supplier: OrganizationalEntity,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Tools {
+    components: Vec<Component>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OrganizationalEntity {
+    name: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Component {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<Hash>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    licenses: Vec<LicenseChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    copyright: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_references: Vec<ExternalReference>,
+}
+
+impl Component {
+    fn new(type_: &'static str, bom_ref: String, name: String) -> Self {
+        Component {
+            type_,
+            bom_ref,
+            name,
+            version: None,
+            purl: None,
+            hashes: Vec::new(),
+            licenses: Vec::new(),
+            copyright: None,
+            external_references: Vec::new(),
+        }
+    }
+
+    fn license(&mut self, license: Option<&License>) {
+        if let Some(license) = license {
+            self.licenses.push(LicenseChoice { expression: license.spdx.clone() });
+            if !license.copyright.is_empty() {
+                self.copyright = Some(license.copyright.join("\n"));
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Hash {
+    alg: &'static str,
+    content: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LicenseChoice {
+    expression: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalReference {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Dependency {
+    #[serde(rename = "ref")]
+    ref_: String,
+    depends_on: Vec<String>,
+}
+
+pub(crate) fn render(sbom: &Sbom) -> Result<String, Error> {
+    let mut package = Component::new("application", PACKAGE_REF.into(), sbom.package.clone());
+    package.version = Some(sbom.version.clone());
+    if let Some(commit) = &sbom.commit {
+        package.external_references.push(ExternalReference {
+            type_: "vcs",
+            url: "https://github.com/ferrocene/ferrocene".into(),
+            comment: Some(format!("commit {commit}")),
+        });
+    }
+
+    let mut tool = Component::new("application", "tool".into(), "generate-sbom".into());
+    tool.version = Some(env!("CARGO_PKG_VERSION").into());
+
+    let mut components = Vec::new();
+    let mut depends_on = Vec::new();
+    for (idx, component) in sbom.components.iter().enumerate() {
+        let kind = match component.kind {
+            ComponentKind::Crate => "crate",
+            ComponentKind::Llvm => "llvm",
+            ComponentKind::Subtree => "subtree",
+            ComponentKind::Source => "source",
+        };
+        let mut out = Component::new("library", format!("{kind}-{idx}"), component.name.clone());
+        out.version = component.version.clone();
+        out.purl = component.purl.clone();
+        out.hashes.extend(
+            component.sha256.iter().map(|sha256| Hash { alg: "SHA-256", content: sha256.clone() }),
+        );
+        out.license(component.license.as_ref());
+        out.external_references.extend(component.location.as_deref().map(external_reference));
+        depends_on.push(out.bom_ref.clone());
+        components.push(out);
+    }
+    for (idx, file) in sbom.files.iter().enumerate() {
+        let mut out = Component::new("file", format!("file-{idx}"), file.file.path.clone());
+        out.hashes.push(Hash { alg: "SHA-1", content: hex(&file.file.sha1) });
+        out.hashes.push(Hash { alg: "SHA-256", content: hex(&file.file.sha256) });
+        out.license(file.license.as_ref());
+        components.push(out);
+    }
+
+    let bom = Bom {
+        bom_format: "CycloneDX",
+        spec_version: "1.5",
+        serial_number: serial_number(&sbom.digest()),
+        version: 1,
+        metadata: Metadata {
+            timestamp: sbom.created.clone(),
+            tools: Tools { components: vec![tool] },
+            component: package,
+            supplier: OrganizationalEntity { name: "Ferrous Systems" },
+        },
+        components,
+        dependencies: vec![Dependency { ref_: PACKAGE_REF.into(), depends_on }],
+    };
+    Ok(serde_json::to_string_pretty(&bom)?)
+}
+
+/// Converts the SPDX download locations of the components into CycloneDX external references.
+fn external_reference(location: &str) -> ExternalReference {
+    let Some(repository) = location.strip_prefix("git+") else {
+        return ExternalReference { type_: "distribution", url: location.into(), comment: None };
+    };
+    let (url, comment) = match repository.rsplit_once('@') {
+        Some((url, ref_)) => (url, Some(format!("ref {ref_}"))),
+        None => (repository, None),
+    };
+    ExternalReference { type_: "vcs", url: url.into(), comment }
+}
+
+/// CycloneDX requires the serial number to be an UUID. Derive it from the digest of the SBOM, so
+/// that generating the SBOM of the same package again results in the same serial number.
+fn serial_number(digest: &str) -> String {
+    let mut uuid: Vec<char> = digest[..32].chars().collect();
+    // Mark the UUID as version 8 (custom), with the variant defined in RFC 9562.
+    uuid[12] = '8';
+    uuid[16] = ['8', '9', 'a', 'b'][uuid[16].to_digit(16).unwrap() as usize & 0x3];
+    let uuid: String = uuid.into_iter().collect();
+    format!(
+        "urn:uuid:{}-{}-{}-{}-{}",
+        &uuid[..8],
+        &uuid[8..12],
+        &uuid[12..16],
+        &uuid[16..20],
+        &uuid[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serial_number() {
+        assert_eq!(
+            serial_number("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"),
+            "urn:uuid:b94d27b9-934d-8e08-a52e-52d7da7dabfa"
+        );
+        assert_eq!(
+            serial_number("00000000000000000000000000000000"),
+            "urn:uuid:00000000-0000-8000-8000-000000000000"
+        );
+    }
+
+    #[test]
+    fn test_external_reference() {
+        let vcs = external_reference("git+https://github.com/rust-lang/libc.git@main");
+        assert_eq!((vcs.type_, vcs.url.as_str()), ("vcs", "https://github.com/rust-lang/libc.git"));
+        assert_eq!(vcs.comment.as_deref(), Some("ref main"));
+
+        let url = "https://crates.io/api/v1/crates/libc/0.2.149/download";
+        let distribution = external_reference(url);
+        assert_eq!((distribution.type_, distribution.url.as_str()), ("distribution", url));
+    }
+}
@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Files contained in a package, and their hashes.
+//!
+//! Files are collected and hashed the same way `generate-tarball` does when signing the package,
+//! so that the hashes in the SBOM are the same as the ones in the criticaltrust package manifest.
+
+use anyhow::{anyhow, bail, Context, Error};
+use serde::Deserialize;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Debug)]
+pub(crate) struct PackageFile {
+    pub(crate) path: String,
+    pub(crate) sha1: Vec<u8>,
+    pub(crate) sha256: Vec<u8>,
+}
+
+/// Collects all the files of the package, except for the criticaltrust manifest (which is not
+/// listed in itself either).
+pub(crate) fn collect(package_dir: &Path, manifest: &Path) -> Result<Vec<PackageFile>, Error> {
+    let mut files = Vec::new();
+    collect_inner(&mut files, package_dir, package_dir, manifest)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn collect_inner(
+    files: &mut Vec<PackageFile>,
+    package_dir: &Path,
+    dir: &Path,
+    manifest: &Path,
+) -> Result<(), Error> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?.path();
+        let relative_path = entry.strip_prefix(package_dir).unwrap();
+        if relative_path == manifest {
+            continue;
+        }
+
+        if entry.is_file() {
+            let (sha1, sha256) = hash_file(&entry)?;
+            files.push(PackageFile {
+                path: relative_path
+                    .to_str()
+                    .ok_or_else(|| anyhow!("path {entry:?} is not utf-8"))?
+                    .into(),
+                sha1,
+                sha256,
+            });
+        } else if entry.is_dir() {
+            collect_inner(files, package_dir, &entry, manifest)?;
+        }
+    }
+    Ok(())
+}
+
+/// SPDX requires SHA-1 hashes for every file, while criticaltrust uses SHA-256. Calculate both
+/// while reading the file only once.
+fn hash_file(path: &Path) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let mut sha1 = Sha1::new();
+    let mut sha256 = Sha256::new();
+    let mut file =
+        File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let len = file.read(&mut buffer)?;
+        if len == 0 {
+            break;
+        }
+        sha1.update(&buffer[..len]);
+        sha256.update(&buffer[..len]);
+    }
+    Ok((sha1.finalize().to_vec(), sha256.finalize().to_vec()))
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    signed: SignedPayload,
+}
+
+#[derive(Deserialize)]
+struct SignedPayload {
+    signed: String,
+}
+
+#[derive(Deserialize)]
+struct ManifestPackage {
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Deserialize)]
+struct ManifestFile {
+    path: String,
+    sha256: String,
+}
+
+/// Ensures the files of the SBOM are exactly the ones recorded in the criticaltrust manifest of
+/// the package, with the same hashes. The signatures of the manifest are not verified, as that's
+/// done by criticalup when installing the package.
+pub(crate) fn check_manifest(files: &[PackageFile], manifest_path: &Path) -> Result<(), Error> {
+    let manifest: Manifest = serde_json::from_slice(&std::fs::read(manifest_path)?)
+        .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+    let package: ManifestPackage = serde_json::from_str(&manifest.signed.signed)
+        .with_context(|| format!("failed to parse the package in {}", manifest_path.display()))?;
+
+    let mut expected: BTreeMap<_, _> =
+        package.files.into_iter().map(|file| (file.path, file.sha256)).collect();
+    let mut errors = Vec::new();
+    for file in files {
+        match expected.remove(&file.path) {
+            Some(sha256) if sha256 == base64(&file.sha256) => {}
+            Some(_) => errors.push(format!("{}: different hash", file.path)),
+            None => errors.push(format!("{}: not in the criticaltrust manifest", file.path)),
+        }
+    }
+    errors.extend(expected.into_keys().map(|path| format!("{path}: not in the package")));
+
+    if !errors.is_empty() {
+        bail!(
+            "the package doesn't match its criticaltrust manifest ({}):\n{}",
+            manifest_path.display(),
+            errors.join("\n")
+        );
+    }
+    Ok(())
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Standard base64 with padding, as used by criticaltrust to encode hashes.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - i * 8));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn package() -> Result<TempDir, Error> {
+        let package_dir = TempDir::new()?;
+        let create_file = |path: &str, contents: &str| {
+            let path = package_dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(path, contents)
+        };
+        create_file("bin/rustc", "hello world")?;
+        create_file("lib/librustc_driver.so", "not a real library")?;
+        create_file("share/criticaltrust/ferrocene/demo-package.json", "")?;
+        Ok(package_dir)
+    }
+
+    fn write_manifest(package_dir: &Path, files: serde_json::Value) -> Result<(), Error> {
+        let package = serde_json::json!({
+            "product": "ferrocene",
+            "package": "demo-package",
+            "commit": "000000",
+            "files": files,
+            "managed-prefixes": [],
+        });
+        let manifest = serde_json::json!({
+            "version": 1,
+            "signed": { "signatures": [], "signed": package.to_string() },
+        });
+        std::fs::write(
+            package_dir.join("share/criticaltrust/ferrocene/demo-package.json"),
+            manifest.to_string(),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect() -> Result<(), Error> {
+        let package_dir = package()?;
+        let manifest = Path::new("share/criticaltrust/ferrocene/demo-package.json");
+        let files = collect(package_dir.path(), manifest)?;
+
+        let paths: Vec<_> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["bin/rustc", "lib/librustc_driver.so"]);
+        assert_eq!(hex(&files[0].sha1), "2aae6c35c94fcfb415dbe95f408b9ce91ee846ed");
+        assert_eq!(
+            hex(&files[0].sha256),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_manifest() -> Result<(), Error> {
+        let package_dir = package()?;
+        let manifest = Path::new("share/criticaltrust/ferrocene/demo-package.json");
+        let files = collect(package_dir.path(), manifest)?;
+        let file = |path, sha256| serde_json::json!({ "path": path, "sha256": sha256 });
+
+        // Hashes from the snapshot of generate-tarball's signatures test.
+        let rustc = file("bin/rustc", "uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=");
+        let driver = file("lib/librustc_driver.so", "lX4IzN2GdNJIl185uuaM8vab87ZA1p+/LnK0r/JdSH8=");
+        write_manifest(package_dir.path(), serde_json::json!([rustc, driver]))?;
+        check_manifest(&files, &package_dir.path().join(manifest))?;
+
+        let wrong = file("lib/librustc_driver.so", "rOyXaUDGSIqZ/eBYpOQK6GJ7ufWvSq9RaAEsPCsyDvw=");
+        let extra = file("lib/libstd.so", "rOyXaUDGSIqZ/eBYpOQK6GJ7ufWvSq9RaAEsPCsyDvw=");
+        write_manifest(package_dir.path(), serde_json::json!([wrong, extra]))?;
+        let error = check_manifest(&files, &package_dir.path().join(manifest)).unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("bin/rustc: not in the criticaltrust manifest"));
+        assert!(message.contains("lib/librustc_driver.so: different hash"));
+        assert!(message.contains("lib/libstd.so: not in the package"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
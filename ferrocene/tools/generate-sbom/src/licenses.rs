@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Per-file license data of the source code, as collected from REUSE by the
+//! `collect-license-metadata` tool.
+
+use anyhow::{Context, Error};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct License {
+    pub(crate) spdx: String,
+    pub(crate) copyright: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    files: Node,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+enum Node {
+    Root { children: Vec<Node> },
+    Directory { name: PathBuf, children: Vec<Node>, license: Option<License> },
+    File { name: PathBuf, license: License },
+    Group { files: Vec<PathBuf>, directories: Vec<PathBuf>, license: License },
+}
+
+/// Licenses of the files and directories of the source code. The license of a directory applies
+/// to all the files inside of it, except the ones with a more specific entry.
+#[derive(Default)]
+pub(crate) struct Licenses {
+    files: BTreeMap<PathBuf, License>,
+    directories: BTreeMap<PathBuf, License>,
+}
+
+impl Licenses {
+    pub(crate) fn load(path: &Path) -> Result<Self, Error> {
+        let contents =
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let metadata: Metadata = serde_json::from_slice(&contents)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+
+        let mut licenses = Licenses::default();
+        licenses.add(Path::new(""), metadata.files);
+        Ok(licenses)
+    }
+
+    fn add(&mut self, parent: &Path, node: Node) {
+        match node {
+            Node::Root { children } => {
+                for child in children {
+                    self.add(parent, child);
+                }
+            }
+            Node::Directory { name, children, license } => {
+                let path = normalize(&parent.join(name));
+                if let Some(license) = license {
+                    self.directories.insert(path.clone(), license);
+                }
+                for child in children {
+                    self.add(&path, child);
+                }
+            }
+            Node::File { name, license } => {
+                self.files.insert(normalize(&parent.join(name)), license);
+            }
+            Node::Group { files, directories, license } => {
+                for file in files {
+                    self.files.insert(normalize(&parent.join(file)), license.clone());
+                }
+                for directory in directories {
+                    self.directories.insert(normalize(&parent.join(directory)), license.clone());
+                }
+            }
+        }
+    }
+
+    /// License of a file, relative to the root of the source code.
+    pub(crate) fn of_file(&self, path: &Path) -> Option<&License> {
+        let path = normalize(path);
+        self.files.get(&path).or_else(|| self.of_directory(&path))
+    }
+
+    fn of_directory(&self, path: &Path) -> Option<&License> {
+        path.ancestors().find_map(|ancestor| self.directories.get(ancestor))
+    }
+
+    /// All the licenses of the files inside a directory, relative to the root of the source code.
+    pub(crate) fn inside(&self, path: &Path) -> Vec<&License> {
+        let path = normalize(path);
+        let nested = self
+            .files
+            .iter()
+            .chain(self.directories.iter())
+            .filter(|(entry, _)| entry.starts_with(&path))
+            .map(|(_, license)| license);
+        let licenses: BTreeSet<_> = self.of_directory(&path).into_iter().chain(nested).collect();
+        licenses.into_iter().collect()
+    }
+}
+
+/// Combines multiple licenses into a single one, applying all of them.
+pub(crate) fn combine(licenses: &[&License]) -> Option<License> {
+    let expressions: BTreeSet<_> = licenses.iter().map(|license| license.spdx.as_str()).collect();
+    let spdx = match expressions.len() {
+        0 => return None,
+        1 => expressions.into_iter().next().unwrap().to_string(),
+        _ => expressions
+            .into_iter()
+            .map(|expression| {
+                if expression.contains(" OR ") || expression.contains(" AND ") {
+                    format!("({expression})")
+                } else {
+                    expression.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" AND "),
+    };
+    let copyright: BTreeSet<_> =
+        licenses.iter().flat_map(|license| license.copyright.iter().cloned()).collect();
+    Some(License { spdx, copyright: copyright.into_iter().collect() })
+}
+
+/// REUSE outputs paths starting with `./`, remove it to be able to compare them.
+fn normalize(path: &Path) -> PathBuf {
+    path.components().filter(|component| *component != Component::CurDir).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn license(spdx: &str, copyright: &str) -> License {
+        License { spdx: spdx.into(), copyright: vec![copyright.into()] }
+    }
+
+    fn licenses() -> Licenses {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(
+            serde_json::json!({
+                "files": {
+                    "type": "root",
+                    "children": [{
+                        "type": "directory",
+                        "name": ".",
+                        "license": { "spdx": "MIT OR Apache-2.0", "copyright": ["Rust"] },
+                        "children": [
+                            {
+                                "type": "directory",
+                                "name": "src/llvm-project",
+                                "license": {
+                                    "spdx": "Apache-2.0 WITH LLVM-exception",
+                                    "copyright": ["LLVM"],
+                                },
+                                "children": [{
+                                    "type": "file",
+                                    "name": "llvm/lib/Support/regcomp.c",
+                                    "license": { "spdx": "BSD-2-Clause", "copyright": ["BSD"] },
+                                }],
+                            },
+                            {
+                                "type": "group",
+                                "files": ["src/etc/a.svg", "src/etc/b.svg"],
+                                "directories": ["src/doc/book"],
+                                "license": { "spdx": "CC-BY-4.0", "copyright": ["Docs"] },
+                            },
+                        ],
+                    }],
+                },
+            })
+            .to_string()
+            .as_bytes(),
+        )
+        .unwrap();
+        Licenses::load(file.path()).unwrap()
+    }
+
+    #[test]
+    fn test_of_file() {
+        let licenses = licenses();
+        let rust = license("MIT OR Apache-2.0", "Rust");
+        let llvm = license("Apache-2.0 WITH LLVM-exception", "LLVM");
+        let docs = license("CC-BY-4.0", "Docs");
+
+        assert_eq!(licenses.of_file(Path::new("library/core/src/lib.rs")), Some(&rust));
+        assert_eq!(licenses.of_file(Path::new("./src/llvm-project/llvm/README.txt")), Some(&llvm));
+        assert_eq!(
+            licenses.of_file(Path::new("src/llvm-project/llvm/lib/Support/regcomp.c")),
+            Some(&license("BSD-2-Clause", "BSD"))
+        );
+        assert_eq!(licenses.of_file(Path::new("src/etc/a.svg")), Some(&docs));
+        assert_eq!(licenses.of_file(Path::new("src/etc/c.svg")), Some(&rust));
+        assert_eq!(licenses.of_file(Path::new("src/doc/book/src/ch01.md")), Some(&docs));
+        assert_eq!(Licenses::default().of_file(Path::new("x.py")), None);
+    }
+
+    #[test]
+    fn test_inside() {
+        let licenses = licenses();
+        let combined = combine(&licenses.inside(Path::new("src/llvm-project"))).unwrap();
+        assert_eq!(combined.spdx, "Apache-2.0 WITH LLVM-exception AND BSD-2-Clause");
+        assert_eq!(combined.copyright, ["BSD", "LLVM"]);
+
+        let combined = combine(&licenses.inside(Path::new("library"))).unwrap();
+        assert_eq!(combined, license("MIT OR Apache-2.0", "Rust"));
+
+        let combined = combine(&licenses.inside(Path::new("src"))).unwrap();
+        assert_eq!(
+            combined.spdx,
+            "Apache-2.0 WITH LLVM-exception AND BSD-2-Clause AND CC-BY-4.0 AND (MIT OR Apache-2.0)"
+        );
+
+        assert_eq!(combine(&Licenses::default().inside(Path::new("library"))), None);
+    }
+}
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Generates the software bill of materials (SBOM) of a package produced by `./x dist`, both as an
+//! SPDX 2.3 and a CycloneDX 1.5 document.
+//!
+//! The SBOM lists all the files of the package with their hashes, the crates, LLVM, subtrees and
+//! source code included in the package (as configured in `ferrocene/sbom.toml`), and the per-file
+//! license data collected by REUSE. SBOMs are only generated for the packages listed in
+//! `ferrocene/packages.toml`.
+
+mod config;
+mod crates;
+mod cyclonedx;
+mod files;
+mod licenses;
+mod sbom;
+mod spdx;
+
+use crate::config::{PackageConfig, Packages, SbomConfig};
+use crate::licenses::Licenses;
+use crate::sbom::{Inputs, Sbom};
+use anyhow::{bail, Context, Error};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Parser)]
+struct Cli {
+    /// Root of the Ferrocene source code.
+    #[arg(long)]
+    src: PathBuf,
+    /// Name of the package in packages.toml.
+    #[arg(long)]
+    package: String,
+    /// Target of the package, if the package is specific to a target.
+    #[arg(long)]
+    target: Option<String>,
+    /// Directory containing the files of the package, as they're installed.
+    #[arg(long)]
+    package_dir: PathBuf,
+    /// Name of the tarball, used as the name of the SBOM files.
+    #[arg(long)]
+    package_name: String,
+    /// The package is a bare tarball, which is not installed by criticalup and thus has no
+    /// criticaltrust manifest.
+    #[arg(long)]
+    bare: bool,
+    #[arg(long)]
+    version: String,
+    #[arg(long)]
+    commit: Option<String>,
+    /// Version of LLVM, as reported by `llvm-config --version`.
+    #[arg(long)]
+    llvm_version: Option<String>,
+    /// License metadata generated by collect-license-metadata. Licenses are not included in the
+    /// SBOM if this is not provided.
+    #[arg(long)]
+    license_metadata: Option<PathBuf>,
+    /// Directory to write the SBOM files to.
+    #[arg(long)]
+    output_dir: PathBuf,
+}
+
+fn main() -> Result<(), Error> {
+    let cli = Cli::parse();
+
+    let packages = Packages::load(&cli.src)?;
+    if !packages.is_released(&cli.package, cli.target.as_deref()) {
+        return Ok(());
+    }
+    let config = SbomConfig::load(&cli.src, &packages)?;
+    let default_package = PackageConfig::default();
+    let licenses = match &cli.license_metadata {
+        Some(path) => Licenses::load(path)?,
+        None => Licenses::default(),
+    };
+    let inputs = Inputs {
+        src: &cli.src,
+        config: &config,
+        package: config.packages.get(&cli.package).unwrap_or(&default_package),
+        subtrees: &config::load_subtrees(&cli.src)?,
+        licenses: &licenses,
+        llvm_version: cli.llvm_version.as_deref(),
+    };
+
+    // Must match the path of the manifest written by generate-tarball.
+    let component = match &cli.target {
+        Some(target) => format!("{}-{target}", cli.package),
+        None => cli.package.clone(),
+    };
+    let manifest = Path::new("share/criticaltrust/ferrocene").join(format!("{component}.json"));
+    let files = files::collect(&cli.package_dir, &manifest)?;
+    let manifest_path = cli.package_dir.join(&manifest);
+    if manifest_path.is_file() {
+        files::check_manifest(&files, &manifest_path)?;
+    } else if !cli.bare {
+        bail!("missing the criticaltrust manifest of the package ({})", manifest.display());
+    }
+
+    let sbom = Sbom {
+        package: cli.package.clone(),
+        name: cli.package_name.clone(),
+        version: cli.version.clone(),
+        commit: cli.commit.clone(),
+        created: sbom::format_timestamp(timestamp()?),
+        components: sbom::components(&inputs)?,
+        files: sbom::files(files, &inputs),
+    };
+
+    std::fs::create_dir_all(&cli.output_dir)?;
+    let write = |extension: &str, contents: String| {
+        let path = cli.output_dir.join(format!("{}.{extension}", cli.package_name));
+        std::fs::write(&path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))
+    };
+    write("spdx.json", spdx::render(&sbom, &cli.src)?)?;
+    write("cdx.json", cyclonedx::render(&sbom)?)?;
+
+    Ok(())
+}
+
+/// Honors `SOURCE_DATE_EPOCH`, to allow generating reproducible SBOMs.
+fn timestamp() -> Result<u64, Error> {
+    match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(epoch) => epoch.parse().context("SOURCE_DATE_EPOCH is not a valid timestamp"),
+        Err(_) => Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs()),
+    }
+}
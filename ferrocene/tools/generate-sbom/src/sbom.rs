@@ -0,0 +1,206 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Contents of the SBOM of a package, independently of the format it's going to be written in.
+
+use crate::config::{PackageConfig, SbomConfig, Subtree};
+use crate::files::{hex, PackageFile};
+use crate::licenses::{self, License, Licenses};
+use anyhow::Error;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+pub(crate) struct Sbom {
+    /// Name of the package in `packages.toml`, like `rust-std`.
+    pub(crate) package: String,
+    /// Name of the tarball, like `rust-std-1.75.0-x86_64-unknown-linux-gnu`.
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) commit: Option<String>,
+    /// Creation time, formatted as an ISO 8601 UTC timestamp.
+    pub(crate) created: String,
+    pub(crate) files: Vec<SbomFile>,
+    pub(crate) components: Vec<Component>,
+}
+
+impl Sbom {
+    /// Hash of the identity and contents of the package, used to generate unique identifiers for
+    /// the documents that don't change when generating them again for the same package.
+    pub(crate) fn digest(&self) -> String {
+        let mut sha256 = Sha256::new();
+        for part in [&self.name, &self.version, self.commit.as_deref().unwrap_or("")] {
+            sha256.update(part.as_bytes());
+            sha256.update([0]);
+        }
+        for file in &self.files {
+            sha256.update(file.file.path.as_bytes());
+            sha256.update([0]);
+            sha256.update(&file.file.sha256);
+        }
+        hex(&sha256.finalize())
+    }
+}
+
+pub(crate) struct SbomFile {
+    pub(crate) file: PackageFile,
+    pub(crate) license: Option<License>,
+}
+
+pub(crate) struct Component {
+    pub(crate) kind: ComponentKind,
+    pub(crate) name: String,
+    pub(crate) version: Option<String>,
+    pub(crate) purl: Option<String>,
+    pub(crate) location: Option<String>,
+    pub(crate) sha256: Option<String>,
+    pub(crate) license: Option<License>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ComponentKind {
+    Crate,
+    Llvm,
+    Subtree,
+    Source,
+}
+
+pub(crate) struct Inputs<'a> {
+    pub(crate) src: &'a Path,
+    pub(crate) config: &'a SbomConfig,
+    pub(crate) package: &'a PackageConfig,
+    pub(crate) subtrees: &'a [Subtree],
+    pub(crate) licenses: &'a Licenses,
+    pub(crate) llvm_version: Option<&'a str>,
+}
+
+/// Collects the crates, LLVM, the subtrees and the source code included in the package.
+pub(crate) fn components(inputs: &Inputs<'_>) -> Result<Vec<Component>, Error> {
+    let Inputs { src, config, package, subtrees, licenses, llvm_version } = inputs;
+    let license_inside = |path: &Path| licenses::combine(&licenses.inside(path));
+    let mut components = Vec::new();
+
+    for crates in &package.crates {
+        let lockfile = src.join(&crates.lockfile);
+        for krate in crate::crates::load(&lockfile, crates.roots.as_deref())? {
+            components.push(Component {
+                kind: ComponentKind::Crate,
+                purl: krate.purl(),
+                location: krate.download_location(),
+                sha256: krate.checksum,
+                name: krate.name,
+                version: Some(krate.version),
+                license: None,
+            });
+        }
+    }
+    // Crates can be present in multiple lockfiles.
+    components.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    components
+        .dedup_by(|a, b| (&a.name, &a.version, &a.location) == (&b.name, &b.version, &b.location));
+
+    if package.llvm {
+        components.push(Component {
+            kind: ComponentKind::Llvm,
+            name: "llvm-project".into(),
+            version: llvm_version.map(|version| version.to_string()),
+            purl: None,
+            location: Some(format!("git+{}", config.llvm.repository)),
+            sha256: None,
+            license: license_inside(&config.llvm.path),
+        });
+    }
+
+    for subtree in subtrees.iter() {
+        let included = package
+            .sources
+            .iter()
+            .any(|source| subtree.path.starts_with(source) || source.starts_with(&subtree.path));
+        if included {
+            components.push(Component {
+                kind: ComponentKind::Subtree,
+                name: subtree.path.display().to_string(),
+                version: None,
+                purl: None,
+                location: Some(format!(
+                    "git+https://github.com/{}.git@{}",
+                    subtree.repo, subtree.ref_
+                )),
+                sha256: None,
+                license: license_inside(&subtree.path),
+            });
+        }
+    }
+
+    for source in &package.sources {
+        components.push(Component {
+            kind: ComponentKind::Source,
+            name: source.display().to_string(),
+            version: None,
+            purl: None,
+            location: None,
+            sha256: None,
+            license: license_inside(source),
+        });
+    }
+
+    Ok(components)
+}
+
+/// Attaches the license of the corresponding source file to the files of packages shipping source
+/// code.
+pub(crate) fn files(files: Vec<PackageFile>, inputs: &Inputs<'_>) -> Vec<SbomFile> {
+    files
+        .into_iter()
+        .map(|file| {
+            let license = inputs
+                .package
+                .sources_in
+                .as_deref()
+                .and_then(|sources_in| Path::new(&file.path).strip_prefix(sources_in).ok())
+                // Files generated while packaging (like vendored crates) are not part of the
+                // source code, even if their directory has a license.
+                .filter(|source| inputs.src.join(source).is_file())
+                .and_then(|source| inputs.licenses.of_file(source))
+                .cloned();
+            SbomFile { file, license }
+        })
+        .collect()
+}
+
+/// Formats a UNIX timestamp as an ISO 8601 UTC timestamp.
+pub(crate) fn format_timestamp(timestamp: u64) -> String {
+    let (days, seconds) = (timestamp / 86400, timestamp % 86400);
+
+    // Conversion from days since the epoch to a civil date, from Howard Hinnant's algorithms:
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days % 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(951782400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_timestamp(1700000000), "2023-11-14T22:13:20Z");
+        assert_eq!(format_timestamp(4107542399), "2100-02-28T23:59:59Z");
+    }
+}
@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! SPDX 2.3 documents, in the JSON format: https://spdx.github.io/spdx-spec/v2.3/
+
+use crate::files::hex;
+use crate::licenses::License;
+use crate::sbom::{ComponentKind, Sbom};
+use anyhow::{Context, Error};
+use serde::Serialize;
+use sha1::Sha1;
+use sha2::Digest;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+const PACKAGE_ID: &str = "SPDXRef-Package";
+const NOASSERTION: &str = "NOASSERTION";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    spdx_version: &'static str,
+    data_license: &'static str,
+    #[serde(rename = "SPDXID")]
+    spdx_id: &'static str,
+    name: String,
+    document_namespace: String,
+    creation_info: CreationInfo,
+    document_describes: Vec<&'static str>,
+    packages: Vec<Package>,
+    files: Vec<File>,
+    relationships: Vec<Relationship>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    has_extracted_licensing_infos: Vec<ExtractedLicensingInfo>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreationInfo {
+    created: String,
+    creators: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Package {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_info: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    supplier: Option<&'static str>,
+    download_location: String,
+    files_analyzed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package_verification_code: Option<PackageVerificationCode>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    checksums: Vec<Checksum>,
+    license_concluded: String,
+    license_declared: String,
+    copyright_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_info: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_refs: Vec<ExternalRef>,
+    primary_package_purpose: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageVerificationCode {
+    package_verification_code_value: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct File {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    file_name: String,
+    checksums: Vec<Checksum>,
+    license_concluded: String,
+    copyright_text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Checksum {
+    algorithm: &'static str,
+    checksum_value: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalRef {
+    reference_category: &'static str,
+    reference_type: &'static str,
+    reference_locator: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Relationship {
+    spdx_element_id: String,
+    relationship_type: &'static str,
+    related_spdx_element: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExtractedLicensingInfo {
+    license_id: String,
+    extracted_text: String,
+}
+
+pub(crate) fn render(sbom: &Sbom, src: &Path) -> Result<String, Error> {
+    let mut packages = vec![Package {
+        spdx_id: PACKAGE_ID.into(),
+        name: sbom.package.clone(),
+        version_info: Some(sbom.version.clone()),
+        supplier: Some("Organization: Ferrous Systems"),
+        download_location: NOASSERTION.into(),
+        files_analyzed: true,
+        package_verification_code: Some(PackageVerificationCode {
+            package_verification_code_value: verification_code(sbom),
+        }),
+        checksums: Vec::new(),
+        license_concluded: NOASSERTION.into(),
+        license_declared: NOASSERTION.into(),
+        copyright_text: NOASSERTION.into(),
+        source_info: sbom.commit.as_ref().map(|commit| format!("built from commit {commit}")),
+        external_refs: Vec::new(),
+        primary_package_purpose: "INSTALL",
+    }];
+    let mut files = Vec::new();
+    let mut relationships = vec![Relationship {
+        spdx_element_id: "SPDXRef-DOCUMENT".into(),
+        relationship_type: "DESCRIBES",
+        related_spdx_element: PACKAGE_ID.into(),
+    }];
+    let mut contains = |id: &str| {
+        relationships.push(Relationship {
+            spdx_element_id: PACKAGE_ID.into(),
+            relationship_type: "CONTAINS",
+            related_spdx_element: id.into(),
+        });
+    };
+
+    for (idx, file) in sbom.files.iter().enumerate() {
+        let spdx_id = format!("SPDXRef-File-{idx}");
+        contains(&spdx_id);
+        files.push(File {
+            spdx_id,
+            file_name: format!("./{}", file.file.path),
+            checksums: vec![
+                Checksum { algorithm: "SHA1", checksum_value: hex(&file.file.sha1) },
+                Checksum { algorithm: "SHA256", checksum_value: hex(&file.file.sha256) },
+            ],
+            license_concluded: license_expression(file.license.as_ref()),
+            copyright_text: copyright_text(file.license.as_ref()),
+        });
+    }
+
+    for (idx, component) in sbom.components.iter().enumerate() {
+        let (kind, purpose) = match component.kind {
+            ComponentKind::Crate => ("Crate", "LIBRARY"),
+            ComponentKind::Llvm => ("LLVM", "LIBRARY"),
+            ComponentKind::Subtree => ("Subtree", "SOURCE"),
+            ComponentKind::Source => ("Source", "SOURCE"),
+        };
+        let spdx_id = format!("SPDXRef-{kind}-{idx}");
+        contains(&spdx_id);
+        packages.push(Package {
+            spdx_id,
+            name: component.name.clone(),
+            version_info: component.version.clone(),
+            supplier: None,
+            download_location: component.location.clone().unwrap_or_else(|| NOASSERTION.into()),
+            files_analyzed: false,
+            package_verification_code: None,
+            checksums: component
+                .sha256
+                .iter()
+                .map(|sha256| Checksum { algorithm: "SHA256", checksum_value: sha256.clone() })
+                .collect(),
+            license_concluded: license_expression(component.license.as_ref()),
+            license_declared: NOASSERTION.into(),
+            copyright_text: copyright_text(component.license.as_ref()),
+            source_info: None,
+            external_refs: component
+                .purl
+                .iter()
+                .map(|purl| ExternalRef {
+                    reference_category: "PACKAGE-MANAGER",
+                    reference_type: "purl",
+                    reference_locator: purl.clone(),
+                })
+                .collect(),
+            primary_package_purpose: purpose,
+        });
+    }
+
+    let licenses = sbom
+        .files
+        .iter()
+        .map(|file| file.license.as_ref())
+        .chain(sbom.components.iter().map(|component| component.license.as_ref()))
+        .flatten();
+    let document = Document {
+        spdx_version: "SPDX-2.3",
+        data_license: "CC0-1.0",
+        spdx_id: "SPDXRef-DOCUMENT",
+        name: sbom.name.clone(),
+        document_namespace: format!("https://ferrocene.dev/spdx/{}-{}", sbom.name, sbom.digest()),
+        creation_info: CreationInfo {
+            created: sbom.created.clone(),
+            creators: vec![
+                "Organization: Ferrous Systems".into(),
+                format!("Tool: generate-sbom-{}", env!("CARGO_PKG_VERSION")),
+            ],
+        },
+        document_describes: vec![PACKAGE_ID],
+        packages,
+        files,
+        relationships,
+        has_extracted_licensing_infos: extracted_licensing_infos(licenses, src)?,
+    };
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Calculates the package verification code, as defined in section 7.9 of the specification.
+fn verification_code(sbom: &Sbom) -> String {
+    let mut hashes: Vec<_> = sbom.files.iter().map(|file| hex(&file.file.sha1)).collect();
+    hashes.sort();
+
+    let mut sha1 = Sha1::new();
+    sha1.update(hashes.concat().as_bytes());
+    hex(&sha1.finalize())
+}
+
+fn license_expression(license: Option<&License>) -> String {
+    license.map(|license| license.spdx.clone()).unwrap_or_else(|| NOASSERTION.into())
+}
+
+fn copyright_text(license: Option<&License>) -> String {
+    match license {
+        Some(license) if !license.copyright.is_empty() => license.copyright.join("\n"),
+        _ => NOASSERTION.into(),
+    }
+}
+
+/// Licenses not in the SPDX license list are referred to with `LicenseRef-` identifiers, whose
+/// text must be included in the document. REUSE stores their text in the `LICENSES` directory.
+fn extracted_licensing_infos<'a>(
+    licenses: impl Iterator<Item = &'a License>,
+    src: &Path,
+) -> Result<Vec<ExtractedLicensingInfo>, Error> {
+    let ids: BTreeSet<_> = licenses
+        .flat_map(|license| license.spdx.split(|c: char| c.is_whitespace() || c == '(' || c == ')'))
+        .filter(|token| token.starts_with("LicenseRef-"))
+        .collect();
+    ids.into_iter()
+        .map(|id| {
+            let path = src.join("LICENSES").join(format!("{id}.txt"));
+            Ok(ExtractedLicensingInfo {
+                license_id: id.into(),
+                extracted_text: std::fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read the text of {id}"))?,
+            })
+        })
+        .collect()
+}
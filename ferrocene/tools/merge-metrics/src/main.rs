@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Merges the `metrics.json` files produced by the shards of a test run into a single document,
+//! containing the invocations of all the shards.
+//!
+//! Usage: `merge-metrics <output> <input>...`
+
+use anyhow::{Context, Error};
+use build_helper::metrics::JsonRoot;
+use std::path::{Path, PathBuf};
+
+fn main() -> Result<(), Error> {
+    let args = std::env::args_os().skip(1).map(PathBuf::from).collect::<Vec<_>>();
+    let [output, inputs @ ..] = args.as_slice() else {
+        anyhow::bail!("usage: merge-metrics <output> <input>...");
+    };
+
+    let metrics = inputs.iter().map(|input| load(input)).collect::<Result<Vec<_>, _>>()?;
+    let merged = merge(metrics)?;
+
+    std::fs::write(output, serde_json::to_vec(&merged)?)
+        .with_context(|| format!("failed to write {}", output.display()))?;
+    Ok(())
+}
+
+fn load(path: &Path) -> Result<JsonRoot, Error> {
+    serde_json::from_slice(&std::fs::read(path)?)
+        .with_context(|| format!("failed to read metrics from {}", path.display()))
+}
+
+fn merge(metrics: Vec<JsonRoot>) -> Result<JsonRoot, Error> {
+    let mut metrics = metrics.into_iter();
+    let mut merged = metrics.next().context("no metrics files to merge")?;
+    for other in metrics {
+        if other.format_version != merged.format_version {
+            anyhow::bail!(
+                "cannot merge build metrics with format versions {} and {}",
+                merged.format_version,
+                other.format_version
+            );
+        }
+        merged.invocations.extend(other.invocations);
+    }
+    merged.invocations.sort_by_key(|invocation| invocation.start_time);
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(format_version: usize, start_times: &[u64]) -> JsonRoot {
+        serde_json::from_value(serde_json::json!({
+            "format_version": format_version,
+            "system_stats": {
+                "cpu_threads_count": 1,
+                "cpu_model": "cpu",
+                "memory_total_bytes": 1,
+            },
+            "invocations": start_times
+                .iter()
+                .map(|start_time| serde_json::json!({
+                    "start_time": start_time,
+                    "duration_including_children_sec": 1.0,
+                    "children": [],
+                }))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), Error> {
+        let merged = merge(vec![metrics(1, &[30, 10]), metrics(1, &[20])])?;
+        assert_eq!(1, merged.format_version);
+        assert_eq!(
+            vec![10, 20, 30],
+            merged.invocations.iter().map(|i| i.start_time).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_different_versions() {
+        assert!(merge(vec![metrics(1, &[10]), metrics(0, &[20])]).is_err());
+    }
+}
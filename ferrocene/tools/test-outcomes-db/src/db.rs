@@ -0,0 +1,438 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+use anyhow::Error;
+use build_helper::metrics::{JsonNode, JsonRoot, TestOutcome, TestSuite, TestSuiteMetadata};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS runs (
+        id INTEGER PRIMARY KEY,
+        commit_sha TEXT NOT NULL,
+        job TEXT NOT NULL,
+        start_time INTEGER NOT NULL,
+        UNIQUE (commit_sha, job)
+    );
+
+    CREATE TABLE IF NOT EXISTS outcomes (
+        run INTEGER NOT NULL REFERENCES runs (id) ON DELETE CASCADE,
+        host TEXT NOT NULL,
+        stage INTEGER NOT NULL,
+        target TEXT NOT NULL,
+        test TEXT NOT NULL,
+        outcome TEXT NOT NULL,
+        attempts INTEGER NOT NULL,
+        PRIMARY KEY (run, host, stage, target, test)
+    );
+
+    CREATE INDEX IF NOT EXISTS outcomes_by_test ON outcomes (test, target);
+";
+
+pub(crate) struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub(crate) fn open(path: &Path) -> Result<Self, Error> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Database { conn })
+    }
+
+    /// Stores the outcomes of the tests contained in the build metrics of a CI job, returning how
+    /// many outcomes were stored. Ingesting the same job of the same commit again replaces the
+    /// outcomes stored previously.
+    pub(crate) fn ingest(
+        &mut self,
+        commit: &str,
+        job: &str,
+        metrics: &JsonRoot,
+    ) -> Result<usize, Error> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM runs WHERE commit_sha = ?1 AND job = ?2", params![commit, job])?;
+
+        // Runs are ordered by the time they started, as commit hashes carry no ordering.
+        let start_time = metrics.invocations.iter().map(|i| i.start_time).min().unwrap_or(0);
+        tx.execute(
+            "INSERT INTO runs (commit_sha, job, start_time) VALUES (?1, ?2, ?3)",
+            params![commit, job, start_time as i64],
+        )?;
+        let run = tx.last_insert_rowid();
+
+        let mut count = 0;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO outcomes (run, host, stage, target, test, outcome, attempts)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            // The nodes are visited in the order they were executed, so that when a test ran more
+            // than once the outcome stored is the last one.
+            let mut stack = metrics
+                .invocations
+                .iter()
+                .rev()
+                .flat_map(|i| i.children.iter().rev())
+                .collect::<Vec<_>>();
+            while let Some(node) = stack.pop() {
+                match node {
+                    JsonNode::RustbuildStep { children, .. } => stack.extend(children.iter().rev()),
+                    JsonNode::TestSuite(TestSuite { metadata, tests }) => {
+                        let (host, stage, target) = suite_environment(metadata);
+                        for test in tests {
+                            let name = test_name(metadata, &test.name);
+                            let outcome = match test.outcome {
+                                TestOutcome::Passed => "passed",
+                                TestOutcome::Failed => "failed",
+                                TestOutcome::Ignored { .. } => "ignored",
+                            };
+                            let attempts = test.attempts.len().max(1) as i64;
+                            insert.execute(params![
+                                run, host, stage, target, name, outcome, attempts
+                            ])?;
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(count)
+    }
+
+    /// Returns, for each target, the most recent commit the test passed on.
+    pub(crate) fn last_passed(
+        &self,
+        test: &str,
+        target: Option<&str>,
+    ) -> Result<Vec<LastPassed>, Error> {
+        // SQLite takes the bare columns from the row with the maximum start time.
+        let mut query = self.conn.prepare(
+            "SELECT o.target, r.commit_sha, MAX(r.start_time)
+             FROM outcomes o JOIN runs r ON r.id = o.run
+             WHERE o.test = ?1 AND o.outcome = 'passed' AND (?2 IS NULL OR o.target = ?2)
+             GROUP BY o.target
+             ORDER BY o.target",
+        )?;
+        let rows = query.query_map(params![test, target], |row| {
+            Ok(LastPassed { target: row.get(0)?, commit: row.get(1)? })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Returns, for each target, how many times the test was executed, how many times it failed
+    /// and how many times it only passed after being retried.
+    pub(crate) fn flakiness(
+        &self,
+        test: &str,
+        target: Option<&str>,
+    ) -> Result<Vec<Flakiness>, Error> {
+        let mut query = self.conn.prepare(
+            "SELECT
+                 target,
+                 COUNT(*),
+                 SUM(outcome = 'failed'),
+                 SUM(outcome = 'passed' AND attempts > 1)
+             FROM outcomes
+             WHERE test = ?1 AND outcome != 'ignored' AND (?2 IS NULL OR target = ?2)
+             GROUP BY target
+             ORDER BY target",
+        )?;
+        let rows = query.query_map(params![test, target], |row| {
+            Ok(Flakiness {
+                target: row.get(0)?,
+                executions: row.get(1)?,
+                failures: row.get(2)?,
+                passed_on_retry: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Returns the commits that changed the outcome of the test compared to the previous run
+    /// on the same target, in chronological order.
+    pub(crate) fn changes(&self, test: &str, target: Option<&str>) -> Result<Vec<Change>, Error> {
+        let mut query = self.conn.prepare(
+            "SELECT target, commit_sha, previous, outcome FROM (
+                 SELECT
+                     o.target,
+                     r.commit_sha,
+                     r.start_time,
+                     o.outcome,
+                     LAG(o.outcome) OVER (
+                         PARTITION BY o.target, o.host, o.stage ORDER BY r.start_time
+                     ) AS previous
+                 FROM outcomes o JOIN runs r ON r.id = o.run
+                 WHERE o.test = ?1 AND (?2 IS NULL OR o.target = ?2)
+             )
+             WHERE previous IS NOT NULL AND previous != outcome
+             ORDER BY target, start_time",
+        )?;
+        let rows = query.query_map(params![test, target], |row| {
+            Ok(Change {
+                target: row.get(0)?,
+                commit: row.get(1)?,
+                from: row.get(2)?,
+                to: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+}
+
+/// Returns the host, the stage and the target the tests of a suite ran with.
+fn suite_environment(metadata: &TestSuiteMetadata) -> (&str, u32, &str) {
+    match metadata {
+        TestSuiteMetadata::Compiletest { host, stage, target, .. }
+        | TestSuiteMetadata::CargoPackage { host, stage, target, .. } => (host, *stage, target),
+    }
+}
+
+/// Returns the name of a test. Compiletest names already include the suite, while the names of
+/// unit tests and doctests are prefixed by the crates they belong to, and suffixed by the codegen
+/// backend they were built with when it isn't the default one.
+fn test_name(metadata: &TestSuiteMetadata, name: &str) -> String {
+    match metadata {
+        TestSuiteMetadata::Compiletest { .. } => name.into(),
+        TestSuiteMetadata::CargoPackage { crates, codegen_backend: None, .. } => {
+            format!("{}::{name}", crates.join(","))
+        }
+        TestSuiteMetadata::CargoPackage { crates, codegen_backend: Some(backend), .. } => {
+            format!("{}::{name} ({backend})", crates.join(","))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct LastPassed {
+    pub(crate) target: String,
+    pub(crate) commit: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Flakiness {
+    pub(crate) target: String,
+    pub(crate) executions: u64,
+    pub(crate) failures: u64,
+    pub(crate) passed_on_retry: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Change {
+    pub(crate) target: String,
+    pub(crate) commit: String,
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TEST: &str = "[ui] tests/ui/foo.rs";
+
+    fn metrics(start_time: u64, tests: serde_json::Value) -> JsonRoot {
+        metrics_of_invocations(vec![serde_json::json!({
+            "start_time": start_time,
+            "duration_including_children_sec": 1.0,
+            "children": [{
+                "kind": "rustbuild_step",
+                "type": "test::Compiletest",
+                "debug_repr": "Compiletest",
+                "duration_excluding_children_sec": 1.0,
+                "system_stats": { "cpu_utilization_percent": 1.0 },
+                "children": [test_suite(1, tests)],
+            }],
+        })])
+    }
+
+    fn metrics_of_invocations(invocations: Vec<serde_json::Value>) -> JsonRoot {
+        serde_json::from_value(serde_json::json!({
+            "format_version": 1,
+            "system_stats": {
+                "cpu_threads_count": 1,
+                "cpu_model": "cpu",
+                "memory_total_bytes": 1,
+            },
+            "invocations": invocations,
+        }))
+        .unwrap()
+    }
+
+    fn test_suite(stage: u32, tests: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "kind": "test_suite",
+            "metadata": {
+                "kind": "compiletest",
+                "suite": "ui",
+                "mode": "ui",
+                "compare_mode": null,
+                "target": "x86_64-unknown-linux-gnu",
+                "host": "x86_64-unknown-linux-gnu",
+                "stage": stage,
+            },
+            "tests": tests,
+        })
+    }
+
+    fn database() -> Result<(TempDir, Database), Error> {
+        let dir = TempDir::new()?;
+        let db = Database::open(&dir.path().join("outcomes.sqlite"))?;
+        Ok((dir, db))
+    }
+
+    #[test]
+    fn test_history() -> Result<(), Error> {
+        let (_dir, mut db) = database()?;
+        let passed = serde_json::json!([{ "name": TEST, "outcome": "passed" }]);
+        let failed = serde_json::json!([{ "name": TEST, "outcome": "failed" }]);
+        let retried = serde_json::json!([{
+            "name": TEST,
+            "outcome": "passed",
+            "attempts": [
+                { "outcome": "failed", "message": null },
+                { "outcome": "passed", "message": null },
+            ],
+        }]);
+        db.ingest("aaa", "x86_64-linux-test", &metrics(10, passed))?;
+        db.ingest("bbb", "x86_64-linux-test", &metrics(20, failed))?;
+        db.ingest("ccc", "x86_64-linux-test", &metrics(30, retried))?;
+
+        let target = "x86_64-unknown-linux-gnu".to_string();
+        assert_eq!(
+            vec![LastPassed { target: target.clone(), commit: "ccc".into() }],
+            db.last_passed(TEST, None)?
+        );
+        assert_eq!(
+            vec![Flakiness {
+                target: target.clone(),
+                executions: 3,
+                failures: 1,
+                passed_on_retry: 1
+            }],
+            db.flakiness(TEST, Some(&target))?
+        );
+        assert_eq!(
+            vec![
+                Change {
+                    target: target.clone(),
+                    commit: "bbb".into(),
+                    from: "passed".into(),
+                    to: "failed".into()
+                },
+                Change { target, commit: "ccc".into(), from: "failed".into(), to: "passed".into() },
+            ],
+            db.changes(TEST, None)?
+        );
+        assert!(db.last_passed(TEST, Some("aarch64-unknown-linux-gnu"))?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_again() -> Result<(), Error> {
+        let (_dir, mut db) = database()?;
+        let failed = serde_json::json!([{ "name": TEST, "outcome": "failed" }]);
+        let passed = serde_json::json!([{ "name": TEST, "outcome": "passed" }]);
+        db.ingest("aaa", "x86_64-linux-test", &metrics(10, failed))?;
+        db.ingest("aaa", "x86_64-linux-test", &metrics(10, passed))?;
+
+        let flakiness = db.flakiness(TEST, None)?;
+        assert_eq!(1, flakiness[0].executions);
+        assert_eq!(0, flakiness[0].failures);
+
+        Ok(())
+    }
+    #[test]
+    fn test_ingest_jobs() -> Result<(), Error> {
+        let (_dir, mut db) = database()?;
+        let passed = serde_json::json!([{ "name": TEST, "outcome": "passed" }]);
+        let failed = serde_json::json!([{ "name": TEST, "outcome": "failed" }]);
+        db.ingest("aaa", "job1/metrics.json", &metrics(10, passed))?;
+        db.ingest("aaa", "job2/metrics.json", &metrics(10, failed))?;
+
+        let flakiness = db.flakiness(TEST, None)?;
+        assert_eq!(2, flakiness[0].executions);
+        assert_eq!(1, flakiness[0].failures);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_stages() -> Result<(), Error> {
+        let (_dir, mut db) = database()?;
+        let passed = serde_json::json!([{ "name": TEST, "outcome": "passed" }]);
+        let failed = serde_json::json!([{ "name": TEST, "outcome": "failed" }]);
+        let invocation = serde_json::json!({
+            "start_time": 10,
+            "duration_including_children_sec": 1.0,
+            "children": [test_suite(1, passed), test_suite(2, failed)],
+        });
+        db.ingest("aaa", "x86_64-linux-test", &metrics_of_invocations(vec![invocation]))?;
+
+        let flakiness = db.flakiness(TEST, None)?;
+        assert_eq!(2, flakiness[0].executions);
+        assert_eq!(1, flakiness[0].failures);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_invocations_in_order() -> Result<(), Error> {
+        let (_dir, mut db) = database()?;
+        let failed = serde_json::json!([{ "name": TEST, "outcome": "failed" }]);
+        let passed = serde_json::json!([{ "name": TEST, "outcome": "passed" }]);
+        // The test suite of the first invocation is nested deeper than the one of the second.
+        let mut invocations = metrics(10, failed).invocations;
+        invocations.extend(
+            metrics_of_invocations(vec![serde_json::json!({
+                "start_time": 20,
+                "duration_including_children_sec": 1.0,
+                "children": [test_suite(1, passed)],
+            })])
+            .invocations,
+        );
+        let metrics = JsonRoot { invocations, ..metrics_of_invocations(Vec::new()) };
+        db.ingest("aaa", "x86_64-linux-test", &metrics)?;
+
+        assert_eq!(1, db.last_passed(TEST, None)?.len());
+        assert_eq!(0, db.flakiness(TEST, None)?[0].failures);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_codegen_backends() -> Result<(), Error> {
+        let (_dir, mut db) = database()?;
+        let suite = |codegen_backend: Option<&str>, outcome: &str| {
+            let mut metadata = serde_json::json!({
+                "kind": "cargo_package",
+                "crates": ["core"],
+                "target": "x86_64-unknown-linux-gnu",
+                "host": "x86_64-unknown-linux-gnu",
+                "stage": 1,
+            });
+            if let Some(backend) = codegen_backend {
+                metadata["codegen_backend"] = backend.into();
+            }
+            serde_json::json!({
+                "kind": "test_suite",
+                "metadata": metadata,
+                "tests": [{ "name": "num::foo", "outcome": outcome }],
+            })
+        };
+        let invocation = serde_json::json!({
+            "start_time": 10,
+            "duration_including_children_sec": 1.0,
+            "children": [suite(None, "passed"), suite(Some("cranelift"), "failed")],
+        });
+        db.ingest("aaa", "x86_64-linux-test", &metrics_of_invocations(vec![invocation]))?;
+
+        assert_eq!(0, db.flakiness("core::num::foo", None)?[0].failures);
+        assert_eq!(1, db.flakiness("core::num::foo (cranelift)", None)?[0].failures);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Persistent store of the test outcomes of every CI run, keyed by commit, target and test.
+//!
+//! The build metrics of each CI job are ingested into a SQLite database, which can then be queried
+//! for the history of a test: when it last passed on a target, how flaky it is, and which commits
+//! changed its outcome.
+
+mod db;
+
+use crate::db::Database;
+use anyhow::{Context, Error};
+use build_helper::metrics::JsonRoot;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+struct Cli {
+    /// Path of the SQLite database, created if missing.
+    #[arg(long)]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Ingest the build metrics of the jobs of a CI run. Directories are searched for `.json`
+    /// files, and each file is recorded as a separate job identified by its path.
+    Ingest {
+        #[arg(long)]
+        commit: String,
+        /// Identifier of the job, instead of the path of the file. Only one file can be ingested
+        /// when this is passed.
+        #[arg(long)]
+        job: Option<String>,
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Show the most recent commit a test passed on, for each target.
+    LastPassed {
+        test: String,
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// Show how many times a test failed or only passed after being retried, for each target.
+    Flakiness {
+        test: String,
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// Show the commits that changed the outcome of a test, for each target.
+    Changes {
+        test: String,
+        #[arg(long)]
+        target: Option<String>,
+    },
+}
+
+fn main() -> Result<(), Error> {
+    let cli = Cli::parse();
+    let mut db = Database::open(&cli.db)
+        .with_context(|| format!("failed to open the database at {}", cli.db.display()))?;
+
+    match cli.command {
+        Command::Ingest { commit, job, paths } => {
+            let files = metrics_files(&paths)?;
+            if job.is_some() && files.len() != 1 {
+                anyhow::bail!("--job requires exactly one metrics file, found {}", files.len());
+            }
+            for file in files {
+                let job = job.clone().unwrap_or_else(|| file.display().to_string());
+                let count = db.ingest(&commit, &job, &load(&file)?)?;
+                eprintln!("ingested {count} test outcomes from {}", file.display());
+            }
+        }
+        Command::LastPassed { test, target } => {
+            for last in db.last_passed(&test, target.as_deref())? {
+                println!("{}: {}", last.target, last.commit);
+            }
+        }
+        Command::Flakiness { test, target } => {
+            for flakiness in db.flakiness(&test, target.as_deref())? {
+                println!(
+                    "{}: {} executions, {} failed, {} passed on retry",
+                    flakiness.target,
+                    flakiness.executions,
+                    flakiness.failures,
+                    flakiness.passed_on_retry
+                );
+            }
+        }
+        Command::Changes { test, target } => {
+            for change in db.changes(&test, target.as_deref())? {
+                println!("{}: {} ({} -> {})", change.target, change.commit, change.from, change.to);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn metrics_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut found = Vec::new();
+            for entry in std::fs::read_dir(path)? {
+                let path = entry?.path();
+                if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                    found.push(path);
+                }
+            }
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn load(path: &Path) -> Result<JsonRoot, Error> {
+    serde_json::from_slice(&std::fs::read(path)?)
+        .with_context(|| format!("failed to read metrics from {}", path.display()))
+}
@@ -11,17 +11,29 @@ pub(crate) struct AnnotatedFile {
     pub(crate) test: PathBuf,
     pub(crate) source: AnnotationSource,
     pub(crate) targets: Targets,
+    pub(crate) name: Option<String>,
+    pub(crate) revision: Option<String>,
+    pub(crate) diagnostic: Option<String>,
 }
 
 impl std::fmt::Display for AnnotatedFile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.test.display())?;
+        if let Some(name) = &self.name {
+            write!(f, " ({name})")?;
+        }
+        if let Some(revision) = &self.revision {
+            write!(f, "#{revision}")?;
+        }
         match &self.source {
-            AnnotationSource::TestItself => write!(f, "{}", self.test.display()),
-            AnnotationSource::Makefile => write!(f, "{} (from its Makefile)", self.test.display()),
-            AnnotationSource::ParentDirectory { .. } => {
-                write!(f, "{} (from its parent directory)", self.test.display())
-            }
+            AnnotationSource::TestItself => {}
+            AnnotationSource::Makefile => write!(f, " (from its Makefile)")?,
+            AnnotationSource::ParentDirectory { .. } => write!(f, " (from its parent directory)")?,
         }
+        if let Some(diagnostic) = &self.diagnostic {
+            write!(f, " (expecting `{diagnostic}`)")?;
+        }
+        Ok(())
     }
 }
 
@@ -99,6 +111,10 @@ struct JsonOutput {
         #[derive(serde::Deserialize)]
         struct JsonTestFile {
             file: PathBuf,
+            // Present when the file contains multiple tests, as with library unit tests and
+            // doctests, in which case the outcome of the test is looked up by its name.
+            #[serde(default)]
+            name: Option<String>,
             annotations: Vec<JsonAnnotation>,
         }
 
@@ -106,6 +122,10 @@ struct JsonTestFile {
         struct JsonAnnotation {
             id: String,
             file: PathBuf,
+            #[serde(default)]
+            revision: Option<String>,
+            #[serde(default)]
+            diagnostic: Option<String>,
         }
 
         // Mark the annotations as not considering ignored tests as soon as test outcomes are not
@@ -129,17 +149,18 @@ struct JsonAnnotation {
                 .to_str()
                 .ok_or_else(|| anyhow::anyhow!("non-utf8 path: {}", file.file.display()))?
                 .to_string();
+            let test_name = file.name.clone().unwrap_or_else(|| relative_file.clone());
             let tmp_binding;
             let outcomes = if let Some(outcomes) = test_outcomes {
                 // Do not consider annotations from ignored tests.
-                if !outcomes.executed_tests.contains_key(&relative_file) {
+                if !outcomes.executed_tests.contains_key(&test_name) {
                     if file.annotations.is_empty() {
                         unreachable!("source files should already be annotated")
                     }
-                    if let Some(tests) = outcomes.ignored_tests.get(&relative_file) {
-                        self.ignored_tests.insert(relative_file.clone(), tests.clone());
+                    if let Some(tests) = outcomes.ignored_tests.get(&test_name) {
+                        self.ignored_tests.insert(test_name, tests.clone());
                     } else {
-                        unknown_tests.insert(relative_file);
+                        unknown_tests.insert(test_name);
                     }
                     continue;
                 }
@@ -165,23 +186,39 @@ struct JsonAnnotation {
                     );
                 };
 
+                // Annotations scoped to a revision only count when that revision was executed.
+                let (executed_tests, ignored_tests, outcome_key) = match &annotation.revision {
+                    Some(revision) => (
+                        &outcomes.executed_revisions,
+                        &outcomes.ignored_revisions,
+                        format!("{test_name}#{revision}"),
+                    ),
+                    None => (&outcomes.executed_tests, &outcomes.ignored_tests, test_name.clone()),
+                };
+                if test_outcomes.is_some() && !executed_tests.contains_key(&outcome_key) {
+                    if let Some(tests) = ignored_tests.get(&outcome_key) {
+                        self.ignored_tests.insert(outcome_key, tests.clone());
+                    } else {
+                        unknown_tests.insert(outcome_key);
+                    }
+                    continue;
+                }
+
                 let ignored = DisplayCommaSeparatedSet(
-                    outcomes
-                        .ignored_tests
-                        .get(&relative_file)
-                        .unwrap_or(&BTreeSet::new())
-                        .to_owned(),
+                    ignored_tests.get(&outcome_key).unwrap_or(&BTreeSet::new()).to_owned(),
                 );
                 let executed = DisplayCommaSeparatedSet(
-                    outcomes
-                        .executed_tests
-                        .get(&relative_file)
-                        .unwrap_or(&BTreeSet::new())
-                        .to_owned(),
+                    executed_tests.get(&outcome_key).unwrap_or(&BTreeSet::new()).to_owned(),
                 );
                 let targets = Targets { executed, ignored };
-                let annotated_file =
-                    AnnotatedFile { test: shrink_path(&file.file), source, targets };
+                let annotated_file = AnnotatedFile {
+                    test: shrink_path(&file.file),
+                    source,
+                    targets,
+                    name: file.name.clone(),
+                    revision: annotation.revision.clone(),
+                    diagnostic: annotation.diagnostic.clone(),
+                };
                 self.ids.entry(annotation.id.clone()).or_default().insert(annotated_file);
             }
         }
@@ -219,6 +256,7 @@ fn test_load_file() -> Result<(), Error> {
                 ("example/ignored.rs".into(), BTreeSet::default()),
                 ("example/ignored-without-annotations.rs".into(), BTreeSet::default()),
             ]),
+            ..Default::default()
         });
 
         let mut annotations = Annotations::new();
@@ -239,6 +277,9 @@ fn test_load_file() -> Result<(), Error> {
                             bulk_file: "example/ferrocene-annotations".into(),
                         },
                         targets: Default::default(),
+                        name: None,
+                        revision: None,
+                        diagnostic: None,
                     }])
                 ),
             ]),
@@ -272,6 +313,7 @@ fn test_load_directory() -> Result<(), Error> {
                 ("example/ignored.rs".into(), BTreeSet::default()),
                 ("example/ignored-without-annotations.rs".into(), BTreeSet::default()),
             ]),
+            ..Default::default()
         });
 
         let mut annotations = Annotations::new();
@@ -297,6 +339,9 @@ fn test_load_directory() -> Result<(), Error> {
                             bulk_file: "example/ferrocene-annotations".into(),
                         },
                         targets: Default::default(),
+                        name: None,
+                        revision: None,
+                        diagnostic: None,
                     }])
                 ),
                 // quux is not loaded as it's in a subdirectory
@@ -309,11 +354,116 @@ fn test_load_directory() -> Result<(), Error> {
         Ok(())
     }
 
+    #[test]
+    fn test_load_file_with_revisions() -> Result<(), Error> {
+        let file = NamedTempFile::new()?;
+        std::fs::write(
+            file.path(),
+            serde_json::to_vec(&serde_json::json!({
+                "tests": [
+                    {
+                        "file": "/base/example/revs.rs",
+                        "annotations": [
+                            {
+                                "id": "a",
+                                "file": "/base/example/revs.rs",
+                                "revision": "a",
+                                "diagnostic": "mismatched types",
+                            },
+                            {
+                                "id": "b",
+                                "file": "/base/example/revs.rs",
+                                "revision": "b",
+                            },
+                        ],
+                    },
+                ],
+                "bulk_annotations_file_name": "ferrocene-annotations",
+            }))?,
+        )?;
+
+        let outcomes = Some(TestOutcomes {
+            executed_tests: BTreeMap::from([("example/revs.rs".into(), BTreeSet::default())]),
+            ignored_tests: BTreeMap::from([("example/revs.rs".into(), BTreeSet::default())]),
+            executed_revisions: BTreeMap::from([("example/revs.rs#a".into(), BTreeSet::default())]),
+            ignored_revisions: BTreeMap::from([("example/revs.rs#b".into(), BTreeSet::default())]),
+            passed_on_retry: BTreeMap::new(),
+        });
+
+        let mut annotations = Annotations::new();
+        annotations.load_file(file.path(), Path::new("/base"), outcomes.as_ref())?;
+
+        let annotated = AnnotatedFile {
+            revision: Some("a".into()),
+            diagnostic: Some("mismatched types".into()),
+            ..test_itself("example/revs.rs")
+        };
+        assert_eq!("example/revs.rs#a (expecting `mismatched types`)", annotated.to_string());
+        assert_eq!(BTreeMap::from([("a".into(), BTreeSet::from([annotated]))]), annotations.ids);
+        let expected = BTreeMap::from([("example/revs.rs#b".into(), BTreeSet::default())]);
+        assert_eq!(expected, annotations.ignored_tests);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_file_with_named_tests() -> Result<(), Error> {
+        let file = NamedTempFile::new()?;
+        std::fs::write(
+            file.path(),
+            serde_json::to_vec(&serde_json::json!({
+                "tests": [
+                    {
+                        "file": "/base/library/core/tests/mem.rs",
+                        "name": "core::mem::test_swap",
+                        "annotations": [
+                            {"id": "swap", "file": "/base/library/core/tests/mem.rs"},
+                        ],
+                    },
+                    {
+                        "file": "/base/library/core/tests/mem.rs",
+                        "name": "core::mem::test_replace",
+                        "annotations": [
+                            {"id": "replace", "file": "/base/library/core/tests/mem.rs"},
+                        ],
+                    },
+                ],
+                "bulk_annotations_file_name": "ferrocene-annotations",
+            }))?,
+        )?;
+
+        let outcomes = Some(TestOutcomes {
+            executed_tests: BTreeMap::from([("core::mem::test_swap".into(), BTreeSet::default())]),
+            ignored_tests: BTreeMap::from([(
+                "core::mem::test_replace".into(),
+                BTreeSet::default(),
+            )]),
+            ..Default::default()
+        });
+
+        let mut annotations = Annotations::new();
+        annotations.load_file(file.path(), Path::new("/base"), outcomes.as_ref())?;
+
+        let annotated = AnnotatedFile {
+            name: Some("core::mem::test_swap".into()),
+            ..test_itself("library/core/tests/mem.rs")
+        };
+        assert_eq!("library/core/tests/mem.rs (core::mem::test_swap)", annotated.to_string());
+        assert_eq!(BTreeMap::from([("swap".into(), BTreeSet::from([annotated]))]), annotations.ids);
+        let expected = BTreeMap::from([("core::mem::test_replace".into(), BTreeSet::default())]);
+        assert_eq!(expected, annotations.ignored_tests);
+
+        Ok(())
+    }
+
     fn test_itself(path: impl AsRef<Path>) -> AnnotatedFile {
         AnnotatedFile {
             test: path.as_ref().into(),
             source: AnnotationSource::TestItself,
             targets: Default::default(),
+            name: None,
+            revision: None,
+            diagnostic: None,
         }
     }
 
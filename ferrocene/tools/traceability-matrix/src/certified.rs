@@ -0,0 +1,378 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Discovery of the functions in the certified subset of the standard library, and of the tests
+//! exercising them.
+//!
+//! Functions in the subset are marked with `#[ferrocene::certified]` in the source code of `core`
+//! and `alloc`. A function is considered tested if one of its doctests passed, or if a test of
+//! its crate calling it passed. The calls are recorded by the compiler with `-Z certified-calls`,
+//! which resolves them to the called function and writes one JSON file per test crate.
+//!
+//! When no test outcomes are available, all the doctests and all the tests are considered.
+
+use crate::test_outcomes::TestOutcomes;
+use anyhow::{Context, Error};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+const MARKER: &str = "#[ferrocene::certified]";
+const CRATES: &[&str] = &["core", "alloc"];
+
+/// Library crate tested by each test crate whose calls are recorded.
+const TEST_CRATES: &[(&str, &str)] =
+    &[("alloc", "alloc"), ("collectionstests", "alloc"), ("core", "core"), ("coretests", "core")];
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct CertifiedSubset {
+    pub(crate) tested: Vec<CertifiedItem>,
+    pub(crate) untested: Vec<CertifiedItem>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct CertifiedItem {
+    pub(crate) name: String,
+    pub(crate) file: PathBuf,
+    pub(crate) line: usize,
+    /// First and last lines of the documentation of the item, if it contains a doctest.
+    pub(crate) doctests: Option<(usize, usize)>,
+    pub(crate) tests: BTreeSet<CertifiedTest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum CertifiedTest {
+    Doctest,
+    Test { krate: String, name: String },
+}
+
+impl CertifiedSubset {
+    pub(crate) fn load(
+        src_base: &Path,
+        calls_dir: Option<&Path>,
+        outcomes: Option<&TestOutcomes>,
+    ) -> Result<Self, Error> {
+        let mut items = Vec::new();
+        for krate in CRATES {
+            let mut sources = Vec::new();
+            collect_rust_files(&src_base.join("library").join(krate).join("src"), &mut sources)?;
+            for source in sources {
+                let file: PathBuf = source.strip_prefix(src_base)?.into();
+                for mut item in find_items(&file, &std::fs::read_to_string(&source)?) {
+                    let doctest_passed = match (&item.doctests, outcomes) {
+                        (None, _) => false,
+                        (Some(_), None) => true,
+                        (&Some((first, last)), Some(outcomes)) => {
+                            outcomes.library_doctest_passed(krate, &item.file, first..=last)
+                        }
+                    };
+                    if doctest_passed {
+                        item.tests.insert(CertifiedTest::Doctest);
+                    }
+                    items.push((*krate, item));
+                }
+            }
+        }
+
+        if let Some(calls_dir) = calls_dir {
+            let locations: BTreeMap<(&Path, usize), usize> = items
+                .iter()
+                .enumerate()
+                .map(|(i, (_, item))| ((item.file.as_path(), item.line), i))
+                .collect();
+            let mut tests = Vec::new();
+            for calls in load_calls(calls_dir)? {
+                let Some(&(_, krate)) = TEST_CRATES.iter().find(|(c, _)| *c == calls.crate_name)
+                else {
+                    continue;
+                };
+                for test in calls.tests {
+                    if outcomes.is_some_and(|o| !o.library_test_passed(krate, &test.name)) {
+                        continue;
+                    }
+                    for call in &test.calls {
+                        if let Some(i) = find_location(&locations, Path::new(&call.file), call.line)
+                        {
+                            tests.push((i, calls.crate_name.clone(), test.name.clone()));
+                        }
+                    }
+                }
+            }
+            for (i, krate, name) in tests {
+                items[i].1.tests.insert(CertifiedTest::Test { krate, name });
+            }
+        }
+
+        let mut subset = CertifiedSubset::default();
+        for (_, item) in items {
+            if item.tests.is_empty() {
+                subset.untested.push(item);
+            } else {
+                subset.tested.push(item);
+            }
+        }
+        subset.tested.sort();
+        subset.untested.sort();
+        Ok(subset)
+    }
+
+    pub(crate) fn total(&self) -> usize {
+        self.tested.len() + self.untested.len()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct CertifiedCalls {
+    crate_name: String,
+    tests: Vec<TestCalls>,
+}
+
+#[derive(serde::Deserialize)]
+struct TestCalls {
+    name: String,
+    calls: Vec<CertifiedCall>,
+}
+
+#[derive(serde::Deserialize)]
+struct CertifiedCall {
+    file: String,
+    line: usize,
+}
+
+fn load_calls(dir: &Path) -> Result<Vec<CertifiedCalls>, Error> {
+    let mut calls = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        calls.push(
+            serde_json::from_slice(&std::fs::read(&path)?).with_context(|| {
+                format!("failed to read certified calls from {}", path.display())
+            })?,
+        );
+    }
+    Ok(calls)
+}
+
+/// Finds the item defined at `line` of `file`. The file emitted by the compiler can be absolute
+/// or remapped, so it matches items whose path relative to the source base is a suffix of it.
+fn find_location(
+    locations: &BTreeMap<(&Path, usize), usize>,
+    file: &Path,
+    line: usize,
+) -> Option<usize> {
+    let mut components = file.components();
+    loop {
+        if let Some(&i) = locations.get(&(components.as_path(), line)) {
+            return Some(i);
+        }
+        components.next()?;
+    }
+}
+
+fn collect_rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_rust_files(&path, files)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn find_items(file: &Path, contents: &str) -> Vec<CertifiedItem> {
+    let lines = contents.lines().collect::<Vec<_>>();
+    let mut items = Vec::new();
+    for (marker, _) in lines.iter().enumerate().filter(|(_, line)| line.trim() == MARKER) {
+        // The marker is followed by other attributes and then by the function signature.
+        let Some((offset, name)) = lines[marker..]
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().starts_with("#[") && !line.trim().starts_with("//"))
+            .find_map(|(i, line)| Some((i, fn_name(line)?)))
+        else {
+            continue;
+        };
+
+        // The marker is preceded by other attributes and by the documentation.
+        let docs = lines[..marker]
+            .iter()
+            .rev()
+            .map(|line| line.trim())
+            .take_while(|line| line.starts_with("///") || line.starts_with("#["))
+            .count();
+        let has_doctest =
+            lines[marker - docs..marker].iter().any(|line| line.trim().starts_with("/// ```"));
+
+        items.push(CertifiedItem {
+            name: name.into(),
+            file: file.into(),
+            line: marker + offset + 1,
+            // Line numbers start at 1, so `marker` is the number of the line preceding the marker.
+            doctests: has_doctest.then(|| (marker - docs + 1, marker)),
+            tests: BTreeSet::new(),
+        });
+    }
+    items
+}
+
+fn fn_name(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once("fn ")?;
+    let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+    (end > 0).then(|| &rest[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_items() {
+        let source = "\
+/// Returns the answer.
+///
+/// ```
+/// assert_eq!(answer(), 42);
+/// ```
+#[inline]
+#[ferrocene::certified]
+#[stable(feature = \"rust1\", since = \"1.0.0\")]
+pub const fn answer() -> u32 {
+    42
+}
+
+impl Foo {
+    /// Not tested by a doctest.
+    #[ferrocene::certified]
+    pub fn method(&self) {}
+}
+
+pub fn not_certified() {}
+";
+        let items = find_items(Path::new("lib.rs"), source);
+        assert_eq!(
+            vec![
+                CertifiedItem {
+                    name: "answer".into(),
+                    file: "lib.rs".into(),
+                    line: 9,
+                    doctests: Some((1, 6)),
+                    tests: BTreeSet::new(),
+                },
+                CertifiedItem {
+                    name: "method".into(),
+                    file: "lib.rs".into(),
+                    line: 16,
+                    doctests: None,
+                    tests: BTreeSet::new(),
+                },
+            ],
+            items
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let src_base = TempDir::new().unwrap();
+        let src = src_base.path().join("library/core/src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            src.join("lib.rs"),
+            "\
+/// ```
+/// assert!(foo());
+/// ```
+#[ferrocene::certified]
+pub fn foo() -> bool { true }
+
+#[ferrocene::certified]
+pub fn bar() {}
+
+#[ferrocene::certified]
+pub fn baz() {}
+",
+        )
+        .unwrap();
+
+        let calls = TempDir::new().unwrap();
+        let content = json!({
+            "crate_name": "coretests",
+            "tests": [
+                {
+                    "name": "test_bar",
+                    "calls": [
+                        {
+                            "path": "core::bar",
+                            "file": "/checkout/library/core/src/lib.rs",
+                            "line": 8,
+                        },
+                    ],
+                },
+                {
+                    "name": "test_baz",
+                    "calls": [
+                        {
+                            "path": "core::baz",
+                            "file": "library/core/src/lib.rs",
+                            "line": 11,
+                        },
+                    ],
+                },
+            ],
+        });
+        std::fs::write(calls.path().join("coretests.json"), content.to_string()).unwrap();
+
+        let item = |name: &str, line, doctests, tests: &[CertifiedTest]| CertifiedItem {
+            name: name.into(),
+            file: "library/core/src/lib.rs".into(),
+            line,
+            doctests,
+            tests: tests.iter().cloned().collect(),
+        };
+        let test =
+            |name: &str| CertifiedTest::Test { krate: "coretests".into(), name: name.into() };
+
+        // Without test outcomes, all the tests are considered.
+        assert_eq!(
+            CertifiedSubset {
+                tested: vec![
+                    item("bar", 8, None, &[test("test_bar")]),
+                    item("baz", 11, None, &[test("test_baz")]),
+                    item("foo", 5, Some((1, 3)), &[CertifiedTest::Doctest]),
+                ],
+                untested: vec![],
+            },
+            CertifiedSubset::load(src_base.path(), Some(calls.path()), None).unwrap()
+        );
+
+        // With test outcomes, only the tests that passed are considered.
+        let outcomes = TestOutcomes {
+            executed_tests: BTreeMap::from([(
+                "core::test_bar".into(),
+                BTreeSet::from(["x86_64-unknown-linux-gnu".into()]),
+            )]),
+            ignored_tests: BTreeMap::from([
+                ("core::test_baz".into(), BTreeSet::from(["x86_64-unknown-linux-gnu".into()])),
+                (
+                    "core::library/core/src/lib.rs - foo (line 1)".into(),
+                    BTreeSet::from(["x86_64-unknown-linux-gnu".into()]),
+                ),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            CertifiedSubset {
+                tested: vec![item("bar", 8, None, &[test("test_bar")])],
+                untested: vec![item("baz", 11, None, &[]), item("foo", 5, Some((1, 3)), &[])],
+            },
+            CertifiedSubset::load(src_base.path(), Some(calls.path()), Some(&outcomes)).unwrap()
+        );
+    }
+}
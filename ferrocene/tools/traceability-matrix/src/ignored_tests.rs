@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Justifications of the tests ignored through `ferrocene/ignored-tests.toml`.
+//!
+//! Those tests are skipped by bootstrap rather than ignored by compiletest, so they are missing
+//! from the test outcomes, and are added back to them as ignored on the targets they're listed
+//! for. Validation of the file (expiry, existence of the tests) is done by bootstrap.
+
+use anyhow::Error;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct IgnoredTestsEntry {
+    pub(crate) tests: Vec<String>,
+    pub(crate) targets: Vec<String>,
+    pub(crate) reason: String,
+    #[serde(default)]
+    pub(crate) issue: Option<String>,
+    pub(crate) impact: Impact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Impact {
+    TestEnvironment,
+    NotApplicable,
+    KnownProblem,
+}
+
+impl Impact {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Impact::TestEnvironment => "test environment",
+            Impact::NotApplicable => "not applicable",
+            Impact::KnownProblem => "known problem",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct IgnoredTestsFile {
+    pub(crate) entries: Vec<IgnoredTestsEntry>,
+}
+
+impl IgnoredTestsFile {
+    pub(crate) fn load(path: &Path) -> Result<Self, Error> {
+        let suites: BTreeMap<String, Vec<IgnoredTestsEntry>> =
+            toml::from_str(&std::fs::read_to_string(path)?)?;
+        Ok(IgnoredTestsFile { entries: suites.into_values().flatten().collect() })
+    }
+
+    /// Returns the entries justifying why a test is ignored. Entries can list whole directories,
+    /// and apply to all the revisions of a test.
+    pub(crate) fn justifications(&self, test: &str) -> Vec<&IgnoredTestsEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry.tests.iter().any(|t| {
+                    test.strip_prefix(t.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '#']))
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_justifications() -> Result<(), Error> {
+        let file = NamedTempFile::new()?;
+        std::fs::write(
+            file.path(),
+            r#"
+                [["tests/ui"]]
+                tests = ["tests/ui/foo.rs", "tests/ui/bar"]
+                targets = ["aarch64-unknown-linux-gnu"]
+                reason = "Foo"
+                impact = "test-environment"
+
+                [["tests/run-make"]]
+                tests = ["tests/run-make/baz"]
+                targets = ["x86_64-unknown-linux-gnu"]
+                reason = "Baz"
+                issue = "ferrocene/ferrocene#1"
+                expires = "1.80.0"
+                impact = "known-problem"
+            "#,
+        )?;
+        let ignored = IgnoredTestsFile::load(file.path())?;
+
+        let impacts =
+            |test| ignored.justifications(test).iter().map(|e| e.impact).collect::<Vec<_>>();
+        assert_eq!(vec![Impact::TestEnvironment], impacts("tests/ui/foo.rs"));
+        assert_eq!(vec![Impact::TestEnvironment], impacts("tests/ui/foo.rs#rev"));
+        assert_eq!(vec![Impact::TestEnvironment], impacts("tests/ui/bar/quux.rs"));
+        assert_eq!(vec![Impact::KnownProblem], impacts("tests/run-make/baz"));
+        assert!(impacts("tests/ui/foo.rs.bak").is_empty());
+        assert!(impacts("tests/ui/barbaz.rs").is_empty());
+
+        Ok(())
+    }
+}
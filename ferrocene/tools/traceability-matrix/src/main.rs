@@ -2,13 +2,17 @@
 // SPDX-FileCopyrightText: The Ferrocene Developers
 
 mod annotations;
+mod certified;
 mod documentations;
+mod ignored_tests;
 mod matrix;
 mod report;
 mod test_outcomes;
 mod utils;
 
 use crate::annotations::Annotations;
+use crate::certified::CertifiedSubset;
+use crate::ignored_tests::IgnoredTestsFile;
 use crate::matrix::TraceabilityMatrix;
 use crate::report::Urls;
 use crate::test_outcomes::TestOutcomes;
@@ -19,8 +23,10 @@
 fn main() -> Result<(), Error> {
     let annotations_path = env_path("ANNOTATIONS");
     let html_out = env_path("HTML_OUT");
+    let json_out = maybe_env_path("JSON_OUT");
     let src_base = env_path("SRC_BASE");
     let test_outcomes_dir = maybe_env_path("TEST_OUTCOMES_DIR");
+    let certified_calls_dir = maybe_env_path("CERTIFIED_CALLS");
 
     let urls = Urls { src: env_str("SRC_URL") };
 
@@ -29,17 +35,33 @@ fn main() -> Result<(), Error> {
         documentations::load("UM", &env_path("UM_IDS"), &env_str("UM_URL"))?,
     ];
 
-    let test_outcomes =
+    let ignored_tests = IgnoredTestsFile::load(&src_base.join("ferrocene/ignored-tests.toml"))?;
+    let mut test_outcomes =
         if let Some(dir) = test_outcomes_dir { Some(TestOutcomes::load(&dir)?) } else { None };
+    if let Some(test_outcomes) = &mut test_outcomes {
+        test_outcomes.add_ignored_tests(&ignored_tests);
+    }
 
     let mut annotations = Annotations::new();
     annotations.load_directory(&annotations_path, &src_base, test_outcomes.as_ref())?;
 
     let matrix = matrix::prepare(&documentations, &annotations)?;
-    cli_summary(&matrix);
-
-    let report = report::generate(&annotations, &matrix, urls)?;
+    let certified =
+        CertifiedSubset::load(&src_base, certified_calls_dir.as_deref(), test_outcomes.as_ref())?;
+    cli_summary(&matrix, &certified);
+
+    let report = report::generate(
+        &annotations,
+        &matrix,
+        &certified,
+        &ignored_tests,
+        test_outcomes.as_ref(),
+        urls,
+    )?;
     std::fs::write(&html_out, report.as_bytes())?;
+    if let Some(json_out) = &json_out {
+        std::fs::write(json_out, report::generate_json(&matrix, &certified)?.as_bytes())?;
+    }
 
     // The file:// link is hopefully clickable in terminals.
     eprintln!("Full report: file://{}", std::fs::canonicalize(&html_out)?.display());
@@ -52,7 +74,7 @@ fn main() -> Result<(), Error> {
     Ok(())
 }
 
-fn cli_summary(matrix: &TraceabilityMatrix) {
+fn cli_summary(matrix: &TraceabilityMatrix, certified: &CertifiedSubset) {
     eprintln!("=====================================");
     eprintln!("==   Traceability matrix summary   ==");
     eprintln!("=====================================");
@@ -70,6 +92,13 @@ fn cli_summary(matrix: &TraceabilityMatrix) {
         eprintln!("Total {} in the spec: {total_items}", analysis.kind.plural);
         eprintln!();
     }
+    if certified.total() > 0 {
+        let tested_count = certified.tested.len();
+        let percent = tested_count as f32 * 100.0 / certified.total() as f32;
+        eprintln!("Certified functions with a test: {tested_count} ({percent:.2}%)");
+        eprintln!("Total functions in the certified subset: {}", certified.total());
+        eprintln!();
+    }
     if !matrix.unknown_annotations.is_empty() {
         eprintln!("Tests with unknown annotations:");
         for test in &matrix.unknown_annotations {
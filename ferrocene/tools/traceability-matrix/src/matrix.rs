@@ -721,6 +721,9 @@ fn test_itself(path: impl AsRef<Path>) -> AnnotatedFile {
             test: path.as_ref().into(),
             source: AnnotationSource::TestItself,
             targets: Default::default(),
+            name: None,
+            revision: None,
+            diagnostic: None,
         }
     }
 }
@@ -2,9 +2,13 @@
 // SPDX-FileCopyrightText: The Ferrocene Developers
 
 use crate::annotations::{AnnotationSource, Annotations};
-use crate::matrix::{ElementKind, LinkTest, Page, TraceabilityMatrix};
+use crate::certified::{CertifiedItem, CertifiedSubset, CertifiedTest};
+use crate::ignored_tests::{IgnoredTestsEntry, IgnoredTestsFile};
+use crate::matrix::{Element, ElementKind, Link, LinkTest, Page, TraceabilityMatrix};
+use crate::test_outcomes::TestOutcomes;
 use anyhow::Error;
 use askama::Template;
+use serde_json::json;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 #[derive(Template)]
@@ -12,11 +16,18 @@
 struct Report<'a> {
     considers_ignored_tests: bool,
     matrix: &'a TraceabilityMatrix,
+    certified: &'a CertifiedSubset,
     summary: Vec<SummaryRow<'a>>,
-    ignored_tests: BTreeMap<String, BTreeSet<String>>,
+    ignored_tests: Vec<IgnoredTest<'a>>,
+    passed_on_retry: BTreeMap<String, BTreeSet<String>>,
     urls: Urls,
 }
 
+struct IgnoredTest<'a> {
+    name: String,
+    justifications: Vec<&'a IgnoredTestsEntry>,
+}
+
 struct SummaryRow<'a> {
     page: Option<&'a Page>,
     kinds: Vec<SummaryItem>,
@@ -52,18 +63,102 @@ pub(crate) struct Urls {
 pub(crate) fn generate(
     annotations: &Annotations,
     matrix: &TraceabilityMatrix,
+    certified: &CertifiedSubset,
+    ignored_tests: &IgnoredTestsFile,
+    test_outcomes: Option<&TestOutcomes>,
     urls: Urls,
 ) -> Result<String, Error> {
     Ok(Report {
         matrix,
+        certified,
         summary: build_summary(matrix),
         urls,
-        ignored_tests: annotations.ignored_tests.clone(),
+        ignored_tests: annotations
+            .ignored_tests
+            .keys()
+            .map(|name| IgnoredTest {
+                name: name.clone(),
+                justifications: ignored_tests.justifications(name),
+            })
+            .collect(),
+        passed_on_retry: test_outcomes.map(|o| o.passed_on_retry.clone()).unwrap_or_default(),
         considers_ignored_tests: annotations.considers_ignored_tests,
     }
     .render()?)
 }
 
+/// Machine-readable counterpart of the report, referenced by the qualification evidence.
+pub(crate) fn generate_json(
+    matrix: &TraceabilityMatrix,
+    certified: &CertifiedSubset,
+) -> Result<String, Error> {
+    let link_test = |test: &LinkTest| match test {
+        LinkTest::File(file) => json!({
+            "kind": "test",
+            "test": file.test,
+            "name": file.name,
+            "revision": file.revision,
+            "executed_targets": file.targets.executed.0,
+            "ignored_targets": file.targets.ignored.0,
+        }),
+        LinkTest::InheritFromSection { section_id, .. } => {
+            json!({ "kind": "inherit_from_section", "section_id": section_id })
+        }
+        LinkTest::Informational => json!({ "kind": "informational" }),
+        LinkTest::NoParagraphsInSection => json!({ "kind": "no_paragraphs_in_section" }),
+    };
+    let element = |element: &Element, status: &str, link: Option<&Link>| {
+        json!({
+            "id": element.id,
+            "name": element.name(),
+            "documentation": element.page.documentation,
+            "status": status,
+            "tests": link.map(|l| l.tests.iter().map(link_test).collect::<Vec<_>>()),
+            "untested_targets": link.map(|l| &l.untested_targets),
+        })
+    };
+
+    let mut elements = serde_json::Map::new();
+    for analysis in matrix.analyses_by_kind() {
+        let linked = analysis.linked.iter().map(|l| element(l, "linked", Some(l)));
+        let partially_linked =
+            analysis.partially_linked.iter().map(|l| element(l, "partially_linked", Some(l)));
+        let unlinked = analysis.unlinked.iter().map(|e| element(e, "unlinked", None));
+        elements.insert(
+            analysis.kind.plural.replace(' ', "_"),
+            linked.chain(partially_linked).chain(unlinked).collect(),
+        );
+    }
+
+    let certified_item = |item: &CertifiedItem| {
+        let tests = item
+            .tests
+            .iter()
+            .map(|test| match test {
+                CertifiedTest::Doctest => json!({ "kind": "doctest" }),
+                CertifiedTest::Test { krate, name } => {
+                    json!({ "kind": "test", "crate": krate, "name": name })
+                }
+            })
+            .collect::<Vec<_>>();
+        json!({ "name": item.name, "file": item.file, "line": item.line, "tests": tests })
+    };
+
+    Ok(serde_json::to_string_pretty(&json!({
+        "format_version": 1,
+        "elements": elements,
+        "unknown_annotations": matrix
+            .unknown_annotations
+            .iter()
+            .map(|u| json!({ "annotation": u.annotation, "test": u.file.test }))
+            .collect::<Vec<_>>(),
+        "certified": {
+            "tested": certified.tested.iter().map(certified_item).collect::<Vec<_>>(),
+            "untested": certified.untested.iter().map(certified_item).collect::<Vec<_>>(),
+        },
+    }))?)
+}
+
 fn build_summary(matrix: &TraceabilityMatrix) -> Vec<SummaryRow<'_>> {
     let sample_kinds = matrix
         .analyses_by_kind()
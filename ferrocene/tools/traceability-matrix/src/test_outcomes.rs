@@ -1,8 +1,10 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // SPDX-FileCopyrightText: The Ferrocene Developers
 
+use crate::ignored_tests::IgnoredTestsFile;
 use anyhow::{Context, Error};
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::ops::RangeInclusive;
 use std::path::Path;
 
 const COMPILETEST_TYPE: &str = "bootstrap::test::Compiletest";
@@ -17,6 +19,15 @@ pub(crate) struct TestOutcomes {
     // key is name of test, also represented by a path
     // value can be any number of ignored tests
     pub(crate) ignored_tests: BTreeMap<String, BTreeSet<String>>,
+    // same as executed_tests, but only for tests with revisions, with the key being the path
+    // followed by `#revision`
+    pub(crate) executed_revisions: BTreeMap<String, BTreeSet<String>>,
+    // same as ignored_tests, but only for tests with revisions, with the key being the path
+    // followed by `#revision`
+    pub(crate) ignored_revisions: BTreeMap<String, BTreeSet<String>>,
+    // key is name of test, including the revision if any
+    // value is targets on which the test only passed after being retried
+    pub(crate) passed_on_retry: BTreeMap<String, BTreeSet<String>>,
 }
 
 impl TestOutcomes {
@@ -54,30 +65,73 @@ pub(crate) fn load(directory: &Path) -> Result<Self, Error> {
                     }
                     MetricsNode::TestSuite(TestSuite { tests, metadata }) => {
                         if !inside_compiletest {
+                            // Unit tests and doctests of library crates are identified by their
+                            // name, prefixed by the name of the crate they belong to. Only the
+                            // tests built by the default codegen backend are traced, the other
+                            // backends only run them again for differential testing.
+                            if let TestSuiteMetadata::CargoPackage {
+                                crates,
+                                target,
+                                codegen_backend: None,
+                            } = metadata
+                            {
+                                if let [krate] = crates.as_slice() {
+                                    for Test { name, outcome, .. } in tests {
+                                        let tests = if let MetricsTestOutcome::Ignored = outcome {
+                                            &mut test_outcomes.ignored_tests
+                                        } else {
+                                            &mut test_outcomes.executed_tests
+                                        };
+                                        tests
+                                            .entry(library_test_name(krate, name))
+                                            .or_insert_with(BTreeSet::new)
+                                            .insert(target.to_owned());
+                                    }
+                                }
+                            }
                             continue;
                         }
 
-                        for Test { name, outcome } in tests {
+                        for Test { name, outcome, attempts } in tests {
                             // Compiletest test names are in the "[suite] path/to/test.rs#revision"
                             // format, with the revision being optional.
                             let Some(name) = name.split_once("] ").map(|(_, n)| n) else {
                                 continue;
                             };
+                            if attempts.len() > 1 && matches!(outcome, MetricsTestOutcome::Passed) {
+                                let (TestSuiteMetadata::CargoPackage { target, .. }
+                                | TestSuiteMetadata::Compiletest { target }) = metadata;
+                                test_outcomes
+                                    .passed_on_retry
+                                    .entry(name.to_string())
+                                    .or_default()
+                                    .insert(target.to_owned());
+                            }
+                            let has_revision = name.contains('#');
+                            let full_name = name.to_string();
                             let name = name.rsplit_once('#').map(|(n, _)| n).unwrap_or(name).into();
 
-                            let (TestSuiteMetadata::CargoPackage { target }
+                            let (TestSuiteMetadata::CargoPackage { target, .. }
                             | TestSuiteMetadata::Compiletest { target }) = metadata;
 
-                            if let MetricsTestOutcome::Ignored = outcome {
-                                test_outcomes
-                                    .ignored_tests
-                                    .entry(name)
-                                    .or_insert_with(BTreeSet::new)
-                                    .insert(target.to_owned());
+                            let (tests, revisions) = if let MetricsTestOutcome::Ignored = outcome {
+                                (
+                                    &mut test_outcomes.ignored_tests,
+                                    &mut test_outcomes.ignored_revisions,
+                                )
                             } else {
-                                test_outcomes
-                                    .executed_tests
-                                    .entry(name)
+                                (
+                                    &mut test_outcomes.executed_tests,
+                                    &mut test_outcomes.executed_revisions,
+                                )
+                            };
+                            tests
+                                .entry(name)
+                                .or_insert_with(BTreeSet::new)
+                                .insert(target.to_owned());
+                            if has_revision {
+                                revisions
+                                    .entry(full_name)
                                     .or_insert_with(BTreeSet::new)
                                     .insert(target.to_owned());
                             }
@@ -89,6 +143,58 @@ pub(crate) fn load(directory: &Path) -> Result<Self, Error> {
 
         Ok(test_outcomes)
     }
+
+    /// Adds the tests skipped through `ferrocene/ignored-tests.toml` as ignored on the targets
+    /// they're listed for, as skipped tests are not recorded in the build metrics.
+    pub(crate) fn add_ignored_tests(&mut self, ignored: &IgnoredTestsFile) {
+        for entry in &ignored.entries {
+            for test in &entry.tests {
+                for target in &entry.targets {
+                    if self.executed_tests.get(test).is_some_and(|t| t.contains(target)) {
+                        continue;
+                    }
+                    self.ignored_tests.entry(test.clone()).or_default().insert(target.clone());
+                }
+            }
+        }
+    }
+
+    /// Returns whether the library test with the given libtest name passed.
+    pub(crate) fn library_test_passed(&self, krate: &str, name: &str) -> bool {
+        self.executed_tests.contains_key(&library_test_name(krate, name))
+    }
+
+    /// Returns whether a doctest of `file` (relative to the source base) starting within `lines`
+    /// passed.
+    pub(crate) fn library_doctest_passed(
+        &self,
+        krate: &str,
+        file: &Path,
+        lines: RangeInclusive<usize>,
+    ) -> bool {
+        let prefix = format!("{krate}::");
+        self.executed_tests.keys().filter_map(|name| name.strip_prefix(&prefix)).any(|name| {
+            parse_doctest_name(name).is_some_and(|(doctest_file, line)| {
+                file.ends_with(doctest_file) && lines.contains(&line)
+            })
+        })
+    }
+}
+
+/// Splits the name of a library doctest, "file.rs - path::to::Item (line N)", into its file and
+/// line. The item path is empty for the documentation of the crate root.
+fn parse_doctest_name(name: &str) -> Option<(&str, usize)> {
+    let (rest, line) = name.strip_suffix(')')?.rsplit_once("(line ")?;
+    let (file, _) = rest.split_once(" - ")?;
+    Some((file, line.parse().ok()?))
+}
+
+/// Converts the libtest name of a library test into the name used by its annotations.
+///
+/// Doctests are named "library/<crate>/src/file.rs - path::to::Item (line N)" by rustdoc, which
+/// compiletest reproduces when collecting their annotations, so the names are kept as they are.
+fn library_test_name(krate: &str, name: &str) -> String {
+    format!("{krate}::{name}")
 }
 
 #[derive(serde::Deserialize)]
@@ -123,14 +229,25 @@ struct TestSuite {
 #[derive(serde::Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 enum TestSuiteMetadata {
-    CargoPackage { target: String },
-    Compiletest { target: String },
+    CargoPackage {
+        #[serde(default)]
+        crates: Vec<String>,
+        target: String,
+        #[serde(default)]
+        codegen_backend: Option<String>,
+    },
+    Compiletest {
+        target: String,
+    },
 }
 
 #[derive(serde::Deserialize)]
 struct Test {
     name: String,
     outcome: MetricsTestOutcome,
+    // Only present when the test was retried, with the failed attempts before the last one.
+    #[serde(default)]
+    attempts: Vec<serde::de::IgnoredAny>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -145,6 +262,7 @@ enum MetricsTestOutcome {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ignored_tests::{IgnoredTestsEntry, Impact};
     use serde_json::json;
     use tempfile::TempDir;
 
@@ -184,11 +302,29 @@ fn test_load_outcomes() {
                                                 {
                                                     "name": "[ui] tests/ui/bar.rs",
                                                     "outcome": "passed",
+                                                    "attempts": [
+                                                        {
+                                                            "outcome": "failed",
+                                                            "message": "timeout",
+                                                        },
+                                                        {
+                                                            "outcome": "passed",
+                                                            "message": null,
+                                                        },
+                                                    ],
                                                 },
                                                 {
                                                     "name": "[ui] tests/ui/baz.rs",
                                                     "outcome": "ignored",
                                                 },
+                                                {
+                                                    "name": "[ui] tests/ui/revs.rs#a",
+                                                    "outcome": "passed",
+                                                },
+                                                {
+                                                    "name": "[ui] tests/ui/revs.rs#b",
+                                                    "outcome": "ignored",
+                                                },
                                             ],
                                         },
                                     ],
@@ -239,6 +375,30 @@ fn test_load_outcomes() {
                                 },
                             ],
                         },
+                        {
+                            "kind": "rustbuild_step",
+                            "type": "bootstrap::test::Crate",
+                            "children": [
+                                {
+                                    "kind": "test_suite",
+                                    "metadata": {
+                                        "crates": ["core"],
+                                        "target": "aarch64-unknown-linux-gnu",
+                                        "kind": "cargo_package",
+                                    },
+                                    "tests": [
+                                        {
+                                            "name": "mem::test_swap",
+                                            "outcome": "passed",
+                                        },
+                                        {
+                                            "name": "library/core/src/mem/mod.rs - mem::swap (line 700)",
+                                            "outcome": "ignored",
+                                        },
+                                    ],
+                                },
+                            ],
+                        },
                     ],
                 },
             ],
@@ -339,13 +499,97 @@ fn test_load_outcomes() {
                         "tests/codegen/foo.rs".into(),
                         BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
                     ),
+                    (
+                        "tests/ui/revs.rs".into(),
+                        BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+                    ),
+                    (
+                        "core::mem::test_swap".into(),
+                        BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+                    ),
+                ]),
+                ignored_tests: BTreeMap::from([
+                    (
+                        "tests/ui/baz.rs".into(),
+                        BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+                    ),
+                    (
+                        "tests/ui/revs.rs".into(),
+                        BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+                    ),
+                    (
+                        "core::library/core/src/mem/mod.rs - mem::swap (line 700)".into(),
+                        BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+                    ),
                 ]),
-                ignored_tests: BTreeMap::from([(
-                    "tests/ui/baz.rs".into(),
+                executed_revisions: BTreeMap::from([
+                    (
+                        "tests/run-make/foo.rs#revision".into(),
+                        BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+                    ),
+                    (
+                        "tests/ui/revs.rs#a".into(),
+                        BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+                    ),
+                ]),
+                ignored_revisions: BTreeMap::from([(
+                    "tests/ui/revs.rs#b".into(),
+                    BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+                )]),
+                passed_on_retry: BTreeMap::from([(
+                    "tests/ui/bar.rs".into(),
                     BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
                 )]),
             },
             outcomes,
         )
     }
+
+    #[test]
+    fn test_add_ignored_tests() {
+        let mut outcomes = TestOutcomes {
+            executed_tests: BTreeMap::from([(
+                "tests/ui/foo.rs".into(),
+                BTreeSet::from(["x86_64-unknown-linux-gnu".into()]),
+            )]),
+            ..Default::default()
+        };
+        outcomes.add_ignored_tests(&IgnoredTestsFile {
+            entries: vec![IgnoredTestsEntry {
+                tests: vec!["tests/ui/foo.rs".into()],
+                targets: vec![
+                    "x86_64-unknown-linux-gnu".into(),
+                    "aarch64-unknown-linux-gnu".into(),
+                ],
+                reason: "Foo".into(),
+                issue: None,
+                impact: Impact::TestEnvironment,
+            }],
+        });
+        assert_eq!(
+            BTreeMap::from([(
+                "tests/ui/foo.rs".into(),
+                BTreeSet::from(["aarch64-unknown-linux-gnu".into()])
+            )]),
+            outcomes.ignored_tests
+        );
+    }
+
+    #[test]
+    fn test_library_doctest_passed() {
+        let target = BTreeSet::from(["x86_64-unknown-linux-gnu".into()]);
+        let outcomes = TestOutcomes {
+            executed_tests: BTreeMap::from([
+                ("core::library/core/src/lib.rs - (line 3)".into(), target.clone()),
+                ("core::library/core/src/mem/mod.rs - mem::swap (line 700)".into(), target),
+            ]),
+            ..Default::default()
+        };
+        let passed =
+            |krate, file, lines| outcomes.library_doctest_passed(krate, Path::new(file), lines);
+        assert!(passed("core", "library/core/src/lib.rs", 1..=5));
+        assert!(passed("core", "library/core/src/mem/mod.rs", 690..=700));
+        assert!(!passed("core", "library/core/src/mem/mod.rs", 701..=710));
+        assert!(!passed("alloc", "library/core/src/mem/mod.rs", 690..=700));
+    }
 }
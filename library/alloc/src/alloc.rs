@@ -12,7 +12,7 @@
 #[doc(inline)]
 pub use core::alloc::*;
 
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod tests;
 
 extern "Rust" {
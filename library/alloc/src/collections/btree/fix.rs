@@ -1,5 +1,7 @@
 use super::map::MIN_LEN;
-use super::node::{marker, ForceResult::*, Handle, LeftOrRight::*, NodeRef, Root};
+use super::node::{marker, LeftOrRight::*, NodeRef};
+#[cfg(not(no_global_oom_handling))]
+use super::node::{ForceResult::*, Handle, Root};
 use core::alloc::Allocator;
 
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
@@ -65,6 +67,7 @@ pub fn fix_node_and_affected_ancestors<A: Allocator + Clone>(mut self, alloc: A)
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<K, V> Root<K, V> {
     /// Removes empty levels on the top, but keeps an empty leaf if the entire tree is empty.
     pub fn fix_top<A: Allocator + Clone>(&mut self, alloc: A) {
@@ -114,6 +117,7 @@ pub fn fix_right_border_of_plentiful(&mut self) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV> {
     fn fix_left_border_of_left_edge<A: Allocator + Clone>(mut self, alloc: A) {
         while let Internal(internal_kv) = self.force() {
@@ -130,6 +134,7 @@ fn fix_right_border_of_right_edge<A: Allocator + Clone>(mut self, alloc: A) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
     /// Stocks up the left child, assuming the right child isn't underfull, and
     /// provisions an extra element to allow merging its children in turn
@@ -1,3 +1,4 @@
+#[cfg(not(no_global_oom_handling))]
 use crate::vec::Vec;
 use core::borrow::Borrow;
 use core::cmp::Ordering;
@@ -10,11 +11,15 @@
 use core::ptr;
 
 use crate::alloc::{Allocator, Global};
+use crate::collections::TryReserveError;
 
 use super::borrow::DormantMutRef;
+#[cfg(not(no_global_oom_handling))]
 use super::dedup_sorted_iter::DedupSortedIter;
 use super::navigate::{LazyLeafRange, LeafRange};
-use super::node::{self, marker, ForceResult::*, Handle, NodeRef, Root};
+#[cfg(not(no_global_oom_handling))]
+use super::node::ForceResult::*;
+use super::node::{self, marker, Handle, NodeRef, Root};
 use super::search::{SearchBound, SearchResult::*};
 use super::set_val::SetValZST;
 
@@ -202,6 +207,7 @@ impl<K, V, A: Allocator + Clone> core::panic::UnwindSafe for BTreeMap<K, V, A>
 {
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for BTreeMap<K, V, A> {
     fn clone(&self) -> BTreeMap<K, V, A> {
@@ -321,6 +327,7 @@ fn take(&mut self, key: &Q) -> Option<K> {
         }
     }
 
+    #[cfg(not(no_global_oom_handling))]
     fn replace(&mut self, key: K) -> Option<K> {
         let (map, dormant_map) = DormantMutRef::new(self);
         let root_node =
@@ -977,6 +984,7 @@ pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
     /// assert_eq!(map.insert(37, "c"), Some("b"));
     /// assert_eq!(map[&37], "c");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(&mut self, key: K, value: V) -> Option<V>
     where
@@ -991,6 +999,46 @@ pub fn insert(&mut self, key: K, value: V) -> Option<V>
         }
     }
 
+    /// Tries to insert a key-value pair into the map.
+    ///
+    /// Returns the old value like [`insert`], but returns an error instead of
+    /// aborting when allocating the nodes of the map fails. The map is left
+    /// unchanged by an error. This method is not named `try_insert`, which
+    /// already refers to inserting without replacing an existing value.
+    ///
+    /// [`insert`]: BTreeMap::insert
+    ///
+    /// # Errors
+    ///
+    /// If the allocator reports a failure, then an error is returned and the
+    /// key and value are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// assert_eq!(map.fallible_insert(37, "a"), Ok(None));
+    /// assert_eq!(map.fallible_insert(37, "b"), Ok(Some("a")));
+    /// assert_eq!(map[&37], "b");
+    /// ```
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn fallible_insert(&mut self, key: K, value: V) -> Result<Option<V>, TryReserveError>
+    where
+        K: Ord,
+    {
+        match self.entry(key) {
+            Occupied(mut entry) => Ok(Some(entry.insert(value))),
+            Vacant(entry) => {
+                entry.fallible_insert(value)?;
+                Ok(None)
+            }
+        }
+    }
+
     /// Tries to insert a key-value pair into the map, and returns
     /// a mutable reference to the value in the entry.
     ///
@@ -1012,6 +1060,7 @@ pub fn insert(&mut self, key: K, value: V) -> Option<V>
     /// assert_eq!(err.entry.get(), &"a");
     /// assert_eq!(err.value, "b");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "map_try_insert", issue = "82766")]
     pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V, A>>
     where
@@ -1142,6 +1191,7 @@ pub fn retain<F>(&mut self, mut f: F)
     /// assert_eq!(a[&4], "e");
     /// assert_eq!(a[&5], "f");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_append", since = "1.11.0")]
     pub fn append(&mut self, other: &mut Self)
     where
@@ -1328,6 +1378,7 @@ pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A>
     /// assert_eq!(b[&17], "d");
     /// assert_eq!(b[&41], "e");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_split_off", since = "1.11.0")]
     pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
     where
@@ -1465,6 +1516,7 @@ pub fn into_values(self) -> IntoValues<K, V, A> {
     }
 
     /// Makes a `BTreeMap` from a sorted iterator.
+    #[cfg(not(no_global_oom_handling))]
     pub(crate) fn bulk_build_from_sorted_iter<I>(iter: I, alloc: A) -> Self
     where
         K: Ord,
@@ -2215,6 +2267,7 @@ fn next_back(&mut self) -> Option<(&'a K, &'a mut V)> {
 #[stable(feature = "fused", since = "1.26.0")]
 impl<K, V> FusedIterator for RangeMut<'_, K, V> {}
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
@@ -2230,6 +2283,7 @@ fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Ord, V, A: Allocator + Clone> Extend<(K, V)> for BTreeMap<K, V, A> {
     #[inline]
@@ -2245,6 +2299,7 @@ fn extend_one(&mut self, (k, v): (K, V)) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
 impl<'a, K: Ord + Copy, V: Copy, A: Allocator + Clone> Extend<(&'a K, &'a V)>
     for BTreeMap<K, V, A>
@@ -2329,6 +2384,7 @@ fn index(&self, key: &Q) -> &V {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "std_collections_from_array", since = "1.56.0")]
 impl<K: Ord, V, const N: usize> From<[(K, V); N]> for BTreeMap<K, V> {
     /// Converts a `[(K, V); N]` into a `BTreeMap<(K, V)>`.
@@ -3067,6 +3123,7 @@ impl<'a, K: Ord, V, A: Allocator + Clone> CursorMut<'a, K, V, A> {
     ///
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
         let edge = match self.current.take() {
@@ -3110,6 +3167,7 @@ pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
     ///
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
         let edge = match self.current.take() {
@@ -3153,6 +3211,7 @@ pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
     ///   any).
     /// - the given key compares greater than or equal to the next element (if
     ///   any).
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub fn insert_after(&mut self, key: K, value: V) {
         if let Some(current) = self.key() {
@@ -3182,6 +3241,7 @@ pub fn insert_after(&mut self, key: K, value: V) {
     ///   (if any).
     /// - the given key compares less than or equal to the previous element (if
     ///   any).
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub fn insert_before(&mut self, key: K, value: V) {
         if let Some(current) = self.key() {
@@ -3248,5 +3308,8 @@ pub fn remove_current_and_move_back(&mut self) -> Option<(K, V)> {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod tests;
+
+#[cfg(test)]
+mod fallible_tests;
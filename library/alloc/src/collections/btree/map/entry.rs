@@ -3,9 +3,10 @@
 use core::mem;
 
 use crate::alloc::{Allocator, Global};
+use crate::collections::TryReserveError;
 
 use super::super::borrow::DormantMutRef;
-use super::super::node::{marker, Handle, NodeRef};
+use super::super::node::{marker, Handle, NodeRef, ReservedNodes};
 use super::BTreeMap;
 
 use Entry::*;
@@ -157,6 +158,7 @@ impl<'a, K: Ord, V, A: Allocator + Clone> Entry<'a, K, V, A> {
     ///
     /// assert_eq!(map["poneyland"], 12);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn or_insert(self, default: V) -> &'a mut V {
         match self {
@@ -180,6 +182,7 @@ pub fn or_insert(self, default: V) -> &'a mut V {
     ///
     /// assert_eq!(map["poneyland"], "hoho".to_string());
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
         match self {
@@ -206,6 +209,7 @@ pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
     ///
     /// assert_eq!(map["poneyland"], 9);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[stable(feature = "or_insert_with_key", since = "1.50.0")]
     pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
@@ -286,6 +290,7 @@ impl<'a, K: Ord, V: Default, A: Allocator + Clone> Entry<'a, K, V, A> {
     ///
     /// assert_eq!(map["poneyland"], None);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     pub fn or_default(self) -> &'a mut V {
         match self {
             Occupied(entry) => entry.into_mut(),
@@ -346,28 +351,74 @@ pub fn into_key(self) -> K {
     /// }
     /// assert_eq!(map["poneyland"], 37);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn insert(mut self, value: V) -> &'a mut V {
+    pub fn insert(self, value: V) -> &'a mut V {
+        let alloc = self.alloc.clone();
+        self.insert_in(value, alloc)
+    }
+
+    /// Sets the value of the entry with the `VacantEntry`'s key,
+    /// and returns a mutable reference to it.
+    ///
+    /// Unlike [`insert`], this method returns an error instead of aborting
+    /// when allocating the nodes of the map fails. The nodes are allocated
+    /// before modifying the map, which is left unchanged by an error.
+    ///
+    /// [`insert`]: VacantEntry::insert
+    ///
+    /// # Errors
+    ///
+    /// If the allocator reports a failure, then an error is returned and the
+    /// key and value are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::collections::btree_map::Entry;
+    ///
+    /// let mut map: BTreeMap<&str, u32> = BTreeMap::new();
+    ///
+    /// if let Entry::Vacant(o) = map.entry("poneyland") {
+    ///     o.fallible_insert(37).expect("why is the test harness OOMing on a node?");
+    /// }
+    /// assert_eq!(map["poneyland"], 37);
+    /// ```
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn fallible_insert(self, value: V) -> Result<&'a mut V, TryReserveError> {
+        let nodes = match &self.handle {
+            None => (1, 0),
+            Some(handle) => handle.nodes_to_reserve(),
+        };
+        let nodes = ReservedNodes::<K, V, A>::new(self.alloc.clone(), nodes)?;
+        Ok(self.insert_in(value, &nodes))
+    }
+
+    /// Inserts the entry, allocating the new nodes with `alloc` rather than the allocator of the
+    /// map. They must be deallocatable by the latter.
+    fn insert_in<B: Allocator + Clone>(mut self, value: V, alloc: B) -> &'a mut V {
         let out_ptr = match self.handle {
             None => {
                 // SAFETY: There is no tree yet so no reference to it exists.
                 let map = unsafe { self.dormant_map.awaken() };
-                let mut root = NodeRef::new_leaf(self.alloc.clone());
+                let mut root = NodeRef::new_leaf(alloc);
                 let val_ptr = root.borrow_mut().push(self.key, value) as *mut V;
                 map.root = Some(root.forget_type());
                 map.length = 1;
                 val_ptr
             }
             Some(handle) => {
-                let new_handle =
-                    handle.insert_recursing(self.key, value, self.alloc.clone(), |ins| {
-                        drop(ins.left);
-                        // SAFETY: Pushing a new root node doesn't invalidate
-                        // handles to existing nodes.
-                        let map = unsafe { self.dormant_map.reborrow() };
-                        let root = map.root.as_mut().unwrap(); // same as ins.left
-                        root.push_internal_level(self.alloc).push(ins.kv.0, ins.kv.1, ins.right)
-                    });
+                let new_handle = handle.insert_recursing(self.key, value, alloc.clone(), |ins| {
+                    drop(ins.left);
+                    // SAFETY: Pushing a new root node doesn't invalidate
+                    // handles to existing nodes.
+                    let map = unsafe { self.dormant_map.reborrow() };
+                    let root = map.root.as_mut().unwrap(); // same as ins.left
+                    root.push_internal_level(alloc).push(ins.kv.0, ins.kv.1, ins.right)
+                });
 
                 // Get the pointer to the value
                 let val_ptr = new_handle.into_val_mut();
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+// These tests only use the fallible APIs, as they also run when alloc is built with
+// `no_global_oom_handling`.
+
+use super::Entry::{Occupied, Vacant};
+use super::*;
+use crate::alloc::{AllocError, Layout};
+use crate::collections::{BTreeSet, TryReserveErrorKind};
+use core::cell::Cell;
+use core::ptr::NonNull;
+
+// Allocator failing once it has used up its budget, and counting the live allocations.
+#[derive(Clone)]
+struct Budgeted<'a> {
+    budget: &'a Cell<usize>,
+    live: &'a Cell<usize>,
+}
+
+unsafe impl Allocator for Budgeted<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if self.budget.get() == 0 {
+            return Err(AllocError);
+        }
+        self.budget.set(self.budget.get() - 1);
+        self.live.set(self.live.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+fn height<K, V, A: Allocator + Clone>(map: &BTreeMap<K, V, A>) -> usize {
+    map.root.as_ref().map_or(0, |root| root.height())
+}
+
+fn assert_contains_range<A: Allocator + Clone>(map: &BTreeMap<usize, usize, A>, len: usize) {
+    assert_eq!(map.len(), len);
+    assert!(map.iter().map(|(&k, &v)| (k, v)).eq((0..len).map(|i| (i, i * 2))));
+}
+
+#[test]
+fn test_fallible_insert() {
+    let mut map = BTreeMap::new();
+    // Inserting in descending order splits the leftmost nodes up to the root.
+    for i in (0..1000).rev() {
+        assert_eq!(map.fallible_insert(i, i * 2), Ok(None));
+    }
+    assert!(height(&map) >= 3);
+    assert_contains_range(&map, 1000);
+
+    assert_eq!(map.fallible_insert(500, 1000), Ok(Some(1000)));
+    assert_contains_range(&map, 1000);
+}
+
+#[test]
+fn test_fallible_insert_entry() {
+    let mut map = BTreeMap::new();
+    match map.entry(1) {
+        Occupied(_) => unreachable!(),
+        Vacant(entry) => *entry.fallible_insert(10).unwrap() += 1,
+    }
+    assert_eq!(map.get(&1), Some(&11));
+}
+
+#[test]
+fn test_fallible_insert_alloc_failure() {
+    let budget = Cell::new(0);
+    let live = Cell::new(0);
+    let mut map = BTreeMap::new_in(Budgeted { budget: &budget, live: &live });
+
+    // Every insertion that needs new nodes first fails without changing the map, and then
+    // succeeds with a budget of one more allocation at a time. Inserting in ascending order splits
+    // the rightmost nodes, eventually up to the root.
+    let mut failures = 0;
+    for i in 0..500 {
+        while let Err(err) = map.fallible_insert(i, i * 2) {
+            assert!(matches!(err.kind(), TryReserveErrorKind::AllocError { .. }));
+            assert_contains_range(&map, i);
+            failures += 1;
+            budget.set(failures);
+        }
+        budget.set(0);
+        failures = 0;
+    }
+    assert!(height(&map) >= 2);
+    assert_contains_range(&map, 500);
+
+    // No reserved node is leaked, whether the reservation failed or wasn't fully used.
+    drop(map);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_fallible_insert_set() {
+    let mut set = BTreeSet::new();
+    for i in 0..100 {
+        assert_eq!(set.fallible_insert(i % 50), Ok(i < 50));
+    }
+    assert_eq!(set.len(), 50);
+    assert!(set.iter().copied().eq(0..50));
+}
@@ -1,5 +1,7 @@
+#[cfg(not(no_global_oom_handling))]
 mod append;
 mod borrow;
+#[cfg(not(no_global_oom_handling))]
 mod dedup_sorted_iter;
 mod fix;
 pub mod map;
@@ -11,6 +13,7 @@
 mod search;
 pub mod set;
 mod set_val;
+#[cfg(not(no_global_oom_handling))]
 mod split;
 
 trait Recover<Q: ?Sized> {
@@ -18,5 +21,6 @@ trait Recover<Q: ?Sized> {
 
     fn get(&self, key: &Q) -> Option<&Self::Key>;
     fn take(&mut self, key: &Q) -> Option<Self::Key>;
+    #[cfg(not(no_global_oom_handling))]
     fn replace(&mut self, key: Self::Key) -> Option<Self::Key>;
 }
@@ -418,6 +418,7 @@ pub fn next_back_kv(
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<BorrowType: marker::BorrowType, K, V>
     Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge>
 {
@@ -652,12 +653,14 @@ pub fn last_leaf_edge(self) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, m
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 pub enum Position<BorrowType, K, V> {
     Leaf(NodeRef<BorrowType, K, V, marker::Leaf>),
     Internal(NodeRef<BorrowType, K, V, marker::Internal>),
     InternalKV(Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::KV>),
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
     /// Visits leaf nodes and internal KVs in order of ascending keys, and also
     /// visits internal nodes as a whole in a depth first order, meaning that
@@ -31,13 +31,15 @@
 //   since leaf edges are empty and need no data representation. In an internal node,
 //   an edge both identifies a position and contains a pointer to a child node.
 
+use core::cell::Cell;
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use core::ptr::{self, NonNull};
 use core::slice::SliceIndex;
 
-use crate::alloc::{Allocator, Layout};
+use crate::alloc::{AllocError, Allocator, Layout};
 use crate::boxed::Box;
+use crate::collections::{TryReserveError, TryReserveErrorKind};
 
 const B: usize = 6;
 pub const CAPACITY: usize = 2 * B - 1;
@@ -80,7 +82,7 @@ unsafe fn init(this: *mut Self) {
     /// Creates a new boxed `LeafNode`.
     fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
         unsafe {
-            let mut leaf = Box::new_uninit_in(alloc);
+            let mut leaf = new_uninit_node(alloc);
             LeafNode::init(leaf.as_mut_ptr());
             leaf.assume_init()
         }
@@ -112,7 +114,7 @@ impl<K, V> InternalNode<K, V> {
     /// such an edge.
     unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
         unsafe {
-            let mut node = Box::<Self, _>::new_uninit_in(alloc);
+            let mut node = new_uninit_node::<Self, _>(alloc);
             // We only need to initialize the data; the edges are MaybeUninit.
             LeafNode::init(ptr::addr_of_mut!((*node.as_mut_ptr()).data));
             node.assume_init()
@@ -120,6 +122,100 @@ unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
     }
 }
 
+/// Allocates the memory of a new node.
+#[cfg(not(no_global_oom_handling))]
+fn new_uninit_node<T, A: Allocator>(alloc: A) -> Box<MaybeUninit<T>, A> {
+    Box::new_uninit_in(alloc)
+}
+
+/// Allocates the memory of a new node. Without global OOM handling, the only nodes ever
+/// allocated come from [`ReservedNodes`], which hands them out without failing.
+#[cfg(no_global_oom_handling)]
+fn new_uninit_node<T, A: Allocator>(alloc: A) -> Box<MaybeUninit<T>, A> {
+    match Box::try_new_uninit_in(alloc) {
+        Ok(node) => node,
+        Err(_) => panic!("allocated a B-tree node that wasn't reserved"),
+    }
+}
+
+/// Allocator handing out nodes allocated in advance, so that an insertion that can't allocate
+/// fails before modifying the tree rather than in the middle of splitting its nodes.
+///
+/// The reserved internal nodes are kept in a list linked through their `parent` field. The nodes
+/// that are not used are deallocated when the `ReservedNodes` is dropped.
+pub struct ReservedNodes<K, V, A: Allocator> {
+    alloc: A,
+    leaf: Cell<Option<NonNull<LeafNode<K, V>>>>,
+    internals: Cell<Option<NonNull<InternalNode<K, V>>>>,
+}
+
+impl<K, V, A: Allocator> ReservedNodes<K, V, A> {
+    /// Allocates the nodes that an insertion needs, as returned by
+    /// `Handle::nodes_to_reserve`.
+    pub fn new(alloc: A, (leaves, internals): (usize, usize)) -> Result<Self, TryReserveError> {
+        debug_assert!(leaves <= 1);
+        let nodes = ReservedNodes { alloc, leaf: Cell::new(None), internals: Cell::new(None) };
+        if leaves > 0 {
+            nodes.leaf.set(Some(nodes.allocate_node()?));
+        }
+        for _ in 0..internals {
+            let node = nodes.allocate_node::<InternalNode<K, V>>()?;
+            // SAFETY: the node was just allocated, and the field is written without reading or
+            // dropping the uninitialized memory.
+            unsafe { ptr::addr_of_mut!((*node.as_ptr()).data.parent).write(nodes.internals.get()) };
+            nodes.internals.set(Some(node));
+        }
+        Ok(nodes)
+    }
+
+    fn allocate_node<T>(&self) -> Result<NonNull<T>, TryReserveError> {
+        let layout = Layout::new::<T>();
+        match self.alloc.allocate(layout) {
+            Ok(node) => Ok(node.cast()),
+            Err(_) => Err(TryReserveErrorKind::AllocError { layout, non_exhaustive: () }.into()),
+        }
+    }
+
+    fn take_internal(&self) -> Option<NonNull<InternalNode<K, V>>> {
+        let node = self.internals.get()?;
+        // SAFETY: the reserved nodes have their `parent` field initialized by `new`.
+        self.internals.set(unsafe { ptr::addr_of!((*node.as_ptr()).data.parent).read() });
+        Some(node)
+    }
+}
+
+unsafe impl<K, V, A: Allocator> Allocator for ReservedNodes<K, V, A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let node = if layout == Layout::new::<LeafNode<K, V>>() {
+            self.leaf.take().map(NonNull::cast)
+        } else if layout == Layout::new::<InternalNode<K, V>>() {
+            self.take_internal().map(NonNull::cast)
+        } else {
+            None
+        };
+        node.map(|node| NonNull::slice_from_raw_parts(node, layout.size())).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: the nodes handed out are allocated by `self.alloc`.
+        unsafe { self.alloc.deallocate(ptr, layout) }
+    }
+}
+
+impl<K, V, A: Allocator> Drop for ReservedNodes<K, V, A> {
+    fn drop(&mut self) {
+        // SAFETY: the nodes left were allocated by `new` with the same layouts.
+        unsafe {
+            if let Some(leaf) = self.leaf.take() {
+                self.alloc.deallocate(leaf.cast(), Layout::new::<LeafNode<K, V>>());
+            }
+            while let Some(node) = self.take_internal() {
+                self.alloc.deallocate(node.cast(), Layout::new::<InternalNode<K, V>>());
+            }
+        }
+    }
+}
+
 /// A managed, non-null pointer to a node. This is either an owned pointer to
 /// `LeafNode<K, V>` or an owned pointer to `InternalNode<K, V>`.
 ///
@@ -345,6 +441,7 @@ pub fn last_edge(self) -> Handle<Self, marker::Edge> {
     }
 
     /// Note that `self` must be nonempty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn first_kv(self) -> Handle<Self, marker::KV> {
         let len = self.len();
         assert!(len > 0);
@@ -352,6 +449,7 @@ pub fn first_kv(self) -> Handle<Self, marker::KV> {
     }
 
     /// Note that `self` must be nonempty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn last_kv(self) -> Handle<Self, marker::KV> {
         let len = self.len();
         assert!(len > 0);
@@ -580,6 +678,7 @@ fn clear_parent_link(&mut self) {
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
     /// Returns a new owned tree, with its own root node that is initially empty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn new<A: Allocator + Clone>(alloc: A) -> Self {
         NodeRef::new_leaf(alloc).forget_type()
     }
@@ -1052,6 +1151,31 @@ pub fn insert_recursing<A: Allocator + Clone>(
     }
 }
 
+impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge> {
+    /// Returns how many leaf and internal nodes `insert_recursing` allocates when inserting at
+    /// this edge: one for the leaf and each of its ancestors that are full, and a new root when
+    /// all of them are.
+    pub fn nodes_to_reserve(&self) -> (usize, usize) {
+        let (mut leaves, mut internals) = (0, 0);
+        let mut node = self.reborrow().into_node().forget_type();
+        while node.len() == CAPACITY {
+            if node.height() == 0 {
+                leaves += 1;
+            } else {
+                internals += 1;
+            }
+            match node.ascend() {
+                Ok(parent) => node = parent.into_node().forget_type(),
+                Err(_) => {
+                    internals += 1;
+                    break;
+                }
+            }
+        }
+        (leaves, internals)
+    }
+}
+
 impl<BorrowType: marker::BorrowType, K, V>
     Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge>
 {
@@ -1090,6 +1214,7 @@ pub fn into_kv(self) -> (&'a K, &'a V) {
 }
 
 impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, marker::KV> {
+    #[cfg(not(no_global_oom_handling))]
     pub fn key_mut(&mut self) -> &mut K {
         unsafe { self.node.key_area_mut(self.idx).assume_init_mut() }
     }
@@ -1261,6 +1386,7 @@ pub struct BalancingContext<'a, K, V> {
 }
 
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
+    #[cfg(not(no_global_oom_handling))]
     pub fn consider_for_balancing(self) -> BalancingContext<'a, K, V> {
         let self1 = unsafe { ptr::read(&self) };
         let self2 = unsafe { ptr::read(&self) };
@@ -1318,10 +1444,12 @@ pub fn right_child_len(&self) -> usize {
         self.right_child.len()
     }
 
+    #[cfg(not(no_global_oom_handling))]
     pub fn into_left_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
         self.left_child
     }
 
+    #[cfg(not(no_global_oom_handling))]
     pub fn into_right_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
         self.right_child
     }
@@ -1652,6 +1780,7 @@ pub unsafe fn cast_to_leaf_unchecked(
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
     /// Move the suffix after `self` from one node to another one. `right` must be empty.
     /// The first edge of `right` remains unchanged.
+    #[cfg(not(no_global_oom_handling))]
     pub fn move_suffix(
         &mut self,
         right: &mut NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
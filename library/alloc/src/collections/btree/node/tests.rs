@@ -1,9 +1,14 @@
+#[cfg(not(no_global_oom_handling))]
 use super::super::navigate;
 use super::*;
+#[cfg(not(no_global_oom_handling))]
 use crate::alloc::Global;
+#[cfg(not(no_global_oom_handling))]
 use crate::fmt::Debug;
+#[cfg(not(no_global_oom_handling))]
 use crate::string::String;
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
     // Asserts that the back pointer in each reachable node points to its parent.
     pub fn assert_back_pointers(self) {
@@ -66,6 +71,7 @@ fn test_splitpoint() {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[test]
 fn test_partial_eq() {
     let mut root1 = NodeRef::new_leaf(Global);
@@ -1,3 +1,4 @@
+#[cfg(not(no_global_oom_handling))]
 use crate::vec::Vec;
 use core::borrow::Borrow;
 use core::cmp::Ordering::{self, Equal, Greater, Less};
@@ -5,8 +6,11 @@
 use core::fmt::{self, Debug};
 use core::hash::{Hash, Hasher};
 use core::iter::{FusedIterator, Peekable};
+#[cfg(not(no_global_oom_handling))]
 use core::mem::ManuallyDrop;
-use core::ops::{BitAnd, BitOr, BitXor, RangeBounds, Sub};
+use core::ops::RangeBounds;
+#[cfg(not(no_global_oom_handling))]
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
 
 use super::map::{BTreeMap, Keys};
 use super::merge_iter::MergeIterInner;
@@ -14,6 +18,7 @@
 use super::Recover;
 
 use crate::alloc::{Allocator, Global};
+use crate::collections::TryReserveError;
 
 /// An ordered set based on a B-Tree.
 ///
@@ -110,6 +115,7 @@ fn cmp(&self, other: &BTreeSet<T, A>) -> Ordering {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Clone, A: Allocator + Clone> Clone for BTreeSet<T, A> {
     fn clone(&self) -> Self {
@@ -895,6 +901,7 @@ pub fn pop_last(&mut self) -> Option<T>
     /// assert_eq!(set.insert(2), false);
     /// assert_eq!(set.len(), 1);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(&mut self, value: T) -> bool
     where
@@ -903,6 +910,40 @@ pub fn insert(&mut self, value: T) -> bool
         self.map.insert(value, SetValZST::default()).is_none()
     }
 
+    /// Tries to add a value to the set.
+    ///
+    /// Returns whether the value was newly inserted, like [`insert`], but
+    /// returns an error instead of aborting when allocating the nodes of the
+    /// set fails. The set is left unchanged by an error.
+    ///
+    /// [`insert`]: BTreeSet::insert
+    ///
+    /// # Errors
+    ///
+    /// If the allocator reports a failure, then an error is returned and the
+    /// value is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set = BTreeSet::new();
+    ///
+    /// assert_eq!(set.fallible_insert(2), Ok(true));
+    /// assert_eq!(set.fallible_insert(2), Ok(false));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn fallible_insert(&mut self, value: T) -> Result<bool, TryReserveError>
+    where
+        T: Ord,
+    {
+        Ok(self.map.fallible_insert(value, SetValZST::default())?.is_none())
+    }
+
     /// Adds a value to the set, replacing the existing element, if any, that is
     /// equal to the value. Returns the replaced element.
     ///
@@ -918,6 +959,7 @@ pub fn insert(&mut self, value: T) -> bool
     /// set.replace(Vec::with_capacity(10));
     /// assert_eq!(set.get(&[][..]).unwrap().capacity(), 10);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "set_recovery", since = "1.9.0")]
     pub fn replace(&mut self, value: T) -> Option<T>
     where
@@ -1030,6 +1072,7 @@ pub fn retain<F>(&mut self, mut f: F)
     /// assert!(a.contains(&4));
     /// assert!(a.contains(&5));
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_append", since = "1.11.0")]
     pub fn append(&mut self, other: &mut Self)
     where
@@ -1068,6 +1111,7 @@ pub fn append(&mut self, other: &mut Self)
     /// assert!(b.contains(&17));
     /// assert!(b.contains(&41));
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_split_off", since = "1.11.0")]
     pub fn split_off<Q: ?Sized + Ord>(&mut self, value: &Q) -> Self
     where
@@ -1180,6 +1224,7 @@ pub const fn is_empty(&self) -> bool {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord> FromIterator<T> for BTreeSet<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BTreeSet<T> {
@@ -1195,6 +1240,7 @@ fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BTreeSet<T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<T: Ord, A: Allocator + Clone> BTreeSet<T, A> {
     fn from_sorted_iter<I: Iterator<Item = T>>(iter: I, alloc: A) -> BTreeSet<T, A> {
         let iter = iter.map(|k| (k, SetValZST::default()));
@@ -1203,6 +1249,7 @@ fn from_sorted_iter<I: Iterator<Item = T>>(iter: I, alloc: A) -> BTreeSet<T, A>
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "std_collections_from_array", since = "1.56.0")]
 impl<T: Ord, const N: usize> From<[T; N]> for BTreeSet<T> {
     /// Converts a `[T; N]` into a `BTreeSet<T>`.
@@ -1309,6 +1356,7 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 #[unstable(feature = "btree_extract_if", issue = "70530")]
 impl<T, F, A: Allocator + Clone> FusedIterator for ExtractIf<'_, T, F, A> where F: FnMut(&T) -> bool {}
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord, A: Allocator + Clone> Extend<T> for BTreeSet<T, A> {
     #[inline]
@@ -1324,6 +1372,7 @@ fn extend_one(&mut self, elem: T) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
 impl<'a, T: 'a + Ord + Copy, A: Allocator + Clone> Extend<&'a T> for BTreeSet<T, A> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
@@ -1344,6 +1393,7 @@ fn default() -> BTreeSet<T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord + Clone, A: Allocator + Clone> Sub<&BTreeSet<T, A>> for &BTreeSet<T, A> {
     type Output = BTreeSet<T, A>;
@@ -1369,6 +1419,7 @@ fn sub(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord + Clone, A: Allocator + Clone> BitXor<&BTreeSet<T, A>> for &BTreeSet<T, A> {
     type Output = BTreeSet<T, A>;
@@ -1394,6 +1445,7 @@ fn bitxor(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord + Clone, A: Allocator + Clone> BitAnd<&BTreeSet<T, A>> for &BTreeSet<T, A> {
     type Output = BTreeSet<T, A>;
@@ -1419,6 +1471,7 @@ fn bitand(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord + Clone, A: Allocator + Clone> BitOr<&BTreeSet<T, A>> for &BTreeSet<T, A> {
     type Output = BTreeSet<T, A>;
@@ -1812,5 +1865,5 @@ fn min(mut self) -> Option<&'a T> {
 #[stable(feature = "fused", since = "1.26.0")]
 impl<T: Ord> FusedIterator for Union<'_, T> {}
 
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod tests;
@@ -4,14 +4,12 @@
 
 #[cfg(not(no_global_oom_handling))]
 pub mod binary_heap;
-#[cfg(not(no_global_oom_handling))]
 mod btree;
 #[cfg(not(no_global_oom_handling))]
 pub mod linked_list;
 #[cfg(not(no_global_oom_handling))]
 pub mod vec_deque;
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub mod btree_map {
     //! An ordered map based on a B-Tree.
@@ -19,7 +17,6 @@ pub mod btree_map {
     pub use super::btree::map::*;
 }
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub mod btree_set {
     //! An ordered set based on a B-Tree.
@@ -32,12 +29,10 @@ pub mod btree_set {
 #[doc(no_inline)]
 pub use binary_heap::BinaryHeap;
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use btree_map::BTreeMap;
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use btree_set::BTreeSet;
@@ -93,6 +93,11 @@
 #![allow(internal_features)]
 #![allow(rustdoc::redundant_explicit_links)]
 //
+// Ferrocene: items in the certified subset are marked with `#[ferrocene::certified]`.
+#![cfg_attr(not(bootstrap), feature(ferrocene_certified))]
+#![cfg_attr(bootstrap, feature(register_tool))]
+#![cfg_attr(bootstrap, register_tool(ferrocene))]
+//
 // Library features:
 // tidy-alphabetical-start
 #![cfg_attr(not(no_global_oom_handling), feature(const_alloc_error))]
@@ -217,7 +222,7 @@
 extern crate std;
 #[cfg(test)]
 extern crate test;
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod testing;
 
 // Module with internal macros used by other modules (needs to be included before other modules).
@@ -255,7 +260,7 @@ mod boxed {
 pub mod sync;
 #[cfg(all(not(no_global_oom_handling), not(no_rc), not(no_sync), target_has_atomic = "ptr"))]
 pub mod task;
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod tests;
 pub mod vec;
 
@@ -265,7 +270,7 @@ pub mod __export {
     pub use core::format_args;
 }
 
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 #[allow(dead_code)] // Not used in all configurations
 pub(crate) mod test_helpers {
     /// Copied from `std::test_helpers::test_rng`, since these tests rely on the
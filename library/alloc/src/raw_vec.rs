@@ -14,7 +14,7 @@
 use crate::collections::TryReserveError;
 use crate::collections::TryReserveErrorKind::*;
 
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod tests;
 
 #[cfg(not(no_global_oom_handling))]
@@ -278,7 +278,7 @@
 #[cfg(not(no_global_oom_handling))]
 use crate::vec::Vec;
 
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod tests;
 
 // This is repr(C) to future-proof against possible field-reordering, which
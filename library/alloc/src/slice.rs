@@ -30,7 +30,7 @@
 use crate::boxed::Box;
 use crate::vec::Vec;
 
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod tests;
 
 #[unstable(feature = "slice_range", issue = "76393")]
@@ -81,7 +81,7 @@
 
 // HACK(japaric) needed for the implementation of `Vec::clone` during testing
 // N.B., see the `hack` module in this file for more details.
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 pub use hack::to_vec;
 
 // HACK(japaric): With cfg(test) `impl [T]` is not available, these three
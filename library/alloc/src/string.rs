@@ -1053,6 +1053,35 @@ pub fn push_str(&mut self, string: &str) {
         self.vec.extend_from_slice(string.as_bytes())
     }
 
+    /// Tries to append a given string slice onto the end of this `String`.
+    ///
+    /// Unlike [`push_str`], this method returns an error instead of aborting
+    /// when the string needs to grow and the allocation fails.
+    ///
+    /// [`push_str`]: String::push_str
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned and the string is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// let mut s = String::from("foo");
+    ///
+    /// s.try_push_str("bar").expect("why is the test harness OOMing on 6 bytes?");
+    ///
+    /// assert_eq!("foobar", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), TryReserveError> {
+        self.vec.try_extend_from_slice(string.as_bytes())
+    }
+
     /// Copies elements from `src` range to the end of the string.
     ///
     /// # Panics
@@ -1348,6 +1377,39 @@ pub fn push(&mut self, ch: char) {
         }
     }
 
+    /// Tries to append the given [`char`] to the end of this `String`.
+    ///
+    /// Unlike [`push`], this method returns an error instead of aborting when
+    /// the string needs to grow and the allocation fails.
+    ///
+    /// [`push`]: String::push
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned and the string is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// let mut s = String::from("abc");
+    ///
+    /// s.try_push('1').expect("why is the test harness OOMing on 4 bytes?");
+    /// s.try_push('€').expect("why is the test harness OOMing on 7 bytes?");
+    ///
+    /// assert_eq!("abc1€", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn try_push(&mut self, ch: char) -> Result<(), TryReserveError> {
+        match ch.len_utf8() {
+            1 => self.vec.try_push(ch as u8),
+            _ => self.vec.try_extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+
     /// Returns a byte slice of this `String`'s contents.
     ///
     /// The inverse of this method is [`from_utf8`].
@@ -43,7 +43,7 @@
 #[cfg(not(no_global_oom_handling))]
 use crate::vec::Vec;
 
-#[cfg(test)]
+#[cfg(all(not(no_global_oom_handling), test))]
 mod tests;
 
 /// A soft limit on the amount of references that may be made to an `Arc`.
@@ -417,6 +417,7 @@ impl<T> Vec<T> {
     #[rustc_const_stable(feature = "const_vec_new", since = "1.39.0")]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[must_use]
+    #[ferrocene::certified]
     pub const fn new() -> Self {
         Vec { buf: RawVec::NEW, len: 0 }
     }
@@ -479,6 +480,37 @@ pub fn with_capacity(capacity: usize) -> Self {
         Self::with_capacity_in(capacity, Global)
     }
 
+    /// Tries to create a vector from the elements of an iterator.
+    ///
+    /// This is the fallible counterpart of collecting into a `Vec<T>`: it
+    /// returns an error instead of aborting when the allocation fails. The
+    /// lower bound of the iterator's size hint is reserved upfront.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned and the elements collected so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// let vec = Vec::try_from_iter((1..=3).map(|x| x * 2))
+    ///     .expect("why is the test harness OOMing on 12 bytes?");
+    /// assert_eq!(vec, [2, 4, 6]);
+    /// ```
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, TryReserveError> {
+        let iter = iter.into_iter();
+        let mut vec = Vec::new();
+        vec.try_reserve(iter.size_hint().0)?;
+        for element in iter {
+            vec.try_push(element)?;
+        }
+        Ok(vec)
+    }
+
     /// Creates a `Vec<T>` directly from a pointer, a capacity, and a length.
     ///
     /// # Safety
@@ -1522,6 +1554,65 @@ fn assert_failed(index: usize, len: usize) -> ! {
         }
     }
 
+    /// Tries to insert an element at position `index` within the vector,
+    /// shifting all elements after it to the right.
+    ///
+    /// Unlike [`insert`], this method returns an error instead of aborting
+    /// when the vector needs to grow and the allocation fails.
+    ///
+    /// [`insert`]: Vec::insert
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned and the element is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// let mut vec = vec![1, 2, 3];
+    /// vec.try_insert(1, 4).expect("why is the test harness OOMing on 16 bytes?");
+    /// assert_eq!(vec, [1, 4, 2, 3]);
+    /// ```
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), TryReserveError> {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("insertion index (is {index}) should be <= len (is {len})");
+        }
+
+        let len = self.len();
+        if index > len {
+            assert_failed(index, len);
+        }
+
+        // space for the new element
+        if len == self.buf.capacity() {
+            self.try_reserve(1)?;
+        }
+
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            if index < len {
+                // Shift everything over to make space. (Duplicating the
+                // `index`th element into two consecutive places.)
+                ptr::copy(p, p.add(1), len - index);
+            }
+            // Write it in, overwriting the first copy of the `index`th
+            // element.
+            ptr::write(p, element);
+            self.set_len(len + 1);
+        }
+        Ok(())
+    }
+
     /// Removes and returns the element at position `index` within the vector,
     /// shifting all elements after it to the left.
     ///
@@ -1933,6 +2024,48 @@ pub fn push_within_capacity(&mut self, value: T) -> Result<(), T> {
         Ok(())
     }
 
+    /// Tries to append an element to the back of a collection.
+    ///
+    /// Unlike [`push`], this method returns an error instead of aborting when
+    /// the vector needs to grow and the allocation fails.
+    ///
+    /// [`push`]: Vec::push
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned and the element is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// use std::collections::TryReserveError;
+    ///
+    /// fn process_data(data: &[u32]) -> Result<Vec<u32>, TryReserveError> {
+    ///     let mut output = Vec::new();
+    ///     for &val in data {
+    ///         output.try_push(val * 2 + 5)?;
+    ///     }
+    ///     Ok(output)
+    /// }
+    /// # process_data(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.len == self.buf.capacity() {
+            self.buf.try_reserve(self.len, 1)?;
+        }
+        unsafe {
+            let end = self.as_mut_ptr().add(self.len);
+            ptr::write(end, value);
+            self.len += 1;
+        }
+        Ok(())
+    }
+
     /// Removes the last element from a vector and returns it, or [`None`] if it
     /// is empty.
     ///
@@ -2101,6 +2234,7 @@ pub fn clear(&mut self) {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[ferrocene::certified]
     pub fn len(&self) -> usize {
         self.len
     }
@@ -2117,6 +2251,7 @@ pub fn len(&self) -> usize {
     /// assert!(!v.is_empty());
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[ferrocene::certified]
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -2441,6 +2576,39 @@ pub fn extend_from_slice(&mut self, other: &[T]) {
         self.spec_extend(other.iter())
     }
 
+    /// Tries to clone and append all elements in a slice to the `Vec`.
+    ///
+    /// Unlike [`extend_from_slice`], this method returns an error instead of
+    /// aborting when the vector needs to grow and the allocation fails. The
+    /// required capacity is reserved upfront, so the vector is left unchanged
+    /// when an error is returned.
+    ///
+    /// [`extend_from_slice`]: Vec::extend_from_slice
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an
+    /// error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_collection_ops)]
+    ///
+    /// let mut vec = vec![1];
+    /// vec.try_extend_from_slice(&[2, 3, 4]).expect("why is the test harness OOMing on 16 bytes?");
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// ```
+    #[unstable(feature = "fallible_collection_ops", issue = "none")]
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), TryReserveError> {
+        self.try_reserve(other.len())?;
+        for element in other {
+            // This cannot fail, the capacity for all the elements was reserved above.
+            let _ = self.push_within_capacity(element.clone());
+        }
+        Ok(())
+    }
+
     /// Copies elements from `src` range to the end of the vector.
     ///
     /// # Panics
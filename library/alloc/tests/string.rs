@@ -247,6 +247,28 @@ fn test_push() {
     assert_eq!(data, "ประเทศไทย中华b¢€𤭢");
 }
 
+#[test]
+fn test_try_push() {
+    let mut data = String::from("ประเทศไทย中");
+    data.try_push('华').unwrap();
+    data.try_push('b').unwrap(); // 1 byte
+    data.try_push('¢').unwrap(); // 2 byte
+    data.try_push('€').unwrap(); // 3 byte
+    data.try_push('𤭢').unwrap(); // 4 byte
+    assert_eq!(data, "ประเทศไทย中华b¢€𤭢");
+}
+
+#[test]
+fn test_try_push_str() {
+    let mut s = String::new();
+    s.try_push_str("").unwrap();
+    assert_eq!(&s[0..], "");
+    s.try_push_str("abc").unwrap();
+    assert_eq!(&s[0..], "abc");
+    s.try_push_str("ประเทศไทย中华Việt Nam").unwrap();
+    assert_eq!(&s[0..], "abcประเทศไทย中华Việt Nam");
+}
+
 #[test]
 fn test_pop() {
     let mut data = String::from("ประเทศไทย中华b¢€𤭢");
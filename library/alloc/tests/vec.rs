@@ -122,6 +122,42 @@ fn test_push() {
     assert_eq!(v, [1, 2, 3]);
 }
 
+#[test]
+fn test_try_push() {
+    let mut v = vec![];
+    v.try_push(1).unwrap();
+    assert_eq!(v, [1]);
+    v.try_push(2).unwrap();
+    assert_eq!(v, [1, 2]);
+    v.try_push(3).unwrap();
+    assert_eq!(v, [1, 2, 3]);
+
+    // The length of a vector of zero sized types is bounded by usize::MAX.
+    let mut zst: Vec<()> = Vec::new();
+    unsafe { zst.set_len(usize::MAX) };
+    assert_matches!(zst.try_push(()).map_err(|e| e.kind()), Err(CapacityOverflow));
+    assert_eq!(zst.len(), usize::MAX);
+    unsafe { zst.set_len(0) };
+}
+
+#[test]
+fn test_try_insert() {
+    let mut v = vec![1, 2, 3];
+    v.try_insert(1, 4).unwrap();
+    assert_eq!(v, [1, 4, 2, 3]);
+    v.try_insert(4, 5).unwrap();
+    assert_eq!(v, [1, 4, 2, 3, 5]);
+    v.try_insert(0, 6).unwrap();
+    assert_eq!(v, [6, 1, 4, 2, 3, 5]);
+}
+
+#[test]
+#[should_panic]
+fn test_try_insert_out_of_bounds() {
+    let mut v = vec![1, 2, 3];
+    let _ = v.try_insert(4, 4);
+}
+
 #[test]
 fn test_extend() {
     let mut v = Vec::new();
@@ -167,6 +203,33 @@ fn test_extend() {
     assert_eq!(count_x, 1);
 }
 
+#[test]
+fn test_try_extend_from_slice() {
+    let mut v: Vec<isize> = vec![1, 2, 3, 4, 5];
+    v.try_extend_from_slice(&[6, 7, 8, 9, 0]).unwrap();
+    assert_eq!(v, [1, 2, 3, 4, 5, 6, 7, 8, 9, 0]);
+
+    // A failed reservation leaves the vector unchanged.
+    let mut zst: Vec<()> = Vec::new();
+    unsafe { zst.set_len(usize::MAX - 1) };
+    assert_matches!(
+        zst.try_extend_from_slice(&[(), ()]).map_err(|e| e.kind()),
+        Err(CapacityOverflow)
+    );
+    assert_eq!(zst.len(), usize::MAX - 1);
+    unsafe { zst.set_len(0) };
+}
+
+#[test]
+fn test_try_from_iter() {
+    let v = Vec::try_from_iter((1..=5).filter(|x| x % 2 == 1)).unwrap();
+    assert_eq!(v, [1, 3, 5]);
+
+    // The lower bound of the size hint is reserved upfront.
+    let too_long = std::iter::repeat(0u64).take(usize::MAX);
+    assert_matches!(Vec::try_from_iter(too_long).map_err(|e| e.kind()), Err(CapacityOverflow));
+}
+
 #[test]
 fn test_extend_from_slice() {
     let a: Vec<isize> = vec![1, 2, 3, 4, 5];
@@ -1201,6 +1201,7 @@ fn ge(&self, other: &Rhs) -> bool {
 #[must_use]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[cfg_attr(not(test), rustc_diagnostic_item = "cmp_min")]
+#[ferrocene::certified]
 pub fn min<T: Ord>(v1: T, v2: T) -> T {
     v1.min(v2)
 }
@@ -1270,6 +1271,7 @@ pub fn min_by_key<T, F: FnMut(&T) -> K, K: Ord>(v1: T, v2: T, mut f: F) -> T {
 #[must_use]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[cfg_attr(not(test), rustc_diagnostic_item = "cmp_max")]
+#[ferrocene::certified]
 pub fn max<T: Ord>(v1: T, v2: T) -> T {
     v1.max(v2)
 }
@@ -0,0 +1,354 @@
+use super::CapacityError;
+use crate::fmt;
+use crate::iter::{Chain, FusedIterator};
+use crate::mem::MaybeUninit;
+use crate::ptr;
+use crate::slice;
+
+/// A double-ended queue with a fixed capacity of `N` elements, stored inline.
+///
+/// `ArrayDeque` is implemented as a ring buffer over an array. Its contents
+/// can be accessed as a pair of slices with [`as_slices`], or as a single
+/// slice after rearranging them with [`make_contiguous`]. Unlike `VecDeque`,
+/// it never allocates: adding an element to a full `ArrayDeque` returns a
+/// [`CapacityError`] holding the element instead.
+///
+/// [`as_slices`]: ArrayDeque::as_slices
+/// [`make_contiguous`]: ArrayDeque::make_contiguous
+///
+/// # Examples
+///
+/// ```
+/// #![feature(fixed_capacity_collections)]
+///
+/// use core::collections::ArrayDeque;
+///
+/// let mut deque = ArrayDeque::<i32, 3>::new();
+/// deque.push_back(2).unwrap();
+/// deque.push_back(3).unwrap();
+/// deque.push_front(1).unwrap();
+/// assert!(deque.push_back(4).is_err());
+///
+/// assert_eq!(deque.pop_front(), Some(1));
+/// deque.push_back(4).unwrap();
+/// assert_eq!(deque.make_contiguous(), [2, 3, 4]);
+/// ```
+pub struct ArrayDeque<T, const N: usize> {
+    // Invariant: the `len` elements starting at `head` (wrapping around the
+    // end of `data`) are initialized, and the remaining ones are not. `head`
+    // is always less than `N`, unless `N` is zero.
+    head: usize,
+    len: usize,
+    data: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> ArrayDeque<T, N> {
+    /// Creates a new, empty `ArrayDeque`.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        ArrayDeque { head: 0, len: 0, data: MaybeUninit::uninit_array() }
+    }
+
+    /// Returns the number of elements in the deque.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the deque can hold, which is always `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the deque cannot hold any more elements.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Maps a logical index in the deque to an index in the buffer.
+    #[inline]
+    fn to_physical_idx(&self, idx: usize) -> usize {
+        let idx = self.head + idx;
+        if idx >= N { idx - N } else { idx }
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// # Errors
+    ///
+    /// If the deque is full, an error holding `value` is returned.
+    pub fn push_back(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError::new(value));
+        }
+        let idx = self.to_physical_idx(self.len);
+        self.data[idx].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Prepends an element to the front of the deque.
+    ///
+    /// # Errors
+    ///
+    /// If the deque is full, an error holding `value` is returned.
+    pub fn push_front(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError::new(value));
+        }
+        self.head = if self.head == 0 { N - 1 } else { self.head - 1 };
+        self.data[self.head].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the last element from the deque and returns it, or [`None`] if
+    /// it is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let idx = self.to_physical_idx(self.len);
+        // SAFETY: the element was initialized, and is now outside of the
+        // initialized range, so it won't be read again.
+        Some(unsafe { self.data[idx].assume_init_read() })
+    }
+
+    /// Removes the first element from the deque and returns it, or [`None`] if
+    /// it is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let idx = self.head;
+        self.head = self.to_physical_idx(1);
+        self.len -= 1;
+        // SAFETY: the element was initialized, and is now outside of the
+        // initialized range, so it won't be read again.
+        Some(unsafe { self.data[idx].assume_init_read() })
+    }
+
+    /// Provides a reference to the element at the given index, or [`None`] if
+    /// the index is out of bounds.
+    ///
+    /// The element at index 0 is the front of the deque.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        let idx = self.to_physical_idx(index);
+        // SAFETY: the element is within the initialized range.
+        Some(unsafe { self.data[idx].assume_init_ref() })
+    }
+
+    /// Provides a mutable reference to the element at the given index, or
+    /// [`None`] if the index is out of bounds.
+    ///
+    /// The element at index 0 is the front of the deque.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let idx = self.to_physical_idx(index);
+        // SAFETY: the element is within the initialized range.
+        Some(unsafe { self.data[idx].assume_init_mut() })
+    }
+
+    /// Provides a reference to the front element, or [`None`] if the deque is
+    /// empty.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Provides a reference to the back element, or [`None`] if the deque is
+    /// empty.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// deque.
+    ///
+    /// If [`make_contiguous`] was previously called, all elements of the deque
+    /// will be in the first slice and the second slice will be empty.
+    ///
+    /// [`make_contiguous`]: ArrayDeque::make_contiguous
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.slice_ranges();
+        let ptr = self.data.as_ptr().cast::<T>();
+        // SAFETY: both ranges are within the initialized part of the buffer,
+        // and don't overlap.
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(front.0), front.1),
+                slice::from_raw_parts(ptr.add(back.0), back.1),
+            )
+        }
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the contents
+    /// of the deque.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.slice_ranges();
+        let ptr = self.data.as_mut_ptr().cast::<T>();
+        // SAFETY: both ranges are within the initialized part of the buffer,
+        // and don't overlap.
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(front.0), front.1),
+                slice::from_raw_parts_mut(ptr.add(back.0), back.1),
+            )
+        }
+    }
+
+    /// Returns the start and length of the two initialized parts of the buffer.
+    fn slice_ranges(&self) -> ((usize, usize), (usize, usize)) {
+        let head_len = N - self.head;
+        if self.len <= head_len {
+            ((self.head, self.len), (0, 0))
+        } else {
+            ((self.head, head_len), (0, self.len - head_len))
+        }
+    }
+
+    /// Rearranges the internal storage of the deque so it is one contiguous
+    /// slice, which is then returned.
+    ///
+    /// This method does not allocate and does not change the order of the
+    /// inserted elements.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.head + self.len > N {
+            // Rotating the whole buffer moves the element at `head` to index
+            // zero, and keeps all the other elements in order.
+            self.data.rotate_left(self.head);
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Returns a front-to-back iterator.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter { inner: front.iter().chain(back.iter()), len: self.len }
+    }
+
+    /// Removes all the elements of the deque.
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let front = front as *mut [T];
+        let back = back as *mut [T];
+        // The deque is emptied before dropping the elements, so that a
+        // panicking destructor doesn't cause a double drop.
+        self.head = 0;
+        self.len = 0;
+        // SAFETY: both slices were initialized, and are now outside of the
+        // initialized range.
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayDeque<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for ArrayDeque<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayDeque<T, N> {
+    fn clone(&self) -> Self {
+        let mut new = Self::new();
+        for element in self.iter() {
+            // `new` has the same capacity as `self`, so this cannot fail.
+            let _ = new.push_back(element.clone());
+        }
+        new
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayDeque<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArrayDeque<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayDeque<T, N> {}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayDeque<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of an [`ArrayDeque`].
+///
+/// This `struct` is created by the [`ArrayDeque::iter`] method.
+#[derive(Clone)]
+pub struct Iter<'a, T> {
+    inner: Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        let next = self.inner.next()?;
+        self.len -= 1;
+        Some(next)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        let next = self.inner.next_back()?;
+        self.len -= 1;
+        Some(next)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.inner).finish()
+    }
+}
@@ -0,0 +1,282 @@
+use super::{ArrayVec, CapacityError};
+use crate::cmp::Ordering;
+use crate::fmt;
+use crate::hash::{Hash, Hasher};
+use crate::ops::{Deref, DerefMut};
+use crate::str;
+
+/// A UTF-8 encoded string with a fixed capacity of `N` bytes, stored inline.
+///
+/// `ArrayString` dereferences to a [`str`](prim@str), so all the methods of string
+/// slices are available on it. Unlike `String`, it never allocates: appending
+/// to a full `ArrayString` returns a [`CapacityError`] instead.
+///
+/// `ArrayString` implements [`fmt::Write`], so it can be the target of the
+/// [`write!`] macro. Formatting output that doesn't fit results in an error.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(fixed_capacity_collections)]
+///
+/// use core::collections::ArrayString;
+/// use core::fmt::Write;
+///
+/// let mut s = ArrayString::<16>::new();
+/// write!(s, "{}-{}", "answer", 42).unwrap();
+/// assert_eq!(s, "answer-42");
+///
+/// assert!(write!(s, "{}", "too long to fit").is_err());
+/// ```
+#[derive(Clone, Default)]
+pub struct ArrayString<const N: usize> {
+    // Invariant: the bytes in `vec` are valid UTF-8.
+    vec: ArrayVec<u8, N>,
+}
+
+impl<const N: usize> ArrayString<N> {
+    /// Creates a new, empty `ArrayString`.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        ArrayString { vec: ArrayVec::new() }
+    }
+
+    /// Returns the length of the string, in bytes.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns `true` if the string has a length of zero.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns the capacity of the string in bytes, which is always `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of bytes that can still be appended to the string.
+    #[inline]
+    pub const fn remaining_capacity(&self) -> usize {
+        self.vec.remaining_capacity()
+    }
+
+    /// Appends the given [`char`] to the end of the string.
+    ///
+    /// # Errors
+    ///
+    /// If the UTF-8 encoding of `ch` doesn't fit in the remaining capacity, an
+    /// error holding `ch` is returned and the string is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fixed_capacity_collections)]
+    ///
+    /// use core::collections::ArrayString;
+    ///
+    /// let mut s = ArrayString::<4>::new();
+    /// s.push('a').unwrap();
+    /// s.push('€').unwrap();
+    /// assert_eq!(s.push('€').unwrap_err().into_inner(), '€');
+    /// assert_eq!(s, "a€");
+    /// ```
+    pub fn push(&mut self, ch: char) -> Result<(), CapacityError<char>> {
+        let mut buf = [0; 4];
+        match self.vec.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(CapacityError::new(ch)),
+        }
+    }
+
+    /// Appends a given string slice onto the end of the string.
+    ///
+    /// # Errors
+    ///
+    /// If `string` doesn't fit in the remaining capacity, an error holding
+    /// `string` is returned and the string is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fixed_capacity_collections)]
+    ///
+    /// use core::collections::ArrayString;
+    ///
+    /// let mut s = ArrayString::<6>::new();
+    /// s.push_str("foo").unwrap();
+    /// s.push_str("bar").unwrap();
+    /// assert!(s.push_str("baz").is_err());
+    /// assert_eq!(s, "foobar");
+    /// ```
+    pub fn push_str<'a>(&mut self, string: &'a str) -> Result<(), CapacityError<&'a str>> {
+        match self.vec.extend_from_slice(string.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(CapacityError::new(string)),
+        }
+    }
+
+    /// Removes the last character from the string and returns it, or [`None`]
+    /// if it is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.chars().next_back()?;
+        let new_len = self.len() - ch.len_utf8();
+        // SAFETY: `new_len` is the start of the last character, so the
+        // remaining bytes are still valid UTF-8.
+        unsafe { self.vec.set_len(new_len) };
+        Some(ch)
+    }
+
+    /// Shortens the string to the specified length, in bytes.
+    ///
+    /// If `new_len` is greater than the string's current length, this has no
+    /// effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` does not lie on a [`char`] boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(self.is_char_boundary(new_len));
+            self.vec.truncate(new_len)
+        }
+    }
+
+    /// Removes all the contents of the string.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.vec.clear()
+    }
+
+    /// Extracts a string slice containing the entire `ArrayString`.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: the contents of `vec` are always valid UTF-8.
+        unsafe { str::from_utf8_unchecked(self.vec.as_slice()) }
+    }
+
+    /// Converts the `ArrayString` into a mutable string slice.
+    #[inline]
+    pub fn as_mut_str(&mut self) -> &mut str {
+        // SAFETY: the contents of `vec` are always valid UTF-8.
+        unsafe { str::from_utf8_unchecked_mut(self.vec.as_mut_slice()) }
+    }
+
+    /// Returns a byte slice of the string's contents.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.vec.as_slice()
+    }
+}
+
+impl<const N: usize> Deref for ArrayString<N> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> DerefMut for ArrayString<N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for ArrayString<N> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayString<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for ArrayString<N> {
+    type Error = CapacityError;
+
+    fn try_from(string: &str) -> Result<Self, CapacityError> {
+        let mut new = Self::new();
+        new.push_str(string).map_err(CapacityError::simplify)?;
+        Ok(new)
+    }
+}
+
+impl<const N: usize> fmt::Write for ArrayString<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> fmt::Display for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Hash for ArrayString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.as_str(), state)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<ArrayString<M>> for ArrayString<N> {
+    #[inline]
+    fn eq(&self, other: &ArrayString<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> PartialEq<str> for ArrayString<N> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for ArrayString<N> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> Eq for ArrayString<N> {}
+
+impl<const N: usize> PartialOrd for ArrayString<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for ArrayString<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(self.as_str(), other.as_str())
+    }
+}
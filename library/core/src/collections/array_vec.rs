@@ -0,0 +1,505 @@
+use super::CapacityError;
+use crate::array;
+use crate::cmp::Ordering;
+use crate::fmt;
+use crate::hash::{Hash, Hasher};
+use crate::mem::{ManuallyDrop, MaybeUninit};
+use crate::ops::{Deref, DerefMut};
+use crate::ptr;
+use crate::slice;
+
+/// A vector with a fixed capacity of `N` elements, stored inline.
+///
+/// `ArrayVec` dereferences to a slice, so all the methods of [`slice`](prim@slice) are
+/// available on it. Unlike `Vec`, it never allocates: adding an element to a
+/// full `ArrayVec` returns a [`CapacityError`] holding the element instead.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(fixed_capacity_collections)]
+///
+/// use core::collections::ArrayVec;
+///
+/// let mut vec = ArrayVec::<i32, 4>::new();
+/// vec.push(3).unwrap();
+/// vec.push(1).unwrap();
+/// vec.push(2).unwrap();
+///
+/// vec.sort();
+/// assert_eq!(vec, [1, 2, 3]);
+/// assert_eq!(vec.remaining_capacity(), 1);
+/// ```
+pub struct ArrayVec<T, const N: usize> {
+    // Invariant: the first `len` elements of `data` are initialized, and the
+    // remaining ones are not.
+    len: usize,
+    data: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> ArrayVec<T, N> {
+    /// Creates a new, empty `ArrayVec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fixed_capacity_collections)]
+    ///
+    /// use core::collections::ArrayVec;
+    ///
+    /// let vec = ArrayVec::<u8, 16>::new();
+    /// assert!(vec.is_empty());
+    /// assert_eq!(vec.capacity(), 16);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        ArrayVec { len: 0, data: MaybeUninit::uninit_array() }
+    }
+
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the vector can hold, which is always `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the vector cannot hold any more elements.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the number of elements that can still be added to the vector.
+    #[inline]
+    pub const fn remaining_capacity(&self) -> usize {
+        N - self.len
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// # Errors
+    ///
+    /// If the vector is full, an error holding `value` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fixed_capacity_collections)]
+    ///
+    /// use core::collections::ArrayVec;
+    ///
+    /// let mut vec = ArrayVec::<i32, 2>::new();
+    /// assert!(vec.push(1).is_ok());
+    /// assert!(vec.push(2).is_ok());
+    /// assert_eq!(vec.push(3).unwrap_err().into_inner(), 3);
+    /// assert_eq!(vec, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError::new(value));
+        }
+        self.data[self.len].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the last element from the vector and returns it, or [`None`] if
+    /// it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the element at the old `len - 1` was initialized, and is now
+        // outside of the initialized range, so it won't be read again.
+        Some(unsafe { self.data[self.len].assume_init_read() })
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right.
+    ///
+    /// # Errors
+    ///
+    /// If the vector is full, an error holding `element` is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fixed_capacity_collections)]
+    ///
+    /// use core::collections::ArrayVec;
+    ///
+    /// let mut vec = ArrayVec::<i32, 4>::try_from(&[1, 2, 3][..]).unwrap();
+    /// vec.insert(1, 4).unwrap();
+    /// assert_eq!(vec, [1, 4, 2, 3]);
+    /// assert!(vec.insert(4, 5).is_err());
+    /// ```
+    pub fn insert(&mut self, index: usize, element: T) -> Result<(), CapacityError<T>> {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("insertion index (is {index}) should be <= len (is {len})");
+        }
+
+        let len = self.len;
+        if index > len {
+            assert_failed(index, len);
+        }
+        if len == N {
+            return Err(CapacityError::new(element));
+        }
+
+        // SAFETY: `index <= len < N`, so both the source and the destination
+        // of the copy are within the buffer.
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), len - index);
+            ptr::write(p, element);
+        }
+        self.len = len + 1;
+        Ok(())
+    }
+
+    /// Removes and returns the element at position `index` within the vector,
+    /// shifting all elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) -> T {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("removal index (is {index}) should be < len (is {len})");
+        }
+
+        let len = self.len;
+        if index >= len {
+            assert_failed(index, len);
+        }
+
+        // SAFETY: `index < len`, so the element is initialized, and the
+        // elements after it are shifted over the hole it leaves behind.
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            let value = ptr::read(p);
+            ptr::copy(p.add(1), p, len - index - 1);
+            self.len = len - 1;
+            value
+        }
+    }
+
+    /// Removes an element from the vector and returns it, replacing it with the
+    /// last element of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let last = self.len.checked_sub(1);
+        match last {
+            Some(last) if index <= last => {
+                self.as_mut_slice().swap(index, last);
+                // The element to remove is now the last one.
+                self.pop().unwrap()
+            }
+            _ => panic!("swap_remove index (is {index}) should be < len (is {})", self.len),
+        }
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater than or equal to the vector's current length, this
+    /// has no effect.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let remaining = self.len - len;
+        // SAFETY: the elements in `len..self.len` are initialized. The length
+        // is updated before dropping them, so that a panicking destructor
+        // doesn't cause a double drop.
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.as_mut_ptr().add(len), remaining);
+            self.len = len;
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Removes all the elements of the vector.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Clones and appends all the elements of `other` to the vector.
+    ///
+    /// # Errors
+    ///
+    /// If `other` does not fit in the remaining capacity, an error is returned
+    /// and the vector is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fixed_capacity_collections)]
+    ///
+    /// use core::collections::ArrayVec;
+    ///
+    /// let mut vec = ArrayVec::<i32, 4>::new();
+    /// vec.extend_from_slice(&[1, 2, 3]).unwrap();
+    /// assert!(vec.extend_from_slice(&[4, 5]).is_err());
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    pub fn extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        if other.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(()));
+        }
+        for element in other {
+            // The remaining capacity was checked above, so this cannot fail.
+            let _ = self.push(element.clone());
+        }
+        Ok(())
+    }
+
+    /// Returns a raw pointer to the vector's buffer.
+    #[inline]
+    pub const fn as_ptr(&self) -> *const T {
+        self.data.as_ptr().cast()
+    }
+
+    /// Returns an unsafe mutable pointer to the vector's buffer.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr().cast()
+    }
+
+    /// Extracts a slice containing the entire vector.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    /// Extracts a mutable slice of the entire vector.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+    }
+
+    /// Forces the length of the vector to `new_len`.
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to `N`.
+    /// - The elements at `old_len..new_len` must be initialized.
+    #[inline]
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= N);
+        self.len = new_len;
+    }
+
+    /// Converts the vector into an array, if it is full.
+    ///
+    /// # Errors
+    ///
+    /// If the vector is not full, it is returned unchanged.
+    pub fn into_array(self) -> Result<[T; N], Self> {
+        if self.len < N {
+            return Err(self);
+        }
+        let this = ManuallyDrop::new(self);
+        // SAFETY: all the `N` elements are initialized, and `this` won't drop
+        // them.
+        Ok(unsafe { MaybeUninit::array_assume_init(ptr::read(&this.data)) })
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayVec<T, N> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayVec<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayVec<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for ArrayVec<T, N> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for ArrayVec<T, N> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> Default for ArrayVec<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut new = Self::new();
+        for element in self.iter() {
+            // `new` has the same capacity as `self`, so this cannot fail.
+            let _ = new.push(element.clone());
+        }
+        new
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for ArrayVec<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.as_slice(), state)
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize, const M: usize> PartialEq<ArrayVec<U, M>>
+    for ArrayVec<T, N>
+{
+    #[inline]
+    fn eq(&self, other: &ArrayVec<U, M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U]> for ArrayVec<T, N> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<&[U]> for ArrayVec<T, N> {
+    #[inline]
+    fn eq(&self, other: &&[U]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize, const M: usize> PartialEq<[U; M]> for ArrayVec<T, N> {
+    #[inline]
+    fn eq(&self, other: &[U; M]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayVec<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for ArrayVec<T, N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(self.as_slice(), other.as_slice())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for ArrayVec<T, N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(self.as_slice(), other.as_slice())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArrayVec<T, N> {
+    fn from(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+        // SAFETY: `[T; N]` and `[MaybeUninit<T>; N]` have the same layout, and
+        // the ownership of the elements is transferred to the vector.
+        ArrayVec { len: N, data: unsafe { ptr::read(ptr::addr_of!(array).cast()) } }
+    }
+}
+
+impl<T: Clone, const N: usize> TryFrom<&[T]> for ArrayVec<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(slice: &[T]) -> Result<Self, CapacityError> {
+        let mut vec = Self::new();
+        vec.extend_from_slice(slice)?;
+        Ok(vec)
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArrayVec<T, N> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: the first `len` elements are initialized, and their ownership
+        // is transferred to the iterator since `this` won't drop them.
+        unsafe { array::IntoIter::new_unchecked(ptr::read(&this.data), 0..this.len) }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
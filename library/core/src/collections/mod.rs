@@ -0,0 +1,90 @@
+//! Fixed-capacity collections.
+//!
+//! The collections in this module store their elements inline, in a buffer
+//! whose capacity is fixed at compile time through a const generic parameter.
+//! They never allocate, which makes them usable in environments without a
+//! heap.
+//!
+//! Instead of growing, every operation that would exceed the capacity of the
+//! collection returns a [`CapacityError`], handing back the element that could
+//! not be stored. No operation panics because of a full collection.
+//!
+//! - [`ArrayVec`] is a vector backed by an array, and dereferences to a slice.
+//! - [`ArrayString`] is a UTF-8 string backed by a byte array, and
+//!   dereferences to a `str`.
+//! - [`ArrayDeque`] is a double-ended queue implemented as a ring buffer over
+//!   an array.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(fixed_capacity_collections)]
+//!
+//! use core::collections::ArrayVec;
+//!
+//! let mut vec = ArrayVec::<u32, 2>::new();
+//! assert!(vec.push(1).is_ok());
+//! assert!(vec.push(2).is_ok());
+//!
+//! let err = vec.push(3).unwrap_err();
+//! assert_eq!(err.into_inner(), 3);
+//! assert_eq!(vec, [1, 2]);
+//! ```
+
+#![unstable(feature = "fixed_capacity_collections", issue = "none")]
+
+mod array_deque;
+mod array_string;
+mod array_vec;
+
+pub use array_deque::{ArrayDeque, Iter as ArrayDequeIter};
+pub use array_string::ArrayString;
+pub use array_vec::ArrayVec;
+
+use crate::error::Error;
+use crate::fmt;
+
+/// The error returned when an element could not be added to a fixed-capacity
+/// collection because it is full.
+///
+/// The element that could not be added is stored in the error, and can be
+/// retrieved with [`CapacityError::into_inner`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    /// Creates a new `CapacityError` holding the element that could not be
+    /// added to the collection.
+    #[inline]
+    pub const fn new(element: T) -> Self {
+        CapacityError { element }
+    }
+
+    /// Returns the element that could not be added to the collection.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.element
+    }
+
+    /// Discards the element that could not be added to the collection.
+    #[inline]
+    pub fn simplify(self) -> CapacityError {
+        CapacityError { element: () }
+    }
+}
+
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapacityError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "insufficient capacity".fmt(f)
+    }
+}
+
+impl<T> Error for CapacityError<T> {}
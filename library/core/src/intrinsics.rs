@@ -2493,6 +2493,35 @@ pub fn const_eval_select<ARG: Tuple, F, G, RET>(
     /// constructing an empty slice) is returned.
     #[rustc_nounwind]
     pub fn option_payload_ptr<T>(arg: *const Option<T>) -> *const T;
+
+    /// Returns whether debug assertions are enabled for the function calling this intrinsic.
+    ///
+    /// This is the `-C debug-assertions` setting of the crate, unless it is overridden with
+    /// `#[debug_assertions(on|off)]` on the function or on an item enclosing it. The call is
+    /// replaced by a constant when the calling function is built, and `debug_assert!` relies on
+    /// it instead of `cfg!(debug_assertions)` to honor these attributes. Backends and the interpreter
+    /// evaluate any other call to the `-C debug-assertions` setting of the crate.
+    ///
+    /// Note that, unlike most intrinsics, this is safe to call;
+    /// it does not require an `unsafe` block.
+    /// Therefore, implementations must not require the user to uphold
+    /// any safety invariants.
+    ///
+    /// This intrinsic does not have a stable counterpart.
+    #[cfg(not(bootstrap))]
+    #[rustc_const_unstable(feature = "const_debug_assertions", issue = "none")]
+    #[rustc_safe_intrinsic]
+    #[rustc_nounwind]
+    pub fn debug_assertions() -> bool;
+}
+
+/// Returns whether debug assertions are enabled, which the bootstrap compiler only knows for the
+/// whole crate.
+#[cfg(bootstrap)]
+#[rustc_const_unstable(feature = "const_debug_assertions", issue = "none")]
+#[inline(always)]
+pub const fn debug_assertions() -> bool {
+    cfg!(debug_assertions)
 }
 
 // Some functions are defined here because they accidentally got made
@@ -109,6 +109,11 @@
 // Do not check link redundancy on bootstraping phase
 #![allow(rustdoc::redundant_explicit_links)]
 //
+// Ferrocene: items in the certified subset are marked with `#[ferrocene::certified]`.
+#![cfg_attr(not(bootstrap), feature(ferrocene_certified))]
+#![cfg_attr(bootstrap, feature(register_tool))]
+#![cfg_attr(bootstrap, register_tool(ferrocene))]
+//
 // Library features:
 // tidy-alphabetical-start
 #![feature(char_indices_offset)]
@@ -365,6 +370,7 @@ pub mod assert_matches {
 pub mod async_iter;
 pub mod cell;
 pub mod char;
+pub mod collections;
 pub mod ffi;
 pub mod iter;
 pub mod net;
@@ -212,10 +212,10 @@ macro_rules! assert_ne {
 #[macro_export]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_diagnostic_item = "debug_assert_macro"]
-#[allow_internal_unstable(edition_panic)]
+#[allow_internal_unstable(core_intrinsics, const_debug_assertions, edition_panic)]
 macro_rules! debug_assert {
     ($($arg:tt)*) => {
-        if $crate::cfg!(debug_assertions) {
+        if $crate::intrinsics::debug_assertions() {
             $crate::assert!($($arg)*);
         }
     };
@@ -243,9 +243,10 @@ macro_rules! debug_assert {
 #[macro_export]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[cfg_attr(not(test), rustc_diagnostic_item = "debug_assert_eq_macro")]
+#[allow_internal_unstable(core_intrinsics, const_debug_assertions)]
 macro_rules! debug_assert_eq {
     ($($arg:tt)*) => {
-        if $crate::cfg!(debug_assertions) {
+        if $crate::intrinsics::debug_assertions() {
             $crate::assert_eq!($($arg)*);
         }
     };
@@ -273,9 +274,10 @@ macro_rules! debug_assert_eq {
 #[macro_export]
 #[stable(feature = "assert_ne", since = "1.13.0")]
 #[cfg_attr(not(test), rustc_diagnostic_item = "debug_assert_ne_macro")]
+#[allow_internal_unstable(core_intrinsics, const_debug_assertions)]
 macro_rules! debug_assert_ne {
     ($($arg:tt)*) => {
-        if $crate::cfg!(debug_assertions) {
+        if $crate::intrinsics::debug_assertions() {
             $crate::assert_ne!($($arg)*);
         }
     };
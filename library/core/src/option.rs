@@ -595,6 +595,7 @@ impl<T> Option<T> {
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_stable(feature = "const_option_basics", since = "1.48.0")]
+    #[ferrocene::certified]
     pub const fn is_some(&self) -> bool {
         matches!(*self, Some(_))
     }
@@ -639,6 +640,7 @@ pub fn is_some_and(self, f: impl FnOnce(T) -> bool) -> bool {
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_stable(feature = "const_option_basics", since = "1.48.0")]
+    #[ferrocene::certified]
     pub const fn is_none(&self) -> bool {
         !self.is_some()
     }
@@ -942,6 +944,7 @@ pub const fn unwrap(self) -> T {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[ferrocene::certified]
     pub fn unwrap_or(self, default: T) -> T {
         match self {
             Some(x) => x,
@@ -0,0 +1,231 @@
+use core::cell::Cell;
+use core::collections::{ArrayDeque, ArrayString, ArrayVec};
+use core::fmt::Write;
+
+struct DropCounter<'a> {
+    count: &'a Cell<usize>,
+}
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.count.set(self.count.get() + 1);
+    }
+}
+
+#[test]
+fn array_vec_push_pop() {
+    let mut vec = ArrayVec::<i32, 3>::new();
+    assert!(vec.is_empty());
+    assert_eq!(vec.push(1), Ok(()));
+    assert_eq!(vec.push(2), Ok(()));
+    assert_eq!(vec.push(3), Ok(()));
+    assert!(vec.is_full());
+    assert_eq!(vec.push(4).unwrap_err().into_inner(), 4);
+    assert_eq!(vec, [1, 2, 3]);
+
+    assert_eq!(vec.pop(), Some(3));
+    assert_eq!(vec.remaining_capacity(), 1);
+    assert_eq!(vec.pop(), Some(2));
+    assert_eq!(vec.pop(), Some(1));
+    assert_eq!(vec.pop(), None);
+}
+
+#[test]
+fn array_vec_insert_remove() {
+    let mut vec = ArrayVec::<i32, 4>::from([1, 2, 3, 4]);
+    assert_eq!(vec.remove(1), 2);
+    assert_eq!(vec, [1, 3, 4]);
+    assert_eq!(vec.insert(0, 0), Ok(()));
+    assert_eq!(vec, [0, 1, 3, 4]);
+    assert!(vec.insert(4, 5).is_err());
+    assert_eq!(vec.swap_remove(0), 0);
+    assert_eq!(vec, [4, 1, 3]);
+    assert_eq!(vec.insert(3, 5), Ok(()));
+    assert_eq!(vec, [4, 1, 3, 5]);
+}
+
+#[test]
+#[should_panic]
+fn array_vec_insert_out_of_bounds() {
+    let mut vec = ArrayVec::<i32, 4>::new();
+    let _ = vec.insert(1, 1);
+}
+
+#[test]
+fn array_vec_slice_methods() {
+    let mut vec = ArrayVec::<i32, 8>::try_from(&[5, 3, 4, 1, 2][..]).unwrap();
+    vec.sort_unstable();
+    assert_eq!(vec, [1, 2, 3, 4, 5]);
+    assert_eq!(vec.binary_search(&4), Ok(3));
+    assert_eq!(vec.iter().sum::<i32>(), 15);
+    assert!(ArrayVec::<i32, 2>::try_from(&[1, 2, 3][..]).is_err());
+}
+
+#[test]
+fn array_vec_extend_from_slice() {
+    let mut vec = ArrayVec::<i32, 4>::new();
+    assert!(vec.extend_from_slice(&[1, 2, 3]).is_ok());
+    assert!(vec.extend_from_slice(&[4, 5]).is_err());
+    assert_eq!(vec, [1, 2, 3]);
+    assert!(vec.extend_from_slice(&[4]).is_ok());
+    assert_eq!(vec.into_array(), Ok([1, 2, 3, 4]));
+}
+
+#[test]
+fn array_vec_into_iter() {
+    let vec = ArrayVec::<i32, 4>::try_from(&[1, 2, 3][..]).unwrap();
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn array_vec_drop() {
+    let count = Cell::new(0);
+    {
+        let mut vec = ArrayVec::<DropCounter<'_>, 4>::new();
+        for _ in 0..3 {
+            assert!(vec.push(DropCounter { count: &count }).is_ok());
+        }
+        vec.truncate(1);
+        assert_eq!(count.get(), 2);
+        let mut iter = vec.into_iter();
+        drop(iter.next());
+        assert_eq!(count.get(), 3);
+    }
+    assert_eq!(count.get(), 3);
+
+    let count = Cell::new(0);
+    {
+        let mut vec = ArrayVec::<DropCounter<'_>, 4>::new();
+        for _ in 0..4 {
+            assert!(vec.push(DropCounter { count: &count }).is_ok());
+        }
+        let err = vec.push(DropCounter { count: &count }).unwrap_err();
+        assert_eq!(count.get(), 0);
+        drop(err);
+        assert_eq!(count.get(), 1);
+    }
+    assert_eq!(count.get(), 5);
+}
+
+#[test]
+fn array_string_push() {
+    let mut s = ArrayString::<8>::new();
+    assert_eq!(s.push('a'), Ok(()));
+    assert_eq!(s.push_str("b€"), Ok(()));
+    assert_eq!(s, "ab€");
+    assert_eq!(s.len(), 5);
+    assert_eq!(s.push_str("cdef").unwrap_err().into_inner(), "cdef");
+    assert_eq!(s.push('𤭢').unwrap_err().into_inner(), '𤭢');
+    assert_eq!(s.push_str("cde"), Ok(()));
+    assert_eq!(s, "ab€cde");
+    assert_eq!(s.remaining_capacity(), 0);
+}
+
+#[test]
+fn array_string_pop_truncate() {
+    let mut s = ArrayString::<16>::try_from("ab€c").unwrap();
+    assert_eq!(s.pop(), Some('c'));
+    assert_eq!(s.pop(), Some('€'));
+    assert_eq!(s, "ab");
+    s.truncate(1);
+    assert_eq!(s, "a");
+    s.clear();
+    assert!(s.is_empty());
+    assert_eq!(s.pop(), None);
+    assert!(ArrayString::<2>::try_from("abc").is_err());
+}
+
+#[test]
+#[should_panic]
+fn array_string_truncate_not_char_boundary() {
+    let mut s = ArrayString::<8>::try_from("€").unwrap();
+    s.truncate(1);
+}
+
+#[test]
+fn array_string_write() {
+    let mut s = ArrayString::<8>::new();
+    assert!(write!(s, "{}+{}", 12, 34).is_ok());
+    assert_eq!(s.as_str(), "12+34");
+    assert!(write!(s, "{}", 5678).is_err());
+    s.make_ascii_uppercase();
+    assert_eq!(s, "12+34");
+}
+
+#[test]
+fn array_deque_push_pop() {
+    let mut deque = ArrayDeque::<i32, 3>::new();
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+    assert_eq!(deque.push_back(2), Ok(()));
+    assert_eq!(deque.push_front(1), Ok(()));
+    assert_eq!(deque.push_back(3), Ok(()));
+    assert_eq!(deque.push_front(0).unwrap_err().into_inner(), 0);
+    assert_eq!(deque.front(), Some(&1));
+    assert_eq!(deque.back(), Some(&3));
+    assert_eq!(deque.get(1), Some(&2));
+    assert_eq!(deque.get(3), None);
+
+    assert_eq!(deque.pop_front(), Some(1));
+    assert_eq!(deque.push_back(4), Ok(()));
+    assert!(deque.iter().eq(&[2, 3, 4]));
+    assert_eq!(deque.pop_back(), Some(4));
+    assert_eq!(deque.pop_back(), Some(3));
+    assert_eq!(deque.pop_back(), Some(2));
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn array_deque_slices() {
+    let mut deque = ArrayDeque::<i32, 4>::new();
+    for i in 0..4 {
+        assert!(deque.push_back(i).is_ok());
+    }
+    assert_eq!(deque.pop_front(), Some(0));
+    assert_eq!(deque.pop_front(), Some(1));
+    assert!(deque.push_back(4).is_ok());
+    assert!(deque.push_back(5).is_ok());
+
+    let (front, back) = deque.as_slices();
+    assert_eq!(front, [2, 3]);
+    assert_eq!(back, [4, 5]);
+    assert!(deque.iter().rev().eq(&[5, 4, 3, 2]));
+    assert_eq!(deque.iter().len(), 4);
+
+    let contiguous = deque.make_contiguous();
+    assert_eq!(contiguous, [2, 3, 4, 5]);
+    contiguous.reverse();
+    assert_eq!(deque.as_slices(), (&[5, 4, 3, 2][..], &[][..]));
+}
+
+#[test]
+fn array_deque_drop() {
+    let count = Cell::new(0);
+    {
+        let mut deque = ArrayDeque::<DropCounter<'_>, 3>::new();
+        for _ in 0..3 {
+            assert!(deque.push_front(DropCounter { count: &count }).is_ok());
+        }
+        drop(deque.pop_back());
+        assert_eq!(count.get(), 1);
+        assert!(deque.push_back(DropCounter { count: &count }).is_ok());
+    }
+    assert_eq!(count.get(), 4);
+}
+
+#[test]
+fn zero_capacity() {
+    let mut vec = ArrayVec::<i32, 0>::new();
+    assert!(vec.push(1).is_err());
+    let mut s = ArrayString::<0>::new();
+    assert!(s.push('a').is_err());
+    let mut deque = ArrayDeque::<i32, 0>::new();
+    assert!(deque.push_front(1).is_err());
+    assert!(deque.push_back(1).is_err());
+    assert_eq!(deque.back(), None);
+}
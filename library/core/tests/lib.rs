@@ -32,6 +32,7 @@
 #![feature(duration_constants)]
 #![feature(exact_size_is_empty)]
 #![feature(extern_types)]
+#![feature(fixed_capacity_collections)]
 #![feature(flt2dec)]
 #![feature(fmt_internals)]
 #![feature(float_minimum_maximum)]
@@ -133,6 +134,7 @@
 mod char;
 mod clone;
 mod cmp;
+mod collections;
 mod const_ptr;
 mod convert;
 mod fmt;
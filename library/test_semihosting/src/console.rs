@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Reporting of the test results on the semihosted console, in the same formats as libtest.
+
+use crate::options::OutputFormat;
+use crate::semihosting::Console;
+use crate::{ShouldPanic, TestDesc};
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+static FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Pretty as u8);
+
+static PASSED: AtomicUsize = AtomicUsize::new(0);
+static FAILED: AtomicUsize = AtomicUsize::new(0);
+static IGNORED: AtomicUsize = AtomicUsize::new(0);
+static FILTERED_OUT: AtomicUsize = AtomicUsize::new(0);
+
+/// Why a test failed.
+pub(crate) enum Failure<'a> {
+    Panic(&'a core::panic::PanicInfo<'a>),
+    Message(fmt::Arguments<'a>),
+}
+
+pub(crate) fn print(args: fmt::Arguments<'_>) {
+    // There is nowhere else to report the failure to write to the console.
+    let _ = Console.write_fmt(args);
+}
+
+fn format() -> OutputFormat {
+    match FORMAT.load(Ordering::Relaxed) {
+        f if f == OutputFormat::Json as u8 => OutputFormat::Json,
+        f if f == OutputFormat::Terse as u8 => OutputFormat::Terse,
+        _ => OutputFormat::Pretty,
+    }
+}
+
+pub(crate) fn init(format: OutputFormat, filtered_out: usize) {
+    FORMAT.store(format as u8, Ordering::Relaxed);
+    FILTERED_OUT.store(filtered_out, Ordering::Relaxed);
+}
+
+pub(crate) fn run_start(test_count: usize) {
+    match format() {
+        OutputFormat::Json => print(format_args!(
+            "{{ \"type\": \"suite\", \"event\": \"started\", \"test_count\": {test_count} }}\n"
+        )),
+        OutputFormat::Pretty | OutputFormat::Terse => {
+            let noun = if test_count != 1 { "tests" } else { "test" };
+            print(format_args!("\nrunning {test_count} {noun}\n"));
+        }
+    }
+}
+
+pub(crate) fn test_start(desc: &TestDesc) {
+    match format() {
+        OutputFormat::Json => print(format_args!(
+            "{{ \"type\": \"test\", \"event\": \"started\", \"name\": \"{}\" }}\n",
+            Escaped(desc.name.as_str())
+        )),
+        // The name is printed before running the test, as a test that hangs would otherwise
+        // leave no trace of which test it was.
+        OutputFormat::Pretty => print(format_args!("test {} ... ", PrettyName(desc))),
+        OutputFormat::Terse => {}
+    }
+}
+
+pub(crate) fn ok(desc: &TestDesc) {
+    PASSED.fetch_add(1, Ordering::Relaxed);
+    match format() {
+        OutputFormat::Json => print(format_args!(
+            "{{ \"type\": \"test\", \"name\": \"{}\", \"event\": \"ok\" }}\n",
+            Escaped(desc.name.as_str())
+        )),
+        OutputFormat::Pretty => print(format_args!("ok\n")),
+        OutputFormat::Terse => print(format_args!(".")),
+    }
+}
+
+pub(crate) fn ignored(desc: &TestDesc, message: Option<&str>) {
+    IGNORED.fetch_add(1, Ordering::Relaxed);
+    match (format(), message) {
+        (OutputFormat::Json, Some(message)) => print(format_args!(
+            "{{ \"type\": \"test\", \"name\": \"{}\", \"event\": \"ignored\", \
+             \"message\": \"{}\" }}\n",
+            Escaped(desc.name.as_str()),
+            Escaped(message)
+        )),
+        (OutputFormat::Json, None) => print(format_args!(
+            "{{ \"type\": \"test\", \"name\": \"{}\", \"event\": \"ignored\" }}\n",
+            Escaped(desc.name.as_str())
+        )),
+        (OutputFormat::Pretty, Some(message)) => {
+            print(format_args!("test {} ... ignored, {message}\n", PrettyName(desc)))
+        }
+        (OutputFormat::Pretty, None) => {
+            print(format_args!("test {} ... ignored\n", PrettyName(desc)))
+        }
+        (OutputFormat::Terse, _) => print(format_args!("i")),
+    }
+}
+
+pub(crate) fn failed(desc: &TestDesc, failure: Failure<'_>) {
+    FAILED.fetch_add(1, Ordering::Relaxed);
+    match (format(), failure) {
+        (OutputFormat::Json, Failure::Panic(info)) => print(format_args!(
+            "{{ \"type\": \"test\", \"name\": \"{}\", \"event\": \"failed\", \
+             \"stdout\": \"{}\" }}\n",
+            Escaped(desc.name.as_str()),
+            EscapedDisplay(info)
+        )),
+        (OutputFormat::Json, Failure::Message(message)) => print(format_args!(
+            "{{ \"type\": \"test\", \"name\": \"{}\", \"event\": \"failed\", \
+             \"message\": \"{}\" }}\n",
+            Escaped(desc.name.as_str()),
+            EscapedDisplay(&message)
+        )),
+        (format, failure) => {
+            match format {
+                OutputFormat::Pretty => print(format_args!("FAILED\n")),
+                _ => print(format_args!("F")),
+            }
+            print(format_args!("\n---- {} stdout ----\n", desc.name.as_str()));
+            match failure {
+                Failure::Panic(info) => print(format_args!("{info}\n")),
+                Failure::Message(message) => print(format_args!("{message}\n")),
+            }
+        }
+    }
+}
+
+/// Prints the summary of the run, returning whether all the tests passed.
+pub(crate) fn run_finish() -> bool {
+    let passed = PASSED.load(Ordering::Relaxed);
+    let failed = FAILED.load(Ordering::Relaxed);
+    let ignored = IGNORED.load(Ordering::Relaxed);
+    let filtered_out = FILTERED_OUT.load(Ordering::Relaxed);
+    match format() {
+        OutputFormat::Json => print(format_args!(
+            "{{ \"type\": \"suite\", \"event\": \"{}\", \"passed\": {passed}, \
+             \"failed\": {failed}, \"ignored\": {ignored}, \"measured\": 0, \
+             \"filtered_out\": {filtered_out} }}\n",
+            if failed == 0 { "ok" } else { "failed" },
+        )),
+        OutputFormat::Pretty | OutputFormat::Terse => print(format_args!(
+            "\ntest result: {}. {passed} passed; {failed} failed; {ignored} ignored; \
+             0 measured; {filtered_out} filtered out\n\n",
+            if failed == 0 { "ok" } else { "FAILED" },
+        )),
+    }
+    failed == 0
+}
+
+pub(crate) fn list(desc: &TestDesc, is_bench: bool) {
+    let kind = if is_bench { "bench" } else { "test" };
+    match format() {
+        OutputFormat::Json => print(format_args!(
+            "{{ \"type\": \"{kind}\", \"event\": \"discovered\", \"name\": \"{}\" }}\n",
+            Escaped(desc.name.as_str())
+        )),
+        OutputFormat::Pretty | OutputFormat::Terse => {
+            print(format_args!("{}: {kind}\n", desc.name.as_str()))
+        }
+    }
+}
+
+/// The name of a test in the pretty format, which also tells whether it should panic.
+struct PrettyName<'a>(&'a TestDesc);
+
+impl fmt::Display for PrettyName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.name.as_str())?;
+        if self.0.should_panic != ShouldPanic::No {
+            f.write_str(" - should panic")?;
+        }
+        Ok(())
+    }
+}
+
+/// A string escaped to be embedded in a JSON string.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut start = 0;
+        for (i, c) in self.0.char_indices() {
+            if !matches!(c, '"' | '\\') && !c.is_control() {
+                continue;
+            }
+            f.write_str(&self.0[start..i])?;
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c => write!(f, "\\u{:04x}", c as u32)?,
+            }
+            start = i + c.len_utf8();
+        }
+        f.write_str(&self.0[start..])
+    }
+}
+
+/// A value whose `Display` output is escaped to be embedded in a JSON string, without
+/// formatting it into an intermediate buffer.
+struct EscapedDisplay<'a, T: fmt::Display>(&'a T);
+
+impl<T: fmt::Display> fmt::Display for EscapedDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Adapter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl fmt::Write for Adapter<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                write!(self.0, "{}", Escaped(s))
+            }
+        }
+
+        write!(Adapter(f), "{}", self.0)
+    }
+}
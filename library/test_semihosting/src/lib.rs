@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! A `no_std` variant of the test harness, for bare-metal targets tested under QEMU system
+//! emulation.
+//!
+//! Test crates compiled with `-Z semihosting-tests` use this crate instead of libtest. It provides
+//! the same items the `#[test]` and `#[bench]` attributes expand to, runs the tests one after the
+//! other in the same program, and reports the results over Arm semihosting: the command line is
+//! read with `SYS_GET_CMDLINE`, the output (in libtest's pretty, terse or JSON format) is written
+//! to the host console, and the program stops with `SYS_EXIT`, reporting whether all the tests
+//! passed as the exit status. QEMU exits with that status, which is what the runner checks.
+//!
+//! Without unwinding a panic can't be recovered from, so the panic handler reports the result of
+//! the test that panicked (a success for `#[should_panic]` tests) and restarts the program on a
+//! fresh stack, which resumes the run after that test. Benchmarks are run once, as tests.
+//!
+//! The crate also provides the startup code and the linker scripts of the QEMU machines
+//! `remote-test-client run-semihosting` boots the test binaries on, in the `link` directory:
+//! `aarch64-virt.ld` for AArch64 and `thumbv7em-mps2-an386.ld` for Armv7E-M.
+
+#![no_std]
+#![unstable(feature = "test", issue = "50297")]
+#![feature(lang_items)]
+#![feature(panic_info_message)]
+#![feature(staged_api)]
+#![allow(internal_features)]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+mod console;
+mod options;
+mod rt;
+mod semihosting;
+
+use crate::console::Failure;
+use crate::options::{Options, RunIgnored};
+
+use core::fmt;
+use core::panic::PanicInfo;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+pub use core::hint::black_box;
+pub use TestFn::*;
+pub use TestName::*;
+
+/// Exit status of a run with failed tests, the same as libtest's.
+const ERROR_EXIT_CODE: i32 = 101;
+
+/// The test being run, whose result is reported by the panic handler.
+static CURRENT: AtomicPtr<TestDesc> = AtomicPtr::new(ptr::null_mut());
+
+/// Position among the selected tests of the next test to run, kept when the program is restarted
+/// after a panic. The run starts when it's 0.
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+pub struct TestDescAndFn {
+    pub desc: TestDesc,
+    pub testfn: TestFn,
+}
+
+pub struct TestDesc {
+    pub name: TestName,
+    pub ignore: bool,
+    pub ignore_message: Option<&'static str>,
+    pub source_file: &'static str,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+    pub should_panic: ShouldPanic,
+    pub compile_fail: bool,
+    pub no_run: bool,
+    pub test_type: TestType,
+}
+
+pub enum TestName {
+    StaticTestName(&'static str),
+}
+
+impl TestName {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            StaticTestName(name) => name,
+        }
+    }
+}
+
+pub enum TestFn {
+    StaticTestFn(fn() -> Result<(), TestError>),
+    StaticBenchFn(fn(&mut Bencher) -> Result<(), TestError>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ShouldPanic {
+    No,
+    Yes,
+    YesWithMessage(&'static str),
+}
+
+/// Type of the test, see [`TestType`](../test/enum.TestType.html) in libtest.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TestType {
+    UnitTest,
+    IntegrationTest,
+    DocTest,
+    Unknown,
+}
+
+/// Runs benchmarks once, as tests.
+pub struct Bencher {
+    pub bytes: u64,
+}
+
+impl Bencher {
+    pub fn iter<T, F: FnMut() -> T>(&mut self, mut inner: F) {
+        black_box(inner());
+    }
+}
+
+/// The termination value of a test that indicates a failure.
+pub struct TestError {
+    code: i32,
+}
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the test returned a termination value with a non-zero status code ({}) which \
+             indicates a failure",
+            self.code
+        )
+    }
+}
+
+/// The equivalent of `std::process::Termination` for the return value of tests.
+pub trait Termination {
+    /// Reports the value as a status code, where 0 is a success.
+    fn report(self) -> i32;
+}
+
+impl Termination for () {
+    fn report(self) -> i32 {
+        0
+    }
+}
+
+impl Termination for core::convert::Infallible {
+    fn report(self) -> i32 {
+        match self {}
+    }
+}
+
+impl<T: Termination, E: fmt::Debug> Termination for Result<T, E> {
+    fn report(self) -> i32 {
+        match self {
+            Ok(value) => value.report(),
+            Err(error) => {
+                console::print(format_args!("Error: {error:?}\n"));
+                1
+            }
+        }
+    }
+}
+
+/// Invoked when unit tests terminate. Returns `Result::Err` if the test is considered a failure.
+pub fn assert_test_result<T: Termination>(result: T) -> Result<(), TestError> {
+    match result.report() {
+        0 => Ok(()),
+        code => Err(TestError { code }),
+    }
+}
+
+/// The entry point of the test harness, called by the `main` function generated by rustc.
+pub fn test_main_static(tests: &[&TestDescAndFn]) -> ! {
+    let mut cmdline = [0; 4096];
+    let len = semihosting::command_line(&mut cmdline).unwrap_or(0);
+    let cmdline = core::str::from_utf8(&cmdline[..len]).unwrap_or("");
+    let options = match Options::parse(cmdline) {
+        Ok(options) => options,
+        Err(arg) => {
+            console::print(format_args!("error: unsupported or invalid argument: {arg}\n"));
+            semihosting::exit(ERROR_EXIT_CODE);
+        }
+    };
+
+    let selected = || tests.iter().filter(|test| options.selects(&test.desc));
+    let resume_from = NEXT.load(Ordering::SeqCst);
+    if resume_from == 0 {
+        let selected_count = selected().count();
+        console::init(options.format, tests.len() - selected_count);
+        if options.list {
+            for test in selected() {
+                console::list(&test.desc, matches!(test.testfn, StaticBenchFn(_)));
+            }
+            semihosting::exit(0);
+        }
+        console::run_start(selected_count);
+    }
+
+    for (position, test) in selected().enumerate().skip(resume_from) {
+        NEXT.store(position + 1, Ordering::SeqCst);
+        let desc = &test.desc;
+        if desc.ignore && options.run_ignored == RunIgnored::No {
+            console::ignored(desc, desc.ignore_message);
+            continue;
+        }
+
+        console::test_start(desc);
+        CURRENT.store(desc as *const TestDesc as *mut TestDesc, Ordering::SeqCst);
+        let result = match test.testfn {
+            StaticTestFn(f) => f(),
+            StaticBenchFn(f) => f(&mut Bencher { bytes: 0 }),
+        };
+        CURRENT.store(ptr::null_mut(), Ordering::SeqCst);
+
+        match (result, desc.should_panic) {
+            (Ok(()), ShouldPanic::No) => console::ok(desc),
+            (Ok(()), _) => console::failed(
+                desc,
+                Failure::Message(format_args!("test did not panic as expected")),
+            ),
+            (Err(error), _) => console::failed(desc, Failure::Message(format_args!("{error}"))),
+        }
+    }
+
+    semihosting::exit(if console::run_finish() { 0 } else { ERROR_EXIT_CODE })
+}
+
+/// The entry point of the test harness with `-Z panic-abort-tests`, which is the only way tests
+/// are run by this harness anyway.
+pub fn test_main_static_abort(tests: &[&TestDescAndFn]) -> ! {
+    test_main_static(tests)
+}
+
+#[lang = "start"]
+fn lang_start<T: 'static>(
+    main: fn() -> T,
+    _argc: isize,
+    _argv: *const *const u8,
+    _sigpipe: u8,
+) -> isize {
+    main();
+    0
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    // Taking the current test also prevents reporting it twice if reporting it panics.
+    let current = CURRENT.swap(ptr::null_mut(), Ordering::SeqCst);
+    // SAFETY: the pointer is only set while the test is being run, in a frame of
+    // `test_main_static` that is still alive as the panic can't unwind it.
+    let Some(desc) = (unsafe { current.as_ref() }) else {
+        console::print(format_args!("{info}\n"));
+        semihosting::exit(ERROR_EXIT_CODE);
+    };
+
+    match desc.should_panic {
+        ShouldPanic::No => console::failed(desc, Failure::Panic(info)),
+        ShouldPanic::Yes => console::ok(desc),
+        ShouldPanic::YesWithMessage(expected) => {
+            let mut message = MessageBuffer { buf: [0; 1024], len: 0 };
+            if let Some(args) = info.message() {
+                let _ = fmt::Write::write_fmt(&mut message, *args);
+            }
+            if message.as_str().contains(expected) {
+                console::ok(desc);
+            } else {
+                console::failed(
+                    desc,
+                    Failure::Message(format_args!(
+                        "panic did not contain expected string\n      panic message: `{:?}`,\n \
+                         expected substring: `{expected:?}`",
+                        message.as_str()
+                    )),
+                );
+            }
+        }
+    }
+    // The frames of the test can't be unwound, so the run is resumed after the test from a fresh
+    // stack instead.
+    rt::restart()
+}
+
+/// Unexpected processor exception, like an undefined instruction or a bus fault, taken while the
+/// program is running. The run is stopped, as the state of the processor can't be trusted anymore.
+#[no_mangle]
+extern "C" fn __semihosting_fault() -> ! {
+    let current = CURRENT.swap(ptr::null_mut(), Ordering::SeqCst);
+    // SAFETY: see the panic handler.
+    match unsafe { current.as_ref() } {
+        Some(desc) => {
+            console::failed(desc, Failure::Message(format_args!("unexpected processor exception")));
+            console::print(format_args!("\nthe run was stopped by the processor exception\n"));
+            console::run_finish();
+        }
+        None => console::print(format_args!("unexpected processor exception\n")),
+    }
+    semihosting::exit(ERROR_EXIT_CODE)
+}
+
+/// Called by the startup code if `main` returns.
+#[no_mangle]
+extern "C" fn __semihosting_exit(status: i32) -> ! {
+    semihosting::exit(status)
+}
+
+/// Fixed-size buffer the panic message is formatted into, truncated if it doesn't fit.
+struct MessageBuffer {
+    buf: [u8; 1024],
+    len: usize,
+}
+
+impl MessageBuffer {
+    fn as_str(&self) -> &str {
+        // Truncating can split a character, in which case only the valid prefix is kept.
+        match core::str::from_utf8(&self.buf[..self.len]) {
+            Ok(message) => message,
+            Err(error) => {
+                // SAFETY: `valid_up_to` is the length of the prefix that is valid UTF-8.
+                unsafe { core::str::from_utf8_unchecked(&self.buf[..error.valid_up_to()]) }
+            }
+        }
+    }
+}
+
+impl fmt::Write for MessageBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let len = s.len().min(self.buf.len() - self.len);
+        self.buf[self.len..self.len + len].copy_from_slice(&s.as_bytes()[..len]);
+        self.len += len;
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Parsing of the command line of the test binary.
+//!
+//! Only the subset of libtest's options that makes sense without threads or processes is
+//! supported, and the options that don't change anything here (like `--test-threads`) are
+//! accepted and ignored. The command line is walked again every time the filters are needed, as
+//! there is no allocator to collect them into.
+
+use crate::TestDesc;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutputFormat {
+    Pretty,
+    Terse,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RunIgnored {
+    No,
+    Yes,
+    Only,
+}
+
+pub(crate) struct Options<'a> {
+    cmdline: &'a str,
+    pub(crate) format: OutputFormat,
+    pub(crate) run_ignored: RunIgnored,
+    pub(crate) exact: bool,
+    pub(crate) list: bool,
+}
+
+impl<'a> Options<'a> {
+    pub(crate) fn parse(cmdline: &'a str) -> Result<Self, &'a str> {
+        let mut options = Options {
+            cmdline,
+            format: OutputFormat::Pretty,
+            run_ignored: RunIgnored::No,
+            exact: false,
+            list: false,
+        };
+        for arg in args(cmdline) {
+            match arg? {
+                Arg::Flag("--exact") => options.exact = true,
+                Arg::Flag("--list") => options.list = true,
+                Arg::Flag("--ignored") => options.run_ignored = RunIgnored::Only,
+                Arg::Flag("--include-ignored") => options.run_ignored = RunIgnored::Yes,
+                Arg::Flag("-q" | "--quiet") => options.format = OutputFormat::Terse,
+                Arg::Option("--format", "pretty") => options.format = OutputFormat::Pretty,
+                Arg::Option("--format", "terse") => options.format = OutputFormat::Terse,
+                Arg::Option("--format", "json") => options.format = OutputFormat::Json,
+                Arg::Option("--format", _) => return Err("--format"),
+                // There is nothing to capture, and tests are always run one at a time.
+                Arg::Flag("--nocapture" | "--show-output" | "--test" | "--bench") => {}
+                Arg::Option("--test-threads" | "--color" | "-Z", _) => {}
+                Arg::Option("--skip", _) | Arg::Filter(_) => {}
+                Arg::Flag(flag) => return Err(flag),
+                Arg::Option(option, _) => return Err(option),
+            }
+        }
+        Ok(options)
+    }
+
+    /// Returns whether the test is selected by the filters and by `--ignored`.
+    pub(crate) fn selects(&self, desc: &TestDesc) -> bool {
+        let name = desc.name.as_str();
+        let matches =
+            |filter: &str| if self.exact { name == filter } else { name.contains(filter) };
+
+        let mut has_filters = false;
+        let mut filtered = false;
+        for arg in args(self.cmdline) {
+            match arg {
+                Ok(Arg::Filter(filter)) => {
+                    has_filters = true;
+                    filtered |= matches(filter);
+                }
+                Ok(Arg::Option("--skip", skip)) if matches(skip) => return false,
+                _ => {}
+            }
+        }
+        (!has_filters || filtered) && (self.run_ignored != RunIgnored::Only || desc.ignore)
+    }
+}
+
+enum Arg<'a> {
+    Flag(&'a str),
+    Option(&'a str, &'a str),
+    Filter(&'a str),
+}
+
+/// Options that take a value, either as the next argument or after `=`.
+const OPTIONS_WITH_VALUE: &[&str] = &["--format", "--skip", "--test-threads", "--color", "-Z"];
+
+/// Iterates over the arguments, skipping the program name.
+///
+/// Semihosting provides the command line as a single string, in which QEMU separates the
+/// arguments with spaces.
+fn args(cmdline: &str) -> impl Iterator<Item = Result<Arg<'_>, &str>> {
+    let mut words = cmdline.split(' ').filter(|word| !word.is_empty()).skip(1);
+    core::iter::from_fn(move || {
+        let word = words.next()?;
+        if !word.starts_with('-') {
+            return Some(Ok(Arg::Filter(word)));
+        }
+        if let Some((option, value)) = word.split_once('=') {
+            if OPTIONS_WITH_VALUE.contains(&option) {
+                return Some(Ok(Arg::Option(option, value)));
+            }
+        }
+        if OPTIONS_WITH_VALUE.contains(&word) {
+            return Some(words.next().map(|value| Arg::Option(word, value)).ok_or(word));
+        }
+        Some(Ok(Arg::Flag(word)))
+    })
+}
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Startup code of the test binaries, for the QEMU machines described by the linker scripts in
+//! the `link` directory.
+//!
+//! QEMU loads every section of the binary at its address, so the startup code only has to set up
+//! the stack and the processor, zero `.bss` and call the `main` function generated by rustc. The
+//! symbols it uses (`__stack_top`, `__bss_start` and `__bss_end`) are defined by the linker
+//! scripts.
+
+use core::arch::{asm, global_asm};
+
+extern "C" {
+    static __stack_top: u8;
+}
+
+#[cfg(target_arch = "aarch64")]
+global_asm!(
+    r#"
+    .section .text._start, "ax"
+    .global _start
+    .type _start, %function
+_start:
+    ldr x0, =__stack_top
+    mov sp, x0
+
+    // Enable the FP and SIMD registers, used by the code generated for the hard-float target.
+    mrs x0, cpacr_el1
+    orr x0, x0, #(0x3 << 20)
+    msr cpacr_el1, x0
+    ldr x0, =__semihosting_vectors
+    msr vbar_el1, x0
+    isb
+
+    ldr x0, =__bss_start
+    ldr x1, =__bss_end
+1:
+    cmp x0, x1
+    b.hs 2f
+    str xzr, [x0], #8
+    b 1b
+2:
+
+    .global __semihosting_main
+__semihosting_main:
+    mov x0, #0
+    mov x1, #0
+    bl main
+    b __semihosting_exit
+
+    // Every exception taken at EL1 is unexpected, as interrupts are never enabled.
+    .section .text.__semihosting_vectors, "ax"
+    .balign 0x800
+__semihosting_vectors:
+    .rept 16
+    .balign 0x80
+    b __semihosting_fault
+    .endr
+"#
+);
+
+#[cfg(target_arch = "arm")]
+global_asm!(
+    r#"
+    .syntax unified
+    .thumb
+
+    // The processor starts with the stack pointer and the reset handler of the vector table.
+    // All the other exceptions are unexpected, as interrupts are never enabled.
+    .section .vector_table, "a"
+    .global __semihosting_vector_table
+__semihosting_vector_table:
+    .word __stack_top
+    .word _start
+    .rept 14
+    .word __semihosting_fault
+    .endr
+
+    .section .text._start, "ax"
+    .global _start
+    .type _start, %function
+    .thumb_func
+_start:
+    ldr r0, =__stack_top
+    mov sp, r0
+
+    // Enable the FPU (CP10 and CP11 in CPACR), used by the code generated for the hard-float
+    // target.
+    ldr r0, =0xe000ed88
+    ldr r1, [r0]
+    orr r1, r1, #(0xf << 20)
+    str r1, [r0]
+    dsb
+    isb
+
+    ldr r0, =__bss_start
+    ldr r1, =__bss_end
+    movs r2, #0
+1:
+    cmp r0, r1
+    bhs 2f
+    str r2, [r0], #4
+    b 1b
+2:
+
+    .global __semihosting_main
+    .type __semihosting_main, %function
+    .thumb_func
+__semihosting_main:
+    movs r0, #0
+    movs r1, #0
+    bl main
+    b __semihosting_exit
+"#
+);
+
+/// Calls `main` again on a fresh stack, abandoning all the frames of the current one.
+pub(crate) fn restart() -> ! {
+    // SAFETY: nothing on the stack is used anymore, and `__semihosting_main` doesn't return.
+    unsafe {
+        asm!(
+            "mov sp, {stack_top}",
+            "b __semihosting_main",
+            stack_top = in(reg) core::ptr::addr_of!(__stack_top),
+            options(noreturn),
+        )
+    }
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Arm semihosting operations used by the harness.
+//!
+//! See the [semihosting specification](https://github.com/ARM-software/abi-aa/blob/main/semihosting/semihosting.rst)
+//! for the meaning of the operations and of their parameter blocks.
+
+use core::arch::asm;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(not(any(target_arch = "aarch64", all(target_arch = "arm", target_feature = "mclass"))))]
+compile_error!("semihosting is only supported on AArch64 and on M-profile Arm");
+
+const SYS_OPEN: usize = 0x01;
+const SYS_WRITE: usize = 0x05;
+const SYS_GET_CMDLINE: usize = 0x15;
+#[cfg(target_arch = "aarch64")]
+const SYS_EXIT: usize = 0x18;
+#[cfg(target_arch = "arm")]
+const SYS_EXIT_EXTENDED: usize = 0x20;
+
+const ADP_STOPPED_APPLICATION_EXIT: usize = 0x20026;
+
+/// Mode of `SYS_OPEN` equivalent to `fopen`'s `"w"`.
+const OPEN_MODE_WRITE: usize = 4;
+
+/// Handle of the console, opened the first time something is written to it.
+static CONSOLE: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+unsafe fn call(operation: usize, parameter: *const usize) -> usize {
+    let result;
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        asm!(
+            "hlt #0xf000",
+            inout("x0") operation => result,
+            in("x1") parameter,
+            options(nostack),
+        );
+    }
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        asm!(
+            "bkpt #0xab",
+            inout("r0") operation => result,
+            in("r1") parameter,
+            options(nostack),
+        );
+    }
+    result
+}
+
+/// The console of the host, which QEMU forwards to its standard output.
+pub(crate) struct Console;
+
+impl fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut handle = CONSOLE.load(Ordering::Relaxed);
+        if handle == usize::MAX {
+            let block = [":tt\0".as_ptr() as usize, OPEN_MODE_WRITE, 3];
+            handle = unsafe { call(SYS_OPEN, block.as_ptr()) };
+            if handle == usize::MAX {
+                return Err(fmt::Error);
+            }
+            CONSOLE.store(handle, Ordering::Relaxed);
+        }
+
+        let block = [handle, s.as_ptr() as usize, s.len()];
+        // The result is the number of bytes that were *not* written.
+        match unsafe { call(SYS_WRITE, block.as_ptr()) } {
+            0 => Ok(()),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+/// Writes the command line the program was started with to `buf`, returning its length.
+pub(crate) fn command_line(buf: &mut [u8]) -> Option<usize> {
+    let mut block = [buf.as_mut_ptr() as usize, buf.len()];
+    match unsafe { call(SYS_GET_CMDLINE, block.as_mut_ptr()) } {
+        0 => Some(block[1]),
+        _ => None,
+    }
+}
+
+/// Stops the program, reporting `status` as its exit code.
+///
+/// QEMU exits with this status, which is how the runner learns whether the tests passed.
+pub(crate) fn exit(status: i32) -> ! {
+    let block = [ADP_STOPPED_APPLICATION_EXIT, status as usize];
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        call(SYS_EXIT, block.as_ptr());
+    }
+    // On AArch32 `SYS_EXIT` only takes the reason, and the status is reported through the
+    // extended variant instead.
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        call(SYS_EXIT_EXTENDED, block.as_ptr());
+    }
+    // If the host didn't stop the program, hang until the runner times out and reports a failure.
+    loop {}
+}
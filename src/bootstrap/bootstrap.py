@@ -46,7 +46,7 @@ def eprint(*args, **kwargs):
     print(*args, **kwargs)
 
 
-def get(base, url, path, checksums, verbose=False):
+def get(base, url, path, checksums, verbose=False, mirrors=(), aws_profile=None):
     with tempfile.NamedTemporaryFile(delete=False) as temp_file:
         temp_path = temp_file.name
 
@@ -68,9 +68,16 @@ def get(base, url, path, checksums, verbose=False):
                     eprint("ignoring already-download file",
                         path, "due to failed verification")
                 os.unlink(path)
-        download(temp_path, "{}/{}".format(base, url), True, verbose)
-        if not verify(temp_path, sha256, verbose):
-            raise RuntimeError("failed verification")
+        # Ferrocene addition: try the mirrors configured in `ferrocene.artifact-mirrors` before
+        # the original location of the artifact.
+        for mirror in mirrors:
+            if download_from_mirror(temp_path, mirror, "{}/{}".format(base, url), sha256,
+                                    aws_profile, verbose):
+                break
+        else:
+            download(temp_path, "{}/{}".format(base, url), True, verbose)
+            if not verify(temp_path, sha256, verbose):
+                raise RuntimeError("failed verification")
         if verbose:
             eprint("moving {} to {}".format(temp_path, path))
         shutil.move(temp_path, path)
@@ -130,6 +137,51 @@ def _download(path, url, probably_big, verbose, exception):
             raise
 
 
+def artifact_key(url):
+    """Return the path identifying an artifact in the mirrors, ignoring the
+    protocol and the host (or the bucket for S3)
+
+    >>> artifact_key("https://static.rust-lang.org/dist/2023-10-04/rustc.tar.xz")
+    'dist/2023-10-04/rustc.tar.xz'
+    >>> artifact_key("s3://ferrocene-ci-artifacts/ferrocene/dist/abc/rustc.tar.xz")
+    'ferrocene/dist/abc/rustc.tar.xz'
+    >>> artifact_key("/local/dist/rustc.tar.xz") is None
+    True
+    """
+    if "://" not in url:
+        return None
+    host_and_path = url.split("://", 1)[1]
+    if "/" not in host_and_path:
+        return None
+    return host_and_path.split("/", 1)[1]
+
+
+def download_from_mirror(path, mirror, url, sha256, aws_profile, verbose):
+    """Try downloading the artifact at `url` from a mirror, returning whether
+    the artifact was found in the mirror and its checksum is valid"""
+    key = artifact_key(url)
+    if key is None:
+        return False
+    source = "{}/{}".format(mirror.rstrip("/"), key)
+    try:
+        if source.startswith("s3://"):
+            profile = ["--profile", aws_profile] if aws_profile is not None else []
+            run(["aws", "s3", "cp"] + profile + [source, path], verbose=verbose, exception=True)
+        elif "://" in source:
+            _download(path, source, True, verbose, True)
+        else:
+            if verbose:
+                eprint("copying", source)
+            shutil.copyfile(source, path)
+    except (subprocess.CalledProcessError, OSError, RuntimeError) as err:
+        eprint("warning: failed to download {} from mirror {}: {}".format(key, mirror, err))
+        return False
+    if not verify(path, sha256, verbose):
+        eprint("warning: ignoring {} from mirror {} due to failed verification".format(key, mirror))
+        return False
+    return True
+
+
 def verify(path, expected, verbose):
     """Check if the sha256 sum of the given path is valid"""
     if verbose:
@@ -442,6 +494,8 @@ class DownloadInfo:
         checksums_sha256,
         pattern,
         verbose,
+        mirrors,
+        aws_profile,
     ):
         self.base_download_url = base_download_url
         self.download_path = download_path
@@ -451,6 +505,8 @@ class DownloadInfo:
         self.checksums_sha256 = checksums_sha256
         self.pattern = pattern
         self.verbose = verbose
+        self.mirrors = mirrors
+        self.aws_profile = aws_profile
 
 def download_component(download_info):
     if not os.path.exists(download_info.tarball_path):
@@ -460,6 +516,8 @@ def download_component(download_info):
             download_info.tarball_path,
             download_info.checksums_sha256,
             verbose=download_info.verbose,
+            mirrors=download_info.mirrors,
+            aws_profile=download_info.aws_profile,
         )
 
 def unpack_component(download_info):
@@ -508,6 +566,7 @@ class RustBuild(object):
         self.use_locked_deps = self.get_toml('locked-deps', 'build') == 'true'
 
         build_dir = args.build_dir or self.get_toml('build-dir', 'build') or 'build'
+        self.artifact_mirrors = self.get_toml_list('artifact-mirrors', 'ferrocene')
         self.build_dir = os.path.abspath(build_dir)
 
         with open(os.path.join(self.rust_root, "src", "stage0.json")) as f:
@@ -582,6 +641,8 @@ class RustBuild(object):
                     checksums_sha256=self.checksums_sha256,
                     pattern=pattern,
                     verbose=self.verbose,
+                    mirrors=self.artifact_mirrors,
+                    aws_profile=self.get_toml('aws-profile', 'ferrocene'),
                 )
                 for filename, pattern in tarballs_to_download
             ]
@@ -629,6 +690,8 @@ class RustBuild(object):
                 tarball,
                 self.checksums_sha256,
                 verbose=self.verbose,
+                mirrors=self.artifact_mirrors,
+                aws_profile=self.get_toml('aws-profile', 'ferrocene'),
             )
         unpack(tarball, tarball_suffix, self.bin_root(), match=pattern, verbose=self.verbose)
 
@@ -823,6 +886,70 @@ class RustBuild(object):
                     return RustBuild.get_string(value) or value.strip()
         return None
 
+    def get_toml_list(self, key, section=None):
+        """Returns the strings of a list in config.toml, otherwise returns an
+        empty list
+
+        >>> rb = RustBuild()
+        >>> rb.config_toml = '[a]\\nkey = ["one", "two"]'
+        >>> rb.get_toml_list('key', 'a')
+        ['one', 'two']
+        >>> rb.get_toml_list('key', 'b')
+        []
+
+        The list can span multiple lines, with comments between the items
+
+        >>> rb.config_toml = '[a]\\nkey = [\\n  "one", # first\\n  \\'two\\',\\n]'
+        >>> rb.get_toml_list('key', 'a')
+        ['one', 'two']
+
+        Values that are not lists of strings are rejected
+
+        >>> rb.config_toml = '[a]\\nkey = "one"'
+        >>> rb.get_toml_list('key', 'a')
+        Traceback (most recent call last):
+        ...
+        Exception: `a.key` in config.toml must be a list of strings
+        """
+        name = key if section is None else "{}.{}".format(section, key)
+        error = Exception("`{}` in config.toml must be a list of strings".format(name))
+
+        cur_section = None
+        lines = iter(self.config_toml.splitlines())
+        for line in lines:
+            section_match = re.match(r'^\s*\[(.*)\]\s*$', line)
+            if section_match is not None:
+                cur_section = section_match.group(1)
+
+            match = re.match(r'^{}\s*=(.*)$'.format(key), line)
+            if match is None or (section is not None and section != cur_section):
+                continue
+            rest = match.group(1).strip()
+            if not rest.startswith("["):
+                raise error
+            rest = rest[1:]
+            items = []
+            while True:
+                rest = rest.strip()
+                if rest == "" or rest.startswith("#"):
+                    # The list continues on the next line.
+                    rest = next(lines, None)
+                    if rest is None:
+                        raise error
+                    continue
+                if rest.startswith("]"):
+                    return items
+                item = re.match(r'"([^"]*)"|\'([^\']*)\'', rest)
+                if item is None:
+                    raise error
+                items.append(item.group(1) if item.group(1) is not None else item.group(2))
+                rest = rest[item.end():].strip()
+                if rest.startswith(","):
+                    rest = rest[1:]
+                elif rest != "" and not rest.startswith("#") and not rest.startswith("]"):
+                    raise error
+        return []
+
     def cargo(self):
         """Return config path for cargo"""
         return self.program_config('cargo')
@@ -65,6 +65,40 @@ class VerifyTestCase(unittest.TestCase):
         self.assertFalse(bootstrap.verify(self.bad_src, self.expected, False))
 
 
+class MirrorsTestCase(unittest.TestCase):
+    """Test Case for the downloads from the artifact mirrors"""
+    def setUp(self):
+        self.container = tempfile.mkdtemp()
+        self.key = "dist/2023-10-04/rustc.tar.xz"
+        content = "Hello world"
+        self.checksums = {self.key: hashlib.sha256(content.encode("utf-8")).hexdigest()}
+
+        self.bad_mirror = os.path.join(self.container, "bad")
+        self.good_mirror = os.path.join(self.container, "good")
+        self.empty_mirror = os.path.join(self.container, "empty")
+        for mirror, mirror_content in [(self.bad_mirror, "Hello!"), (self.good_mirror, content)]:
+            os.makedirs(os.path.join(mirror, "dist", "2023-10-04"))
+            with open(os.path.join(mirror, self.key), "w") as f:
+                f.write(mirror_content)
+
+    def tearDown(self):
+        rmtree(self.container)
+
+    def test_fallback_between_mirrors(self):
+        """Mirrors missing the artifact or with an invalid one should be skipped"""
+        dest = os.path.join(self.container, "rustc.tar.xz")
+        mirrors = [self.empty_mirror, self.bad_mirror, self.good_mirror]
+        bootstrap.get("https://static.invalid", self.key, dest, self.checksums, mirrors=mirrors)
+        self.assertTrue(bootstrap.verify(dest, self.checksums[self.key], False))
+
+    def test_multi_line_mirrors(self):
+        """Mirrors should be read from config.toml even when the list spans multiple lines"""
+        config_toml = '[ferrocene]\nartifact-mirrors = [\n    "{}", # local\n    "{}",\n]\n'
+        build = bootstrap.RustBuild(
+            config_toml=config_toml.format(self.bad_mirror, self.good_mirror))
+        self.assertEqual(build.artifact_mirrors, [self.bad_mirror, self.good_mirror])
+
+
 class ProgramOutOfDate(unittest.TestCase):
     """Test if a program is out of date"""
     def setUp(self):
@@ -103,6 +103,16 @@ fn main() {
             cmd.arg("-C").arg("panic=abort");
         }
 
+        // When testing alloc without its infallible allocating APIs, only its unit tests are built
+        // with them compiled out: the rest of the standard library, including the alloc linked by
+        // the test harness, needs them.
+        if crate_name == Some("alloc")
+            && env::var_os("RUSTC_ALLOC_NO_GLOBAL_OOM_HANDLING").is_some()
+            && args.iter().any(|arg| arg == "--test")
+        {
+            cmd.arg("--cfg").arg("no_global_oom_handling");
+        }
+
         // `-Ztls-model=initial-exec` must not be applied to proc-macros, see
         // issue https://github.com/rust-lang/rust/issues/100530
         if env::var("RUSTC_TLS_MODEL_INITIAL_EXEC").is_ok()
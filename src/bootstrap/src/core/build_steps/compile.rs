@@ -400,6 +400,11 @@ pub fn std_cargo(builder: &Builder<'_>, target: TargetSelection, stage: u32, car
             .arg(builder.src.join("library/alloc/Cargo.toml"))
             .arg("--features")
             .arg(features);
+
+        // The test harness of targets tested with semihosting has to be in their sysroot.
+        if builder.qemu_semihosting(target) {
+            cargo.args(&["-p", "test_semihosting"]);
+        }
     } else {
         features += &builder.std_features(target);
         features.push_str(compiler_builtins_c_feature);
@@ -25,12 +25,16 @@
 use crate::core::config::flags::get_completion;
 use crate::core::config::flags::Subcommand;
 use crate::core::config::TargetSelection;
+use crate::ferrocene::differential::BehaviorLog;
+use crate::ferrocene::resume::ResumeLog;
 use crate::utils;
 use crate::utils::cache::{Interned, INTERNER};
 use crate::utils::helpers::{
     self, add_link_lib_path, dylib_path, dylib_path_var, output, t, up_to_date,
 };
-use crate::utils::render_tests::{add_flags_and_try_run_tests, try_run_tests};
+use crate::utils::render_tests::{
+    add_flags_and_try_run_tests, try_run_tests, try_run_tests_with_retries_log,
+};
 use crate::{envify, CLang, DocTests, GitRepo, Mode};
 
 const ADB_TEST_DIR: &str = "/data/local/tmp/work";
@@ -331,12 +335,13 @@ fn run(self, builder: &Builder<'_>) {
                 target: self.host.triple.to_string(),
                 host: self.host.triple.to_string(),
                 stage: self.stage,
+                codegen_backend: None,
             },
             builder,
         );
 
         let _time = helpers::timeit(&builder);
-        add_flags_and_try_run_tests(builder, &mut cargo);
+        add_flags_and_try_run_tests(builder, &mut cargo, None, None);
     }
 }
 
@@ -1348,6 +1353,12 @@ fn run(self, builder: &Builder<'_>) {
 
 default_test!(Assembly { path: "tests/assembly", mode: "assembly", suite: "assembly" });
 
+default_test!(ObjectCheck {
+    path: "tests/object-check",
+    mode: "object-check",
+    suite: "object-check"
+});
+
 default_test!(CoverageMap {
     path: "tests/coverage-map",
     mode: "coverage-map",
@@ -1589,7 +1600,7 @@ fn run(self, builder: &Builder<'_>) {
             builder.sysroot(compiler).to_path_buf()
         };
         cmd.arg("--sysroot-base").arg(sysroot);
-        cmd.arg("--stage-id").arg(stage_id);
+        cmd.arg("--stage-id").arg(&stage_id);
         cmd.arg("--suite").arg(suite);
         cmd.arg("--mode").arg(mode);
         cmd.arg("--target").arg(target.rustc_target_arg());
@@ -1655,6 +1666,9 @@ fn run(self, builder: &Builder<'_>) {
         let mut targetflags = flags;
         targetflags.push(format!("-Lnative={}", builder.test_helpers_out(target).display()));
         targetflags.extend(builder.lld_flags(target));
+        if builder.qemu_semihosting(target) {
+            targetflags.extend(builder.qemu_semihosting_flags(target));
+        }
         for flag in targetflags {
             cmd.arg("--target-rustcflags").arg(flag);
         }
@@ -1698,11 +1712,56 @@ fn run(self, builder: &Builder<'_>) {
             cmd.arg(&exclude);
         }
 
+        cmd.arg("--flaky-retries").arg(builder.config.ferrocene_flaky_test_retries.to_string());
+        let flaky_retries_log =
+            testdir(builder, compiler.host).join(suite).join("flaky-retries.json");
+        cmd.arg("--flaky-retries-log").arg(&flaky_retries_log);
+
+        if let Some(shard) = builder.config.cmd.shard() {
+            cmd.arg("--shard").arg(shard.to_string());
+        }
+        // The tests run with and without the compare mode have different names, so they can share
+        // the same log.
+        let resume_log = ResumeLog::open(
+            builder,
+            testdir(builder, compiler.host)
+                .join(suite)
+                .join(format!("resume-{mode}-{stage_id}.json")),
+        );
+        if builder.config.cmd.resume() {
+            cmd.arg("--resume-log").arg(resume_log.path());
+        }
+
         for ignored in crate::ferrocene::ignored_tests_for_suite(builder, self.target, self.path) {
-            cmd.arg("--skip");
+            if builder.config.ferrocene_check_ignored_tests {
+                cmd.arg("--ferrocene-expected-failure");
+            } else {
+                cmd.arg("--skip");
+            }
             cmd.arg(&ignored);
         }
 
+        // Only the ui tests are built with both backends, as they are the ones executing the
+        // programs they build without checking backend-specific properties of them.
+        if builder.config.ferrocene_differential_testing && suite == "ui" {
+            cmd.arg("--differential-codegen-backend").arg("cranelift");
+            let skipped = crate::ferrocene::differential_skipped_tests_for_suite(
+                builder,
+                self.target,
+                self.path,
+            );
+            for test in skipped {
+                cmd.arg("--differential-skip").arg(builder.src.join(test));
+            }
+        }
+
+        // The instrumented compiler writes the coverage of each suite in a separate directory, so
+        // that the coverage report can be broken down by suite.
+        if builder.config.ferrocene_compiler_coverage {
+            let dir = crate::ferrocene::compiler_coverage_dir(builder, compiler.host);
+            cmd.env("LLVM_PROFILE_FILE", dir.join("suites").join(suite).join("%9m.profraw"));
+        }
+
         // Get paths from cmd args
         let paths = match &builder.config.cmd {
             Subcommand::Test { .. } => &builder.config.paths[..],
@@ -1824,9 +1883,12 @@ fn run(self, builder: &Builder<'_>) {
                 .arg("");
         }
 
-        if builder.remote_tested(target) {
+        if builder.remote_tested(target) || builder.qemu_semihosting(target) {
             cmd.arg("--remote-test-client").arg(builder.tool_exe(Tool::RemoteTestClient));
         }
+        if builder.qemu_semihosting(target) {
+            cmd.arg("--qemu-semihosting");
+        }
 
         // Running a C compiler on MSVC requires a few env vars to be set, to be
         // sure to set them here.
@@ -1901,7 +1963,7 @@ fn run(self, builder: &Builder<'_>) {
             compiler.host,
             target,
         );
-        try_run_tests(builder, &mut cmd, false);
+        try_run_tests_with_retries_log(builder, &mut cmd, &flaky_retries_log, &resume_log);
 
         if let Some(compare_mode) = compare_mode {
             cmd.arg("--compare-mode").arg(compare_mode);
@@ -1924,7 +1986,7 @@ fn run(self, builder: &Builder<'_>) {
                 suite, mode, compare_mode, &compiler.host, target
             ));
             let _time = helpers::timeit(&builder);
-            try_run_tests(builder, &mut cmd, false);
+            try_run_tests_with_retries_log(builder, &mut cmd, &flaky_retries_log, &resume_log);
         }
     }
 }
@@ -2248,12 +2310,49 @@ pub(crate) fn run_cargo_test<'a>(
     target: TargetSelection,
     builder: &Builder<'_>,
 ) -> bool {
-    let mut cargo =
-        prepare_cargo_test(cargo, libtest_args, crates, primary_crate, compiler, target, builder);
-    let _time = helpers::timeit(&builder);
-    let _group = description.into().and_then(|what| {
-        builder.msg_sysroot_tool(Kind::Test, compiler.stage, what, compiler.host, target)
-    });
+    run_cargo_test_with_backend(
+        cargo,
+        libtest_args,
+        crates,
+        primary_crate,
+        description,
+        compiler,
+        target,
+        builder,
+        None,
+        None,
+    )
+}
+
+/// Like [`run_cargo_test`], with the tests built by `codegen_backend` rather than the default one,
+/// and with the outcome and the output of each test appended to `behaviors` if present.
+fn run_cargo_test_with_backend<'a>(
+    cargo: impl Into<Command>,
+    libtest_args: &[&str],
+    crates: &[Interned<String>],
+    primary_crate: &str,
+    description: impl Into<Option<&'a str>>,
+    compiler: Compiler,
+    target: TargetSelection,
+    builder: &Builder<'_>,
+    codegen_backend: Option<&str>,
+    behaviors: Option<&BehaviorLog>,
+) -> bool {
+    // Libtest has no way to partition the tests of a crate, so whole crates are assigned to the
+    // shards instead.
+    if let Some(shard) = builder.config.cmd.shard() {
+        let name = crates.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(",");
+        if !shard.contains(&name) {
+            builder.info(&format!("Skipping tests of {name}, not part of shard {shard}"));
+            return true;
+        }
+    }
+
+    let description = description.into();
+    let resume_log = ResumeLog::open(
+        builder,
+        suite_log_path(builder, "resume", compiler, target, description.unwrap_or(primary_crate)),
+    );
 
     #[cfg(feature = "build-metrics")]
     builder.metrics.begin_test_suite(
@@ -2262,10 +2361,70 @@ pub(crate) fn run_cargo_test<'a>(
             target: target.triple.to_string(),
             host: compiler.host.triple.to_string(),
             stage: compiler.stage,
+            codegen_backend: codegen_backend.map(|backend| backend.to_string()),
         },
         builder,
     );
-    add_flags_and_try_run_tests(builder, &mut cargo)
+    #[cfg(not(feature = "build-metrics"))]
+    let _ = codegen_backend;
+
+    // Libtest can't skip the tests that passed without also skipping the tests containing their
+    // name, so only the crates whose tests all ran successfully are skipped on resume.
+    if resume_log.is_completed() {
+        builder.info(&format!(
+            "Skipping tests of {}, they passed in the run being resumed",
+            description.unwrap_or(primary_crate).trim()
+        ));
+        #[cfg(feature = "build-metrics")]
+        for test in resume_log.previous_tests() {
+            builder.metrics.record_test(test.clone(), builder);
+        }
+        return true;
+    }
+
+    let mut cargo =
+        prepare_cargo_test(cargo, libtest_args, crates, primary_crate, compiler, target, builder);
+    let _time = helpers::timeit(&builder);
+    let _group = description.and_then(|what| {
+        builder.msg_sysroot_tool(Kind::Test, compiler.stage, what, compiler.host, target)
+    });
+
+    let passed = add_flags_and_try_run_tests(builder, &mut cargo, Some(&resume_log), behaviors);
+    if passed {
+        resume_log.complete();
+    }
+    passed
+}
+
+/// Path of a log of the tests of a suite, in the `kind` subdirectory of the test directory.
+fn suite_log_path(
+    builder: &Builder<'_>,
+    kind: &str,
+    compiler: Compiler,
+    target: TargetSelection,
+    description: &str,
+) -> PathBuf {
+    testdir(builder, compiler.host).join(kind).join(format!(
+        "stage{}-{}-{}.json",
+        compiler.stage,
+        target,
+        suite_log_name(description)
+    ))
+}
+
+/// Turns the description of a test suite into a file name, for example `{core, alloc}` into
+/// `core-alloc`.
+fn suite_log_name(description: &str) -> String {
+    let mut name = String::new();
+    for part in description.split(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '-') {
+        if !part.is_empty() {
+            if !name.is_empty() {
+                name.push('-');
+            }
+            name.push_str(part);
+        }
+    }
+    name
 }
 
 /// Given a `cargo test` subcommand, pass it the appropriate test flags given a `builder`.
@@ -2337,6 +2496,15 @@ fn prepare_cargo_test(
         let node = builder.config.nodejs.as_ref().expect("nodejs not configured");
         let runner = format!("{} {}/src/etc/wasm32-shim.js", node.display(), builder.src.display());
         cargo.env(format!("CARGO_TARGET_{}_RUNNER", envify(&target.triple)), &runner);
+    } else if builder.qemu_semihosting(target) {
+        cargo.env(
+            format!("CARGO_TARGET_{}_RUNNER", envify(&target.triple)),
+            format!(
+                "{} run-semihosting {}",
+                builder.tool_exe(Tool::RemoteTestClient).display(),
+                target.triple
+            ),
+        );
     } else if builder.remote_tested(target) {
         cargo.env(
             format!("CARGO_TARGET_{}_RUNNER", envify(&target.triple)),
@@ -2399,38 +2567,163 @@ fn run(self, builder: &Builder<'_>) {
         // we're working with automatically.
         let compiler = builder.compiler_for(compiler.stage, compiler.host, target);
 
-        let mut cargo =
-            builder.cargo(compiler, mode, SourceType::InTree, target, builder.kind.as_str());
-        match mode {
-            Mode::Std => {
-                compile::std_cargo(builder, target, compiler.stage, &mut cargo);
-                // `std_cargo` actually does the wrong thing: it passes `--sysroot build/host/stage2`,
-                // but we want to use the force-recompile std we just built in `build/host/stage2-test-sysroot`.
-                // Override it.
-                if builder.download_rustc() && compiler.stage > 0 {
-                    let sysroot = builder
-                        .out
-                        .join(compiler.host.triple)
-                        .join(format!("stage{}-test-sysroot", compiler.stage));
-                    cargo.env("RUSTC_SYSROOT", sysroot);
+        let make_cargo = || {
+            let mut cargo =
+                builder.cargo(compiler, mode, SourceType::InTree, target, builder.kind.as_str());
+            match mode {
+                Mode::Std => {
+                    compile::std_cargo(builder, target, compiler.stage, &mut cargo);
+                    // `std_cargo` actually does the wrong thing: it passes
+                    // `--sysroot build/host/stage2`, but we want to use the force-recompile std we
+                    // just built in `build/host/stage2-test-sysroot`. Override it.
+                    if builder.download_rustc() && compiler.stage > 0 {
+                        let sysroot = builder
+                            .out
+                            .join(compiler.host.triple)
+                            .join(format!("stage{}-test-sysroot", compiler.stage));
+                        cargo.env("RUSTC_SYSROOT", sysroot);
+                    }
                 }
-            }
-            Mode::Rustc => {
-                compile::rustc_cargo(builder, &mut cargo, target, compiler.stage);
-            }
-            _ => panic!("can only test libraries"),
+                Mode::Rustc => {
+                    compile::rustc_cargo(builder, &mut cargo, target, compiler.stage);
+                }
+                _ => panic!("can only test libraries"),
+            };
+            cargo
         };
 
-        run_cargo_test(
-            cargo,
-            if target.contains("ferrocenecoretest") { &["--test-threads", "1"] } else { &[] },
+        let libtest_args: &[&str] =
+            if target.contains("ferrocenecoretest") || builder.qemu_semihosting(target) {
+                &["--test-threads", "1"]
+            } else {
+                &[]
+            };
+        // With differential testing the outcome and the output of each test are logged, to be
+        // compared with the ones of the tests built by Cranelift.
+        let differential = mode == Mode::Std && builder.config.ferrocene_differential_testing;
+        let description = crate_description(&self.crates);
+        let behaviors = differential.then(|| {
+            BehaviorLog::open(
+                builder,
+                suite_log_path(builder, "differential", compiler, target, &description),
+            )
+        });
+        let mut llvm_libtest_args = libtest_args.to_vec();
+        if differential {
+            llvm_libtest_args.push("--show-output");
+        }
+        let passed = run_cargo_test_with_backend(
+            make_cargo(),
+            &llvm_libtest_args,
             &self.crates,
             &self.crates[0],
-            &*crate_description(&self.crates),
+            &*description,
             compiler,
             target,
             builder,
+            None,
+            behaviors.as_ref(),
         );
+
+        // The unit tests of alloc also run without its infallible allocating APIs, which are
+        // compiled out by `--cfg no_global_oom_handling`. Doc tests can't run this way, as they are
+        // built against the standard library.
+        if passed
+            && mode == Mode::Std
+            && self.crates.iter().any(|krate| krate == "alloc")
+            && builder.doc_tests != DocTests::Only
+        {
+            let mut cargo = make_cargo();
+            cargo.env("RUSTC_ALLOC_NO_GLOBAL_OOM_HANDLING", "1");
+            // The variable isn't tracked by Cargo, so the artifacts have to be kept apart.
+            cargo.env(
+                "CARGO_TARGET_DIR",
+                builder.stage_out(compiler, mode).join("no-global-oom-handling"),
+            );
+            if builder.doc_tests == DocTests::Yes {
+                cargo.arg("--lib");
+            }
+            let alloc = INTERNER.intern_str("alloc");
+            run_cargo_test(
+                cargo,
+                libtest_args,
+                &[alloc],
+                &alloc,
+                "alloc (no_global_oom_handling)",
+                compiler,
+                target,
+                builder,
+            );
+        }
+
+        // The standard library is built from source with the tests, so with differential testing
+        // the whole library is built a second time with Cranelift, and each test has to behave
+        // the same as it did with LLVM.
+        if let (true, Some(behaviors)) = (passed, &behaviors) {
+            let mut cargo = make_cargo();
+            cargo.rustflag("-Zcodegen-backend=cranelift");
+            // Keep the artifacts apart from the LLVM ones, to avoid rebuilding both every time.
+            cargo.env("CARGO_TARGET_DIR", builder.stage_out(compiler, mode).join("cranelift"));
+
+            // Cranelift doesn't support unwinding, so the tests that panicked with LLVM would
+            // abort the whole test binary. Skip filters match substrings of the test names, which
+            // can only skip more tests than needed.
+            let mut skipped =
+                crate::ferrocene::differential_skipped_tests_for_suite(builder, target, "library");
+            skipped.extend(behaviors.unwinding_tests());
+            let mut libtest_args = llvm_libtest_args;
+            for test in &skipped {
+                libtest_args.extend(["--skip", test.as_str()]);
+            }
+
+            builder.info(
+                "Running the tests again with the Cranelift codegen backend, differences are \
+                 potential miscompilations",
+            );
+            let cranelift_description = format!("{description} (cranelift)");
+            let cranelift_behaviors = BehaviorLog::open(
+                builder,
+                suite_log_path(builder, "differential", compiler, target, &cranelift_description),
+            );
+            run_cargo_test_with_backend(
+                cargo,
+                &libtest_args,
+                &self.crates,
+                &self.crates[0],
+                &*cranelift_description,
+                compiler,
+                target,
+                builder,
+                Some("cranelift"),
+                Some(&cranelift_behaviors),
+            );
+
+            let differences = behaviors.differences(&cranelift_behaviors, "cranelift");
+            if !differences.is_empty() {
+                let paths = self
+                    .crates
+                    .iter()
+                    .map(|krate| builder.crates[krate].local_path(builder).display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                for difference in &differences {
+                    println!("\npotential miscompilation: {difference}");
+                }
+                println!(
+                    "\nto reproduce, run each test alone with `./x test --stage {} {paths} \
+                     --test-args '<test name> --exact'` and `ferrocene.differential-testing` \
+                     enabled",
+                    compiler.stage
+                );
+                if builder.fail_fast {
+                    crate::exit!(1);
+                }
+                builder.delayed_failures.borrow_mut().push(format!(
+                    "tests of {} behave differently with Cranelift",
+                    description.trim()
+                ));
+            }
+        }
     }
 }
 
@@ -287,6 +287,11 @@ fn run(self, builder: &Builder<'_>) -> PathBuf {
     FerroceneTraceabilityMatrix, "ferrocene/tools/traceability-matrix", "traceability-matrix";
     FerroceneDocumentSignatures, "ferrocene/tools/document-signatures", "document-signatures";
     FerroceneGenerateTarball, "ferrocene/tools/generate-tarball", "generate-tarball";
+    FerroceneGenerateSbom, "ferrocene/tools/generate-sbom", "generate-sbom";
+    FerroceneMergeMetrics, "ferrocene/tools/merge-metrics", "merge-metrics";
+    FerroceneTestOutcomesDb, "ferrocene/tools/test-outcomes-db", "test-outcomes-db";
+    FerroceneCompilerCoverage, "ferrocene/tools/compiler-coverage", "compiler-coverage";
+    FerroceneApiDiff, "src/tools/api-diff", "api-diff";
 
     Rustbook, "src/tools/rustbook", "rustbook";
     UnstableBookGen, "src/tools/unstable-book-gen", "unstable-book-gen";
@@ -730,6 +730,11 @@ macro_rules! describe {
             ),
             Kind::Test => describe!(
                 crate::ferrocene::test::TraceabilityMatrixTool,
+                crate::ferrocene::test::MergeMetricsTool,
+                crate::ferrocene::test::TestOutcomesDbTool,
+                crate::ferrocene::test::CompilerCoverageTool,
+                crate::ferrocene::test::ApiDiffTool,
+                crate::ferrocene::test::GenerateSbomTool,
                 crate::ferrocene::test::SelfTest,
                 crate::ferrocene::test::CheckDocumentSignatures,
                 crate::ferrocene::test::GenerateTarball,
@@ -744,6 +749,7 @@ macro_rules! describe {
                 test::Codegen,
                 test::CodegenUnits,
                 test::Assembly,
+                test::ObjectCheck,
                 test::Incremental,
                 test::Debuginfo,
                 test::UiFullDeps,
@@ -867,6 +873,7 @@ macro_rules! describe {
                 crate::ferrocene::dist::SelfTest,
                 crate::ferrocene::dist::TestOutcomes,
                 crate::ferrocene::partners::oxidos::DistOxidOs,
+                crate::ferrocene::dist::Evidence,
             ),
             Kind::Install => describe!(
                 install::Docs,
@@ -883,6 +890,10 @@ macro_rules! describe {
             ),
             Kind::Run => describe!(
                 crate::ferrocene::run::TraceabilityMatrix,
+                crate::ferrocene::run::MergeMetrics,
+                crate::ferrocene::run::TestOutcomesDb,
+                crate::ferrocene::run::CompilerCoverage,
+                crate::ferrocene::run::ApiDiff,
                 run::ExpandYamlAnchors,
                 run::BuildManifest,
                 run::BumpStage0,
@@ -1928,6 +1939,17 @@ pub fn cargo(
             rustflags.arg("-Wrustc::internal");
         }
 
+        // Ferrocene: instrument the compiler crates built by the stage 1 compiler, to measure how
+        // much of the stage 2 compiler the test suites exercise. Profiles of the instrumented
+        // compiler being used during the build are kept apart from the ones of the test suites.
+        if self.config.ferrocene_compiler_coverage {
+            if mode == Mode::Rustc && compiler.stage >= 1 {
+                rustflags.arg("-Cinstrument-coverage");
+            }
+            let dir = crate::ferrocene::compiler_coverage_dir(self, compiler.host);
+            cargo.env("LLVM_PROFILE_FILE", dir.join("build").join("%9m.profraw"));
+        }
+
         // Throughout the build Cargo can execute a number of build scripts
         // compiling C/C++ code and we need to pass compilers, archivers, flags, etc
         // obtained previously to those build scripts.
@@ -2113,9 +2135,16 @@ pub fn cargo(
             cargo.env("RUSTFLAGS", &rustc_args.join(" "));
         }
 
-        if target.contains("ferrocenecoretest") {
+        if target.contains("ferrocenecoretest") || self.qemu_semihosting(target) {
             rustflags.arg("-Zpanic-abort-tests");
         }
+        if self.qemu_semihosting(target) {
+            // Tests are linked to the `no_std` harness in `library/test_semihosting`, which is
+            // built into the sysroot of these targets.
+            for flag in self.qemu_semihosting_flags(target) {
+                rustflags.arg(&flag);
+            }
+        }
 
         Cargo { command: cargo, rustflags, rustdocflags, hostflags, allow_features }
     }
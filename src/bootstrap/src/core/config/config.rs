@@ -306,6 +306,13 @@ pub struct Config {
     pub ferrocene_tarball_signing_kms_key_arn: Option<String>,
     pub ferrocene_document_signatures_s3_bucket: String,
     pub ferrocene_ignore_document_signatures: bool,
+    pub ferrocene_check_ignored_tests: bool,
+    pub ferrocene_flaky_test_retries: usize,
+    pub ferrocene_compiler_coverage: bool,
+    pub ferrocene_differential_testing: bool,
+    pub ferrocene_sbom: bool,
+    pub ferrocene_artifact_mirrors: Vec<String>,
+    pub ferrocene_trusted_artifact_mirrors: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -551,6 +558,7 @@ pub struct Target {
     pub musl_libdir: Option<PathBuf>,
     pub wasi_root: Option<PathBuf>,
     pub qemu_rootfs: Option<PathBuf>,
+    pub qemu_semihosting: bool,
     pub no_std: bool,
 }
 
@@ -1076,6 +1084,7 @@ struct TomlTarget {
         musl_libdir: Option<String> = "musl-libdir",
         wasi_root: Option<String> = "wasi-root",
         qemu_rootfs: Option<String> = "qemu-rootfs",
+        qemu_semihosting: Option<bool> = "qemu-semihosting",
         no_std: Option<bool> = "no-std",
     }
 }
@@ -1089,6 +1098,13 @@ struct Ferrocene {
         tarball_signing_kms_key_arn: Option<String> = "tarball-signing-kms-key-arn",
         document_signatures_s3_bucket: Option<String> = "document-signatures-s3-bucket",
         ignore_document_signatures: Option<bool> = "ignore-document-signatures",
+        check_ignored_tests: Option<bool> = "check-ignored-tests",
+        flaky_test_retries: Option<usize> = "flaky-test-retries",
+        compiler_coverage: Option<bool> = "compiler-coverage",
+        differential_testing: Option<bool> = "differential-testing",
+        sbom: Option<bool> = "sbom",
+        artifact_mirrors: Option<Vec<String>> = "artifact-mirrors",
+        trusted_artifact_mirrors: Option<bool> = "trusted-artifact-mirrors",
     }
 }
 
@@ -1113,6 +1129,8 @@ pub fn default_opts() -> Config {
         config.bindir = "bin".into();
         config.dist_include_mingw_linker = true;
         config.dist_compression_profile = "fast".into();
+        config.ferrocene_flaky_test_retries = 2;
+        config.ferrocene_check_ignored_tests = true;
 
         config.stdout_is_tty = std::io::stdout().is_terminal();
         config.stderr_is_tty = std::io::stderr().is_terminal();
@@ -1660,6 +1678,7 @@ fn get_table(option: &str) -> Result<TomlConfig, toml::de::Error> {
                 target.musl_libdir = cfg.musl_libdir.map(PathBuf::from);
                 target.wasi_root = cfg.wasi_root.map(PathBuf::from);
                 target.qemu_rootfs = cfg.qemu_rootfs.map(PathBuf::from);
+                target.qemu_semihosting = cfg.qemu_semihosting.unwrap_or(false);
                 target.sanitizers = cfg.sanitizers;
                 target.profiler = cfg.profiler;
                 target.rpath = cfg.rpath;
@@ -1684,6 +1703,22 @@ fn get_table(option: &str) -> Result<TomlConfig, toml::de::Error> {
                 .unwrap_or_else(|| "ferrocene-document-signatures".into());
             config.ferrocene_ignore_document_signatures =
                 f.ignore_document_signatures.unwrap_or(false);
+            set(&mut config.ferrocene_check_ignored_tests, f.check_ignored_tests);
+            set(&mut config.ferrocene_flaky_test_retries, f.flaky_test_retries);
+            config.ferrocene_compiler_coverage = f.compiler_coverage.unwrap_or(false);
+            config.ferrocene_differential_testing = f.differential_testing.unwrap_or(false);
+            config.ferrocene_sbom = f.sbom.unwrap_or(false);
+            config.ferrocene_artifact_mirrors = f.artifact_mirrors.unwrap_or_default();
+            config.ferrocene_trusted_artifact_mirrors = f.trusted_artifact_mirrors.unwrap_or(false);
+        }
+
+        if config.ferrocene_compiler_coverage && !config.profiler_enabled(config.build) {
+            panic!("ferrocene.compiler-coverage requires the profiler runtime, set build.profiler");
+        }
+        if config.ferrocene_differential_testing
+            && !config.rust_codegen_backends.contains(&INTERNER.intern_str("cranelift"))
+        {
+            panic!("ferrocene.differential-testing requires cranelift in rust.codegen-backends");
         }
 
         if config.llvm_from_ci {
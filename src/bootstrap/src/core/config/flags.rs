@@ -5,6 +5,7 @@
 
 use std::path::{Path, PathBuf};
 
+use build_helper::shard::Shard;
 use clap::{CommandFactory, Parser, ValueEnum};
 
 use crate::core::build_steps::setup::Profile;
@@ -367,6 +368,12 @@ pub enum Subcommand {
         /// enable this to generate a Rustfix coverage file, which is saved in
         /// `/<build_base>/rustfix_missing_coverage.txt`
         rustfix_coverage: bool,
+        #[arg(long, value_name = "INDEX/COUNT")]
+        /// only run the INDEX-th of COUNT deterministic partitions of the tests (starting from 1)
+        shard: Option<Shard>,
+        #[arg(long)]
+        /// skip the tests that passed in a previous, interrupted or failed run
+        resume: bool,
     },
     /// Build and run some benchmarks
     Bench {
@@ -522,6 +529,20 @@ pub fn rustfix_coverage(&self) -> bool {
         }
     }
 
+    pub fn shard(&self) -> Option<Shard> {
+        match *self {
+            Subcommand::Test { shard, .. } => shard,
+            _ => None,
+        }
+    }
+
+    pub fn resume(&self) -> bool {
+        match *self {
+            Subcommand::Test { resume, .. } => resume,
+            _ => false,
+        }
+    }
+
     pub fn compare_mode(&self) -> Option<&str> {
         match *self {
             Subcommand::Test { ref compare_mode, .. } => compare_mode.as_ref().map(|s| &s[..]),
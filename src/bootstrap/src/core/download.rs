@@ -195,6 +195,18 @@ fn fix_bin_or_dylib(&self, fname: &Path) {
     }
 
     pub fn download_file(&self, url: &str, dest_path: &Path, help_on_error: &str) {
+        self.download_file_with_checksum(url, dest_path, help_on_error, None);
+    }
+
+    /// Same as `download_file`, but when downloading through the Ferrocene artifact mirrors, the
+    /// sources providing a file not matching the SHA256 checksum are skipped.
+    pub(crate) fn download_file_with_checksum(
+        &self,
+        url: &str,
+        dest_path: &Path,
+        help_on_error: &str,
+        checksum: Option<&str>,
+    ) {
         self.verbose(&format!("download {url}"));
         if self.dry_run() {
             return;
@@ -202,21 +214,25 @@ pub fn download_file(&self, url: &str, dest_path: &Path, help_on_error: &str) {
 
         // Use a temporary file in case we crash while downloading, to avoid a corrupt download in cache/.
         let tempfile = self.tempdir().join(dest_path.file_name().unwrap());
+        // Ferrocene addition: the file might also be available in one of the configured mirrors.
+        crate::ferrocene::mirrors::download(self, url, &tempfile, help_on_error, checksum);
+        t!(std::fs::rename(&tempfile, dest_path));
+    }
+
+    /// Downloads a file from a single source, returning whether the download succeeded.
+    pub(crate) fn try_download_file(&self, url: &str, tempfile: &Path) -> bool {
         // While bootstrap itself only supports http and https downloads, downstream forks might
         // need to download components from other protocols. The match allows them adding more
         // protocols without worrying about merge conflicts if we change the HTTP implementation.
         match url.split_once("://").map(|(proto, _)| proto) {
-            Some("http") | Some("https") => {
-                self.download_http_with_retries(&tempfile, url, help_on_error)
-            }
-            Some("s3") => crate::ferrocene::download_from_s3(self, url, &tempfile, help_on_error),
+            Some("http") | Some("https") => self.download_http_with_retries(tempfile, url),
+            Some("s3") => crate::ferrocene::download_from_s3(self, url, tempfile),
             Some(other) => panic!("unsupported protocol {other} in {url}"),
-            None => crate::ferrocene::download_from_local_filesystem(url, &tempfile, help_on_error),
+            None => crate::ferrocene::download_from_local_filesystem(url, tempfile),
         }
-        t!(std::fs::rename(&tempfile, dest_path));
     }
 
-    fn download_http_with_retries(&self, tempfile: &Path, url: &str, help_on_error: &str) {
+    fn download_http_with_retries(&self, tempfile: &Path, url: &str) -> bool {
         println!("downloading {url}");
         // Try curl. If that fails and we are on windows, fallback to PowerShell.
         let mut curl = Command::new("curl");
@@ -255,16 +271,14 @@ fn download_http_with_retries(&self, tempfile: &Path, url: &str, help_on_error:
                             url, tempfile.to_str().expect("invalid UTF-8 not supported with powershell downloads"),
                         ),
                     ])).is_err() {
-                        return;
+                        return true;
                     }
                     eprintln!("\nspurious failure, trying again");
                 }
             }
-            if !help_on_error.is_empty() {
-                eprintln!("{help_on_error}");
-            }
-            crate::exit!(1);
+            return false;
         }
+        true
     }
 
     pub(crate) fn unpack(&self, tarball: &Path, dst: &Path, pattern: &str) {
@@ -632,7 +646,12 @@ fn download_component(
 download-rustc = false
 ";
         }
-        self.download_file(&format!("{base_url}/{url}"), &tarball, help_on_error);
+        self.download_file_with_checksum(
+            &format!("{base_url}/{url}"),
+            &tarball,
+            help_on_error,
+            checksum.map(|s| s.as_str()),
+        );
         if let Some(sha256) = checksum {
             if !self.verify(&tarball, sha256) {
                 panic!("failed to verify {}", tarball.display());
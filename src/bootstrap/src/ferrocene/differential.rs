@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! With differential testing, the library tests run a second time with the Cranelift codegen
+//! backend. Libtest reports the outcome of each test rather than an exit code, so the outcome and
+//! the captured output of each test are logged in both runs, and any difference between the two
+//! is reported as a potential miscompilation.
+//!
+//! The logs are kept on disk like the [`ResumeLog`](super::resume::ResumeLog), so that the tests
+//! skipped by `./x test --resume` are still compared with the behavior of their previous run.
+
+use crate::core::builder::Builder;
+use crate::t;
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+/// Message the default panic hook prints, which is captured by libtest with the output of the
+/// test. Unwinding is not supported by Cranelift, so the tests printing it can't be compared.
+const PANIC_MESSAGE: &str = "panicked at";
+
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+struct Behavior {
+    name: String,
+    passed: bool,
+    stdout: String,
+}
+
+pub(crate) struct BehaviorLog {
+    path: PathBuf,
+    behaviors: RefCell<BTreeMap<String, Behavior>>,
+    file: RefCell<Option<File>>,
+}
+
+impl BehaviorLog {
+    /// Opens the log at `path`. With `--resume` the behaviors it contains are loaded and the new
+    /// ones are appended to them, otherwise the log starts empty.
+    pub(crate) fn open(builder: &Builder<'_>, path: PathBuf) -> Self {
+        let log = BehaviorLog {
+            path,
+            behaviors: RefCell::new(BTreeMap::new()),
+            file: RefCell::new(None),
+        };
+        if builder.config.dry_run() {
+            return log;
+        }
+
+        let mut contents = String::new();
+        if builder.config.cmd.resume() {
+            match std::fs::read_to_string(&log.path) {
+                Ok(read) => contents = read,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => panic!("failed to read {}: {err}", log.path.display()),
+            }
+        }
+
+        // The last line is incomplete if the run was interrupted while writing it. Tests that ran
+        // more than once keep the behavior of their last run.
+        let len = contents.rfind('\n').map_or(0, |end| end + 1);
+        for line in contents[..len].lines() {
+            let behavior: Behavior = serde_json::from_str(line)
+                .unwrap_or_else(|err| panic!("invalid line in {}: {err}", log.path.display()));
+            log.behaviors.borrow_mut().insert(behavior.name.clone(), behavior);
+        }
+
+        t!(std::fs::create_dir_all(log.path.parent().unwrap()));
+        let mut file = t!(OpenOptions::new().create(true).write(true).open(&log.path));
+        t!(file.set_len(len as u64));
+        t!(file.seek(SeekFrom::End(0)));
+        *log.file.borrow_mut() = Some(file);
+        log
+    }
+
+    /// Appends the outcome and the captured output of a test to the log. The output of the tests
+    /// that passed is only captured when libtest runs with `--show-output`.
+    pub(crate) fn record(&self, name: &str, passed: bool, stdout: Option<&str>) {
+        let behavior =
+            Behavior { name: name.into(), passed, stdout: stdout.unwrap_or_default().into() };
+        if let Some(file) = &mut *self.file.borrow_mut() {
+            // Each line is written at once, so that only the last one can be incomplete.
+            let mut line = t!(serde_json::to_vec(&behavior));
+            line.push(b'\n');
+            t!(file.write_all(&line));
+        }
+        self.behaviors.borrow_mut().insert(behavior.name.clone(), behavior);
+    }
+
+    /// Names of the tests that panicked, even if the panic was caught or expected.
+    pub(crate) fn unwinding_tests(&self) -> Vec<String> {
+        self.behaviors
+            .borrow()
+            .values()
+            .filter(|behavior| behavior.stdout.contains(PANIC_MESSAGE))
+            .map(|behavior| behavior.name.clone())
+            .collect()
+    }
+
+    /// Describes the tests behaving differently in `other`, which was built with `backend`. The
+    /// tests missing from either log are not compared, as they were skipped or didn't run.
+    pub(crate) fn differences(&self, other: &BehaviorLog, backend: &str) -> Vec<String> {
+        let behaviors = self.behaviors.borrow();
+        let mut differences = Vec::new();
+        for (name, theirs) in other.behaviors.borrow().iter() {
+            let Some(ours) = behaviors.get(name) else { continue };
+            if ours == theirs {
+                continue;
+            }
+            let outcome = |behavior: &Behavior| if behavior.passed { "passed" } else { "failed" };
+            let mut difference = format!(
+                "`{name}` {} with the default codegen backend and {} with `{backend}`",
+                outcome(ours),
+                outcome(theirs),
+            );
+            if ours.stdout != theirs.stdout {
+                difference.push_str(&format!(
+                    "\n---- output with the default codegen backend ----\n{}\
+                     \n---- output with `{backend}` ----\n{}",
+                    ours.stdout, theirs.stdout
+                ));
+            }
+            differences.push(difference);
+        }
+        differences
+    }
+}
@@ -1,9 +1,18 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // SPDX-FileCopyrightText: The Ferrocene Developers
 
-use crate::builder::{Builder, RunConfig, ShouldRun, Step};
+use crate::builder::{Builder, Kind, RunConfig, ShouldRun, Step};
+use crate::core::build_steps::dist;
+use crate::core::build_steps::dist::distdir;
+use crate::core::build_steps::llvm;
+use crate::core::build_steps::run::CollectLicenseMetadata;
+use crate::core::build_steps::tool::Tool;
 use crate::core::config::TargetSelection;
+use crate::ferrocene::run::TraceabilityMatrix;
+use crate::ferrocene::sign::{error_when_signatures_are_ignored, for_each_signable_document};
+use crate::t;
 use crate::utils::tarball::{GeneratedTarball, Tarball};
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -291,3 +300,213 @@ fn run(self, builder: &Builder<'_>) -> Self::Output {
         Some(tarball.generate())
     }
 }
+
+/// Bundle of all the qualification evidence of a commit: the qualification documents, the
+/// traceability matrix, the test outcomes and the self-test. Its manifest cross-references the
+/// document IDs with the hashes of the rest of the evidence and of the toolchain packages, which
+/// are built as part of this step.
+///
+/// The bundle is signed like all other tarballs, and `SHA256SUMS` allows checking its integrity
+/// offline with `sha256sum -c`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Evidence {
+    pub(crate) target: TargetSelection,
+}
+
+impl Step for Evidence {
+    type Output = GeneratedTarball;
+    const DEFAULT: bool = false;
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.alias("ferrocene-evidence")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(Evidence { target: run.target });
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        let Some(test_outcomes) = &builder.config.ferrocene_test_outcomes_dir else {
+            eprintln!("error: the qualification evidence requires ferrocene.test-outcomes-dir");
+            crate::exit!(1);
+        };
+        error_when_signatures_are_ignored(builder, "assemble the qualification evidence");
+
+        let matrix = builder.ensure(TraceabilityMatrix { target: self.target });
+        let self_test = builder.ensure(SelfTest { target: self.target });
+        let packages = toolchain_packages(builder, self.target);
+
+        let tarball = Tarball::new_targetless(builder, "ferrocene-evidence");
+        let evidence_dir = Path::new("share/ferrocene/evidence");
+
+        let doc_out = builder.out.join(self.target.triple).join("doc");
+        let documents = RefCell::new(Vec::new());
+        for_each_signable_document(
+            builder,
+            self.target,
+            |_| true,
+            |_, output| {
+                let relative = output.strip_prefix(&doc_out).unwrap();
+                tarball.add_dir(output, evidence_dir.join("documents").join(relative));
+                documents.borrow_mut().push((relative.to_path_buf(), output.to_path_buf()));
+            },
+        );
+
+        tarball.add_file(&matrix, evidence_dir.join("traceability"), 0o644);
+        tarball.add_dir(test_outcomes, evidence_dir.join("test-outcomes"));
+        tarball.add_file(self_test.tarball(), evidence_dir.join("self-test"), 0o644);
+
+        if !builder.config.dry_run() {
+            let image_dir = tarball.image_dir().join(evidence_dir);
+            let documents = documents.into_inner();
+            write_evidence_manifest(builder, self.target, &image_dir, &documents, &packages);
+        }
+
+        tarball.generate()
+    }
+}
+
+/// Packages of the toolchain the evidence refers to: the host tools, and the standard library of
+/// every configured target.
+fn toolchain_packages(builder: &Builder<'_>, target: TargetSelection) -> Vec<GeneratedTarball> {
+    let stage = builder.top_stage;
+    let compiler = builder.compiler_for(stage, builder.config.build, target);
+
+    let mut packages = vec![
+        builder.ensure(dist::Rustc { compiler: builder.compiler(stage, target) }),
+        builder.ensure(dist::Src),
+    ];
+    for &std_target in &builder.targets {
+        let compiler = builder.compiler_for(stage, builder.config.build, std_target);
+        packages.extend(builder.ensure(dist::Std { compiler, target: std_target }));
+    }
+    packages.extend(builder.ensure_if_default(dist::Cargo { compiler, target }, Kind::Dist));
+    packages.extend(builder.ensure_if_default(dist::Clippy { compiler, target }, Kind::Dist));
+    packages.extend(builder.ensure_if_default(dist::Rustfmt { compiler, target }, Kind::Dist));
+    packages.extend(builder.ensure_if_default(dist::LlvmTools { target }, Kind::Dist));
+    packages
+}
+
+fn write_evidence_manifest(
+    builder: &Builder<'_>,
+    target: TargetSelection,
+    image_dir: &Path,
+    documents: &[(PathBuf, PathBuf)],
+    packages: &[GeneratedTarball],
+) {
+    let mut files = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(image_dir).sort_by_file_name() {
+        let entry = t!(entry);
+        if entry.file_type().is_file() {
+            let relative = entry.path().strip_prefix(image_dir).unwrap();
+            files.insert(relative.to_str().unwrap().replace('\\', "/"), sha256(entry.path()));
+        }
+    }
+    let hashes_in = |dir: &str| {
+        files
+            .iter()
+            .filter(|(path, _)| path.starts_with(&format!("{dir}/")))
+            .map(|(path, sha256)| serde_json::json!({ "path": path, "sha256": sha256 }))
+            .collect::<Vec<_>>()
+    };
+
+    let documents = documents
+        .iter()
+        .map(|(relative, output)| {
+            let id = t!(std::fs::read_to_string(output.join("document-id.txt")));
+            serde_json::json!({
+                "path": format!("documents/{}", relative.to_str().unwrap().replace('\\', "/")),
+                "document_id": id.trim(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // The packages are not included in the bundle, only referenced by their hashes.
+    let mut packages = packages
+        .iter()
+        .map(|package| {
+            let name = package.tarball().file_name().unwrap().to_str().unwrap();
+            serde_json::json!({ "name": name, "sha256": sha256(package.tarball()) })
+        })
+        .collect::<Vec<_>>();
+    packages.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    let manifest = serde_json::json!({
+        "format_version": 1,
+        "commit": builder.rust_sha(),
+        "version": builder.version.trim(),
+        "target": target.triple.to_string(),
+        "documents": documents,
+        "traceability": hashes_in("traceability"),
+        "test_outcomes": hashes_in("test-outcomes"),
+        "self_test": hashes_in("self-test"),
+        "packages": packages,
+    });
+    let manifest_path = image_dir.join("manifest.json");
+    builder.create(&manifest_path, &t!(serde_json::to_string_pretty(&manifest)));
+    files.insert("manifest.json".into(), sha256(&manifest_path));
+
+    let mut sums = String::new();
+    for (path, sha256) in &files {
+        sums.push_str(&format!("{sha256}  {path}\n"));
+    }
+    builder.create(&image_dir.join("SHA256SUMS"), &sums);
+}
+
+/// Writes the SPDX and CycloneDX SBOMs of a package next to its tarball, when `ferrocene.sbom` is
+/// enabled. The tool skips the packages not listed in `ferrocene/packages.toml`, and fails if a
+/// non-bare package lacks its criticaltrust manifest.
+pub(crate) fn generate_sbom(
+    builder: &Builder<'_>,
+    component: &str,
+    target: Option<&str>,
+    package_dir: &Path,
+    package_name: &str,
+    version: &str,
+    bare: bool,
+) {
+    let license_metadata = builder.ensure(CollectLicenseMetadata);
+
+    let mut cmd = builder.tool_cmd(Tool::FerroceneGenerateSbom);
+    cmd.arg("--src")
+        .arg(&builder.src)
+        .arg("--package")
+        .arg(component)
+        .arg("--package-dir")
+        .arg(package_dir)
+        .arg("--package-name")
+        .arg(package_name)
+        .arg("--version")
+        .arg(version)
+        .arg("--license-metadata")
+        .arg(&license_metadata)
+        .arg("--output-dir")
+        .arg(distdir(builder));
+    if let Some(target) = target {
+        cmd.arg("--target").arg(target);
+    }
+    if bare {
+        cmd.arg("--bare");
+    }
+    if let Some(sha) = builder.rust_sha() {
+        cmd.arg("--commit").arg(sha);
+    }
+    if builder.config.llvm_enabled() {
+        let llvm::LlvmResult { llvm_config, .. } =
+            builder.ensure(llvm::Llvm { target: builder.config.build });
+        if !builder.config.dry_run() {
+            let llvm_version = crate::output(Command::new(&llvm_config).arg("--version"));
+            cmd.arg("--llvm-version").arg(llvm_version.trim());
+        }
+    }
+    builder.run(&mut cmd);
+}
+
+fn sha256(path: &Path) -> String {
+    use sha2::Digest;
+
+    let mut hasher = sha2::Sha256::new();
+    t!(std::io::copy(&mut t!(std::fs::File::open(path)), &mut hasher));
+    hex::encode(hasher.finalize())
+}
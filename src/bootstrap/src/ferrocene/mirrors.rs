@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Downloads through the mirrors configured with `ferrocene.artifact-mirrors`, allowing to build
+//! without access to the S3 buckets and to the servers the artifacts are originally hosted on.
+//!
+//! Artifacts are identified in the mirrors by the path of their original URL, ignoring the
+//! protocol and the host (or the bucket for S3). For example, the artifact originally located at
+//! `s3://ferrocene-ci-artifacts/ferrocene/dist/<commit>/rust-dev-<target>.tar.xz` is searched in
+//! `<mirror>/ferrocene/dist/<commit>/rust-dev-<target>.tar.xz`. Mirrors can be local directories,
+//! HTTP(S) servers or S3 buckets, and are tried in order before the original location.
+//!
+//! The checksum and the source of each downloaded artifact is recorded in an index stored in the
+//! cache directory. When the same artifact is downloaded again (for example after its tarball was
+//! removed from the cache, or from a different mirror), its checksum must match the recorded one,
+//! and sources providing a different file are skipped.
+//!
+//! As mirrors could otherwise serve arbitrary content, by default they are only used for artifacts
+//! with a known checksum: either pinned by the caller (like the stage0 toolchain and cosign), or
+//! recorded in the index when the artifact was first downloaded from its original location.
+//! Artifacts without a published checksum (like CI LLVM and the document signatures) are thus
+//! downloaded from their original location on a fresh build directory, unless the mirrors are
+//! marked as trusted with `ferrocene.trusted-artifact-mirrors`, as needed by air-gapped builds.
+//! The checksum of the artifacts downloaded from trusted mirrors is then recorded in the index.
+
+use crate::core::config::Config;
+use crate::t;
+use serde_derive::{Deserialize, Serialize};
+use sha2::Digest;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn download(
+    config: &Config,
+    url: &str,
+    tempfile: &Path,
+    help_on_error: &str,
+    checksum: Option<&str>,
+) {
+    let key = artifact_key(url);
+
+    let mut index = CacheIndex::load(config);
+    let expected = match (checksum, key.and_then(|key| index.artifacts.get(key))) {
+        (Some(checksum), _) => Some(checksum.to_string()),
+        (None, Some(entry)) => Some(entry.sha256.clone()),
+        (None, None) => None,
+    };
+
+    let mut sources = Vec::new();
+    match key {
+        Some(key) if expected.is_some() || config.ferrocene_trusted_artifact_mirrors => {
+            for mirror in &config.ferrocene_artifact_mirrors {
+                sources.push(format!("{}/{key}", mirror.trim_end_matches('/')));
+            }
+        }
+        Some(_) if !config.ferrocene_artifact_mirrors.is_empty() => {
+            config.verbose(&format!(
+                "not using mirrors for {url}, as its checksum is unknown and the mirrors are \
+                 not trusted (see `ferrocene.trusted-artifact-mirrors`)"
+            ));
+        }
+        _ => {}
+    }
+    sources.push(url.to_string());
+
+    let mut s3_failed = false;
+    for source in &sources {
+        if source != url {
+            println!("trying mirror {source}");
+        }
+        if !config.try_download_file(source, tempfile) {
+            s3_failed |= source.starts_with("s3://");
+            eprintln!("warning: failed to download {source}");
+            continue;
+        }
+
+        let sha256 = sha256(tempfile);
+        if let Some(expected) = &expected {
+            if *expected != sha256 {
+                eprintln!("warning: ignoring {source} due to failed verification");
+                eprintln!("    found:    {sha256}");
+                eprintln!("    expected: {expected}");
+                continue;
+            }
+        }
+
+        if let Some(key) = key {
+            index.artifacts.insert(
+                key.to_string(),
+                IndexEntry { sha256, source: source.clone(), original: url.to_string() },
+            );
+            index.store(config);
+        }
+        return;
+    }
+
+    if !help_on_error.is_empty() {
+        eprintln!("{help_on_error}");
+    }
+    if expected.is_some() {
+        eprintln!();
+        eprintln!("If the artifact was intentionally changed, remove its entry from the index:");
+        eprintln!("    {}", CacheIndex::path(config).display());
+    }
+    if s3_failed {
+        super::print_s3_help(config);
+    }
+    crate::exit!(1);
+}
+
+/// Returns the path identifying an artifact in the mirrors, or `None` for local files.
+fn artifact_key(url: &str) -> Option<&str> {
+    let (_protocol, host_and_path) = url.split_once("://")?;
+    let (_host, path) = host_and_path.split_once('/')?;
+    Some(path)
+}
+
+fn sha256(path: &Path) -> String {
+    let mut hasher = sha2::Sha256::new();
+    t!(std::io::copy(&mut BufReader::new(t!(File::open(path))), &mut hasher));
+    hex::encode(hasher.finalize().as_slice())
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CacheIndex {
+    /// Downloaded artifacts, keyed by the path identifying them in the mirrors.
+    artifacts: BTreeMap<String, IndexEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexEntry {
+    sha256: String,
+    /// Location the artifact was downloaded from, either a mirror or the original one.
+    source: String,
+    /// Location the artifact is hosted on when no mirror is configured.
+    original: String,
+}
+
+impl CacheIndex {
+    fn path(config: &Config) -> PathBuf {
+        config.out.join("cache").join("ferrocene-artifacts-index.json")
+    }
+
+    fn load(config: &Config) -> Self {
+        let path = Self::path(config);
+        if !path.exists() {
+            return CacheIndex::default();
+        }
+        t!(serde_json::from_slice(&t!(std::fs::read(&path))))
+    }
+
+    fn store(&self, config: &Config) {
+        let path = Self::path(config);
+        t!(std::fs::create_dir_all(path.parent().unwrap()));
+        t!(std::fs::write(&path, t!(serde_json::to_vec_pretty(self))));
+    }
+}
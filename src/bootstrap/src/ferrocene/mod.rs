@@ -1,9 +1,12 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 // SPDX-FileCopyrightText: The Ferrocene Developers
 
+pub(crate) mod differential;
 pub(crate) mod dist;
 pub(crate) mod doc;
+pub(crate) mod mirrors;
 pub(crate) mod partners;
+pub(crate) mod resume;
 pub(crate) mod run;
 pub(crate) mod sign;
 pub(crate) mod test;
@@ -13,12 +16,12 @@
 use crate::core::config::{Config, TargetSelection};
 use crate::t;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Helper function used to download files from S3. This is used to be able to download artifacts
 /// from our buckets for download-ci-llvm and download-rustc.
-pub(crate) fn download_from_s3(config: &Config, url: &str, tempfile: &Path, help_on_error: &str) {
+pub(crate) fn download_from_s3(config: &Config, url: &str, tempfile: &Path) -> bool {
     // If no profile has been configured, do not pass a profile rather than passing the profile
     // called "default". If we pass "default" as the profile, it will indeed take the default
     // profile from ~/.aws/credentials, but it will ignore environment variables. CI credentials
@@ -35,64 +38,187 @@ pub(crate) fn download_from_s3(config: &Config, url: &str, tempfile: &Path, help
     #[allow(deprecated)]
     let success = config
         .try_run(Command::new("aws").args(["s3", "cp"]).args(profile_flags).arg(url).arg(tempfile));
+    success.is_ok()
+}
 
-    if success.is_err() {
-        if !help_on_error.is_empty() {
-            eprintln!("{help_on_error}");
-        }
-        eprintln!();
-        eprintln!("Note that on Ferrocene, interaction with our storage are only available to");
-        eprintln!("Ferrous Systems employees or contractors working on Ferrocene.");
-        eprintln!();
-        eprintln!("You might also need to authenticate with AWS if you haven't done so today:");
-        eprintln!();
-        if let Some(profile) = config.ferrocene_aws_profile.as_deref() {
-            eprintln!("    aws sso login --profile {profile}");
-        } else {
-            eprintln!("    aws sso login");
-            eprintln!();
-            eprintln!("You don't seem to have an AWS profile configured. If you have access to");
-            eprintln!("Ferrous Systems's AWS environment, please follow the onboarding steps.");
-        }
+/// Explains how to get access to our S3 buckets, after a download from them failed.
+pub(crate) fn print_s3_help(config: &Config) {
+    eprintln!();
+    eprintln!("Note that on Ferrocene, interaction with our storage are only available to");
+    eprintln!("Ferrous Systems employees or contractors working on Ferrocene.");
+    eprintln!();
+    eprintln!("You might also need to authenticate with AWS if you haven't done so today:");
+    eprintln!();
+    if let Some(profile) = config.ferrocene_aws_profile.as_deref() {
+        eprintln!("    aws sso login --profile {profile}");
+    } else {
+        eprintln!("    aws sso login");
         eprintln!();
-        std::process::exit(1);
+        eprintln!("You don't seem to have an AWS profile configured. If you have access to");
+        eprintln!("Ferrous Systems's AWS environment, please follow the onboarding steps.");
     }
+    eprintln!();
 }
 
-pub(crate) fn download_from_local_filesystem(path: &str, dest: &Path, help_on_error: &str) {
+/// Directory containing the coverage profiles written by the instrumented compiler, when
+/// `ferrocene.compiler-coverage` is enabled. Profiles of each test suite are stored in the `suites`
+/// subdirectory, while the ones of the build are stored in the `build` subdirectory.
+pub(crate) fn compiler_coverage_dir(builder: &Builder<'_>, host: TargetSelection) -> PathBuf {
+    builder.out.join(host.triple).join("ferrocene").join("compiler-coverage")
+}
+
+pub(crate) fn download_from_local_filesystem(path: &str, dest: &Path) -> bool {
     if let Err(err) = std::fs::copy(path, dest) {
         eprintln!("Failed to copy {path}: {err}");
-        if !help_on_error.is_empty() {
-            eprintln!("{help_on_error}");
+        return false;
+    }
+    true
+}
+
+/// Lists of tests excluded from the test suites, stored in the `ferrocene` directory.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TestList {
+    /// `ferrocene/ignored-tests.toml`, justifying why some tests are ignored.
+    Ignored,
+    /// `ferrocene/differential-skipped-tests.toml`, listing tests not built with the Cranelift
+    /// codegen backend when `ferrocene.differential-testing` is enabled.
+    DifferentialSkipped,
+}
+
+impl TestList {
+    fn file_name(self) -> &'static str {
+        match self {
+            TestList::Ignored => "ignored-tests.toml",
+            TestList::DifferentialSkipped => "differential-skipped-tests.toml",
+        }
+    }
+
+    /// How the tests of the list are excluded, used in the error messages.
+    fn verb(self) -> (&'static str, &'static str) {
+        match self {
+            TestList::Ignored => ("ignoring", "ignored"),
+            TestList::DifferentialSkipped => ("skipping", "skipped"),
         }
-        std::process::exit(1);
     }
 }
 
+/// Entry of one of the [`TestList`]s. Which fields are required depends on the list.
+#[derive(serde_derive::Deserialize)]
+struct TestListEntry {
+    /// Paths of the tests for compiletest suites, or libtest filters for the `library` suite.
+    tests: Vec<String>,
+    /// Targets the tests are excluded on. Required for ignored tests, while differential entries
+    /// apply to all targets if missing.
+    targets: Option<Vec<String>>,
+    /// Issue tracking the removal of the entry, like `ferrocene/ferrocene#123`.
+    issue: Option<String>,
+    /// Rust version the entry expires with, after which it has to be reviewed again.
+    expires: Option<String>,
+    /// Required for ignored tests.
+    impact: Option<IgnoredTestsImpact>,
+    /// Required for differential entries: why Cranelift can't build the tests or gives them a
+    /// different but correct behavior.
+    reason: Option<String>,
+    // Other fields are only used by the traceability matrix...
+}
+
+#[derive(serde_derive::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+enum IgnoredTestsImpact {
+    /// The tests cannot be executed in the environment they run in, like an emulator.
+    TestEnvironment,
+    /// The tests exercise functionality outside of the qualified scope.
+    NotApplicable,
+    /// The tests fail due to a known problem of the toolchain, which must be tracked by an issue.
+    KnownProblem,
+}
+
 pub(crate) fn ignored_tests_for_suite(
     builder: &Builder<'_>,
     target: TargetSelection,
     suite: &str,
 ) -> Vec<String> {
-    #[derive(serde_derive::Deserialize)]
-    struct Item {
-        tests: Vec<String>,
-        targets: Vec<String>,
-        // Other fields ignored here...
-    }
+    tests_for_suite(builder, target, suite, TestList::Ignored)
+}
 
-    let contents: HashMap<String, Vec<Item>> = t!(toml::from_slice(&t!(std::fs::read(
-        builder.src.join("ferrocene").join("ignored-tests.toml")
-    ))));
+pub(crate) fn differential_skipped_tests_for_suite(
+    builder: &Builder<'_>,
+    target: TargetSelection,
+    suite: &str,
+) -> Vec<String> {
+    tests_for_suite(builder, target, suite, TestList::DifferentialSkipped)
+}
+
+/// Loads the tests of `list` applying to `suite` on `target`, exiting if any entry is invalid.
+fn tests_for_suite(
+    builder: &Builder<'_>,
+    target: TargetSelection,
+    suite: &str,
+    list: TestList,
+) -> Vec<String> {
+    let path = builder.src.join("ferrocene").join(list.file_name());
+    let contents: HashMap<String, Vec<TestListEntry>> =
+        t!(toml::from_slice(&t!(std::fs::read(&path))));
+    let entries = contents.get(suite).map(|s| s.as_slice()).unwrap_or(&[]);
+    let (verb, participle) = list.verb();
+
+    let mut errors = Vec::new();
+    for entry in entries {
+        let first = entry.tests.first().map(|t| t.as_str()).unwrap_or("<no tests>");
+        if let Some(expires) = &entry.expires {
+            if parse_version(&builder.version) >= parse_version(expires) {
+                errors.push(format!("the entry {verb} {first} expired with Rust {expires}"));
+            }
+        }
+        match list {
+            TestList::Ignored => {
+                if entry.targets.is_none() {
+                    errors.push(format!("the entry {verb} {first} doesn't have targets"));
+                }
+                match &entry.impact {
+                    None => errors.push(format!("the entry {verb} {first} doesn't have an impact")),
+                    Some(IgnoredTestsImpact::KnownProblem) if entry.issue.is_none() => errors.push(
+                        format!("the entry {verb} {first} is a known problem without an issue"),
+                    ),
+                    Some(_) => {}
+                }
+            }
+            TestList::DifferentialSkipped => {
+                if entry.reason.as_deref().map_or(true, |reason| reason.trim().is_empty()) {
+                    errors.push(format!("the entry {verb} {first} doesn't have a reason"));
+                }
+            }
+        }
+        // Tests of the library are listed by name rather than by path.
+        if suite != "library" {
+            for test in &entry.tests {
+                if !builder.src.join(test).exists() {
+                    errors.push(format!("the {participle} test {test} doesn't exist anymore"));
+                }
+            }
+        }
+    }
+    if !errors.is_empty() {
+        eprintln!("error: invalid entries for {suite} in {}:", path.display());
+        for error in errors {
+            eprintln!("  - {error}");
+        }
+        crate::exit!(1);
+    }
 
     let triple = target.triple.to_string();
-    contents
-        .get(suite)
-        .map(|s| s.as_slice())
-        .unwrap_or(&[])
+    entries
         .iter()
-        .filter(|item| item.targets.contains(&triple))
+        .filter(|item| item.targets.as_ref().map_or(true, |targets| targets.contains(&triple)))
         .flat_map(|item| item.tests.iter())
-        .map(|i| i.clone())
+        .cloned()
+        .collect()
+}
+
+fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .trim()
+        .split(|c| c == '.' || c == '-')
+        .map_while(|component| component.parse().ok())
         .collect()
 }
@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Bootstrap only persists the build metrics once all the tests passed, so the outcomes of an
+//! interrupted or failed test run would be lost. To let `./x test --resume` skip the tests that
+//! already passed, the outcome of each test is appended to a log of its suite as soon as it's
+//! known, and the resumed run records the outcomes of the skipped tests from that log.
+
+use crate::core::builder::Builder;
+use crate::t;
+use build_helper::metrics::{ResumeLogEntry, Test, TestOutcome};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+pub(crate) struct ResumeLog {
+    path: PathBuf,
+    previous: Vec<Test>,
+    // Position in `previous` of the last outcome of each test.
+    previous_by_name: HashMap<String, usize>,
+    completed: bool,
+    file: RefCell<Option<File>>,
+}
+
+impl ResumeLog {
+    /// Opens the log at `path`. With `--resume` the outcomes it contains are loaded and the new
+    /// ones are appended to them, otherwise the log starts empty.
+    pub(crate) fn open(builder: &Builder<'_>, path: PathBuf) -> Self {
+        let mut log = ResumeLog {
+            path,
+            previous: Vec::new(),
+            previous_by_name: HashMap::new(),
+            completed: false,
+            file: RefCell::new(None),
+        };
+        if builder.config.dry_run() {
+            return log;
+        }
+
+        let mut contents = String::new();
+        if builder.config.cmd.resume() {
+            match std::fs::read_to_string(&log.path) {
+                Ok(read) => contents = read,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => panic!("failed to read {}: {err}", log.path.display()),
+            }
+        }
+
+        // The last line is incomplete if the run was interrupted while writing it.
+        let len = contents.rfind('\n').map_or(0, |end| end + 1);
+        for line in contents[..len].lines() {
+            match serde_json::from_str(line) {
+                Ok(ResumeLogEntry::Test(test)) => {
+                    log.previous_by_name.insert(test.name.clone(), log.previous.len());
+                    log.previous.push(test);
+                }
+                Ok(ResumeLogEntry::Completed) => log.completed = true,
+                Err(err) => panic!("invalid line in {}: {err}", log.path.display()),
+            }
+        }
+
+        t!(std::fs::create_dir_all(log.path.parent().unwrap()));
+        let mut file = t!(OpenOptions::new().create(true).write(true).open(&log.path));
+        t!(file.set_len(len as u64));
+        t!(file.seek(SeekFrom::End(0)));
+        log.file = RefCell::new(Some(file));
+        log
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether all the tests of the suite ran successfully in the run being resumed.
+    pub(crate) fn is_completed(&self) -> bool {
+        self.completed
+    }
+
+    /// Outcomes of the tests in the run being resumed, in the order they were recorded.
+    #[cfg_attr(not(feature = "build-metrics"), allow(dead_code))]
+    pub(crate) fn previous_tests(&self) -> &[Test] {
+        &self.previous
+    }
+
+    pub(crate) fn previous_test(&self, name: &str) -> Option<&Test> {
+        self.previous_by_name.get(name).map(|&index| &self.previous[index])
+    }
+
+    /// Appends the outcome of a test to the log. Failed tests are not recorded, so that they run
+    /// again when resuming.
+    pub(crate) fn record(&self, test: &Test) {
+        if let TestOutcome::Failed = test.outcome {
+            return;
+        }
+        self.write(&ResumeLogEntry::Test(test.clone()));
+    }
+
+    /// Marks all the tests of the suite as having run successfully.
+    pub(crate) fn complete(&self) {
+        self.write(&ResumeLogEntry::Completed);
+    }
+
+    fn write(&self, entry: &ResumeLogEntry) {
+        let Some(file) = &mut *self.file.borrow_mut() else { return };
+        // Each line is written at once, so that only the last one can be incomplete.
+        let mut line = t!(serde_json::to_vec(entry));
+        line.push(b'\n');
+        t!(file.write_all(&line));
+    }
+}
@@ -2,9 +2,15 @@
 // SPDX-FileCopyrightText: The Ferrocene Developers
 
 use crate::builder::{Builder, RunConfig, ShouldRun, Step};
-use crate::core::build_steps::tool::Tool;
+use crate::core::build_steps::compile;
+use crate::core::build_steps::llvm;
+use crate::core::build_steps::tool::{SourceType, Tool};
 use crate::core::config::{FerroceneTraceabilityMatrixMode, TargetSelection};
+use crate::ferrocene::compiler_coverage_dir;
 use crate::ferrocene::doc::{Specification, UserManual};
+use crate::t;
+use crate::utils::helpers::is_dylib;
+use crate::Mode;
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -14,6 +20,7 @@ pub(crate) struct TraceabilityMatrix {
 }
 
 impl Step for TraceabilityMatrix {
+    /// Machine-readable export of the matrix, generated alongside the HTML report.
     type Output = PathBuf;
     const ONLY_HOSTS: bool = true;
     const DEFAULT: bool = false;
@@ -49,6 +56,24 @@ fn run(self, builder: &Builder<'_>) -> Self::Output {
             );
         }
 
+        // The unit tests and doctests of the certified library crates are annotated as well, with
+        // compiletest collecting their annotations in its "library" mode.
+        for krate in ["core", "alloc"] {
+            builder.info(&format!("Loading test annotations from library/{krate}"));
+
+            let dest = test_annotations_base.join(format!("library-{krate}.json"));
+            builder.run(
+                Command::new(&compiletest)
+                    .env("FERROCENE_COLLECT_ANNOTATIONS", "1")
+                    .env("FERROCENE_DEST", dest)
+                    .env("FERROCENE_SRC_BASE", builder.src.join("library").join(krate))
+                    .env("FERROCENE_MODE", "library")
+                    .env("FERROCENE_SUITE", krate),
+            );
+        }
+
+        let certified_calls = builder.ensure(CertifiedCalls { target: self.target });
+
         let html_output = builder
             .out
             .join(self.target.triple)
@@ -56,6 +81,7 @@ fn run(self, builder: &Builder<'_>) -> Self::Output {
             .join("qualification")
             .join("traceability-matrix.html");
         builder.create_dir(html_output.parent().unwrap());
+        let json_output = html_output.with_extension("json");
 
         let (spec_url, user_manual_url, src_url);
         match builder.config.ferrocene_traceability_matrix_mode {
@@ -80,7 +106,9 @@ fn run(self, builder: &Builder<'_>) -> Self::Output {
             .env("TRACEABILITY_MATRIX_UM_IDS", user_manual.join("traceability-ids.json"))
             .env("TRACEABILITY_MATRIX_UM_URL", user_manual_url)
             .env("TRACEABILITY_MATRIX_ANNOTATIONS", test_annotations_base)
+            .env("TRACEABILITY_MATRIX_CERTIFIED_CALLS", certified_calls)
             .env("TRACEABILITY_MATRIX_HTML_OUT", &html_output)
+            .env("TRACEABILITY_MATRIX_JSON_OUT", &json_output)
             .env("TRACEABILITY_MATRIX_SRC_BASE", &builder.src)
             .env("TRACEABILITY_MATRIX_SRC_URL", src_url);
 
@@ -88,7 +116,189 @@ fn run(self, builder: &Builder<'_>) -> Self::Output {
             cmd.env("TRACEABILITY_MATRIX_TEST_OUTCOMES_DIR", dir);
         }
 
+        builder.run(&mut cmd);
+        json_output
+    }
+}
+
+/// Records the functions of the certified subset called by each unit test of the certified
+/// library crates, by checking the tests with `-Z certified-calls`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub(crate) struct CertifiedCalls {
+    pub(crate) target: TargetSelection,
+}
+
+impl Step for CertifiedCalls {
+    type Output = PathBuf;
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.never()
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        // The flag is not known by the stage 0 compiler.
+        let compiler = builder.compiler(builder.top_stage.max(1), builder.config.build);
+        builder.ensure(compile::Std::new(compiler, self.target));
+
+        let dest = builder.out.join(self.target.triple).join("ferrocene").join("certified-calls");
+        builder.create_dir(&dest);
+
+        let mut cargo =
+            builder.cargo(compiler, Mode::Std, SourceType::InTree, self.target, "check");
+        compile::std_cargo(builder, self.target, compiler.stage, &mut cargo);
+        cargo
+            .arg("--tests")
+            .args(["-p", "core", "-p", "alloc"])
+            // Keep the artifacts apart from the other builds of the library, which would
+            // otherwise be rebuilt because of the different flags.
+            .arg("--target-dir")
+            .arg(builder.stage_out(compiler, Mode::Std).join("certified-calls"))
+            .rustflag(&format!("-Zcertified-calls={}", dest.display()));
+
+        builder.info("Recording the certified functions called by the library tests");
+        builder.run(&mut cargo.into());
+        dest
+    }
+}
+
+/// Merges the `metrics.json` files of the shards of a test run, passed with `--args` after the
+/// path of the merged file.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub(crate) struct MergeMetrics;
+
+impl Step for MergeMetrics {
+    type Output = ();
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("ferrocene/tools/merge-metrics")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(MergeMetrics);
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        let mut cmd = builder.tool_cmd(Tool::FerroceneMergeMetrics);
+        cmd.args(builder.config.args());
+        builder.run(&mut cmd);
+    }
+}
+
+/// Ingests build metrics into the test outcomes database, or queries the history of a test, with
+/// the arguments passed with `--args`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub(crate) struct TestOutcomesDb;
+
+impl Step for TestOutcomesDb {
+    type Output = ();
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("ferrocene/tools/test-outcomes-db")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(TestOutcomesDb);
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        let mut cmd = builder.tool_cmd(Tool::FerroceneTestOutcomesDb);
+        cmd.args(builder.config.args());
+        builder.run(&mut cmd);
+    }
+}
+
+/// Generates the coverage report of the stage 2 compiler, from the profiles it wrote while the
+/// test suites were executed with `ferrocene.compiler-coverage` enabled.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub(crate) struct CompilerCoverage {
+    pub(crate) target: TargetSelection,
+}
+
+impl Step for CompilerCoverage {
+    type Output = PathBuf;
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("ferrocene/tools/compiler-coverage")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(CompilerCoverage { target: run.target });
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        if !builder.config.ferrocene_compiler_coverage {
+            eprintln!("error: the compiler coverage report requires ferrocene.compiler-coverage");
+            eprintln!("help: enable it in config.toml and run the test suites with --stage 2");
+            crate::exit!(1);
+        }
+
+        let compiler = builder.compiler(2, self.target);
+        let llvm::LlvmResult { llvm_config, .. } =
+            builder.ensure(llvm::Llvm { target: builder.config.build });
+
+        // The compiler crates are all linked into the rustc_driver dylib, except for the tiny
+        // `rustc` binary calling into it.
+        let mut cmd = builder.tool_cmd(Tool::FerroceneCompilerCoverage);
+        cmd.arg(builder.rustc(compiler));
+        if !builder.config.dry_run() {
+            for entry in t!(std::fs::read_dir(builder.rustc_libdir(compiler))) {
+                let path = t!(entry).path();
+                let name = path.file_name().unwrap().to_string_lossy();
+                if name.contains("rustc_driver") && is_dylib(&name) {
+                    cmd.arg(path);
+                }
+            }
+        }
+
+        let coverage_dir = compiler_coverage_dir(builder, self.target);
+        let qualification_dir =
+            builder.out.join(self.target.triple).join("doc").join("qualification");
+        let html_output = qualification_dir.join("compiler-coverage.html");
+        builder.create_dir(&qualification_dir);
+
+        cmd.arg("--profiles")
+            .arg(coverage_dir.join("suites"))
+            .arg("--llvm-bin-dir")
+            .arg(llvm_config.parent().unwrap())
+            .arg("--src-base")
+            .arg(&builder.src)
+            .arg("--work-dir")
+            .arg(coverage_dir.join("merged"))
+            .arg("--html-out")
+            .arg(&html_output)
+            .arg("--json-out")
+            .arg(qualification_dir.join("compiler-coverage.json"));
         builder.run(&mut cmd);
         html_output
     }
 }
+
+/// Reports the differences between the public APIs of two versions of a crate, from the rustdoc
+/// JSON files passed with `--args`. The JSON output of the standard library is generated with
+/// `./x doc library/core --json`, and files of older releases are accepted as long as the tool
+/// can upgrade their version of the rustdoc JSON format.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub(crate) struct ApiDiff;
+
+impl Step for ApiDiff {
+    type Output = ();
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("src/tools/api-diff")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(ApiDiff);
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        let mut cmd = builder.tool_cmd(Tool::FerroceneApiDiff);
+        cmd.args(builder.config.args());
+        builder.run(&mut cmd);
+    }
+}
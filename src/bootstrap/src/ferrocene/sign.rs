@@ -198,7 +198,7 @@ fn run(self, builder: &Builder<'_>) -> PathBuf {
         );
 
         if !dest.exists() {
-            builder.config.download_file(&url, &dest, "test");
+            builder.config.download_file_with_checksum(&url, &dest, "", Some(artifact.sha256));
         }
         if !builder.config.verify(&dest, artifact.sha256) && !builder.config.dry_run() {
             panic!("invalid cosign downloaded");
@@ -43,6 +43,186 @@ fn run(self, builder: &Builder<'_>) -> Self::Output {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct MergeMetricsTool {
+    host: TargetSelection,
+}
+
+impl Step for MergeMetricsTool {
+    type Output = ();
+    const DEFAULT: bool = true;
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("ferrocene/tools/merge-metrics")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(MergeMetricsTool { host: run.target });
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        builder.info("Testing ferrocene/tools/merge-metrics");
+        builder.run(
+            &mut tool::prepare_tool_cargo(
+                builder,
+                builder.compiler(0, self.host),
+                Mode::ToolBootstrap,
+                self.host,
+                "test",
+                "ferrocene/tools/merge-metrics",
+                SourceType::InTree,
+                &[],
+            )
+            .into(),
+        );
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct TestOutcomesDbTool {
+    host: TargetSelection,
+}
+
+impl Step for TestOutcomesDbTool {
+    type Output = ();
+    const DEFAULT: bool = true;
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("ferrocene/tools/test-outcomes-db")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(TestOutcomesDbTool { host: run.target });
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        builder.info("Testing ferrocene/tools/test-outcomes-db");
+        builder.run(
+            &mut tool::prepare_tool_cargo(
+                builder,
+                builder.compiler(0, self.host),
+                Mode::ToolBootstrap,
+                self.host,
+                "test",
+                "ferrocene/tools/test-outcomes-db",
+                SourceType::InTree,
+                &[],
+            )
+            .into(),
+        );
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CompilerCoverageTool {
+    host: TargetSelection,
+}
+
+impl Step for CompilerCoverageTool {
+    type Output = ();
+    const DEFAULT: bool = true;
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("ferrocene/tools/compiler-coverage")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(CompilerCoverageTool { host: run.target });
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        builder.info("Testing ferrocene/tools/compiler-coverage");
+        builder.run(
+            &mut tool::prepare_tool_cargo(
+                builder,
+                builder.compiler(0, self.host),
+                Mode::ToolBootstrap,
+                self.host,
+                "test",
+                "ferrocene/tools/compiler-coverage",
+                SourceType::InTree,
+                &[],
+            )
+            .into(),
+        );
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ApiDiffTool {
+    host: TargetSelection,
+}
+
+impl Step for ApiDiffTool {
+    type Output = ();
+    const DEFAULT: bool = true;
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("src/tools/api-diff")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(ApiDiffTool { host: run.target });
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        builder.info("Testing src/tools/api-diff");
+        builder.run(
+            &mut tool::prepare_tool_cargo(
+                builder,
+                builder.compiler(0, self.host),
+                Mode::ToolBootstrap,
+                self.host,
+                "test",
+                "src/tools/api-diff",
+                SourceType::InTree,
+                &[],
+            )
+            .into(),
+        );
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct GenerateSbomTool {
+    host: TargetSelection,
+}
+
+impl Step for GenerateSbomTool {
+    type Output = ();
+    const DEFAULT: bool = true;
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("ferrocene/tools/generate-sbom")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(GenerateSbomTool { host: run.target });
+    }
+
+    fn run(self, builder: &Builder<'_>) -> Self::Output {
+        builder.info("Testing ferrocene/tools/generate-sbom");
+        builder.run(
+            &mut tool::prepare_tool_cargo(
+                builder,
+                builder.compiler(0, self.host),
+                Mode::ToolBootstrap,
+                self.host,
+                "test",
+                "ferrocene/tools/generate-sbom",
+                SourceType::InTree,
+                &[],
+            )
+            .into(),
+        );
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct SelfTest {
     target: TargetSelection,
@@ -1327,6 +1327,27 @@ fn qemu_rootfs(&self, target: TargetSelection) -> Option<&Path> {
         self.config.target_config.get(&target).and_then(|t| t.qemu_rootfs.as_ref()).map(|p| &**p)
     }
 
+    /// Returns `true` if the tests for this `target` are run by booting each test binary in QEMU
+    /// system emulation, with the test output reported over Arm semihosting.
+    fn qemu_semihosting(&self, target: TargetSelection) -> bool {
+        self.config.target_config.get(&target).map(|t| t.qemu_semihosting).unwrap_or(false)
+    }
+
+    /// Returns the flags building the test binaries of a `target` tested with semihosting: they
+    /// use the `no_std` harness in `library/test_semihosting`, and are linked with its startup
+    /// code and the memory layout of the QEMU machine `remote-test-client` boots them on.
+    fn qemu_semihosting_flags(&self, target: TargetSelection) -> Vec<String> {
+        let script = if target.starts_with("aarch64") {
+            "aarch64-virt.ld"
+        } else if target.starts_with("thumbv7em") {
+            "thumbv7em-mps2-an386.ld"
+        } else {
+            panic!("semihosting tests are not supported on {target}");
+        };
+        let script = self.src.join("library").join("test_semihosting").join("link").join(script);
+        vec!["-Zsemihosting-tests".into(), format!("-Clink-arg=-T{}", script.display())]
+    }
+
     /// Path to the python interpreter to use
     fn python(&self) -> &Path {
         if self.config.build.ends_with("apple-darwin") {
@@ -582,6 +582,8 @@ fn test_with_no_doc_stage0() {
             force_rerun: false,
             compare_mode: None,
             rustfix_coverage: false,
+            shard: None,
+            resume: false,
             pass: None,
             run: None,
             only_modified: false,
@@ -655,6 +657,8 @@ fn test_docs() {
             force_rerun: false,
             compare_mode: None,
             rustfix_coverage: false,
+            shard: None,
+            resume: false,
             pass: None,
             run: None,
             only_modified: false,
@@ -9,7 +9,7 @@
 use crate::Build;
 use build_helper::metrics::{
     JsonInvocation, JsonInvocationSystemStats, JsonNode, JsonRoot, JsonStepSystemStats, Test,
-    TestOutcome, TestSuite, TestSuiteMetadata,
+    TestSuite, TestSuiteMetadata,
 };
 use std::cell::RefCell;
 use std::fs::File;
@@ -124,7 +124,7 @@ pub(crate) fn begin_test_suite(&self, metadata: TestSuiteMetadata, builder: &Bui
         step.test_suites.push(TestSuite { metadata, tests: Vec::new() });
     }
 
-    pub(crate) fn record_test(&self, name: &str, outcome: TestOutcome, builder: &Builder<'_>) {
+    pub(crate) fn record_test(&self, test: Test, builder: &Builder<'_>) {
         // Do not record dry runs, as they'd be duplicates of the actual steps.
         if builder.config.dry_run() {
             return;
@@ -134,7 +134,7 @@ pub(crate) fn record_test(&self, name: &str, outcome: TestOutcome, builder: &Bui
         let step = state.running_steps.last_mut().unwrap();
 
         if let Some(test_suite) = step.test_suites.last_mut() {
-            test_suite.tests.push(Test { name: name.to_string(), outcome });
+            test_suite.tests.push(test);
         } else {
             panic!("metrics.record_test() called without calling metrics.begin_test_suite() first");
         }
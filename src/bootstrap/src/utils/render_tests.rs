@@ -7,28 +7,63 @@
 //! to reimplement all the rendering logic in this module because of that.
 
 use crate::core::builder::Builder;
-use std::io::{BufRead, BufReader, Read, Write};
+use crate::ferrocene::differential::BehaviorLog;
+use crate::ferrocene::resume::ResumeLog;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 use std::process::{ChildStdout, Command, Stdio};
 use std::time::Duration;
 use termcolor::{Color, ColorSpec, WriteColor};
 
 const TERSE_TESTS_PER_LINE: usize = 88;
 
-pub(crate) fn add_flags_and_try_run_tests(builder: &Builder<'_>, cmd: &mut Command) -> bool {
+/// Runs `cargo test`, appending the outcomes of the tests to `resume_log` if present. The outcome
+/// and the output of each test are also appended to `behaviors` if present.
+pub(crate) fn add_flags_and_try_run_tests(
+    builder: &Builder<'_>,
+    cmd: &mut Command,
+    resume_log: Option<&ResumeLog>,
+    behaviors: Option<&BehaviorLog>,
+) -> bool {
     if cmd.get_args().position(|arg| arg == "--").is_none() {
         cmd.arg("--");
     }
     cmd.args(&["-Z", "unstable-options", "--format", "json"]);
 
-    try_run_tests(builder, cmd, false)
+    try_run_tests_inner(builder, cmd, false, None, resume_log, behaviors)
 }
 
 pub(crate) fn try_run_tests(builder: &Builder<'_>, cmd: &mut Command, stream: bool) -> bool {
+    try_run_tests_inner(builder, cmd, stream, None, None, None)
+}
+
+/// Runs compiletest, reading the failed attempts of flaky tests from the file passed to it with
+/// `--flaky-retries-log`, as they can't be part of the JSON output of libtest. The outcomes of
+/// the tests are appended to `resume_log`.
+pub(crate) fn try_run_tests_with_retries_log(
+    builder: &Builder<'_>,
+    cmd: &mut Command,
+    retries_log: &Path,
+    resume_log: &ResumeLog,
+) -> bool {
+    try_run_tests_inner(builder, cmd, false, Some(retries_log), Some(resume_log), None)
+}
+
+fn try_run_tests_inner(
+    builder: &Builder<'_>,
+    cmd: &mut Command,
+    stream: bool,
+    retries_log: Option<&Path>,
+    resume_log: Option<&ResumeLog>,
+    behaviors: Option<&BehaviorLog>,
+) -> bool {
     if builder.config.dry_run() {
         return true;
     }
 
-    if !run_tests(builder, cmd, stream) {
+    if !run_tests(builder, cmd, stream, retries_log, resume_log, behaviors) {
         if builder.fail_fast {
             crate::exit!(1);
         } else {
@@ -41,16 +76,33 @@ pub(crate) fn try_run_tests(builder: &Builder<'_>, cmd: &mut Command, stream: bo
     }
 }
 
-fn run_tests(builder: &Builder<'_>, cmd: &mut Command, stream: bool) -> bool {
+fn run_tests(
+    builder: &Builder<'_>,
+    cmd: &mut Command,
+    stream: bool,
+    retries_log: Option<&Path>,
+    resume_log: Option<&ResumeLog>,
+    behaviors: Option<&BehaviorLog>,
+) -> bool {
     cmd.stdout(Stdio::piped());
 
+    // Attempts recorded by a previous run would be attributed to this one otherwise.
+    if let Some(retries_log) = retries_log {
+        match std::fs::remove_file(retries_log) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => panic!("failed to remove {}: {err}", retries_log.display()),
+        }
+    }
+
     builder.verbose(&format!("running: {cmd:?}"));
 
     let mut process = cmd.spawn().unwrap();
 
     // This runs until the stdout of the child is closed, which means the child exited. We don't
     // run this on another thread since the builder is not Sync.
-    let renderer = Renderer::new(process.stdout.take().unwrap(), builder);
+    let renderer =
+        Renderer::new(process.stdout.take().unwrap(), retries_log, resume_log, behaviors, builder);
     if stream {
         renderer.stream_all();
     } else {
@@ -73,6 +125,16 @@ struct Renderer<'a> {
     stdout: BufReader<ChildStdout>,
     failures: Vec<TestOutcome>,
     benches: Vec<BenchOutcome>,
+    // Messages of the failed attempts of flaky tests that were retried, keyed by test name.
+    failed_attempts: HashMap<String, Vec<Option<String>>>,
+    // File compiletest appends the failed attempts to, and how much of it was already read.
+    retries_log: Option<(PathBuf, u64)>,
+    // Number of failed attempts of the tests retried since the last suite outcome was rendered.
+    retried_tests: BTreeMap<String, usize>,
+    // Log the outcomes of the tests are appended to, for `./x test --resume` to skip them.
+    resume_log: Option<&'a ResumeLog>,
+    // Log the outcomes and the outputs of the tests are appended to, for differential testing.
+    behaviors: Option<&'a BehaviorLog>,
     builder: &'a Builder<'a>,
     tests_count: Option<usize>,
     executed_tests: usize,
@@ -80,11 +142,22 @@ struct Renderer<'a> {
 }
 
 impl<'a> Renderer<'a> {
-    fn new(stdout: ChildStdout, builder: &'a Builder<'a>) -> Self {
+    fn new(
+        stdout: ChildStdout,
+        retries_log: Option<&Path>,
+        resume_log: Option<&'a ResumeLog>,
+        behaviors: Option<&'a BehaviorLog>,
+        builder: &'a Builder<'a>,
+    ) -> Self {
         Self {
             stdout: BufReader::new(stdout),
             benches: Vec::new(),
             failures: Vec::new(),
+            failed_attempts: HashMap::new(),
+            retries_log: retries_log.map(|path| (path.into(), 0)),
+            retried_tests: BTreeMap::new(),
+            resume_log,
+            behaviors,
             builder,
             tests_count: None,
             executed_tests: 0,
@@ -134,21 +207,48 @@ fn stream_all(mut self) {
         }
     }
 
+    /// Reads the failed attempts appended to the retries log since the last call. A test is
+    /// retried before libtest reports its outcome, so its attempts are read by then.
+    fn read_failed_attempts(&mut self) {
+        let Some((path, offset)) = &mut self.retries_log else { return };
+        let mut contents = Vec::new();
+        match File::open(&*path) {
+            Ok(mut file) => {
+                file.seek(SeekFrom::Start(*offset)).unwrap();
+                file.read_to_end(&mut contents).unwrap();
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
+            Err(err) => panic!("failed to read {}: {err}", path.display()),
+        }
+        // Only complete lines are read, the rest will be read on the next call.
+        let Some(end) = contents.iter().rposition(|&b| b == b'\n') else { return };
+        *offset += end as u64 + 1;
+
+        for line in contents[..end].split(|&b| b == b'\n') {
+            let attempt: FailedAttempt = serde_json::from_slice(line).unwrap_or_else(|err| {
+                panic!("invalid failed attempt in {}: {err}", path.display())
+            });
+            // The terse output only lists the retried tests with the outcome of the suite, as
+            // lines in the middle of it would break its layout.
+            if self.builder.config.verbose_tests {
+                println!("test {} failed on attempt {}, retrying", attempt.name, attempt.attempt);
+            }
+            *self.retried_tests.entry(attempt.name.clone()).or_default() += 1;
+            self.failed_attempts.entry(attempt.name).or_default().push(attempt.message);
+        }
+    }
+
     fn render_test_outcome(&mut self, outcome: Outcome<'_>, test: &TestOutcome) {
         self.executed_tests += 1;
-
-        #[cfg(feature = "build-metrics")]
-        self.builder.metrics.record_test(
-            &test.name,
+        self.read_failed_attempts();
+        self.record_test(&outcome, test);
+        if let Some(behaviors) = self.behaviors {
             match outcome {
-                Outcome::Ok | Outcome::BenchOk => build_helper::metrics::TestOutcome::Passed,
-                Outcome::Failed => build_helper::metrics::TestOutcome::Failed,
-                Outcome::Ignored { reason } => build_helper::metrics::TestOutcome::Ignored {
-                    ignore_reason: reason.map(|s| s.to_string()),
-                },
-            },
-            self.builder,
-        );
+                Outcome::Ok => behaviors.record(&test.name, true, test.stdout.as_deref()),
+                Outcome::Failed => behaviors.record(&test.name, false, test.stdout.as_deref()),
+                Outcome::BenchOk | Outcome::Ignored { .. } => {}
+            }
+        }
 
         if self.builder.config.verbose_tests {
             self.render_test_outcome_verbose(outcome, test);
@@ -157,6 +257,51 @@ fn render_test_outcome(&mut self, outcome: Outcome<'_>, test: &TestOutcome) {
         }
     }
 
+    /// Records the outcome of a test in the build metrics and in the resume log. The tests skipped
+    /// on resume are recorded with their outcome in the run being resumed instead.
+    fn record_test(&mut self, outcome: &Outcome<'_>, test: &TestOutcome) {
+        use build_helper::metrics::{Test, TestAttempt, TestOutcome, RESUMED_TEST_MESSAGE};
+
+        let previous = match (outcome, self.resume_log) {
+            (Outcome::Ignored { reason: Some(RESUMED_TEST_MESSAGE) }, Some(log)) => {
+                log.previous_test(&test.name)
+            }
+            _ => None,
+        };
+        let record = if let Some(previous) = previous {
+            previous.clone()
+        } else {
+            let metrics_outcome = || match outcome {
+                Outcome::Ok | Outcome::BenchOk => TestOutcome::Passed,
+                Outcome::Failed => TestOutcome::Failed,
+                Outcome::Ignored { reason } => {
+                    TestOutcome::Ignored { ignore_reason: reason.map(|s| s.to_string()) }
+                }
+            };
+            // Record every attempt of retried tests, including the last one, so that passing
+            // on retry is distinguishable from passing on the first attempt.
+            let mut attempts = Vec::new();
+            if let Some(failed) = self.failed_attempts.remove(&test.name) {
+                attempts.extend(
+                    failed
+                        .into_iter()
+                        .map(|message| TestAttempt { outcome: TestOutcome::Failed, message }),
+                );
+                attempts.push(TestAttempt { outcome: metrics_outcome(), message: None });
+            }
+            let record = Test { name: test.name.clone(), outcome: metrics_outcome(), attempts };
+            if let Some(log) = self.resume_log {
+                log.record(&record);
+            }
+            record
+        };
+
+        #[cfg(feature = "build-metrics")]
+        self.builder.metrics.record_test(record, self.builder);
+        #[cfg(not(feature = "build-metrics"))]
+        let _ = record;
+    }
+
     fn render_test_outcome_verbose(&self, outcome: Outcome<'_>, test: &TestOutcome) {
         print!("test {} ... ", test.name);
         self.builder.colored_stdout(|stdout| outcome.write_long(stdout)).unwrap();
@@ -182,7 +327,7 @@ fn render_test_outcome_terse(&mut self, outcome: Outcome<'_>, _: &TestOutcome) {
         let _ = std::io::stdout().flush();
     }
 
-    fn render_suite_outcome(&self, outcome: Outcome<'_>, suite: &SuiteOutcome) {
+    fn render_suite_outcome(&mut self, outcome: Outcome<'_>, suite: &SuiteOutcome) {
         // The terse output doesn't end with a newline, so we need to add it ourselves.
         if !self.builder.config.verbose_tests {
             println!();
@@ -208,6 +353,14 @@ fn render_suite_outcome(&self, outcome: Outcome<'_>, suite: &SuiteOutcome) {
             }
         }
 
+        if !self.retried_tests.is_empty() {
+            println!("\nretried:");
+            for (name, failed_attempts) in std::mem::take(&mut self.retried_tests) {
+                let plural = if failed_attempts == 1 { "" } else { "s" };
+                println!("    {name} ({failed_attempts} failed attempt{plural})");
+            }
+        }
+
         if !self.benches.is_empty() {
             println!("\nbenchmarks:");
 
@@ -384,6 +537,14 @@ enum TestMessage {
     Started,
 }
 
+/// Failed attempt of a flaky test, written by compiletest to the retries log before retrying.
+#[derive(serde_derive::Deserialize)]
+struct FailedAttempt {
+    name: String,
+    attempt: usize,
+    message: Option<String>,
+}
+
 #[derive(serde_derive::Deserialize)]
 struct BenchOutcome {
     name: String,
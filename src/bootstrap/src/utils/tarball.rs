@@ -243,7 +243,7 @@ pub(crate) fn generate(self) -> GeneratedTarball {
             );
         }
 
-        self.run(|this, cmd| {
+        self.run(false, |this, cmd| {
             cmd.arg("generate")
                 .arg("--image-dir")
                 .arg(&this.image_dir)
@@ -269,7 +269,7 @@ pub(crate) fn combine(self, tarballs: &[GeneratedTarball]) -> GeneratedTarball {
             input_tarballs.push(&tarball.path);
         }
 
-        self.run(|this, cmd| {
+        self.run(false, |this, cmd| {
             cmd.arg("combine").arg("--input-tarballs").arg(input_tarballs);
             this.non_bare_args(cmd);
         })
@@ -282,7 +282,7 @@ pub(crate) fn bare(self) -> GeneratedTarball {
         let dest = self.temp_dir.join(self.package_name());
         t!(std::fs::rename(&self.image_dir, &dest));
 
-        self.run(|this, cmd| {
+        self.run(true, |this, cmd| {
             let distdir = distdir(this.builder);
             t!(std::fs::create_dir_all(&distdir));
             cmd.arg("tarball")
@@ -330,7 +330,11 @@ fn non_bare_args(&self, cmd: &mut Command) {
         }
     }
 
-    fn run(self, build_cli: impl FnOnce(&Tarball<'a>, &mut Command)) -> GeneratedTarball {
+    fn run(
+        self,
+        bare: bool,
+        build_cli: impl FnOnce(&Tarball<'a>, &mut Command),
+    ) -> GeneratedTarball {
         t!(std::fs::create_dir_all(&self.overlay_dir));
         self.builder.create(&self.overlay_dir.join("version"), &self.overlay.version(self.builder));
         if let Some(info) = self.builder.rust_info().info() {
@@ -369,6 +373,18 @@ fn run(self, build_cli: impl FnOnce(&Tarball<'a>, &mut Command)) -> GeneratedTar
             }
         }
 
+        if self.builder.config.ferrocene_sbom {
+            crate::ferrocene::dist::generate_sbom(
+                self.builder,
+                &self.component,
+                self.target.as_deref(),
+                &decompressed_output,
+                &package_name,
+                &self.overlay.version(self.builder),
+                bare,
+            );
+        }
+
         // Use either the first compression format defined, or "gz" as the default.
         let ext = self
             .builder
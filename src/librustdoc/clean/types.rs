@@ -603,6 +603,11 @@ pub(crate) fn is_non_exhaustive(&self) -> bool {
         self.attrs.other_attrs.iter().any(|a| a.has_name(sym::non_exhaustive))
     }
 
+    /// Returns whether the item is part of the Ferrocene certified subset.
+    pub(crate) fn is_ferrocene_certified(&self) -> bool {
+        self.attrs.other_attrs.iter().any(|a| a.path_matches(&[sym::ferrocene, sym::certified]))
+    }
+
     /// Returns a documentation-level item type from the item.
     pub(crate) fn type_(&self) -> ItemType {
         ItemType::from(self)
@@ -1225,6 +1230,20 @@ pub(crate) fn get_doc_aliases(&self) -> Box<[Symbol]> {
         }
         aliases.into_iter().collect::<Vec<_>>().into()
     }
+
+    /// Returns the IDs of the requirements implemented by the item, as set with
+    /// `#[doc(requirement = "...")]`, in the order they were written.
+    pub(crate) fn get_doc_requirements(&self) -> Vec<Symbol> {
+        let mut requirements = Vec::new();
+        for attr in self.other_attrs.lists(sym::doc).filter(|a| a.has_name(sym::requirement)) {
+            if let Some(requirement) = attr.value_str()
+                && !requirements.contains(&requirement)
+            {
+                requirements.push(requirement);
+            }
+        }
+        requirements
+    }
 }
 
 impl PartialEq for Attributes {
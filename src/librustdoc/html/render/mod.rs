@@ -564,6 +564,8 @@ struct ItemInfo {
 /// * Stability
 /// * Deprecated
 /// * Required features (through the `doc_cfg` feature)
+/// * Membership in the Ferrocene certified subset
+/// * Implemented requirements (through `#[doc(requirement = "...")]`)
 fn document_item_info(
     cx: &mut Context<'_>,
     item: &clean::Item,
@@ -605,6 +607,12 @@ enum ShortItemInfo {
     Portability {
         message: String,
     },
+    /// The item is part of the Ferrocene certified subset.
+    Certified,
+    /// The IDs of the requirements implemented by the item, already escaped.
+    Requirements {
+        message: String,
+    },
 }
 
 /// Render the stability, deprecation and portability information that is displayed at the top of
@@ -666,6 +674,20 @@ fn short_item_info(
         extra_info.push(ShortItemInfo::Portability { message });
     }
 
+    if item.is_ferrocene_certified() {
+        extra_info.push(ShortItemInfo::Certified);
+    }
+
+    let requirements = item.attrs.get_doc_requirements();
+    if !requirements.is_empty() {
+        let message = requirements
+            .iter()
+            .map(|requirement| format!("<code>{}</code>", Escape(requirement.as_str())))
+            .collect::<Vec<_>>()
+            .join(", ");
+        extra_info.push(ShortItemInfo::Requirements { message });
+    }
+
     extra_info
 }
 
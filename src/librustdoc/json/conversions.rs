@@ -43,6 +43,8 @@ pub(super) fn convert_item(&self, item: clean::Item) -> Option<Item> {
             .collect();
         let docs = item.opt_doc_value();
         let attrs = item.attributes(self.tcx, self.cache(), true);
+        let requirements =
+            item.attrs.get_doc_requirements().into_iter().map(|r| r.to_string()).collect();
         let span = item.span(self.tcx);
         let visibility = item.visibility(self.tcx);
         let clean::Item { name, item_id, .. } = item;
@@ -73,6 +75,7 @@ pub(super) fn convert_item(&self, item: clean::Item) -> Option<Item> {
             docs,
             attrs,
             deprecation: deprecation.map(from_deprecation),
+            requirements,
             inner,
             links,
         })
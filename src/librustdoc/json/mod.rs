@@ -227,6 +227,18 @@ fn after_krate(&mut self) -> Result<(), Error> {
 
         let index = (*self.index).clone().into_inner();
 
+        debug!("Constructing the reverse index of requirements");
+        let mut requirements = FxHashMap::<String, Vec<types::Id>>::default();
+        for item in index.values() {
+            for requirement in &item.requirements {
+                requirements.entry(requirement.clone()).or_default().push(item.id.clone());
+            }
+        }
+        // Items come from a hash map, sort them to make the output deterministic.
+        for ids in requirements.values_mut() {
+            ids.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+
         debug!("Constructing Output");
         // This needs to be the default HashMap for compatibility with the public interface for
         // rustdoc-json-types
@@ -270,6 +282,7 @@ fn after_krate(&mut self) -> Result<(), Error> {
                     )
                 })
                 .collect(),
+            requirements: requirements.into_iter().collect(),
             format_version: types::FORMAT_VERSION,
         };
         let out_dir = self.out_path.clone();
@@ -8,7 +8,7 @@
 use std::path::PathBuf;
 
 /// rustdoc format-version.
-pub const FORMAT_VERSION: u32 = 27;
+pub const FORMAT_VERSION: u32 = 28;
 
 /// A `Crate` is the root of the emitted JSON blob. It contains all type/documentation information
 /// about the language items in the local crate, as well as info about external items to allow
@@ -28,6 +28,9 @@ pub struct Crate {
     pub paths: FxHashMap<Id, ItemSummary>,
     /// Maps `crate_id` of items to a crate name and html_root_url if it exists.
     pub external_crates: FxHashMap<u32, ExternalCrate>,
+    /// Maps the IDs of the requirements set with `#[doc(requirement = "...")]` to the items of
+    /// the local crate implementing them. This is the reverse of [`Item::requirements`].
+    pub requirements: FxHashMap<String, Vec<Id>>,
     /// A single version number to be used in the future when making backwards incompatible changes
     /// to the JSON output.
     pub format_version: u32,
@@ -83,6 +86,9 @@ pub struct Item {
     /// Stringified versions of the attributes on this item (e.g. `"#[inline]"`)
     pub attrs: Vec<String>,
     pub deprecation: Option<Deprecation>,
+    /// IDs of the requirements implemented by this item, as set with
+    /// `#[doc(requirement = "...")]` (e.g. `["SWR-123"]`).
+    pub requirements: Vec<String>,
     pub inner: ItemEnum,
 }
 
@@ -0,0 +1,572 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Flattening of the public API of a crate into a map of items, keyed by their kind and path.
+//!
+//! The modules are walked from the crate root, following re-exports, so that every path an item
+//! can be named with is part of the API. The members of an item (fields, variants, associated
+//! items and trait implementations) are only listed under its canonical path.
+
+use crate::render::Renderer;
+use rustdoc_json_types::{
+    Crate, Id, Impl, Import, Item, ItemEnum, MacroKind, StructKind, VariantKind, Visibility,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Public API of a crate, keyed by the kind and path of the items, like `fn core::mem::swap`.
+pub(crate) type Api = BTreeMap<String, ApiItem>;
+
+/// Role of an item, determining how changes to it are classified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ItemClass {
+    Trait,
+    /// Associated item of a trait.
+    TraitItem,
+    Variant,
+    Field,
+    TraitImpl,
+    /// Implementation of an auto trait, like `Send`, derived by the compiler.
+    AutoTraitImpl,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ApiItem {
+    pub(crate) class: ItemClass,
+    /// Key of the item this one is a member of, like the struct of a field.
+    pub(crate) parent: Option<String>,
+    /// Signature of the item, without its `const` and `unsafe` qualifiers.
+    pub(crate) signature: String,
+    /// Signature of the item as shown in the reports.
+    pub(crate) display: String,
+    pub(crate) is_const: bool,
+    pub(crate) is_unsafe: bool,
+    pub(crate) non_exhaustive: bool,
+    /// Whether the struct or variant has fields that are not public.
+    pub(crate) has_private_fields: bool,
+    /// Whether the associated item of a trait has a default.
+    pub(crate) has_default: bool,
+    pub(crate) deprecated: bool,
+    pub(crate) docs: Option<String>,
+}
+
+impl ApiItem {
+    fn new(class: ItemClass, item: &Item, signature: String) -> Self {
+        ApiItem {
+            class,
+            parent: None,
+            display: signature.clone(),
+            signature,
+            is_const: false,
+            is_unsafe: false,
+            non_exhaustive: item.attrs.iter().any(|attr| attr == "#[non_exhaustive]"),
+            has_private_fields: false,
+            has_default: false,
+            deprecated: item.deprecation.is_some(),
+            docs: item.docs.clone(),
+        }
+    }
+}
+
+pub(crate) fn collect(krate: &Crate) -> Api {
+    let mut collector = Collector {
+        krate,
+        renderer: Renderer::new(krate),
+        found: Vec::new(),
+        api: Api::new(),
+        seen_impls: HashSet::new(),
+        traits: Vec::new(),
+    };
+    let root = &krate.index[&krate.root];
+    collector.visit_module(&krate.root, &[item_name(root).to_string()], &mut Vec::new());
+
+    let found = std::mem::take(&mut collector.found);
+    let mut paths: HashMap<&Id, Vec<&[String]>> = HashMap::new();
+    for (path, id) in &found {
+        paths.entry(*id).or_default().push(path);
+    }
+    for (path, id) in &found {
+        let canonical = canonical_path(krate, id, &paths[id]);
+        collector.item(path, id, path.as_slice() == canonical);
+    }
+
+    // Implementations of traits for types of other crates are only reachable from the traits.
+    for (trait_key, implementations) in std::mem::take(&mut collector.traits) {
+        for id in implementations {
+            collector.impl_(&trait_key, None, id);
+        }
+    }
+    collector.api
+}
+
+/// Picks the path the members of an item are listed under: the one rustdoc considers canonical if
+/// the item is reachable through it, or the shortest one otherwise.
+fn canonical_path<'a>(krate: &Crate, id: &Id, paths: &[&'a [String]]) -> &'a [String] {
+    if let Some(summary) = krate.paths.get(id) {
+        if let Some(path) = paths.iter().copied().find(|path| *path == summary.path.as_slice()) {
+            return path;
+        }
+    }
+    paths.iter().copied().min_by_key(|path| (path.len(), *path)).unwrap()
+}
+
+fn item_name(item: &Item) -> &str {
+    item.name.as_deref().unwrap_or("<unnamed>")
+}
+
+struct Collector<'a> {
+    krate: &'a Crate,
+    renderer: Renderer<'a>,
+    /// Public paths of the items of the crate, in the order they were found.
+    found: Vec<(Vec<String>, &'a Id)>,
+    api: Api,
+    seen_impls: HashSet<&'a Id>,
+    /// Keys and implementations of the traits found, visited once all types have been.
+    traits: Vec<(String, &'a [Id])>,
+}
+
+impl<'a> Collector<'a> {
+    fn visit_module(&mut self, id: &'a Id, path: &[String], stack: &mut Vec<&'a Id>) {
+        if stack.contains(&id) {
+            return;
+        }
+        let Some(ItemEnum::Module(module)) = self.krate.index.get(id).map(|item| &item.inner)
+        else {
+            return;
+        };
+        stack.push(id);
+        for child_id in &module.items {
+            let Some(child) = self.krate.index.get(child_id) else { continue };
+            if !self.is_public(child) {
+                continue;
+            }
+            match &child.inner {
+                ItemEnum::Import(import) => self.visit_import(child, import, path, stack),
+                ItemEnum::Module(module) if module.is_stripped => {}
+                ItemEnum::Impl(_) | ItemEnum::ExternCrate { .. } => {}
+                inner => {
+                    let child_path = join(path, item_name(child));
+                    if let ItemEnum::Module(_) = inner {
+                        self.visit_module(child_id, &child_path, stack);
+                    }
+                    self.found.push((child_path, child_id));
+                }
+            }
+        }
+        stack.pop();
+    }
+
+    fn visit_import(
+        &mut self,
+        item: &'a Item,
+        import: &'a Import,
+        path: &[String],
+        stack: &mut Vec<&'a Id>,
+    ) {
+        let target = import.id.as_ref().and_then(|id| Some((id, self.krate.index.get(id)?)));
+        match target {
+            Some((target_id, target)) if import.glob => {
+                if let ItemEnum::Module(_) = target.inner {
+                    self.visit_module(target_id, path, stack);
+                } else {
+                    self.reexport(item, import, path);
+                }
+            }
+            Some((target_id, target)) => {
+                let child_path = join(path, &import.name);
+                if let ItemEnum::Module(_) = target.inner {
+                    self.visit_module(target_id, &child_path, stack);
+                }
+                self.found.push((child_path, target_id));
+            }
+            // Items of other crates are not in the index, only the re-export itself is recorded.
+            None => self.reexport(item, import, path),
+        }
+    }
+
+    fn reexport(&mut self, item: &Item, import: &Import, path: &[String]) {
+        let path = path.join("::");
+        let (key, signature) = if import.glob {
+            (format!("use {}::* in {path}", import.source), format!("pub use {}::*", import.source))
+        } else if import.source.ends_with(&format!("::{}", import.name)) {
+            (format!("use {path}::{}", import.name), format!("pub use {}", import.source))
+        } else {
+            let signature = format!("pub use {} as {}", import.source, import.name);
+            (format!("use {path}::{}", import.name), signature)
+        };
+        self.insert(key, ApiItem::new(ItemClass::Other, item, signature));
+    }
+
+    /// Records an item reachable at `path`, and its members if the path is the canonical one.
+    fn item(&mut self, path: &[String], id: &'a Id, canonical: bool) {
+        let item = &self.krate.index[id];
+        let name = item_name(item);
+        let path = path.join("::");
+        let r = &self.renderer;
+        match &item.inner {
+            ItemEnum::Module(_) => {
+                self.insert(
+                    format!("mod {path}"),
+                    ApiItem::new(ItemClass::Other, item, format!("mod {name}")),
+                );
+            }
+            ItemEnum::Struct(struct_) => {
+                let (fields, has_private_fields, shape) = match &struct_.kind {
+                    StructKind::Unit => (Vec::new(), false, ";"),
+                    StructKind::Tuple(fields) => {
+                        (fields.iter().flatten().collect(), fields.contains(&None), "(..)")
+                    }
+                    StructKind::Plain { fields, fields_stripped } => {
+                        (fields.iter().collect(), *fields_stripped, " { .. }")
+                    }
+                };
+                let signature = format!(
+                    "struct {name}{}{}{shape}",
+                    r.generic_params(&struct_.generics.params),
+                    r.where_clause(&struct_.generics),
+                );
+                let key = format!("struct {path}");
+                let mut api_item = ApiItem::new(ItemClass::Other, item, signature);
+                api_item.has_private_fields = has_private_fields;
+                self.insert(key.clone(), api_item);
+                if canonical {
+                    self.fields(&key, &path, &fields, true);
+                    self.impls(&key, &path, &struct_.impls);
+                }
+            }
+            ItemEnum::Union(union_) => {
+                let signature = format!(
+                    "union {name}{}{}",
+                    r.generic_params(&union_.generics.params),
+                    r.where_clause(&union_.generics),
+                );
+                let key = format!("union {path}");
+                let mut api_item = ApiItem::new(ItemClass::Other, item, signature);
+                api_item.has_private_fields = union_.fields_stripped;
+                self.insert(key.clone(), api_item);
+                if canonical {
+                    self.fields(&key, &path, &union_.fields.iter().collect::<Vec<_>>(), true);
+                    self.impls(&key, &path, &union_.impls);
+                }
+            }
+            ItemEnum::Enum(enum_) => {
+                let signature = format!(
+                    "enum {name}{}{}",
+                    r.generic_params(&enum_.generics.params),
+                    r.where_clause(&enum_.generics),
+                );
+                let key = format!("enum {path}");
+                self.insert(key.clone(), ApiItem::new(ItemClass::Other, item, signature));
+                if canonical {
+                    for variant in &enum_.variants {
+                        self.variant(&key, &path, variant);
+                    }
+                    self.impls(&key, &path, &enum_.impls);
+                }
+            }
+            ItemEnum::Function(function) => {
+                let mut api_item =
+                    ApiItem::new(ItemClass::Other, item, r.function(name, function, false));
+                api_item.display = r.function(name, function, true);
+                api_item.is_const = function.header.const_;
+                api_item.is_unsafe = function.header.unsafe_;
+                self.insert(format!("fn {path}"), api_item);
+            }
+            ItemEnum::Trait(trait_) => {
+                let bounds = if trait_.bounds.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", r.bounds(&trait_.bounds))
+                };
+                let signature = format!(
+                    "{}trait {name}{}{bounds}{}",
+                    if trait_.is_auto { "auto " } else { "" },
+                    r.generic_params(&trait_.generics.params),
+                    r.where_clause(&trait_.generics),
+                );
+                let key = format!("trait {path}");
+                let mut api_item = ApiItem::new(ItemClass::Trait, item, signature.clone());
+                if trait_.is_unsafe {
+                    api_item.display = format!("unsafe {signature}");
+                }
+                api_item.is_unsafe = trait_.is_unsafe;
+                self.insert(key.clone(), api_item);
+                if canonical {
+                    for id in &trait_.items {
+                        self.assoc_item(&key, &path, id, ItemClass::TraitItem, None);
+                    }
+                    self.traits.push((key, &trait_.implementations));
+                }
+            }
+            ItemEnum::TraitAlias(alias) => {
+                let signature = format!(
+                    "trait {name}{} = {}{}",
+                    r.generic_params(&alias.generics.params),
+                    r.bounds(&alias.params),
+                    r.where_clause(&alias.generics),
+                );
+                self.insert(
+                    format!("trait alias {path}"),
+                    ApiItem::new(ItemClass::Other, item, signature),
+                );
+            }
+            ItemEnum::TypeAlias(alias) => {
+                let signature = format!(
+                    "type {name}{}{} = {}",
+                    r.generic_params(&alias.generics.params),
+                    r.where_clause(&alias.generics),
+                    r.ty(&alias.type_),
+                );
+                self.insert(
+                    format!("type {path}"),
+                    ApiItem::new(ItemClass::Other, item, signature),
+                );
+            }
+            ItemEnum::Constant(constant) => {
+                let signature = format!("const {name}: {}", r.ty(&constant.type_));
+                self.insert(
+                    format!("const {path}"),
+                    ApiItem::new(ItemClass::Other, item, signature),
+                );
+            }
+            ItemEnum::Static(static_) => {
+                let signature = format!(
+                    "static {}{name}: {}",
+                    if static_.mutable { "mut " } else { "" },
+                    r.ty(&static_.type_)
+                );
+                self.insert(
+                    format!("static {path}"),
+                    ApiItem::new(ItemClass::Other, item, signature),
+                );
+            }
+            ItemEnum::ForeignType => {
+                let signature = format!("extern type {name}");
+                self.insert(
+                    format!("extern type {path}"),
+                    ApiItem::new(ItemClass::Other, item, signature),
+                );
+            }
+            ItemEnum::Macro(_) => {
+                let signature = format!("macro_rules! {name}");
+                self.insert(
+                    format!("macro {path}"),
+                    ApiItem::new(ItemClass::Other, item, signature),
+                );
+            }
+            ItemEnum::ProcMacro(proc_macro) => {
+                let signature = match proc_macro.kind {
+                    MacroKind::Bang => format!("#[proc_macro] {name}"),
+                    MacroKind::Attr => format!("#[proc_macro_attribute] {name}"),
+                    MacroKind::Derive if proc_macro.helpers.is_empty() => {
+                        format!("#[proc_macro_derive({name})]")
+                    }
+                    MacroKind::Derive => format!(
+                        "#[proc_macro_derive({name}, attributes({}))]",
+                        proc_macro.helpers.join(", ")
+                    ),
+                };
+                self.insert(
+                    format!("macro {path}"),
+                    ApiItem::new(ItemClass::Other, item, signature),
+                );
+            }
+            ItemEnum::Primitive(primitive) => {
+                let key = format!("primitive {path}");
+                let signature = format!("primitive {}", primitive.name);
+                self.insert(key.clone(), ApiItem::new(ItemClass::Other, item, signature));
+                if canonical {
+                    self.impls(&key, &path, &primitive.impls);
+                }
+            }
+            ItemEnum::ExternCrate { .. }
+            | ItemEnum::Import(_)
+            | ItemEnum::StructField(_)
+            | ItemEnum::Variant(_)
+            | ItemEnum::Impl(_)
+            | ItemEnum::OpaqueTy(_)
+            | ItemEnum::AssocConst { .. }
+            | ItemEnum::AssocType { .. } => {}
+        }
+    }
+
+    fn fields(&mut self, parent: &str, path: &str, fields: &[&Id], check_visibility: bool) {
+        for id in fields {
+            let Some(item) = self.krate.index.get(id) else { continue };
+            let ItemEnum::StructField(ty) = &item.inner else { continue };
+            if check_visibility && !self.is_public(item) {
+                continue;
+            }
+            let name = item_name(item);
+            let signature = format!("{name}: {}", self.renderer.ty(ty));
+            let mut api_item = ApiItem::new(ItemClass::Field, item, signature);
+            api_item.parent = Some(parent.to_string());
+            self.insert(format!("field {path}::{name}"), api_item);
+        }
+    }
+
+    fn variant(&mut self, parent: &str, enum_path: &str, id: &Id) {
+        let Some(item) = self.krate.index.get(id) else { return };
+        let ItemEnum::Variant(variant) = &item.inner else { return };
+        let name = item_name(item);
+        let (fields, has_private_fields, shape) = match &variant.kind {
+            VariantKind::Plain => (Vec::new(), false, ""),
+            VariantKind::Tuple(fields) => {
+                (fields.iter().flatten().collect(), fields.contains(&None), "(..)")
+            }
+            VariantKind::Struct { fields, fields_stripped } => {
+                (fields.iter().collect(), *fields_stripped, " { .. }")
+            }
+        };
+        let mut signature = format!("{name}{shape}");
+        if let Some(discriminant) = &variant.discriminant {
+            signature.push_str(&format!(" = {}", discriminant.value));
+        }
+        let key = format!("variant {enum_path}::{name}");
+        let mut api_item = ApiItem::new(ItemClass::Variant, item, signature);
+        api_item.parent = Some(parent.to_string());
+        api_item.has_private_fields = has_private_fields;
+        self.insert(key.clone(), api_item);
+        // Fields of variants are public even though they have no visibility of their own.
+        self.fields(&key, &format!("{enum_path}::{name}"), &fields, false);
+    }
+
+    fn impls(&mut self, parent: &str, path: &str, impls: &'a [Id]) {
+        for id in impls {
+            self.impl_(parent, Some(path), id);
+        }
+    }
+
+    /// Records an implementation: the associated items of inherent implementations are members of
+    /// the type at `type_path`, while trait implementations are items of their own.
+    fn impl_(&mut self, parent: &str, type_path: Option<&str>, id: &'a Id) {
+        if !self.seen_impls.insert(id) {
+            return;
+        }
+        let Some(item) = self.krate.index.get(id) else { return };
+        let ItemEnum::Impl(impl_) = &item.inner else { return };
+        // Blanket implementations like `impl<T> From<T> for T` are part of the API of the crate
+        // defining them, rather than of every type.
+        if impl_.blanket_impl.is_some() {
+            return;
+        }
+        let Some(trait_) = &impl_.trait_ else {
+            if let Some(type_path) = type_path {
+                for assoc in &impl_.items {
+                    self.assoc_item(parent, type_path, assoc, ItemClass::Other, Some(impl_));
+                }
+            }
+            return;
+        };
+        let key = format!(
+            "impl {}{} for {}",
+            if impl_.negative { "!" } else { "" },
+            self.renderer.path(trait_),
+            self.renderer.ty(&impl_.for_),
+        );
+        let class = if impl_.synthetic { ItemClass::AutoTraitImpl } else { ItemClass::TraitImpl };
+        let mut api_item = ApiItem::new(class, item, self.renderer.impl_header(impl_, false));
+        api_item.display = self.renderer.impl_header(impl_, true);
+        api_item.is_unsafe = impl_.is_unsafe;
+        api_item.parent = Some(parent.to_string());
+        // The documentation of trait implementations is rarely relevant to their users.
+        api_item.docs = None;
+        self.insert(key, api_item);
+    }
+
+    /// Records an associated item of a trait, or of an inherent implementation when `impl_` is
+    /// set. Associated items of generic implementations include the header of the implementation
+    /// in their signature, as it restricts when they are available.
+    fn assoc_item(
+        &mut self,
+        parent: &str,
+        path: &str,
+        id: &Id,
+        class: ItemClass,
+        impl_: Option<&Impl>,
+    ) {
+        let Some(item) = self.krate.index.get(id) else { return };
+        if impl_.is_some() && !self.is_public(item) {
+            return;
+        }
+        let name = item_name(item);
+        let r = &self.renderer;
+        let (kind, mut api_item) = match &item.inner {
+            ItemEnum::Function(function) => {
+                let mut api_item = ApiItem::new(class, item, r.function(name, function, false));
+                api_item.display = r.function(name, function, true);
+                api_item.is_const = function.header.const_;
+                api_item.is_unsafe = function.header.unsafe_;
+                api_item.has_default = function.has_body;
+                ("fn", api_item)
+            }
+            ItemEnum::AssocConst { type_, default } => {
+                let signature = format!("const {name}: {}", r.ty(type_));
+                let mut api_item = ApiItem::new(class, item, signature);
+                api_item.has_default = default.is_some();
+                ("const", api_item)
+            }
+            ItemEnum::AssocType { generics, bounds, default } => {
+                let mut signature = format!("type {name}{}", r.generic_params(&generics.params));
+                if !bounds.is_empty() {
+                    signature.push_str(&format!(": {}", r.bounds(bounds)));
+                }
+                signature.push_str(&r.where_clause(generics));
+                if let (Some(default), None) = (default, impl_) {
+                    signature.push_str(&format!(" = {}", r.ty(default)));
+                }
+                let mut api_item = ApiItem::new(class, item, signature);
+                api_item.has_default = default.is_some();
+                ("type", api_item)
+            }
+            _ => return,
+        };
+        api_item.parent = Some(parent.to_string());
+
+        let mut key = format!("assoc {kind} {path}::{name}");
+        if let Some(impl_) = impl_ {
+            if !impl_.generics.params.is_empty() || !impl_.generics.where_predicates.is_empty() {
+                let header = r.impl_header(impl_, true);
+                api_item.signature = format!("{header} {{ {} }}", api_item.signature);
+                api_item.display = format!("{header} {{ {} }}", api_item.display);
+            }
+            // Inherent implementations for different instances of a generic type can define
+            // associated items with the same name.
+            if self.api.contains_key(&key) {
+                key = format!("{key} in impl {}", r.ty(&impl_.for_));
+            }
+        }
+        self.insert(key, api_item);
+    }
+
+    fn insert(&mut self, key: String, item: ApiItem) {
+        if self.api.contains_key(&key) {
+            self.api.insert(self.disambiguate(&key), item);
+        } else {
+            self.api.insert(key, item);
+        }
+    }
+
+    fn disambiguate(&self, key: &str) -> String {
+        let mut n = 2;
+        while self.api.contains_key(&format!("{key} #{n}")) {
+            n += 1;
+        }
+        format!("{key} #{n}")
+    }
+
+    /// Whether the item is public, when the crate was documented with its private items. Items
+    /// without a visibility, like the associated items of traits, are checked by their callers.
+    fn is_public(&self, item: &Item) -> bool {
+        !self.krate.includes_private || item.visibility == Visibility::Public
+    }
+}
+
+fn join(path: &[String], name: &str) -> Vec<String> {
+    let mut path = path.to_vec();
+    path.push(name.to_string());
+    path
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,186 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+use super::*;
+use serde_json::{json, Value};
+
+fn item(id: &str, name: Option<&str>, visibility: &str, inner: Value) -> Value {
+    json!({
+        "id": id,
+        "crate_id": 0,
+        "name": name,
+        "span": null,
+        "visibility": visibility,
+        "docs": null,
+        "links": {},
+        "attrs": [],
+        "deprecation": null,
+        "requirements": [],
+        "inner": inner,
+    })
+}
+
+fn no_generics() -> Value {
+    json!({ "params": [], "where_predicates": [] })
+}
+
+fn impl_(trait_: Value, items: &[&str], synthetic: bool) -> Value {
+    json!({ "impl": {
+        "is_unsafe": false,
+        "generics": no_generics(),
+        "provided_trait_methods": [],
+        "trait": trait_,
+        "for": { "resolved_path": { "name": "S", "id": "0:1", "args": null } },
+        "items": items,
+        "negative": false,
+        "synthetic": synthetic,
+        "blanket_impl": null,
+    }})
+}
+
+fn function(inputs: Value, output: Value, const_: bool, has_body: bool) -> Value {
+    json!({ "function": {
+        "decl": { "inputs": inputs, "output": output, "c_variadic": false },
+        "generics": no_generics(),
+        "header": { "const": const_, "unsafe": false, "async": false, "abi": "Rust" },
+        "has_body": has_body,
+    }})
+}
+
+/// Crate equivalent to:
+///
+/// ```ignore (json)
+/// pub struct S { pub x: u8 }
+/// impl S { pub const fn new() -> Self { .. } }
+/// mod inner { pub trait T { fn f(&self); } }
+/// pub use inner::T;
+/// pub use core::mem::swap;
+/// ```
+fn krate() -> Crate {
+    let items = [
+        item(
+            "0:0",
+            Some("krate"),
+            "public",
+            json!({ "module": {
+                "is_crate": true, "items": ["0:1", "0:6", "0:8", "0:10"], "is_stripped": false,
+            }}),
+        ),
+        item(
+            "0:1",
+            Some("S"),
+            "public",
+            json!({ "struct": {
+                "kind": { "plain": { "fields": ["0:2"], "fields_stripped": false } },
+                "generics": no_generics(),
+                "impls": ["0:3", "0:5"],
+            }}),
+        ),
+        item("0:2", Some("x"), "public", json!({ "struct_field": { "primitive": "u8" } })),
+        item("0:3", None, "default", impl_(Value::Null, &["0:4"], false)),
+        item(
+            "0:4",
+            Some("new"),
+            "public",
+            function(json!([]), json!({ "generic": "Self" }), true, true),
+        ),
+        item(
+            "0:5",
+            None,
+            "default",
+            impl_(json!({ "name": "Send", "id": "1:1", "args": null }), &[], true),
+        ),
+        item(
+            "0:6",
+            Some("inner"),
+            "default",
+            json!({ "module": {
+                "is_crate": false, "items": ["0:7"], "is_stripped": true,
+            }}),
+        ),
+        item(
+            "0:7",
+            Some("T"),
+            "public",
+            json!({ "trait": {
+                "is_auto": false,
+                "is_unsafe": false,
+                "items": ["0:9"],
+                "generics": no_generics(),
+                "bounds": [],
+                "implementations": [],
+            }}),
+        ),
+        item(
+            "0:8",
+            None,
+            "public",
+            json!({ "import": {
+                "source": "inner::T", "name": "T", "id": "0:7", "glob": false,
+            }}),
+        ),
+        item(
+            "0:9",
+            Some("f"),
+            "default",
+            function(
+                json!([["self", { "borrowed_ref": {
+                    "lifetime": null, "mutable": false, "type": { "generic": "Self" },
+                }}]]),
+                Value::Null,
+                false,
+                false,
+            ),
+        ),
+        item(
+            "0:10",
+            None,
+            "public",
+            json!({ "import": {
+                "source": "core::mem::swap", "name": "swap", "id": "1:2", "glob": false,
+            }}),
+        ),
+    ];
+    let index: serde_json::Map<_, _> =
+        items.into_iter().map(|item| (item["id"].as_str().unwrap().to_string(), item)).collect();
+    serde_json::from_value(json!({
+        "root": "0:0",
+        "crate_version": null,
+        "includes_private": false,
+        "index": index,
+        "paths": {
+            "0:1": { "crate_id": 0, "path": ["krate", "S"], "kind": "struct" },
+            "0:7": { "crate_id": 0, "path": ["krate", "inner", "T"], "kind": "trait" },
+            "1:1": { "crate_id": 1, "path": ["core", "marker", "Send"], "kind": "trait" },
+        },
+        "external_crates": {},
+        "requirements": {},
+        "format_version": rustdoc_json_types::FORMAT_VERSION,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn collects_public_api() {
+    let api = collect(&krate());
+    let signatures: Vec<_> =
+        api.iter().map(|(key, item)| (key.as_str(), item.display.as_str())).collect();
+    assert_eq!(
+        signatures,
+        [
+            ("assoc fn krate::S::new", "const fn new() -> Self"),
+            ("assoc fn krate::T::f", "fn f(&self)"),
+            ("field krate::S::x", "x: u8"),
+            ("impl core::marker::Send for krate::S", "impl core::marker::Send for krate::S"),
+            ("struct krate::S", "struct S { .. }"),
+            ("trait krate::T", "trait T"),
+            ("use krate::swap", "pub use core::mem::swap"),
+        ]
+    );
+
+    let new = &api["assoc fn krate::S::new"];
+    assert_eq!((new.signature.as_str(), new.is_const), ("fn new() -> Self", true));
+    assert_eq!(api["assoc fn krate::T::f"].class, ItemClass::TraitItem);
+    assert!(!api["assoc fn krate::T::f"].has_default);
+    assert_eq!(api["impl core::marker::Send for krate::S"].class, ItemClass::AutoTraitImpl);
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Comparison of two public APIs, classifying each change by the semver bump it requires.
+//!
+//! The classification follows the SemVer compatibility chapter of the Cargo book, erring on the
+//! side of reporting a change as breaking when it can't be determined from the documentation. For
+//! example, any change to the signature of a function is breaking, even if it only relaxed a bound.
+
+use crate::api::{Api, ApiItem, ItemClass};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Severity {
+    Patch,
+    Minor,
+    Breaking,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Change {
+    /// Key of the item in the API, like `fn core::mem::swap`.
+    pub(crate) item: String,
+    pub(crate) kind: ChangeKind,
+    pub(crate) severity: Severity,
+    /// Signature of the item before the change, if it existed.
+    pub(crate) old: Option<String>,
+    /// Signature of the item after the change, if it still exists.
+    pub(crate) new: Option<String>,
+    /// What changed, justifying the severity.
+    pub(crate) reasons: Vec<String>,
+}
+
+/// Compares the two APIs, returning the changes sorted by decreasing severity. Members removed
+/// together with their parent, like the fields of a removed struct, are not reported, while the
+/// ones added together with their parent are reported as minor additions.
+pub(crate) fn diff(old: &Api, new: &Api) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (key, old_item) in old {
+        match new.get(key) {
+            Some(new_item) => changes.extend(compare(key, old_item, new_item)),
+            None if old_item.parent.as_ref().is_some_and(|parent| !new.contains_key(parent)) => {}
+            None => changes.push(Change {
+                item: key.clone(),
+                kind: ChangeKind::Removed,
+                severity: Severity::Breaking,
+                old: Some(old_item.display.clone()),
+                new: None,
+                reasons: vec![removal_reason(old_item).into()],
+            }),
+        }
+    }
+    for (key, new_item) in new {
+        if old.contains_key(key) {
+            continue;
+        }
+        let old_parent = new_item.parent.as_ref().and_then(|parent| old.get(parent));
+        let (severity, reason) = classify_addition(key, new_item, old_parent);
+        changes.push(Change {
+            item: key.clone(),
+            kind: ChangeKind::Added,
+            severity,
+            old: None,
+            new: Some(new_item.display.clone()),
+            reasons: vec![reason.into()],
+        });
+    }
+    changes.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.item.cmp(&b.item)));
+    changes
+}
+
+fn removal_reason(item: &ApiItem) -> &'static str {
+    match item.class {
+        ItemClass::AutoTraitImpl => "the auto trait is not implemented anymore",
+        ItemClass::TraitImpl => "the trait is not implemented anymore",
+        _ => "removed from the public API",
+    }
+}
+
+fn classify_addition(
+    key: &str,
+    item: &ApiItem,
+    old_parent: Option<&ApiItem>,
+) -> (Severity, &'static str) {
+    match (item.class, old_parent) {
+        (_, None) if item.parent.is_some() => (Severity::Minor, "added together with its parent"),
+        (ItemClass::Variant, Some(parent)) if !parent.non_exhaustive => (
+            Severity::Breaking,
+            "variant added to an exhaustive enum, breaking exhaustive matches on it",
+        ),
+        (ItemClass::Variant, _) => (Severity::Minor, "variant added to a #[non_exhaustive] enum"),
+        (ItemClass::Field, Some(parent))
+            if !parent.non_exhaustive
+                && !parent.has_private_fields
+                && !parent_key_is(item, "union ") =>
+        {
+            (
+                Severity::Breaking,
+                "field added to a struct or variant without private fields, breaking struct \
+                 literals and exhaustive patterns",
+            )
+        }
+        (ItemClass::Field, _) => (Severity::Minor, "field added"),
+        (ItemClass::TraitItem, _) if !item.has_default => (
+            Severity::Breaking,
+            "associated item without a default added to a trait, breaking its implementations",
+        ),
+        (ItemClass::TraitItem, _) => {
+            (Severity::Minor, "associated item with a default added to a trait")
+        }
+        (ItemClass::AutoTraitImpl, _) => (Severity::Minor, "auto trait now implemented"),
+        (ItemClass::TraitImpl, _) => (Severity::Minor, "trait now implemented"),
+        _ if key.starts_with("use ") => (Severity::Minor, "re-export added"),
+        _ => (Severity::Minor, "added to the public API"),
+    }
+}
+
+fn parent_key_is(item: &ApiItem, prefix: &str) -> bool {
+    item.parent.as_deref().is_some_and(|parent| parent.starts_with(prefix))
+}
+
+fn compare(key: &str, old: &ApiItem, new: &ApiItem) -> Option<Change> {
+    let mut reasons = Vec::new();
+    let mut severity = None;
+    let mut note = |change_severity: Severity, reason: &str| {
+        severity = severity.max(Some(change_severity));
+        reasons.push(reason.to_string());
+    };
+
+    if old.signature != new.signature {
+        note(Severity::Breaking, "signature changed");
+    }
+    match (old.is_unsafe, new.is_unsafe) {
+        (false, true) => note(Severity::Breaking, "became unsafe"),
+        // Implementations of the trait are written with `unsafe impl`, which is an error for
+        // traits that are not unsafe.
+        (true, false) if new.class == ItemClass::Trait => {
+            note(Severity::Breaking, "not unsafe anymore, breaking its implementations")
+        }
+        (true, false) => note(Severity::Minor, "not unsafe anymore"),
+        _ => {}
+    }
+    match (old.is_const, new.is_const) {
+        (false, true) => note(Severity::Minor, "became const"),
+        (true, false) => note(Severity::Breaking, "not const anymore"),
+        _ => {}
+    }
+    match (old.non_exhaustive, new.non_exhaustive) {
+        (false, true) => note(Severity::Breaking, "became #[non_exhaustive]"),
+        (true, false) => note(Severity::Minor, "not #[non_exhaustive] anymore"),
+        _ => {}
+    }
+    match (old.has_private_fields, new.has_private_fields) {
+        (false, true) => note(
+            Severity::Breaking,
+            "gained private fields, so it can't be constructed outside of its crate anymore",
+        ),
+        (true, false) => note(Severity::Minor, "all fields are public now"),
+        _ => {}
+    }
+    if old.class == ItemClass::TraitItem {
+        match (old.has_default, new.has_default) {
+            (false, true) => note(Severity::Minor, "default added"),
+            (true, false) => {
+                note(Severity::Breaking, "default removed, breaking implementations relying on it")
+            }
+            _ => {}
+        }
+    }
+    match (old.deprecated, new.deprecated) {
+        (false, true) => note(Severity::Minor, "deprecated"),
+        (true, false) => note(Severity::Patch, "not deprecated anymore"),
+        _ => {}
+    }
+    if old.docs != new.docs {
+        note(Severity::Patch, "documentation changed");
+    }
+
+    Some(Change {
+        item: key.to_string(),
+        kind: ChangeKind::Changed,
+        severity: severity?,
+        old: Some(old.display.clone()),
+        new: Some(new.display.clone()),
+        reasons,
+    })
+}
+
+#[cfg(test)]
+mod tests;
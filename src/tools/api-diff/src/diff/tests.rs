@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+use super::*;
+
+fn item(class: ItemClass, parent: Option<&str>, signature: &str) -> ApiItem {
+    ApiItem {
+        class,
+        parent: parent.map(|parent| parent.to_string()),
+        signature: signature.into(),
+        display: signature.into(),
+        is_const: false,
+        is_unsafe: false,
+        non_exhaustive: false,
+        has_private_fields: false,
+        has_default: false,
+        deprecated: false,
+        docs: None,
+    }
+}
+
+fn api(items: &[(&str, ApiItem)]) -> Api {
+    items.iter().map(|(key, item)| (key.to_string(), item.clone())).collect()
+}
+
+fn summary(changes: &[Change]) -> Vec<(&str, ChangeKind, Severity)> {
+    changes.iter().map(|change| (change.item.as_str(), change.kind, change.severity)).collect()
+}
+
+#[test]
+fn identical() {
+    let old = api(&[("fn krate::f", item(ItemClass::Other, None, "fn f()"))]);
+    assert!(diff(&old, &old.clone()).is_empty());
+}
+
+#[test]
+fn removed_items_are_breaking_without_their_members() {
+    let strukt = item(ItemClass::Other, None, "struct S { .. }");
+    let field = item(ItemClass::Field, Some("struct krate::S"), "x: u8");
+    let f = item(ItemClass::Other, None, "fn f()");
+    let old = api(&[("struct krate::S", strukt), ("field krate::S::x", field), ("fn krate::f", f)]);
+    let new = api(&[]);
+    assert_eq!(
+        summary(&diff(&old, &new)),
+        [
+            ("fn krate::f", ChangeKind::Removed, Severity::Breaking),
+            ("struct krate::S", ChangeKind::Removed, Severity::Breaking),
+        ]
+    );
+}
+
+#[test]
+fn added_items_are_minor_with_their_members() {
+    let trait_ = item(ItemClass::Trait, None, "trait T");
+    let required = item(ItemClass::TraitItem, Some("trait krate::T"), "fn required(&self)");
+    let enum_ = item(ItemClass::Other, None, "enum E");
+    let variant = item(ItemClass::Variant, Some("enum krate::E"), "V");
+    let old = api(&[]);
+    let new = api(&[
+        ("trait krate::T", trait_),
+        ("assoc fn krate::T::required", required),
+        ("enum krate::E", enum_),
+        ("variant krate::E::V", variant),
+    ]);
+    let changes = diff(&old, &new);
+    assert_eq!(
+        summary(&changes),
+        [
+            ("assoc fn krate::T::required", ChangeKind::Added, Severity::Minor),
+            ("enum krate::E", ChangeKind::Added, Severity::Minor),
+            ("trait krate::T", ChangeKind::Added, Severity::Minor),
+            ("variant krate::E::V", ChangeKind::Added, Severity::Minor),
+        ]
+    );
+    assert_eq!(changes[0].reasons, ["added together with its parent"]);
+}
+
+#[test]
+fn variants() {
+    let enum_ = item(ItemClass::Other, None, "enum E");
+    let mut non_exhaustive = enum_.clone();
+    non_exhaustive.non_exhaustive = true;
+    let variant = |parent| item(ItemClass::Variant, Some(parent), "V");
+
+    let old = api(&[("enum krate::E", enum_.clone()), ("enum krate::N", non_exhaustive.clone())]);
+    let new = api(&[
+        ("enum krate::E", enum_),
+        ("enum krate::N", non_exhaustive),
+        ("variant krate::E::V", variant("enum krate::E")),
+        ("variant krate::N::V", variant("enum krate::N")),
+    ]);
+    assert_eq!(
+        summary(&diff(&old, &new)),
+        [
+            ("variant krate::E::V", ChangeKind::Added, Severity::Breaking),
+            ("variant krate::N::V", ChangeKind::Added, Severity::Minor),
+        ]
+    );
+}
+
+#[test]
+fn fields() {
+    let public = item(ItemClass::Other, None, "struct P { .. }");
+    let mut private = item(ItemClass::Other, None, "struct Q { .. }");
+    private.has_private_fields = true;
+    let union_ = item(ItemClass::Other, None, "union U");
+    let field = |parent| item(ItemClass::Field, Some(parent), "y: u8");
+
+    let old = api(&[
+        ("struct krate::P", public.clone()),
+        ("struct krate::Q", private.clone()),
+        ("union krate::U", union_.clone()),
+    ]);
+    let new = api(&[
+        ("struct krate::P", public),
+        ("struct krate::Q", private),
+        ("union krate::U", union_),
+        ("field krate::P::y", field("struct krate::P")),
+        ("field krate::Q::y", field("struct krate::Q")),
+        ("field krate::U::y", field("union krate::U")),
+    ]);
+    assert_eq!(
+        summary(&diff(&old, &new)),
+        [
+            ("field krate::P::y", ChangeKind::Added, Severity::Breaking),
+            ("field krate::Q::y", ChangeKind::Added, Severity::Minor),
+            ("field krate::U::y", ChangeKind::Added, Severity::Minor),
+        ]
+    );
+}
+
+#[test]
+fn trait_items() {
+    let trait_ = item(ItemClass::Trait, None, "trait T");
+    let required = item(ItemClass::TraitItem, Some("trait krate::T"), "fn required(&self)");
+    let mut provided = item(ItemClass::TraitItem, Some("trait krate::T"), "fn provided(&self)");
+    provided.has_default = true;
+
+    let old = api(&[("trait krate::T", trait_.clone())]);
+    let new = api(&[
+        ("trait krate::T", trait_),
+        ("assoc fn krate::T::required", required),
+        ("assoc fn krate::T::provided", provided),
+    ]);
+    assert_eq!(
+        summary(&diff(&old, &new)),
+        [
+            ("assoc fn krate::T::required", ChangeKind::Added, Severity::Breaking),
+            ("assoc fn krate::T::provided", ChangeKind::Added, Severity::Minor),
+        ]
+    );
+}
+
+#[test]
+fn auto_traits() {
+    let strukt = item(ItemClass::Other, None, "struct S;");
+    let send = item(ItemClass::AutoTraitImpl, Some("struct krate::S"), "impl Send for S");
+    let sync = item(ItemClass::AutoTraitImpl, Some("struct krate::S"), "impl Sync for S");
+
+    let old = api(&[("struct krate::S", strukt.clone()), ("impl core::marker::Send for S", send)]);
+    let new = api(&[("struct krate::S", strukt), ("impl core::marker::Sync for S", sync)]);
+    let changes = diff(&old, &new);
+    assert_eq!(
+        summary(&changes),
+        [
+            ("impl core::marker::Send for S", ChangeKind::Removed, Severity::Breaking),
+            ("impl core::marker::Sync for S", ChangeKind::Added, Severity::Minor),
+        ]
+    );
+    assert_eq!(changes[0].reasons, ["the auto trait is not implemented anymore"]);
+}
+
+#[test]
+fn changes() {
+    let check = |old: ApiItem, new: ApiItem, severity, reason: &str| {
+        let changes = diff(&api(&[("fn krate::f", old)]), &api(&[("fn krate::f", new)]));
+        assert_eq!(summary(&changes), [("fn krate::f", ChangeKind::Changed, severity)]);
+        assert_eq!(changes[0].reasons, [reason]);
+    };
+    let f = item(ItemClass::Other, None, "fn f()");
+
+    check(
+        f.clone(),
+        item(ItemClass::Other, None, "fn f(u8)"),
+        Severity::Breaking,
+        "signature changed",
+    );
+
+    let mut const_ = f.clone();
+    const_.is_const = true;
+    check(f.clone(), const_.clone(), Severity::Minor, "became const");
+    check(const_, f.clone(), Severity::Breaking, "not const anymore");
+
+    let mut unsafe_ = f.clone();
+    unsafe_.is_unsafe = true;
+    check(f.clone(), unsafe_.clone(), Severity::Breaking, "became unsafe");
+    check(unsafe_, f.clone(), Severity::Minor, "not unsafe anymore");
+
+    let mut non_exhaustive = f.clone();
+    non_exhaustive.non_exhaustive = true;
+    check(f.clone(), non_exhaustive.clone(), Severity::Breaking, "became #[non_exhaustive]");
+    check(non_exhaustive, f.clone(), Severity::Minor, "not #[non_exhaustive] anymore");
+
+    let mut deprecated = f.clone();
+    deprecated.deprecated = true;
+    check(f.clone(), deprecated.clone(), Severity::Minor, "deprecated");
+    check(deprecated, f.clone(), Severity::Patch, "not deprecated anymore");
+
+    let mut documented = f.clone();
+    documented.docs = Some("Does things.".into());
+    check(f, documented, Severity::Patch, "documentation changed");
+}
+
+#[test]
+fn unsafe_traits() {
+    let mut old = item(ItemClass::Trait, None, "trait T");
+    old.is_unsafe = true;
+    let new = item(ItemClass::Trait, None, "trait T");
+    let changes = diff(&api(&[("trait krate::T", old)]), &api(&[("trait krate::T", new)]));
+    assert_eq!(summary(&changes), [("trait krate::T", ChangeKind::Changed, Severity::Breaking)]);
+}
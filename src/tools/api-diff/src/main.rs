@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Reports the differences between the public APIs of two versions of a crate, from the output of
+//! `rustdoc --output-format json` for each of them.
+//!
+//! Every added, removed and changed item is classified by the semver bump it requires: breaking
+//! changes like removed items, changed signatures, lost auto traits or new `#[non_exhaustive]`
+//! attributes, minor changes like new items or trait implementations, and patch changes like
+//! updated documentation. For the standard library, the JSON files are generated by running
+//! `./x doc library/core --json` on each version of the source code.
+//!
+//! As releases don't all produce the same version of the rustdoc JSON format, the files are
+//! accepted from [`MIN_FORMAT_VERSION`] to the current [`FORMAT_VERSION`]: older files are upgraded
+//! to the current format before being compared.
+
+mod api;
+mod diff;
+mod render;
+mod report;
+
+use crate::diff::Severity;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, ValueEnum};
+use fs_err as fs;
+use rustdoc_json_types::{Crate, FORMAT_VERSION};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Oldest version of the rustdoc JSON format that can be upgraded to [`FORMAT_VERSION`].
+const MIN_FORMAT_VERSION: u32 = 27;
+
+#[derive(Parser)]
+struct Cli {
+    /// Rustdoc JSON output of the old version of the crate.
+    old: PathBuf,
+    /// Rustdoc JSON output of the new version of the crate.
+    new: PathBuf,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Write the report to this file rather than to the standard output.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Only list the changes with at least this severity. The required version bump still
+    /// accounts for all changes.
+    #[arg(long, value_enum, default_value_t = Severity::Patch)]
+    min_severity: Severity,
+    /// Exit with an error if there are breaking changes.
+    #[arg(long)]
+    deny_breaking: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Markdown,
+    Json,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let old = load(&cli.old)?;
+    let new = load(&cli.new)?;
+
+    let changes = diff::diff(&api::collect(&old), &api::collect(&new));
+    let (old_name, new_name) = (version_name(&old, &cli.old), version_name(&new, &cli.new));
+    let report = match cli.format {
+        Format::Text => report::text(&changes, cli.min_severity),
+        Format::Markdown => report::markdown(&changes, cli.min_severity, &old_name, &new_name),
+        Format::Json => report::json(&changes, cli.min_severity, &old_name, &new_name)?,
+    };
+    match &cli.output {
+        Some(output) => fs::write(output, report)?,
+        None => print!("{report}"),
+    }
+
+    if cli.deny_breaking && report::required_bump(&changes) == Some(Severity::Breaking) {
+        bail!("breaking changes were found between {old_name} and {new_name}");
+    }
+    Ok(())
+}
+
+fn load(path: &Path) -> Result<Crate> {
+    let contents = fs::read_to_string(path)?;
+    let mut json: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not a rustdoc JSON file", path.display()))?;
+    let format_version = json
+        .get("format_version")
+        .and_then(|version| version.as_u64())
+        .with_context(|| format!("{} is not a rustdoc JSON file", path.display()))?;
+    if !(u64::from(MIN_FORMAT_VERSION)..=u64::from(FORMAT_VERSION)).contains(&format_version) {
+        bail!(
+            "{} uses version {format_version} of the rustdoc JSON format, but only versions \
+             {MIN_FORMAT_VERSION} to {FORMAT_VERSION} are supported",
+            path.display()
+        );
+    }
+    upgrade(&mut json, format_version as u32);
+    serde_json::from_value(json)
+        .with_context(|| format!("failed to deserialize {}", path.display()))
+}
+
+/// Upgrades a crate in the rustdoc JSON format from `format_version` to [`FORMAT_VERSION`], by
+/// filling the fields added since then with their empty value.
+fn upgrade(json: &mut Value, format_version: u32) {
+    // Version 28 added the requirements set with `#[doc(requirement)]`.
+    if format_version < 28 {
+        json["requirements"] = json!({});
+        if let Some(index) = json["index"].as_object_mut() {
+            for item in index.values_mut().filter_map(|item| item.as_object_mut()) {
+                item.insert("requirements".into(), json!([]));
+            }
+        }
+    }
+    json["format_version"] = json!(FORMAT_VERSION);
+}
+
+/// Names a version of the crate in the reports, from its version number if rustdoc was given one.
+fn version_name(krate: &Crate, path: &Path) -> String {
+    let name = krate.index[&krate.root].name.as_deref().unwrap_or("<unknown>");
+    match &krate.crate_version {
+        Some(version) => format!("{name} {version}"),
+        None => path.display().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,324 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Rendering of types and signatures as Rust source code.
+//!
+//! The IDs generated by rustdoc are not stable across compilations, so items of the two crates are
+//! compared through their rendered signatures instead. Paths are rendered with the canonical path
+//! of the item they refer to, to avoid reporting changes when only an import changed.
+
+use rustdoc_json_types::{
+    Abi, Crate, FnDecl, Function, GenericArg, GenericArgs, GenericBound, GenericParamDef,
+    GenericParamDefKind, Generics, Header, Impl, Path, PolyTrait, Term, TraitBoundModifier, Type,
+    TypeBinding, TypeBindingKind, WherePredicate,
+};
+
+pub(crate) struct Renderer<'a> {
+    krate: &'a Crate,
+}
+
+impl<'a> Renderer<'a> {
+    pub(crate) fn new(krate: &'a Crate) -> Self {
+        Renderer { krate }
+    }
+
+    /// Renders a function, optionally including its `const` and `unsafe` qualifiers.
+    pub(crate) fn function(&self, name: &str, function: &Function, qualifiers: bool) -> String {
+        let Function { decl, generics, header, .. } = function;
+        format!(
+            "{}fn {name}{}({}){}{}",
+            self.header(header, qualifiers),
+            self.generic_params(&generics.params),
+            self.fn_inputs(decl),
+            self.fn_output(decl),
+            self.where_clause(generics),
+        )
+    }
+
+    /// Renders the header of an impl block, optionally including its `unsafe` qualifier.
+    pub(crate) fn impl_header(&self, impl_: &Impl, qualifiers: bool) -> String {
+        let unsafe_ = if impl_.is_unsafe && qualifiers { "unsafe " } else { "" };
+        let params = self.generic_params(&impl_.generics.params);
+        let where_clause = self.where_clause(&impl_.generics);
+        match &impl_.trait_ {
+            Some(trait_) => format!(
+                "{unsafe_}impl{params} {}{} for {}{where_clause}",
+                if impl_.negative { "!" } else { "" },
+                self.path(trait_),
+                self.ty(&impl_.for_),
+            ),
+            None => format!("{unsafe_}impl{params} {}{where_clause}", self.ty(&impl_.for_)),
+        }
+    }
+
+    pub(crate) fn path(&self, path: &Path) -> String {
+        let mut out = match self.krate.paths.get(&path.id) {
+            Some(summary) => summary.path.join("::"),
+            None => path.name.clone(),
+        };
+        if let Some(args) = &path.args {
+            out.push_str(&self.generic_args(args));
+        }
+        out
+    }
+
+    pub(crate) fn ty(&self, ty: &Type) -> String {
+        match ty {
+            Type::ResolvedPath(path) => self.path(path),
+            Type::DynTrait(dyn_trait) => {
+                let mut bounds: Vec<_> =
+                    dyn_trait.traits.iter().map(|poly| self.poly_trait(poly)).collect();
+                bounds.extend(dyn_trait.lifetime.clone());
+                format!("dyn {}", bounds.join(" + "))
+            }
+            Type::Generic(name) | Type::Primitive(name) => name.clone(),
+            Type::FunctionPointer(pointer) => format!(
+                "{}{}fn({}){}",
+                self.hrtb(&pointer.generic_params),
+                self.header(&pointer.header, true),
+                self.fn_inputs(&pointer.decl),
+                self.fn_output(&pointer.decl),
+            ),
+            Type::Tuple(types) if types.len() == 1 => format!("({},)", self.ty(&types[0])),
+            Type::Tuple(types) => format!("({})", self.types(types)),
+            Type::Slice(ty) => format!("[{}]", self.ty(ty)),
+            Type::Array { type_, len } => format!("[{}; {len}]", self.ty(type_)),
+            Type::ImplTrait(bounds) => format!("impl {}", self.bounds(bounds)),
+            Type::Infer => "_".into(),
+            Type::RawPointer { mutable, type_ } => {
+                format!("*{} {}", if *mutable { "mut" } else { "const" }, self.ty(type_))
+            }
+            Type::BorrowedRef { lifetime, mutable, type_ } => {
+                format!("{}{}", self.reference(lifetime, *mutable), self.ty(type_))
+            }
+            Type::QualifiedPath { name, args, self_type, trait_ } => {
+                let args = self.generic_args(args);
+                match trait_ {
+                    Some(trait_) => {
+                        format!("<{} as {}>::{name}{args}", self.ty(self_type), self.path(trait_))
+                    }
+                    None => format!("{}::{name}{args}", self.ty(self_type)),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn bounds(&self, bounds: &[GenericBound]) -> String {
+        bounds.iter().map(|bound| self.bound(bound)).collect::<Vec<_>>().join(" + ")
+    }
+
+    /// Renders generic parameters like `<'a, T: Clone, const N: usize>`, omitting the parameters
+    /// introduced by the compiler for `impl Trait` arguments.
+    pub(crate) fn generic_params(&self, params: &[GenericParamDef]) -> String {
+        let params: Vec<_> = params
+            .iter()
+            .filter(|param| {
+                !matches!(param.kind, GenericParamDefKind::Type { synthetic: true, .. })
+            })
+            .map(|param| self.generic_param(param))
+            .collect();
+        if params.is_empty() { String::new() } else { format!("<{}>", params.join(", ")) }
+    }
+
+    pub(crate) fn where_clause(&self, generics: &Generics) -> String {
+        if generics.where_predicates.is_empty() {
+            return String::new();
+        }
+        let predicates: Vec<_> = generics
+            .where_predicates
+            .iter()
+            .map(|predicate| match predicate {
+                WherePredicate::BoundPredicate { type_, bounds, generic_params } => format!(
+                    "{}{}: {}",
+                    self.hrtb(generic_params),
+                    self.ty(type_),
+                    self.bounds(bounds)
+                ),
+                WherePredicate::RegionPredicate { lifetime, bounds } => {
+                    format!("{lifetime}: {}", self.bounds(bounds))
+                }
+                WherePredicate::EqPredicate { lhs, rhs } => {
+                    format!("{} == {}", self.ty(lhs), self.term(rhs))
+                }
+            })
+            .collect();
+        format!(" where {}", predicates.join(", "))
+    }
+
+    fn header(&self, header: &Header, qualifiers: bool) -> String {
+        let mut out = String::new();
+        if header.const_ && qualifiers {
+            out.push_str("const ");
+        }
+        if header.async_ {
+            out.push_str("async ");
+        }
+        if header.unsafe_ && qualifiers {
+            out.push_str("unsafe ");
+        }
+        let (abi, unwind) = match &header.abi {
+            Abi::Rust => return out,
+            Abi::C { unwind } => ("C", *unwind),
+            Abi::Cdecl { unwind } => ("cdecl", *unwind),
+            Abi::Stdcall { unwind } => ("stdcall", *unwind),
+            Abi::Fastcall { unwind } => ("fastcall", *unwind),
+            Abi::Aapcs { unwind } => ("aapcs", *unwind),
+            Abi::Win64 { unwind } => ("win64", *unwind),
+            Abi::SysV64 { unwind } => ("sysv64", *unwind),
+            Abi::System { unwind } => ("system", *unwind),
+            Abi::Other(abi) => (abi.as_str(), false),
+        };
+        out.push_str(&format!("extern \"{abi}{}\" ", if unwind { "-unwind" } else { "" }));
+        out
+    }
+
+    /// Renders the arguments of a function without their names, as renaming an argument doesn't
+    /// change the API. The receiver of methods is still rendered as `self`.
+    fn fn_inputs(&self, decl: &FnDecl) -> String {
+        let mut inputs: Vec<_> = decl
+            .inputs
+            .iter()
+            .map(|(name, ty)| match ty {
+                _ if name != "self" => self.ty(ty),
+                Type::Generic(generic) if generic == "Self" => "self".into(),
+                Type::BorrowedRef { lifetime, mutable, type_ }
+                    if matches!(&**type_, Type::Generic(generic) if generic == "Self") =>
+                {
+                    format!("{}self", self.reference(lifetime, *mutable))
+                }
+                _ => format!("self: {}", self.ty(ty)),
+            })
+            .collect();
+        if decl.c_variadic {
+            inputs.push("...".into());
+        }
+        inputs.join(", ")
+    }
+
+    fn fn_output(&self, decl: &FnDecl) -> String {
+        match &decl.output {
+            Some(output) => format!(" -> {}", self.ty(output)),
+            None => String::new(),
+        }
+    }
+
+    fn reference(&self, lifetime: &Option<String>, mutable: bool) -> String {
+        let mut out = String::from("&");
+        if let Some(lifetime) = lifetime {
+            out.push_str(lifetime);
+            out.push(' ');
+        }
+        if mutable {
+            out.push_str("mut ");
+        }
+        out
+    }
+
+    fn types(&self, types: &[Type]) -> String {
+        types.iter().map(|ty| self.ty(ty)).collect::<Vec<_>>().join(", ")
+    }
+
+    fn generic_args(&self, args: &GenericArgs) -> String {
+        match args {
+            GenericArgs::AngleBracketed { args, bindings } => {
+                if args.is_empty() && bindings.is_empty() {
+                    return String::new();
+                }
+                let args: Vec<_> = args
+                    .iter()
+                    .map(|arg| self.generic_arg(arg))
+                    .chain(bindings.iter().map(|binding| self.type_binding(binding)))
+                    .collect();
+                format!("<{}>", args.join(", "))
+            }
+            GenericArgs::Parenthesized { inputs, output } => {
+                let output = match output {
+                    Some(output) => format!(" -> {}", self.ty(output)),
+                    None => String::new(),
+                };
+                format!("({}){output}", self.types(inputs))
+            }
+        }
+    }
+
+    fn generic_arg(&self, arg: &GenericArg) -> String {
+        match arg {
+            GenericArg::Lifetime(lifetime) => lifetime.clone(),
+            GenericArg::Type(ty) => self.ty(ty),
+            GenericArg::Const(constant) => constant.expr.clone(),
+            GenericArg::Infer => "_".into(),
+        }
+    }
+
+    fn type_binding(&self, binding: &TypeBinding) -> String {
+        let args = self.generic_args(&binding.args);
+        match &binding.binding {
+            TypeBindingKind::Equality(term) => {
+                format!("{}{args} = {}", binding.name, self.term(term))
+            }
+            TypeBindingKind::Constraint(bounds) => {
+                format!("{}{args}: {}", binding.name, self.bounds(bounds))
+            }
+        }
+    }
+
+    fn term(&self, term: &Term) -> String {
+        match term {
+            Term::Type(ty) => self.ty(ty),
+            Term::Constant(constant) => constant.expr.clone(),
+        }
+    }
+
+    fn generic_param(&self, param: &GenericParamDef) -> String {
+        match &param.kind {
+            GenericParamDefKind::Lifetime { outlives } if outlives.is_empty() => param.name.clone(),
+            GenericParamDefKind::Lifetime { outlives } => {
+                format!("{}: {}", param.name, outlives.join(" + "))
+            }
+            GenericParamDefKind::Type { bounds, default, .. } => {
+                let mut out = param.name.clone();
+                if !bounds.is_empty() {
+                    out.push_str(&format!(": {}", self.bounds(bounds)));
+                }
+                if let Some(default) = default {
+                    out.push_str(&format!(" = {}", self.ty(default)));
+                }
+                out
+            }
+            GenericParamDefKind::Const { type_, default } => {
+                let mut out = format!("const {}: {}", param.name, self.ty(type_));
+                if let Some(default) = default {
+                    out.push_str(&format!(" = {default}"));
+                }
+                out
+            }
+        }
+    }
+
+    fn bound(&self, bound: &GenericBound) -> String {
+        match bound {
+            GenericBound::TraitBound { trait_, generic_params, modifier } => {
+                let modifier = match modifier {
+                    TraitBoundModifier::None => "",
+                    TraitBoundModifier::Maybe => "?",
+                    TraitBoundModifier::MaybeConst => "~const ",
+                };
+                format!("{}{modifier}{}", self.hrtb(generic_params), self.path(trait_))
+            }
+            GenericBound::Outlives(lifetime) => lifetime.clone(),
+        }
+    }
+
+    fn poly_trait(&self, poly: &PolyTrait) -> String {
+        format!("{}{}", self.hrtb(&poly.generic_params), self.path(&poly.trait_))
+    }
+
+    /// Renders the `for<'a>` binder of higher-ranked trait bounds.
+    fn hrtb(&self, params: &[GenericParamDef]) -> String {
+        if params.is_empty() {
+            return String::new();
+        }
+        let params: Vec<_> = params.iter().map(|param| self.generic_param(param)).collect();
+        format!("for<{}> ", params.join(", "))
+    }
+}
@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+use crate::diff::{Change, ChangeKind, Severity};
+use serde::Serialize;
+use std::fmt::Write;
+
+const SECTIONS: &[(Severity, &str)] = &[
+    (Severity::Breaking, "Breaking changes"),
+    (Severity::Minor, "Minor changes"),
+    (Severity::Patch, "Patch changes"),
+];
+
+/// Version bump required by the changes, or `None` if the APIs are identical.
+pub(crate) fn required_bump(changes: &[Change]) -> Option<Severity> {
+    changes.iter().map(|change| change.severity).max()
+}
+
+/// Sections of the report, skipping the empty ones and the ones below `min_severity`.
+fn sections<'a>(
+    changes: &'a [Change],
+    min_severity: Severity,
+) -> impl Iterator<Item = (&'static str, Vec<&'a Change>)> + 'a {
+    SECTIONS.iter().filter(move |(severity, _)| *severity >= min_severity).filter_map(
+        move |(severity, title)| {
+            let section: Vec<_> =
+                changes.iter().filter(|change| change.severity == *severity).collect();
+            if section.is_empty() { None } else { Some((*title, section)) }
+        },
+    )
+}
+
+fn bump_name(bump: Option<Severity>) -> &'static str {
+    match bump {
+        Some(Severity::Breaking) => "major",
+        Some(Severity::Minor) => "minor",
+        Some(Severity::Patch) => "patch",
+        None => "none",
+    }
+}
+
+fn summary(changes: &[Change]) -> String {
+    let count = |severity| changes.iter().filter(|change| change.severity == severity).count();
+    format!(
+        "{} breaking, {} minor and {} patch changes (required version bump: {})",
+        count(Severity::Breaking),
+        count(Severity::Minor),
+        count(Severity::Patch),
+        bump_name(required_bump(changes)),
+    )
+}
+
+fn kind_name(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Added => "added",
+        ChangeKind::Removed => "removed",
+        ChangeKind::Changed => "changed",
+    }
+}
+
+pub(crate) fn text(changes: &[Change], min_severity: Severity) -> String {
+    let mut out = String::new();
+    for (title, section) in sections(changes, min_severity) {
+        writeln!(out, "{title}:").unwrap();
+        for change in section {
+            writeln!(out, "  {} {}", kind_name(change.kind), change.item).unwrap();
+            match (&change.old, &change.new) {
+                (Some(old), Some(new)) if old != new => {
+                    writeln!(out, "    - {old}").unwrap();
+                    writeln!(out, "    + {new}").unwrap();
+                }
+                (_, Some(signature)) | (Some(signature), None) => {
+                    writeln!(out, "      {signature}").unwrap();
+                }
+                (None, None) => {}
+            }
+            writeln!(out, "      {}", change.reasons.join(", ")).unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+    writeln!(out, "{}", summary(changes)).unwrap();
+    out
+}
+
+/// Renders the changes as a Markdown document, meant to be included in release notes.
+pub(crate) fn markdown(
+    changes: &[Change],
+    min_severity: Severity,
+    old_name: &str,
+    new_name: &str,
+) -> String {
+    let mut out = String::new();
+    writeln!(out, "# API changes between {old_name} and {new_name}").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "{}.", summary(changes)).unwrap();
+    for (title, section) in sections(changes, min_severity) {
+        writeln!(out).unwrap();
+        writeln!(out, "## {title}").unwrap();
+        writeln!(out).unwrap();
+        for change in section {
+            let mut kind = kind_name(change.kind).to_string();
+            kind[..1].make_ascii_uppercase();
+            writeln!(out, "- {kind} `{}`: {}.", change.item, change.reasons.join(", ")).unwrap();
+            match (&change.old, &change.new) {
+                (Some(old), Some(new)) if old != new => {
+                    writeln!(out, "  - Before: `{old}`").unwrap();
+                    writeln!(out, "  - After: `{new}`").unwrap();
+                }
+                _ => {}
+            }
+        }
+    }
+    out
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    old: &'a str,
+    new: &'a str,
+    required_bump: &'static str,
+    changes: Vec<&'a Change>,
+}
+
+pub(crate) fn json(
+    changes: &[Change],
+    min_severity: Severity,
+    old_name: &str,
+    new_name: &str,
+) -> serde_json::Result<String> {
+    let report = JsonReport {
+        old: old_name,
+        new: new_name,
+        required_bump: bump_name(required_bump(changes)),
+        changes: changes.iter().filter(|change| change.severity >= min_severity).collect(),
+    };
+    serde_json::to_string_pretty(&report)
+}
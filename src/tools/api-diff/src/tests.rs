@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+use super::*;
+
+#[test]
+fn upgrade_format_27() {
+    let mut json = json!({
+        "root": "0:0",
+        "crate_version": "1.0.0",
+        "includes_private": false,
+        "index": {
+            "0:0": {
+                "id": "0:0",
+                "crate_id": 0,
+                "name": "krate",
+                "span": null,
+                "visibility": "public",
+                "docs": null,
+                "links": {},
+                "attrs": [],
+                "deprecation": null,
+                "inner": { "module": { "is_crate": true, "items": [], "is_stripped": false } },
+            },
+        },
+        "paths": {},
+        "external_crates": {},
+        "format_version": 27,
+    });
+    upgrade(&mut json, 27);
+
+    let krate: Crate = serde_json::from_value(json).unwrap();
+    assert_eq!(krate.format_version, FORMAT_VERSION);
+    assert!(krate.requirements.is_empty());
+    assert!(krate.index[&krate.root].requirements.is_empty());
+}
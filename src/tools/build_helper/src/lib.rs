@@ -1,4 +1,5 @@
 pub mod ci;
 pub mod git;
 pub mod metrics;
+pub mod shard;
 pub mod util;
@@ -50,6 +50,9 @@ pub enum TestSuiteMetadata {
         target: String,
         host: String,
         stage: u32,
+        /// Codegen backend the tests were built with, when it isn't the default one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        codegen_backend: Option<String>,
     },
     Compiletest {
         suite: String,
@@ -61,14 +64,24 @@ pub enum TestSuiteMetadata {
     },
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Test {
     pub name: String,
     #[serde(flatten)]
     pub outcome: TestOutcome,
+    /// Every attempt at running the test, only present when the test was retried.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<TestAttempt>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TestAttempt {
+    #[serde(flatten)]
+    pub outcome: TestOutcome,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(tag = "outcome", rename_all = "snake_case")]
 pub enum TestOutcome {
     Passed,
@@ -76,6 +89,21 @@ pub enum TestOutcome {
     Ignored { ignore_reason: Option<String> },
 }
 
+/// Ignore message of the tests compiletest skips with `--resume-log`, as they passed in the run
+/// being resumed. Bootstrap records them with their outcome from the resume log instead.
+pub const RESUMED_TEST_MESSAGE: &str = "passed in a previous run, skipped on resume";
+
+/// Line of the log bootstrap writes while running a test suite, so that an interrupted or failed
+/// run can be resumed with `./x test --resume` without losing the outcomes of its tests.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResumeLogEntry {
+    /// A test that passed or was ignored, written as soon as its outcome is known.
+    Test(Test),
+    /// All the tests of the suite ran successfully.
+    Completed,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct JsonInvocationSystemStats {
@@ -8,6 +8,7 @@
 use std::str::FromStr;
 
 use crate::util::{add_dylib_path, PathBufExt};
+use build_helper::shard::Shard;
 use lazycell::AtomicLazyCell;
 use serde::de::{Deserialize, Deserializer, Error as _};
 use std::collections::{HashMap, HashSet};
@@ -66,6 +67,7 @@ pub enum Mode {
         JsDocTest => "js-doc-test",
         MirOpt => "mir-opt",
         Assembly => "assembly",
+        ObjectCheck => "object-check",
         CoverageMap => "coverage-map",
         RunCoverage => "run-coverage",
     }
@@ -239,6 +241,32 @@ pub struct Config {
     /// `test::TestOpts::skip`. `filter_exact` does not apply to these flags.
     pub skip: Vec<String>,
 
+    /// Run the tests matching these substrings, expecting them to fail. Used to check whether the
+    /// tests listed in `ferrocene/ignored-tests.toml` still need to be ignored.
+    pub ferrocene_expected_failures: Vec<String>,
+
+    /// How many times tests marked with `flaky-on` for the current target are retried after
+    /// failing. Every failed attempt is reported, so that retries are visible in the results.
+    pub flaky_retries: usize,
+
+    /// File the failed attempts of flaky tests are appended to, one JSON object per line.
+    pub flaky_retries_log: Option<PathBuf>,
+
+    /// Codegen backend `ui` tests that are executed are also built with, to compare the behavior of
+    /// the resulting programs with the ones built by the default backend.
+    pub differential_codegen_backend: Option<String>,
+
+    /// Tests, or directories containing them, not built with the differential codegen backend.
+    pub differential_skip: Vec<PathBuf>,
+
+    /// Only run the tests belonging to this shard.
+    pub shard: Option<Shard>,
+
+    /// Skip the tests recorded as passed in this log, written by bootstrap during the run being
+    /// resumed. Skipped tests are reported as ignored with
+    /// `build_helper::metrics::RESUMED_TEST_MESSAGE`.
+    pub resume_log: Option<PathBuf>,
+
     /// Exactly match the filter, rather than a substring
     pub filter_exact: bool,
 
@@ -325,6 +353,10 @@ pub struct Config {
     /// where to find the remote test client process, if we're using it
     pub remote_test_client: Option<PathBuf>,
 
+    /// whether the remote test client runs the test binaries directly under QEMU system emulation,
+    /// with their output reported over semihosting
+    pub qemu_semihosting: bool,
+
     /// mode describing what file the actual ui output will be compared to
     pub compare_mode: Option<CompareMode>,
 
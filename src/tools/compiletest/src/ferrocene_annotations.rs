@@ -3,6 +3,7 @@
 
 use crate::common::{Config, Mode, TestPaths};
 use crate::find_tests_in_dir;
+use crate::header::line_directive;
 use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs::File;
@@ -11,7 +12,10 @@
 use std::str::FromStr;
 use std::sync::Arc;
 
+mod library;
+
 const BULK_ANNOTATIONS_FILE_NAME: &str = "ferrocene-annotations";
+const LIBRARY_MODE: &str = "library";
 
 #[derive(serde::Serialize)]
 struct Output<'a> {
@@ -22,6 +26,10 @@ struct Output<'a> {
 #[derive(serde::Serialize)]
 struct TestFile {
     file: String,
+    /// The name of the test, when the file contains multiple tests, like the unit tests and
+    /// doctests of library crates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
     annotations: Vec<Annotation>,
 }
 
@@ -29,6 +37,14 @@ struct TestFile {
 struct Annotation {
     id: String,
     file: PathBuf,
+    /// The revision of the test the annotation is scoped to, written as
+    /// `//[revision] ferrocene-annotations: id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revision: Option<String>,
+    /// The expected diagnostic the annotation is scoped to, written as
+    /// `// ferrocene-annotations: id expect: diagnostic`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diagnostic: Option<String>,
 }
 
 pub fn maybe_collect_and_exit() {
@@ -38,10 +54,18 @@ pub fn maybe_collect_and_exit() {
     }
 
     let dest: PathBuf = env("FERROCENE_DEST");
-    let config = sample_config();
-
-    let mut collector = Collector::new(Arc::new(config));
-    collector.collect();
+    let mut collector;
+    if std::env::var("FERROCENE_MODE").as_deref() == Ok(LIBRARY_MODE) {
+        // Library crates are not compiletest suites: collect the annotations of their unit tests
+        // and doctests instead, with the suite being the name of the crate.
+        collector = Collector::new(Arc::new(Config::default()));
+        let krate: String = env("FERROCENE_SUITE");
+        let crate_root: PathBuf = env("FERROCENE_SRC_BASE");
+        collector.tests = library::collect(&krate, &crate_root);
+    } else {
+        collector = Collector::new(Arc::new(sample_config()));
+        collector.collect();
+    }
     let found = collector.write(&dest);
 
     println!("collected {found} tests with annotations");
@@ -91,7 +115,7 @@ fn collect_test(&mut self, paths: &TestPaths) -> Option<TestFile> {
         if annotations.is_empty() {
             None
         } else {
-            Some(TestFile { file: paths.file.to_str().unwrap().into(), annotations })
+            Some(TestFile { file: paths.file.to_str().unwrap().into(), name: None, annotations })
         }
     }
 
@@ -113,26 +137,46 @@ fn append_directory_annotations(&mut self, paths: &TestPaths, extend: &mut Vec<A
     }
 
     fn collect_annotations(&self, path: &Path, contents: &str) -> Vec<Annotation> {
+        let is_makefile = path.file_name() == Some(OsStr::new("Makefile"));
+        let is_bulk_file = path.file_name() == Some(OsStr::new(BULK_ANNOTATIONS_FILE_NAME));
+        let comment = if is_makefile {
+            "#"
+        } else if path.extension() == Some(OsStr::new("rs")) || is_bulk_file {
+            "//"
+        } else {
+            panic!("unknown type of file encountered: {}", path.display());
+        };
+
         let mut found = Vec::new();
         for line in contents.lines() {
-            let prefix = if path.file_name() == Some(OsStr::new("Makefile")) {
-                "# "
-            } else if path.extension() == Some(OsStr::new("rs"))
-                || path.file_name() == Some(OsStr::new(BULK_ANNOTATIONS_FILE_NAME))
-            {
-                "// "
-            } else {
-                panic!("unknown type of file encountered: {}", path.display());
-            };
-            let remaining = if let Some(remaining) = line.strip_prefix(prefix) {
-                remaining
-            } else {
-                continue;
-            };
+            let Some((revision, remaining)) = line_directive(comment, line) else { continue };
             if let Some(remaining) = remaining.strip_prefix("ferrocene-annotations: ") {
-                if !remaining.is_empty() {
-                    found.push(Annotation { id: remaining.into(), file: path.into() });
+                if remaining.is_empty() {
+                    continue;
+                }
+                let (id, diagnostic) = match remaining.split_once(" expect: ") {
+                    Some((id, diagnostic)) => (id.trim(), Some(diagnostic.trim().to_string())),
+                    None => (remaining.trim(), None),
+                };
+                if (revision.is_some() || diagnostic.is_some()) && (is_makefile || is_bulk_file) {
+                    panic!(
+                        "{}: annotations can only be scoped to a revision or to an expected \
+                         diagnostic in the test file itself",
+                        path.display()
+                    );
                 }
+                if let Some(revision) = revision {
+                    check_revision_exists(path, contents, revision);
+                }
+                if let Some(diagnostic) = &diagnostic {
+                    check_diagnostic_expected(path, contents, revision, diagnostic);
+                }
+                found.push(Annotation {
+                    id: id.into(),
+                    file: path.into(),
+                    revision: revision.map(|r| r.into()),
+                    diagnostic,
+                });
             } else if remaining.starts_with("ferrocene-annotation: ") {
                 // Prevent common typos
                 panic!(
@@ -161,6 +205,50 @@ fn write(self, dest: &Path) -> usize {
     }
 }
 
+fn check_revision_exists(path: &Path, contents: &str, revision: &str) {
+    let declared = contents
+        .lines()
+        .filter_map(|line| line_directive("//", line))
+        .filter_map(|(_, directive)| directive.strip_prefix("revisions:"))
+        .any(|revisions| revisions.split_whitespace().any(|r| r == revision));
+    if !declared {
+        panic!("{}: annotation scoped to undeclared revision `{revision}`", path.display());
+    }
+}
+
+fn check_diagnostic_expected(
+    path: &Path,
+    contents: &str,
+    revision: Option<&str>,
+    diagnostic: &str,
+) {
+    // Expected diagnostics are written as `//~ ERROR message`, or as `//[revision]~ ERROR message`
+    // when they only apply to one revision.
+    let matches = |comment: &str| {
+        let (expectation_revision, expectation) = match comment.strip_prefix('[') {
+            Some(rest) => match rest.split_once(']') {
+                Some((revision, expectation)) => (Some(revision), expectation),
+                None => return false,
+            },
+            None => (None, comment),
+        };
+        expectation.starts_with('~')
+            && expectation.contains(diagnostic)
+            && (expectation_revision.is_none() || expectation_revision == revision)
+    };
+    let expected = contents
+        .lines()
+        .filter_map(|line| line.split_once("//").map(|(_, comment)| comment))
+        .any(matches);
+    if !expected {
+        panic!(
+            "{}: annotation scoped to the expected diagnostic `{diagnostic}`, \
+             but no matching `//~` expectation was found",
+            path.display()
+        );
+    }
+}
+
 fn sample_config() -> Config {
     Config {
         color: test::ColorConfig::NeverColor,
@@ -183,3 +271,6 @@ fn env<T>(var: &str) -> T
         panic!("missing variable {var}");
     }
 }
+
+#[cfg(test)]
+mod tests;
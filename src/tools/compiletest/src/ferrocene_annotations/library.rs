@@ -0,0 +1,400 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+//! Collection of the annotations of the unit tests and doctests of a library crate.
+//!
+//! Unit tests are annotated with a `// ferrocene-annotations: id` comment among the attributes of
+//! the `#[test]` function, while doctests are annotated with a hidden line inside the code block,
+//! written as `/// # // ferrocene-annotations: id`.
+//!
+//! As multiple tests are defined in the same file, each of them is named after its libtest name,
+//! prefixed by the crate name, so that the traceability matrix can match it with the test
+//! outcomes. The files are parsed with `syn`, and module paths are derived from the file layout
+//! and from inline `mod` blocks, so tests in modules using `#[path]` are not supported. As `syn`
+//! drops comments, the annotations of unit tests are attached to the items by their line.
+//!
+//! Rustdoc names doctests `library/<crate>/src/file.rs - path::to::Item (line N)`, with the path
+//! of the file relative to the root of the workspace, the path of the documented item inside the
+//! crate, and the line of the opening fence of the code block. Impl blocks are named after their
+//! self type, like `Option<T>`.
+
+use super::{Annotation, TestFile};
+use quote::ToTokens;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::{Attribute, Expr, Field, ForeignItem, ImplItem, Item, Lit, Meta, Stmt, TraitItem};
+
+const ANNOTATION: &str = "// ferrocene-annotations: ";
+
+pub(super) fn collect(krate: &str, crate_root: &Path) -> Vec<TestFile> {
+    let mut tests = Vec::new();
+    for dir in ["src", "tests"] {
+        let mut files = Vec::new();
+        find_rust_files(&crate_root.join(dir), &mut files);
+        files.sort();
+        for file in files {
+            let contents = std::fs::read_to_string(&file)
+                .expect(&format!("failed to read {}", file.display()));
+            let relative = file.strip_prefix(crate_root).unwrap();
+            tests.extend(collect_file(krate, &file, relative, &contents));
+        }
+    }
+    tests
+}
+
+fn find_rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
+        Err(err) => panic!("failed to read {}: {err}", dir.display()),
+    };
+    for entry in entries {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            find_rust_files(&path, files);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// Collects the annotated tests of a file, where `relative` is the path of the file relative to
+/// the root of the crate.
+pub(super) fn collect_file(
+    krate: &str,
+    path: &Path,
+    relative: &Path,
+    contents: &str,
+) -> Vec<TestFile> {
+    let file = syn::parse_file(contents).unwrap_or_else(|err| {
+        let start = err.span().start();
+        panic!("{}:{}:{}: failed to parse: {err}", path.display(), start.line, start.column + 1)
+    });
+
+    // Doc comments are attributes, but plain comments are not part of the syntax tree.
+    let mut annotations = BTreeMap::new();
+    let mut doc_annotations = BTreeSet::new();
+    for (number, line) in contents.lines().enumerate() {
+        if let Some(id) = line.trim().strip_prefix(ANNOTATION) {
+            annotations.insert(number + 1, id.trim().to_string());
+        } else if line.contains(ANNOTATION) {
+            doc_annotations.insert(number + 1);
+        }
+    }
+
+    let mut collector = FileCollector {
+        krate,
+        path,
+        file_module: file_module(relative),
+        doctest_file: Path::new("library").join(krate).join(relative),
+        annotations,
+        doc_annotations,
+        scopes: Vec::new(),
+        found: Vec::new(),
+    };
+    collector.doctests(&file.attrs, None);
+    collector.items(&file.items, 0);
+    if let Some(&line) = collector.annotations.keys().next() {
+        collector.not_on_test(line);
+    }
+    // Annotations in documentation rustdoc does not see, like the one of items defined by macros.
+    if let Some(&line) = collector.doc_annotations.first() {
+        collector.error(line, "failed to find the item documented by the annotated doctests");
+    }
+    collector.found
+}
+
+struct FileCollector<'a> {
+    krate: &'a str,
+    path: &'a Path,
+    file_module: Option<String>,
+    /// Path of the file relative to the root of the workspace, as used in the doctest names.
+    doctest_file: PathBuf,
+    /// Annotations of unit tests not attached to a test yet, by line.
+    annotations: BTreeMap<usize, String>,
+    /// Lines of the annotations in the documentation that were not found in a doctest yet.
+    doc_annotations: BTreeSet<usize>,
+    /// Names of the items containing the current one, like inline modules and impl blocks.
+    scopes: Vec<String>,
+    found: Vec<TestFile>,
+}
+
+impl FileCollector<'_> {
+    /// Collects the tests of a list of items following the line `start`, attaching the annotations
+    /// written between the end of an item and the body of the next one to the latter.
+    fn items<'i>(&mut self, items: impl IntoIterator<Item = &'i Item>, start: usize) {
+        let mut previous_end = start;
+        for item in items {
+            let body_start = match item {
+                Item::Fn(item) => item.block.brace_token.span.open().start().line,
+                Item::Mod(syn::ItemMod { content: Some((brace, _)), .. }) => {
+                    brace.span.open().start().line
+                }
+                _ => item.span().end().line,
+            };
+            let mut attached = self.annotations.split_off(&(previous_end + 1));
+            self.annotations.append(&mut attached.split_off(&body_start));
+            if let Some(&first) = attached.keys().next() {
+                let Item::Fn(test) = item else { self.not_on_test(first) };
+                if !test.attrs.iter().any(|attr| attr.path().is_ident("test")) {
+                    self.not_on_test(first);
+                }
+                let name = test.sig.ident.to_string();
+                let mut segments = vec![self.krate];
+                segments.extend(self.file_module.as_deref());
+                segments.extend(self.scopes.iter().map(String::as_str));
+                segments.push(&name);
+                let annotations = attached.into_values().map(|id| self.annotation(id)).collect();
+                self.found.push(self.test_file(segments.join("::"), annotations));
+            }
+            self.item(item);
+            previous_end = item.span().end().line;
+        }
+    }
+
+    fn item(&mut self, item: &Item) {
+        match item {
+            Item::Fn(item) => {
+                let name = item.sig.ident.to_string();
+                self.doctests(&item.attrs, Some(&name));
+                // Items defined inside of functions are named after them.
+                let items = item.block.stmts.iter().filter_map(|stmt| match stmt {
+                    Stmt::Item(item) => Some(item),
+                    _ => None,
+                });
+                let start = item.block.brace_token.span.open().start().line;
+                self.scoped(name, |this| this.items(items, start));
+            }
+            Item::Mod(item) => {
+                let name = item.ident.to_string();
+                self.doctests(&item.attrs, Some(&name));
+                if let Some((brace, items)) = &item.content {
+                    let start = brace.span.open().start().line;
+                    self.scoped(name, |this| this.items(items, start));
+                }
+            }
+            Item::Impl(item) => {
+                let name = type_name(&item.self_ty);
+                self.doctests(&item.attrs, Some(&name));
+                self.scoped(name, |this| {
+                    for impl_item in &item.items {
+                        let (attrs, name) = match impl_item {
+                            ImplItem::Const(i) => (&i.attrs, &i.ident),
+                            ImplItem::Fn(i) => (&i.attrs, &i.sig.ident),
+                            ImplItem::Type(i) => (&i.attrs, &i.ident),
+                            ImplItem::Macro(i) => {
+                                this.undocumented(&i.attrs);
+                                continue;
+                            }
+                            _ => continue,
+                        };
+                        this.doctests(attrs, Some(&name.to_string()));
+                    }
+                });
+            }
+            Item::Trait(item) => {
+                let name = item.ident.to_string();
+                self.doctests(&item.attrs, Some(&name));
+                self.scoped(name, |this| {
+                    for trait_item in &item.items {
+                        let (attrs, name) = match trait_item {
+                            TraitItem::Const(i) => (&i.attrs, &i.ident),
+                            TraitItem::Fn(i) => (&i.attrs, &i.sig.ident),
+                            TraitItem::Type(i) => (&i.attrs, &i.ident),
+                            TraitItem::Macro(i) => {
+                                this.undocumented(&i.attrs);
+                                continue;
+                            }
+                            _ => continue,
+                        };
+                        this.doctests(attrs, Some(&name.to_string()));
+                    }
+                });
+            }
+            Item::Struct(item) => {
+                let name = item.ident.to_string();
+                self.doctests(&item.attrs, Some(&name));
+                self.scoped(name, |this| this.fields(&item.fields));
+            }
+            Item::Union(item) => {
+                let name = item.ident.to_string();
+                self.doctests(&item.attrs, Some(&name));
+                self.scoped(name, |this| this.fields(&item.fields.named));
+            }
+            Item::Enum(item) => {
+                let name = item.ident.to_string();
+                self.doctests(&item.attrs, Some(&name));
+                self.scoped(name, |this| {
+                    for variant in &item.variants {
+                        let name = variant.ident.to_string();
+                        this.doctests(&variant.attrs, Some(&name));
+                        this.scoped(name, |this| this.fields(&variant.fields));
+                    }
+                });
+            }
+            Item::Const(item) => self.doctests(&item.attrs, Some(&item.ident.to_string())),
+            Item::Static(item) => self.doctests(&item.attrs, Some(&item.ident.to_string())),
+            Item::Type(item) => self.doctests(&item.attrs, Some(&item.ident.to_string())),
+            Item::TraitAlias(item) => self.doctests(&item.attrs, Some(&item.ident.to_string())),
+            Item::Macro(syn::ItemMacro { attrs, ident: Some(ident), .. }) => {
+                self.doctests(attrs, Some(&ident.to_string()))
+            }
+            Item::ForeignMod(item) => {
+                // Foreign items are named as if they were defined outside of the extern block.
+                for foreign_item in &item.items {
+                    let (attrs, name) = match foreign_item {
+                        ForeignItem::Fn(i) => (&i.attrs, &i.sig.ident),
+                        ForeignItem::Static(i) => (&i.attrs, &i.ident),
+                        ForeignItem::Type(i) => (&i.attrs, &i.ident),
+                        ForeignItem::Macro(i) => {
+                            self.undocumented(&i.attrs);
+                            continue;
+                        }
+                        _ => continue,
+                    };
+                    self.doctests(attrs, Some(&name.to_string()));
+                }
+            }
+            Item::Macro(item) => self.undocumented(&item.attrs),
+            Item::Use(item) => self.undocumented(&item.attrs),
+            Item::ExternCrate(item) => self.undocumented(&item.attrs),
+            _ => {}
+        }
+    }
+
+    fn fields<'f>(&mut self, fields: impl IntoIterator<Item = &'f Field>) {
+        for (index, field) in fields.into_iter().enumerate() {
+            let name = field.ident.as_ref().map_or_else(|| index.to_string(), |i| i.to_string());
+            self.doctests(&field.attrs, Some(&name));
+        }
+    }
+
+    fn scoped(&mut self, name: String, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(name);
+        f(self);
+        self.scopes.pop();
+    }
+
+    /// Collects the annotated doctests in the documentation of an item, or in the documentation
+    /// of the current scope if `item` is `None`.
+    fn doctests(&mut self, attrs: &[Attribute], item: Option<&str>) {
+        for (fence, annotations) in self.annotated_doctests(attrs) {
+            let mut segments = Vec::new();
+            segments.extend(self.file_module.as_deref());
+            segments.extend(self.scopes.iter().map(String::as_str));
+            segments.extend(item);
+            let mut path = segments.join("::");
+            if !path.is_empty() {
+                path.push(' ');
+            }
+            let name =
+                format!("{}::{} - {path}(line {fence})", self.krate, self.doctest_file.display());
+            self.found.push(self.test_file(name, annotations));
+        }
+    }
+
+    /// Rejects annotated doctests in the documentation of items rustdoc doesn't name after a path.
+    fn undocumented(&mut self, attrs: &[Attribute]) {
+        if let Some((fence, _)) = self.annotated_doctests(attrs).first() {
+            self.error(*fence, "failed to find the item documented by the annotated doctests");
+        }
+    }
+
+    /// Returns the line of the opening fence and the annotations of the annotated doctests in a
+    /// documentation.
+    fn annotated_doctests(&mut self, attrs: &[Attribute]) -> Vec<(usize, Vec<Annotation>)> {
+        let mut doctests = Vec::new();
+        let mut doctest: Option<(usize, Vec<Annotation>)> = None;
+        for (number, doc) in doc_lines(attrs) {
+            let doc = doc.trim_start();
+            if doc.starts_with("```") {
+                match doctest.take() {
+                    // Rustdoc names doctests after the line of their opening fence.
+                    None => doctest = Some((number, Vec::new())),
+                    Some((fence, annotations)) if !annotations.is_empty() => {
+                        doctests.push((fence, annotations));
+                    }
+                    Some(_) => {}
+                }
+            } else if let Some((_, annotations)) = &mut doctest {
+                if let Some(id) =
+                    doc.strip_prefix('#').and_then(|d| d.trim_start().strip_prefix(ANNOTATION))
+                {
+                    annotations.push(self.annotation(id.into()));
+                    self.doc_annotations.remove(&number);
+                }
+            } else if doc.contains(ANNOTATION) {
+                self.error(
+                    number,
+                    "doctest annotations must be hidden lines inside the code block",
+                );
+            }
+        }
+        doctests
+    }
+
+    fn annotation(&self, id: String) -> Annotation {
+        Annotation {
+            id: id.trim().into(),
+            file: self.path.into(),
+            revision: None,
+            diagnostic: None,
+        }
+    }
+
+    fn test_file(&self, name: String, annotations: Vec<Annotation>) -> TestFile {
+        TestFile { file: self.path.to_str().unwrap().into(), name: Some(name), annotations }
+    }
+
+    fn not_on_test(&self, line: usize) -> ! {
+        self.error(line, "annotations must be attached to a #[test] function")
+    }
+
+    fn error(&self, line: usize, message: &str) -> ! {
+        panic!("{}:{line}: {message}", self.path.display());
+    }
+}
+
+/// Returns the lines of the documentation in a list of attributes, with their line in the file.
+fn doc_lines(attrs: &[Attribute]) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        let Meta::NameValue(meta) = &attr.meta else { continue };
+        let Expr::Lit(syn::ExprLit { lit: Lit::Str(doc), .. }) = &meta.value else { continue };
+        if !meta.path.is_ident("doc") {
+            continue;
+        }
+        let start = attr.span().start().line;
+        lines.extend(doc.value().lines().enumerate().map(|(i, doc)| (start + i, doc.into())));
+    }
+    lines
+}
+
+/// Returns the name rustdoc gives to a type in the doctest names, which only keeps the spaces
+/// between words, like in `Wrapper<T>` or `&'a mut T`.
+fn type_name(ty: &syn::Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '\'');
+    let mut name = String::new();
+    let mut chars = tokens.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != ' ' || (is_word(name.chars().last()) && is_word(chars.peek().copied())) {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// Returns the module path of a file, relative to the root of the crate.
+fn file_module(relative: &Path) -> Option<String> {
+    let mut components = relative
+        .iter()
+        .skip(1)
+        .map(|c| c.to_str().unwrap().trim_end_matches(".rs"))
+        .collect::<Vec<_>>();
+    if let Some(&("lib" | "mod")) = components.last() {
+        components.pop();
+    }
+    (!components.is_empty()).then(|| components.join("::"))
+}
@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+// SPDX-FileCopyrightText: The Ferrocene Developers
+
+// ignore-tidy-linelength
+
+use super::*;
+
+fn collect(path: &str, contents: &str) -> Vec<(String, Option<String>, Option<String>)> {
+    let collector = Collector::new(Arc::new(Config::default()));
+    collector
+        .collect_annotations(Path::new(path), contents)
+        .into_iter()
+        .map(|a| (a.id, a.revision, a.diagnostic))
+        .collect()
+}
+
+#[test]
+fn test_file_annotations() {
+    let contents = "\
+// ferrocene-annotations: fls_abc
+// ferrocene-annotations: fls_def
+fn main() {}
+";
+    assert_eq!(
+        vec![("fls_abc".into(), None, None), ("fls_def".into(), None, None)],
+        collect("tests/ui/foo.rs", contents)
+    );
+}
+
+#[test]
+fn test_revision_annotations() {
+    let contents = "\
+// revisions: a b
+// ferrocene-annotations: fls_abc
+//[b] ferrocene-annotations: fls_def
+fn main() {}
+";
+    assert_eq!(
+        vec![("fls_abc".into(), None, None), ("fls_def".into(), Some("b".into()), None)],
+        collect("tests/ui/foo.rs", contents)
+    );
+}
+
+#[test]
+#[should_panic = "annotation scoped to undeclared revision `c`"]
+fn test_undeclared_revision() {
+    let contents = "\
+// revisions: a b
+//[c] ferrocene-annotations: fls_abc
+fn main() {}
+";
+    collect("tests/ui/foo.rs", contents);
+}
+
+#[test]
+fn test_diagnostic_annotations() {
+    let contents = "\
+// revisions: a b
+// ferrocene-annotations: fls_abc expect: E0308
+//[b] ferrocene-annotations: fls_def expect: cannot find value
+fn main() {
+    let _: u8 = \"\"; //~ ERROR E0308
+    let _ = x; //[b]~ ERROR cannot find value `x`
+}
+";
+    assert_eq!(
+        vec![
+            ("fls_abc".into(), None, Some("E0308".into())),
+            ("fls_def".into(), Some("b".into()), Some("cannot find value".into())),
+        ],
+        collect("tests/ui/foo.rs", contents)
+    );
+}
+
+#[test]
+#[should_panic = "no matching `//~` expectation was found"]
+fn test_diagnostic_of_other_revision() {
+    let contents = "\
+// revisions: a b
+//[a] ferrocene-annotations: fls_abc expect: cannot find value
+fn main() {
+    let _ = x; //[b]~ ERROR cannot find value `x`
+}
+";
+    collect("tests/ui/foo.rs", contents);
+}
+
+#[test]
+#[should_panic = "annotations can only be scoped"]
+fn test_scoped_annotation_in_bulk_file() {
+    collect("tests/ui/ferrocene-annotations", "// ferrocene-annotations: fls_abc expect: E0308\n");
+}
+
+fn collect_library(relative: &str, contents: &str) -> Vec<(String, Vec<String>)> {
+    library::collect_file("core", Path::new(relative), Path::new(relative), contents)
+        .into_iter()
+        .map(|t| (t.name.unwrap(), t.annotations.into_iter().map(|a| a.id).collect()))
+        .collect()
+}
+
+#[test]
+fn test_library_unit_test_annotations() {
+    let contents = "\
+// ferrocene-annotations: fls_abc
+#[test]
+fn top_level() {}
+
+#[test]
+fn not_annotated() {}
+
+mod nested {
+    #[test]
+    // ferrocene-annotations: fls_def
+    // ferrocene-annotations: fls_ghi
+    #[cfg_attr(miri, ignore)]
+    fn inner() {}
+}
+
+// ferrocene-annotations: fls_jkl
+#[test]
+fn after_module() {}
+";
+    assert_eq!(
+        vec![
+            ("core::num::top_level".into(), vec!["fls_abc".into()]),
+            ("core::num::nested::inner".into(), vec!["fls_def".into(), "fls_ghi".into()]),
+            ("core::num::after_module".into(), vec!["fls_jkl".into()]),
+        ],
+        collect_library("tests/num/mod.rs", contents)
+    );
+}
+
+#[test]
+#[should_panic = "annotations must be attached to a #[test] function"]
+fn test_library_annotation_not_on_test() {
+    let contents = "\
+// ferrocene-annotations: fls_abc
+fn helper() {}
+";
+    collect_library("tests/lib.rs", contents);
+}
+
+#[test]
+fn test_library_doctest_annotations() {
+    let contents = "\
+/// Does nothing.
+///
+/// ```
+/// # // ferrocene-annotations: fls_abc
+/// nothing();
+/// ```
+///
+/// ```
+/// nothing();
+/// ```
+pub fn nothing() {}
+";
+    assert_eq!(
+        vec![(
+            "core::library/core/src/mem/mod.rs - mem::nothing (line 3)".into(),
+            vec!["fls_abc".into()]
+        )],
+        collect_library("src/mem/mod.rs", contents)
+    );
+}
+
+#[test]
+fn test_library_doctest_item_layout() {
+    let contents = "\
+pub struct Wrapper<T>(T);
+
+impl<T> Wrapper<T>
+where
+    T: Clone,
+{
+    /// Checks.
+    ///
+    /// ```
+    /// # // ferrocene-annotations: fls_abc
+    /// ```
+    #[must_use = \"if you intended to check, \\
+                  use the result\"]
+    #[inline]
+    pub const
+    fn check(&self) -> bool {
+        true
+    }
+}
+";
+    assert_eq!(
+        vec![(
+            "core::library/core/src/mem/mod.rs - mem::Wrapper<T>::check (line 9)".into(),
+            vec!["fls_abc".into()]
+        )],
+        collect_library("src/mem/mod.rs", contents)
+    );
+}
+
+#[test]
+#[should_panic = "src/lib.rs:2:13: failed to parse: expected `:`"]
+fn test_library_parse_error() {
+    collect_library("src/lib.rs", "fn valid() {}\nfn invalid(x) {}\n");
+}
+
+#[test]
+#[should_panic = "src/lib.rs:4: failed to find the item documented by the annotated doctests"]
+fn test_library_doctest_in_macro() {
+    let contents = "\
+macro_rules! define {
+    () => {
+        /// ```
+        /// # // ferrocene-annotations: fls_abc
+        /// ```
+        pub fn defined() {}
+    };
+}
+";
+    collect_library("src/lib.rs", contents);
+}
+
+#[test]
+fn test_library_doctest_names() {
+    let lib = "\
+//! The crate.
+//!
+//! ```
+//! # // ferrocene-annotations: fls_crate
+//! ```
+
+pub mod mem;
+";
+    let mem = "\
+/// Swaps.
+///
+/// ```
+/// # // ferrocene-annotations: fls_swap
+/// ```
+pub const fn swap() {}
+
+pub struct Wrapper<T>(T);
+
+impl<T: Clone> Clone for Wrapper<T> {
+    /// Clones.
+    ///
+    /// ```
+    /// # // ferrocene-annotations: fls_clone
+    /// ```
+    fn clone(&self) -> Self {
+        Wrapper(self.0.clone())
+    }
+}
+
+impl<T> Wrapper<T> {
+    /// Unwraps.
+    ///
+    /// ```
+    /// # // ferrocene-annotations: fls_into_inner
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+pub mod inner {
+    //! Inner module.
+    //!
+    //! ```
+    //! # // ferrocene-annotations: fls_inner
+    //! ```
+
+    /// A trait.
+    pub trait Answer {
+        /// Answers.
+        ///
+        /// ```
+        /// # // ferrocene-annotations: fls_answer
+        /// ```
+        fn answer(&self) -> u32;
+    }
+}
+";
+    // Output of `cargo test --doc -- -Z unstable-options --format json` for the sources above,
+    // placed in `library/core` of a workspace.
+    let libtest = r#"
+{ "type": "test", "name": "library/core/src/lib.rs - (line 3)", "event": "ok" }
+{ "type": "test", "name": "library/core/src/mem/mod.rs - mem::Wrapper<T>::clone (line 13)", "event": "ok" }
+{ "type": "test", "name": "library/core/src/mem/mod.rs - mem::Wrapper<T>::into_inner (line 24)", "event": "ok" }
+{ "type": "test", "name": "library/core/src/mem/mod.rs - mem::inner (line 35)", "event": "ok" }
+{ "type": "test", "name": "library/core/src/mem/mod.rs - mem::inner::Answer::answer (line 43)", "event": "ok" }
+{ "type": "test", "name": "library/core/src/mem/mod.rs - mem::swap (line 3)", "event": "ok" }
+"#;
+
+    let mut collected: Vec<String> = collect_library("src/lib.rs", lib)
+        .into_iter()
+        .chain(collect_library("src/mem/mod.rs", mem))
+        .map(|(name, _)| name)
+        .collect();
+    collected.sort();
+    let mut expected: Vec<String> = libtest
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let event: serde_json::Value = serde_json::from_str(line).unwrap();
+            format!("core::{}", event["name"].as_str().unwrap())
+        })
+        .collect();
+    expected.sort();
+    assert_eq!(expected, collected);
+}
@@ -36,6 +36,8 @@ pub struct EarlyProps {
     pub aux: Vec<String>,
     pub aux_crate: Vec<(String, String)>,
     pub revisions: Vec<String>,
+    /// Targets the test is known to fail spuriously on, where it's retried on failure.
+    pub flaky_on: Vec<String>,
 }
 
 impl EarlyProps {
@@ -57,6 +59,9 @@ pub fn from_reader<R: Read>(config: &Config, testfile: &Path, rdr: R) -> Self {
                 Config::parse_aux_crate,
             );
             config.parse_and_update_revisions(ln, &mut props.revisions);
+            config.push_name_value_directive(ln, directives::FLAKY_ON, &mut props.flaky_on, |r| {
+                r.trim().to_string()
+            });
         });
         return props;
     }
@@ -178,6 +183,17 @@ pub struct TestProps {
     // Whether to tell `rustc` to remap the "src base" directory to a fake
     // directory.
     pub remap_src_base: bool,
+    // Glob patterns of the symbols that must not be present in the binary of an object-check
+    // test, matched against both the mangled and the demangled names
+    pub no_symbols: Vec<String>,
+    // Maximum size in bytes of the sections of the binary of an object-check test
+    pub section_max_sizes: Vec<(String, u64)>,
+    // ELF header flags the binary of an object-check test must have
+    pub elf_flags: Vec<String>,
+    // Relocation types that must not be present in the binary of an object-check test
+    pub no_relocations: Vec<String>,
+    // Whether the test relies on unwinding, which isn't supported by every codegen backend
+    pub needs_unwind: bool,
 }
 
 mod directives {
@@ -198,6 +214,7 @@ mod directives {
     pub const PRETTY_COMPARE_ONLY: &'static str = "pretty-compare-only";
     pub const AUX_BUILD: &'static str = "aux-build";
     pub const AUX_CRATE: &'static str = "aux-crate";
+    pub const FLAKY_ON: &'static str = "flaky-on";
     pub const EXEC_ENV: &'static str = "exec-env";
     pub const RUSTC_ENV: &'static str = "rustc-env";
     pub const UNSET_EXEC_ENV: &'static str = "unset-exec-env";
@@ -216,6 +233,11 @@ mod directives {
     pub const MIR_UNIT_TEST: &'static str = "unit-test";
     pub const REMAP_SRC_BASE: &'static str = "remap-src-base";
     pub const COMPARE_OUTPUT_LINES_BY_SUBSET: &'static str = "compare-output-lines-by-subset";
+    pub const NO_SYMBOL: &'static str = "no-symbol";
+    pub const SECTION_MAX_SIZE: &'static str = "section-max-size";
+    pub const ELF_FLAG: &'static str = "elf-flag";
+    pub const NO_RELOCATION: &'static str = "no-relocation";
+    pub const NEEDS_UNWIND: &'static str = "needs-unwind";
     // This isn't a real directive, just one that is probably mistyped often
     pub const INCORRECT_COMPILER_FLAGS: &'static str = "compiler-flags";
 }
@@ -265,6 +287,11 @@ pub fn new() -> Self {
             stderr_per_bitwidth: false,
             mir_unit_test: None,
             remap_src_base: false,
+            no_symbols: vec![],
+            section_max_sizes: vec![],
+            elf_flags: vec![],
+            no_relocations: vec![],
+            needs_unwind: false,
         }
     }
 
@@ -450,6 +477,33 @@ fn load_from(&mut self, testfile: &Path, cfg: Option<&str>, config: &Config) {
                     |r| r.trim().to_string(),
                 );
                 config.set_name_directive(ln, STDERR_PER_BITWIDTH, &mut self.stderr_per_bitwidth);
+                config.push_name_value_directive(ln, NO_SYMBOL, &mut self.no_symbols, |r| {
+                    r.trim().to_string()
+                });
+                config.push_name_value_directive(
+                    ln,
+                    SECTION_MAX_SIZE,
+                    &mut self.section_max_sizes,
+                    |r| {
+                        r.trim()
+                            .split_once(' ')
+                            .and_then(|(section, size)| Some((section, size.trim().parse().ok()?)))
+                            .map(|(section, size)| (section.to_string(), size))
+                            .unwrap_or_else(|| {
+                                panic!("{SECTION_MAX_SIZE} expects a section and a size, got `{r}`")
+                            })
+                    },
+                );
+                config.push_name_value_directive(ln, ELF_FLAG, &mut self.elf_flags, |r| {
+                    r.trim().to_string()
+                });
+                config.push_name_value_directive(
+                    ln,
+                    NO_RELOCATION,
+                    &mut self.no_relocations,
+                    |r| r.trim().to_string(),
+                );
+                config.set_name_directive(ln, NEEDS_UNWIND, &mut self.needs_unwind);
                 config.set_name_directive(ln, INCREMENTAL, &mut self.incremental);
 
                 // Unlike the other `name_value_directive`s this needs to be handled manually,
@@ -79,6 +79,11 @@ pub(super) fn handle_needs(
             condition: config.run_enabled(),
             ignore_reason: "ignored when running the resulting test binaries is disabled",
         },
+        Need {
+            name: "needs-qemu-semihosting",
+            condition: config.qemu_semihosting,
+            ignore_reason: "ignored when the tests are not run under QEMU with semihosting",
+        },
         Need {
             name: "needs-unwind",
             condition: config.can_unwind(),
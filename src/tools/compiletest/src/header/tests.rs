@@ -223,6 +223,23 @@ fn aux_build() {
     );
 }
 
+#[test]
+fn flaky_on() {
+    let config: Config = cfg().build();
+
+    assert_eq!(
+        parse_rs(
+            &config,
+            r"
+        // flaky-on: aarch64-unknown-linux-gnu
+        // flaky-on: x86_64-unknown-linux-gnu
+        "
+        )
+        .flaky_on,
+        vec!["aarch64-unknown-linux-gnu", "x86_64-unknown-linux-gnu"],
+    );
+}
+
 #[test]
 fn no_system_llvm() {
     let config: Config = cfg().system_llvm(false).build();
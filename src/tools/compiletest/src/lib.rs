@@ -24,13 +24,14 @@
 use crate::common::{Config, Debugger, Mode, PassMode, TestPaths};
 use crate::util::logv;
 use build_helper::git::{get_git_modified_files, get_git_untracked_files};
+use build_helper::metrics::{ResumeLogEntry, Test, TestOutcome, RESUMED_TEST_MESSAGE};
 use core::panic;
 use getopts::Options;
 use lazycell::AtomicLazyCell;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 use std::ffi::OsString;
 use std::fs;
-use std::io::{self, ErrorKind};
+use std::io::{self, ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::SystemTime;
@@ -41,7 +42,7 @@
 
 use self::header::{make_test_description, EarlyProps};
 use crate::header::HeadersCache;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 pub fn parse_config(args: Vec<String>) -> Config {
     let mut opts = Options::new();
@@ -67,7 +68,7 @@ pub fn parse_config(args: Vec<String>) -> Config {
             "mode",
             "which sort of compile tests to run",
             "run-pass-valgrind | pretty | debug-info | codegen | rustdoc \
-            | rustdoc-json | codegen-units | incremental | run-make | ui | js-doc-test | mir-opt | assembly",
+            | rustdoc-json | codegen-units | incremental | run-make | ui | js-doc-test | mir-opt | assembly | object-check",
         )
         .reqopt(
             "",
@@ -84,6 +85,26 @@ pub fn parse_config(args: Vec<String>) -> Config {
         .optopt("", "run", "whether to execute run-* tests", "auto | always | never")
         .optflag("", "ignored", "run tests marked as ignored")
         .optmulti("", "skip", "skip tests matching SUBSTRING. Can be passed multiple times", "SUBSTRING")
+        .optopt("", "flaky-retries", "how many times to retry failing flaky tests", "N")
+        .optopt("", "flaky-retries-log", "file to record failed attempts of flaky tests", "FILE")
+        .optopt(
+            "",
+            "differential-codegen-backend",
+            "also build and run ui tests with this codegen backend, comparing their behavior",
+            "BACKEND",
+        )
+        .optmulti(
+            "",
+            "differential-skip",
+            "don't build tests under PATH with the differential codegen backend",
+            "PATH",
+        )
+        .optmulti(
+            "",
+            "ferrocene-expected-failure",
+            "run tests matching SUBSTRING expecting them to fail. Can be passed multiple times",
+            "SUBSTRING",
+        )
         .optflag("", "exact", "filters match exactly")
         .optopt(
             "",
@@ -128,6 +149,11 @@ pub fn parse_config(args: Vec<String>) -> Config {
         .optopt("", "nodejs", "the name of nodejs", "PATH")
         .optopt("", "npm", "the name of npm", "PATH")
         .optopt("", "remote-test-client", "path to the remote test client", "PATH")
+        .optflag(
+            "",
+            "qemu-semihosting",
+            "run test binaries with the remote test client under QEMU with semihosting",
+        )
         .optopt(
             "",
             "compare-mode",
@@ -141,6 +167,8 @@ pub fn parse_config(args: Vec<String>) -> Config {
             `./<build_base>/rustfix_missing_coverage.txt`",
         )
         .optflag("", "force-rerun", "rerun tests even if the inputs are unchanged")
+        .optopt("", "shard", "only run the tests of this shard", "INDEX/COUNT")
+        .optopt("", "resume-log", "skip the tests recorded as passed in this log", "PATH")
         .optflag("", "only-modified", "only run tests that result been modified")
         .optflag("", "nocapture", "")
         .optflag("h", "help", "show this message")
@@ -240,6 +268,22 @@ fn make_absolute(path: PathBuf) -> PathBuf {
         run_ignored,
         filters: matches.free.clone(),
         skip: matches.opt_strs("skip"),
+        ferrocene_expected_failures: matches.opt_strs("ferrocene-expected-failure"),
+        flaky_retries: matches
+            .opt_str("flaky-retries")
+            .map(|retries| retries.parse().expect("invalid value for --flaky-retries"))
+            .unwrap_or(0),
+        flaky_retries_log: matches.opt_str("flaky-retries-log").map(PathBuf::from),
+        differential_codegen_backend: matches.opt_str("differential-codegen-backend"),
+        differential_skip: matches
+            .opt_strs("differential-skip")
+            .into_iter()
+            .map(PathBuf::from)
+            .collect(),
+        shard: matches
+            .opt_str("shard")
+            .map(|shard| shard.parse().unwrap_or_else(|err| panic!("{err}"))),
+        resume_log: matches.opt_str("resume-log").map(PathBuf::from),
         filter_exact: matches.opt_present("exact"),
         force_pass_mode: matches.opt_str("pass").map(|mode| {
             mode.parse::<PassMode>()
@@ -284,6 +328,7 @@ fn make_absolute(path: PathBuf) -> PathBuf {
         only_modified: matches.opt_present("only-modified"),
         color,
         remote_test_client: matches.opt_str("remote-test-client").map(PathBuf::from),
+        qemu_semihosting: matches.opt_present("qemu-semihosting"),
         compare_mode: matches
             .opt_str("compare-mode")
             .map(|s| s.parse().expect("invalid --compare-mode provided")),
@@ -412,6 +457,12 @@ pub fn run_tests(config: Arc<Config>) {
     }
 
     tests.sort_by(|a, b| a.desc.name.as_slice().cmp(&b.desc.name.as_slice()));
+    if let Some(shard) = config.shard {
+        tests.retain(|test| shard.contains(test.desc.name.as_slice()));
+    }
+    if let Some(resume_log) = &config.resume_log {
+        skip_passed_tests(resume_log, &mut tests);
+    }
 
     let res = test::run_tests_console(&opts, tests);
     match res {
@@ -762,11 +813,25 @@ fn make_test(
                 std::fs::File::open(&test_path).expect("open test file to parse ignores");
             let cfg = revision.map(|v| &**v);
             let test_name = crate::make_test_name(&config, testpaths, revision);
+            let retries = if early_props.flaky_on.contains(&config.target) {
+                config.flaky_retries
+            } else {
+                0
+            };
             let mut desc = make_test_description(
                 &config, cache, test_name, &test_path, src_file, cfg, poisoned,
             );
+            // Tests known to fail must fail, so that stale entries of ignored-tests.toml are
+            // detected: a test that starts passing is reported as a failure.
+            let expected_failure = config
+                .ferrocene_expected_failures
+                .iter()
+                .any(|failure| desc.name.as_slice().contains(failure.as_str()));
+            if expected_failure {
+                desc.should_panic = test::ShouldPanic::Yes;
+            }
             // Ignore tests that already run and are up to date with respect to inputs.
-            if !config.force_rerun {
+            if !config.force_rerun && !expected_failure {
                 desc.ignore |= is_up_to_date(
                     &config,
                     testpaths,
@@ -775,9 +840,10 @@ fn make_test(
                     inputs,
                 );
             }
+            let name = desc.name.as_slice().to_string();
             test::TestDescAndFn {
                 desc,
-                testfn: make_test_closure(config.clone(), testpaths, revision),
+                testfn: make_test_closure(config.clone(), testpaths, revision, name, retries),
             }
         })
         .collect()
@@ -915,16 +981,89 @@ fn make_test_closure(
     config: Arc<Config>,
     testpaths: &TestPaths,
     revision: Option<&String>,
+    name: String,
+    retries: usize,
 ) -> test::TestFn {
     let config = config.clone();
     let testpaths = testpaths.clone();
     let revision = revision.cloned();
     test::DynTestFn(Box::new(move || {
+        for attempt in 1..=retries {
+            let run = || runtest::run(config.clone(), &testpaths, revision.as_deref());
+            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(run)) {
+                Ok(()) => return Ok(()),
+                Err(payload) => report_failed_attempt(&config, &name, attempt, &*payload),
+            }
+        }
         runtest::run(config, &testpaths, revision.as_deref());
         Ok(())
     }))
 }
 
+/// Reports a failed attempt of a flaky test, which is about to be retried.
+///
+/// Libtest writes its output from another thread, so the attempt can't be reported on stdout
+/// without corrupting it. It's appended instead to the file passed with `--flaky-retries-log`,
+/// for bootstrap to record it in the build metrics, or printed on stderr otherwise.
+fn report_failed_attempt(
+    config: &Config,
+    name: &str,
+    attempt: usize,
+    payload: &(dyn std::any::Any + Send),
+) {
+    let Some(log) = &config.flaky_retries_log else {
+        let _ = writeln!(io::stderr().lock(), "test {name} failed on attempt {attempt}, retrying");
+        return;
+    };
+
+    let message = payload
+        .downcast_ref::<String>()
+        .map(|s| s.as_str())
+        .or_else(|| payload.downcast_ref::<&str>().copied());
+    let line = serde_json::json!({ "name": name, "attempt": attempt, "message": message });
+
+    // Tests run in parallel, and each line must be written at once.
+    static LOG: Mutex<()> = Mutex::new(());
+    let _guard = LOG.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(parent) = log.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log)
+        .unwrap_or_else(|err| panic!("failed to open {}: {err}", log.display()));
+    file.write_all(format!("{line}\n").as_bytes())
+        .unwrap_or_else(|err| panic!("failed to write to {}: {err}", log.display()));
+}
+
+/// Skips the tests recorded as passed in the log bootstrap writes while running the tests. They
+/// are reported as ignored with a dedicated message, for bootstrap to record them in the build
+/// metrics with their outcome from the log.
+fn skip_passed_tests(resume_log: &Path, tests: &mut [test::TestDescAndFn]) {
+    let contents = match fs::read_to_string(resume_log) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return,
+        Err(err) => panic!("failed to read {}: {err}", resume_log.display()),
+    };
+
+    let mut passed = HashSet::new();
+    for line in contents.lines() {
+        let entry = serde_json::from_str(line)
+            .unwrap_or_else(|err| panic!("invalid line in {}: {err}", resume_log.display()));
+        if let ResumeLogEntry::Test(Test { name, outcome: TestOutcome::Passed, .. }) = entry {
+            passed.insert(name);
+        }
+    }
+
+    for test in tests {
+        if passed.contains(test.desc.name.as_slice()) {
+            test.desc.ignore = true;
+            test.desc.ignore_message = Some(RESUMED_TEST_MESSAGE);
+        }
+    }
+}
+
 /// Returns `true` if the given target is an Android target for the
 /// purposes of GDB testing.
 fn is_android_gdb_target(target: &str) -> bool {
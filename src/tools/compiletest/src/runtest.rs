@@ -6,7 +6,7 @@
 use crate::common::{Codegen, CodegenUnits, DebugInfo, Debugger, Rustdoc};
 use crate::common::{CompareMode, FailMode, PassMode};
 use crate::common::{Config, TestPaths};
-use crate::common::{CoverageMap, Pretty, RunCoverage, RunPassValgrind};
+use crate::common::{CoverageMap, ObjectCheck, Pretty, RunCoverage, RunPassValgrind};
 use crate::common::{UI_COVERAGE, UI_COVERAGE_MAP, UI_RUN_STDERR, UI_RUN_STDOUT};
 use crate::compute_diff::{write_diff, write_filtered_diff};
 use crate::errors::{self, Error, ErrorKind};
@@ -44,6 +44,9 @@
 mod debugger;
 use debugger::DebuggerCommands;
 
+mod differential;
+mod object_check;
+
 #[cfg(test)]
 mod tests;
 
@@ -192,6 +195,8 @@ pub fn compute_stamp_hash(config: &Config) -> String {
 
     if let Ui = config.mode {
         config.force_pass_mode.hash(&mut hash);
+        config.differential_codegen_backend.hash(&mut hash);
+        config.differential_skip.hash(&mut hash);
     }
 
     format!("{:x}", hash.finish())
@@ -253,6 +258,7 @@ fn run_revision(&self) {
             Ui => self.run_ui_test(),
             MirOpt => self.run_mir_opt_test(),
             Assembly => self.run_assembly_test(),
+            ObjectCheck => self.run_object_check_test(),
             JsDocTest => self.run_js_doc_test(),
             CoverageMap => self.run_coverage_map_test(),
             RunCoverage => self.run_coverage_test(),
@@ -2002,6 +2008,27 @@ fn exec_compiled_test_general(
             // the program on the emulator with the arguments specified
             // (in the environment we give the process) and then report back
             // the same result.
+            //
+            // Bare-metal targets tested with semihosting have no support
+            // libraries to upload, and are instead transformed into:
+            //
+            //      remote-test-client run-semihosting target program arg1 arg2
+            _ if self.config.remote_test_client.is_some() && self.config.qemu_semihosting => {
+                let aux_dir = self.aux_output_dir_name();
+                let ProcArgs { prog, args } = self.make_run_args();
+                let mut test_client =
+                    Command::new(self.config.remote_test_client.as_ref().unwrap());
+                test_client.args(&["run-semihosting", &self.config.target]).arg(&prog).args(args);
+
+                prepare_env(&mut test_client);
+
+                self.compose_and_run(
+                    test_client,
+                    self.config.run_lib_path.to_str().unwrap(),
+                    Some(aux_dir.to_str().unwrap()),
+                    None,
+                )
+            }
             _ if self.config.remote_test_client.is_some() => {
                 let aux_dir = self.aux_output_dir_name();
                 let ProcArgs { prog, args } = self.make_run_args();
@@ -2484,7 +2511,7 @@ fn make_compile_args(
                 rustc.arg("-Copt-level=0");
             }
             RunPassValgrind | Pretty | DebugInfo | Codegen | Rustdoc | RustdocJson | RunMake
-            | CodegenUnits | JsDocTest | Assembly => {
+            | CodegenUnits | JsDocTest | Assembly | ObjectCheck => {
                 // do not use JSON output
             }
         }
@@ -2898,6 +2925,34 @@ fn run_assembly_test(&self) {
         }
     }
 
+    fn run_object_check_test(&self) {
+        // The binary is only inspected, so it can be built for any target.
+        let output_path = self.make_exe_name();
+        let rustc = self.make_compile_args(
+            &self.testpaths.file,
+            TargetLocation::ThisFile(output_path.clone()),
+            Emit::None,
+            AllowUnused::Yes,
+            LinkToAux::Yes,
+            Vec::new(),
+        );
+        let proc_res = self.compose_and_run_compiler(rustc, None);
+        if !proc_res.status.success() {
+            self.fatal_proc_rec("compilation failed!", &proc_res);
+        }
+
+        let data = fs::read(&output_path)
+            .unwrap_or_else(|err| self.fatal(&format!("failed to read the binary: {err}")));
+        let errors = object_check::check(&self.props, &data)
+            .unwrap_or_else(|err| self.fatal(&format!("failed to parse the binary: {err}")));
+        if !errors.is_empty() {
+            for error in &errors {
+                println!("{error}");
+            }
+            self.fatal_proc_rec("object checks failed", &proc_res);
+        }
+    }
+
     fn charset() -> &'static str {
         // FreeBSD 10.1 defaults to GDB 6.1.1 which doesn't support "auto" charset
         if cfg!(target_os = "freebsd") { "ISO-8859-1" } else { "UTF-8" }
@@ -3927,6 +3982,8 @@ fn run_ui_test(&self) {
                 let output_to_check = self.get_output(&proc_res);
                 self.check_all_error_patterns(&output_to_check, &proc_res, pm);
             }
+
+            self.check_differential_backend(&proc_res);
         }
 
         debug!(
@@ -3981,6 +4038,107 @@ fn run_ui_test(&self) {
         }
     }
 
+    /// Builds and runs the test again with the codegen backend passed to
+    /// `--differential-codegen-backend`, and checks that the program behaves the same as the one
+    /// built by the default backend. A mismatch is reported as a potential miscompilation, along
+    /// with a minimized reproducer.
+    fn check_differential_backend(&self, reference: &ProcRes) {
+        let Some(backend) = self.config.differential_codegen_backend.as_deref() else { return };
+        if self.config.differential_skip.iter().any(|skip| self.testpaths.file.starts_with(skip)) {
+            return;
+        }
+        // Cranelift doesn't support unwinding: panics abort the program, and `catch_unwind` can't
+        // recover from them.
+        if self.props.needs_unwind || differential::panicked(reference) {
+            println!("not compared with the `{backend}` codegen backend, as the test unwinds");
+            return;
+        }
+
+        let proc_res = match self.differential_run(&self.testpaths.file, Some(backend)) {
+            Ok(proc_res) => proc_res,
+            Err(proc_res) => self.fatal_proc_rec(
+                &format!(
+                    "compilation with the `{backend}` codegen backend failed, if the backend \
+                     doesn't support this test it has to be skipped by differential testing"
+                ),
+                &proc_res,
+            ),
+        };
+        let expected = self.differential_outcome(reference);
+        let actual = self.differential_outcome(&proc_res);
+        if expected == actual {
+            return;
+        }
+
+        println!("outcome with the default codegen backend: {expected}");
+        println!("outcome with the `{backend}` codegen backend: {actual}");
+        let reproducer = self.minimize_differential_reproducer(backend);
+        println!("minimized reproducer: {}", reproducer.display());
+        self.fatal_proc_rec(
+            &format!("potential miscompilation: the program behaves differently with `{backend}`"),
+            &proc_res,
+        );
+    }
+
+    /// Builds `input` with `backend`, or the default codegen backend if `None`, and runs it.
+    /// Returns the result of the compilation if it failed.
+    fn differential_run(&self, input: &Path, backend: Option<&str>) -> Result<ProcRes, ProcRes> {
+        let mut rustc = self.make_compile_args(
+            input,
+            TargetLocation::ThisFile(self.make_exe_name()),
+            Emit::None,
+            AllowUnused::Yes,
+            LinkToAux::Yes,
+            Vec::new(),
+        );
+        if let Some(backend) = backend {
+            rustc.arg(format!("-Zcodegen-backend={backend}"));
+        }
+        let proc_res = self.compose_and_run_compiler(rustc, None);
+        if !proc_res.status.success() {
+            return Err(proc_res);
+        }
+        Ok(self.exec_compiled_test_general(&[], false))
+    }
+
+    /// Normalizes the output of a program built for differential testing with the same rules as
+    /// the run output of ui tests, before it's compared between the codegen backends.
+    fn differential_outcome(&self, proc_res: &ProcRes) -> differential::Outcome {
+        let stdout = self.normalize_output(&proc_res.stdout, &self.props.normalize_stdout);
+        differential::Outcome::new(proc_res, stdout)
+    }
+
+    /// Removes the lines of the test that are not needed to observe a difference in behavior
+    /// between the default codegen backend and `backend`, returning the path of the reproducer.
+    fn minimize_differential_reproducer(&self, backend: &str) -> PathBuf {
+        let source = fs::read_to_string(&self.testpaths.file).unwrap();
+        let lines = source.lines().collect::<Vec<_>>();
+        let candidate = self.output_base_dir().join("differential-candidate.rs");
+
+        let minimized = differential::minimize(
+            &lines,
+            differential::MAX_MINIMIZATION_ATTEMPTS,
+            differential::MAX_MINIMIZATION_TIME,
+            |lines| {
+                fs::write(&candidate, lines.join("\n") + "\n").unwrap();
+                match (
+                    self.differential_run(&candidate, None),
+                    self.differential_run(&candidate, Some(backend)),
+                ) {
+                    (Ok(expected), Ok(actual)) => {
+                        self.differential_outcome(&expected) != self.differential_outcome(&actual)
+                    }
+                    _ => false,
+                }
+            },
+        );
+        let _ = fs::remove_file(&candidate);
+
+        let reproducer = self.output_base_dir().join("differential-reproducer.rs");
+        fs::write(&reproducer, minimized.join("\n") + "\n").unwrap();
+        reproducer
+    }
+
     fn run_mir_opt_test(&self) {
         let pm = self.pass_mode();
         let should_run = self.should_run(pm);
@@ -4160,6 +4318,8 @@ fn normalize_output(&self, output: &str, custom_rules: &[(String, String)]) -> S
             || cflags.contains("--error-format pretty-json")
             || cflags.contains("--error-format=json")
             || cflags.contains("--error-format=pretty-json")
+            || cflags.contains("--error-format sarif")
+            || cflags.contains("--error-format=sarif")
             || cflags.contains("--output-format json")
             || cflags.contains("--output-format=json");
 
@@ -0,0 +1,93 @@
+//! Differential testing of the programs built by `ui` tests.
+//!
+//! The programs are built and run a second time with another codegen backend, and their behavior
+//! is compared with the one of the programs built by the default backend. As the backends don't
+//! share any code generation logic, a difference points to a miscompilation in one of them.
+
+use super::ProcRes;
+use std::fmt;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+/// Maximum number of candidates checked while minimizing a reproducer. Each of them is built and
+/// run with both backends, so this bounds the time spent on a single mismatch.
+pub(super) const MAX_MINIMIZATION_ATTEMPTS: usize = 200;
+
+/// Maximum time spent minimizing a reproducer, for the tests that are slow to build or to run.
+pub(super) const MAX_MINIMIZATION_TIME: Duration = Duration::from_secs(10 * 60);
+
+/// Whether the program panicked, as reported by the default panic hook or by the exit code of a
+/// panicking `main`. Panics unwind with the default backend but abort with Cranelift, so the
+/// programs panicking at runtime behave differently by design.
+pub(super) fn panicked(proc_res: &ProcRes) -> bool {
+    proc_res.status.code() == Some(101) || proc_res.stderr.contains("panicked at")
+}
+
+/// Observable behavior of a test program, compared between the two backends.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Outcome {
+    status: ExitStatus,
+    /// Standard output normalized like the one of ui tests, including the `normalize-stdout-test`
+    /// rules of the test, so that legitimate nondeterminism doesn't count as a difference.
+    stdout: String,
+}
+
+impl Outcome {
+    pub(super) fn new(proc_res: &ProcRes, normalized_stdout: String) -> Self {
+        Outcome { status: proc_res.status, stdout: normalized_stdout }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.status)?;
+        writeln!(f, "------stdout------")?;
+        write!(f, "{}", self.stdout)?;
+        if !self.stdout.is_empty() && !self.stdout.ends_with('\n') {
+            writeln!(f)?;
+        }
+        write!(f, "------------------")
+    }
+}
+
+/// Removes lines from `lines` with the ddmin algorithm, as long as `reproduces` returns true for
+/// the remaining ones. Candidates that don't compile are expected to be rejected by `reproduces`.
+/// Minimization stops after `max_attempts` candidates or `max_time`, whichever comes first.
+pub(super) fn minimize<'a>(
+    lines: &[&'a str],
+    max_attempts: usize,
+    max_time: Duration,
+    mut reproduces: impl FnMut(&[&'a str]) -> bool,
+) -> Vec<&'a str> {
+    let deadline = Instant::now() + max_time;
+    let mut current = lines.to_vec();
+    let mut granularity = 2;
+    let mut attempts = 0;
+    let can_continue = |attempts| attempts < max_attempts && Instant::now() < deadline;
+    while current.len() >= 2 && can_continue(attempts) {
+        let chunk_len = (current.len() + granularity - 1) / granularity;
+        let mut reduced = false;
+        for start in (0..current.len()).step_by(chunk_len) {
+            if !can_continue(attempts) {
+                break;
+            }
+            attempts += 1;
+            let end = (start + chunk_len).min(current.len());
+            let candidate: Vec<_> =
+                current[..start].iter().chain(&current[end..]).copied().collect();
+            if reproduces(&candidate) {
+                current = candidate;
+                granularity = (granularity - 1).max(2);
+                reduced = true;
+                break;
+            }
+        }
+        if !reduced {
+            if granularity >= current.len() {
+                break;
+            }
+            granularity = (granularity * 2).min(current.len());
+        }
+    }
+    current
+}
@@ -0,0 +1,199 @@
+//! Checks of the properties of the binaries built by `object-check` tests.
+//!
+//! ELF flags and relocation types can be written either as numbers or by name. Only the names
+//! relevant for the targets we check are known, as they depend on the architecture.
+
+use crate::header::TestProps;
+use object::elf;
+use object::read::elf::{FileHeader, Rel, Rela, SectionHeader};
+use object::{Endianness, FileKind, Object, ObjectSection, ObjectSymbol};
+use std::collections::BTreeSet;
+
+/// Checks the binary against the directives of the test, returning the list of violations.
+pub(super) fn check(props: &TestProps, data: &[u8]) -> Result<Vec<String>, object::Error> {
+    let file = object::File::parse(data)?;
+    let mut errors = Vec::new();
+
+    let patterns = props
+        .no_symbols
+        .iter()
+        .map(|pattern| match glob::Pattern::new(pattern) {
+            Ok(glob) => (pattern, glob),
+            Err(err) => panic!("invalid pattern `{pattern}`: {err}"),
+        })
+        .collect::<Vec<_>>();
+    // Symbols can be both in the static and in the dynamic symbol table.
+    let mut forbidden_symbols = BTreeSet::new();
+    for symbol in file.symbols().chain(file.dynamic_symbols()) {
+        let Ok(name) = symbol.name() else { continue };
+        let demangled = format!("{:#}", rustc_demangle::demangle(name));
+        for (pattern, glob) in &patterns {
+            if glob.matches(name) || glob.matches(&demangled) {
+                forbidden_symbols
+                    .insert(format!("symbol `{demangled}` matches forbidden pattern `{pattern}`"));
+            }
+        }
+    }
+    errors.extend(forbidden_symbols);
+
+    for (section, max_size) in &props.section_max_sizes {
+        let size: u64 =
+            file.sections().filter(|s| s.name() == Ok(section.as_str())).map(|s| s.size()).sum();
+        if size > *max_size {
+            errors.push(format!("section `{section}` is {size} bytes, more than {max_size}"));
+        }
+    }
+
+    if !props.elf_flags.is_empty() || !props.no_relocations.is_empty() {
+        match FileKind::parse(data)? {
+            FileKind::Elf32 => {
+                check_elf::<elf::FileHeader32<Endianness>>(props, data, &mut errors)?
+            }
+            FileKind::Elf64 => {
+                check_elf::<elf::FileHeader64<Endianness>>(props, data, &mut errors)?
+            }
+            kind => errors.push(format!("ELF directives cannot be checked on {kind:?} files")),
+        }
+    }
+
+    Ok(errors)
+}
+
+fn check_elf<Elf: FileHeader<Endian = Endianness>>(
+    props: &TestProps,
+    data: &[u8],
+    errors: &mut Vec<String>,
+) -> Result<(), object::Error> {
+    let header = Elf::parse(data)?;
+    let endian = header.endian()?;
+    let machine = header.e_machine(endian);
+
+    let flags = header.e_flags(endian);
+    for flag in &props.elf_flags {
+        let (mask, value) = elf_flag(machine, flag)
+            .unwrap_or_else(|| panic!("unknown ELF flag `{flag}` for machine {machine}"));
+        if flags & mask != value {
+            errors.push(format!("ELF flag `{flag}` is not set (flags are {flags:#x})"));
+        }
+    }
+
+    if props.no_relocations.is_empty() {
+        return Ok(());
+    }
+    let mut forbidden = props
+        .no_relocations
+        .iter()
+        .map(|name| match relocation_type(machine, name) {
+            Some(r_type) => (name, r_type, 0),
+            None => panic!("unknown relocation `{name}` for machine {machine}"),
+        })
+        .collect::<Vec<_>>();
+    let is_mips64el = header.is_mips64el(endian);
+    let mut found = |r_type: u32| {
+        for (_, forbidden, count) in &mut forbidden {
+            if r_type == *forbidden {
+                *count += 1;
+            }
+        }
+    };
+    for section in header.section_headers(endian, data)? {
+        if let Some((rels, _)) = section.rel(endian, data)? {
+            rels.iter().for_each(|rel| found(rel.r_type(endian)));
+        }
+        if let Some((relas, _)) = section.rela(endian, data)? {
+            relas.iter().for_each(|rela| found(rela.r_type(endian, is_mips64el)));
+        }
+    }
+    for (name, _, count) in forbidden {
+        if count > 0 {
+            errors.push(format!("found {count} forbidden `{name}` relocations"));
+        }
+    }
+    Ok(())
+}
+
+/// ELF header flags known by name, with the mask and the value of the flag.
+const ELF_FLAGS: &[(u16, &str, u32, u32)] = &[
+    (elf::EM_ARM, "EF_ARM_ABI_FLOAT_SOFT", elf::EF_ARM_ABI_FLOAT_SOFT, elf::EF_ARM_ABI_FLOAT_SOFT),
+    (elf::EM_ARM, "EF_ARM_ABI_FLOAT_HARD", elf::EF_ARM_ABI_FLOAT_HARD, elf::EF_ARM_ABI_FLOAT_HARD),
+    (elf::EM_ARM, "EF_ARM_EABI_VER5", elf::EF_ARM_EABIMASK, elf::EF_ARM_EABI_VER5),
+    (elf::EM_RISCV, "EF_RISCV_RVC", elf::EF_RISCV_RVC, elf::EF_RISCV_RVC),
+    (elf::EM_RISCV, "EF_RISCV_RVE", elf::EF_RISCV_RVE, elf::EF_RISCV_RVE),
+    (
+        elf::EM_RISCV,
+        "EF_RISCV_FLOAT_ABI_SOFT",
+        elf::EF_RISCV_FLOAT_ABI,
+        elf::EF_RISCV_FLOAT_ABI_SOFT,
+    ),
+    (
+        elf::EM_RISCV,
+        "EF_RISCV_FLOAT_ABI_SINGLE",
+        elf::EF_RISCV_FLOAT_ABI,
+        elf::EF_RISCV_FLOAT_ABI_SINGLE,
+    ),
+    (
+        elf::EM_RISCV,
+        "EF_RISCV_FLOAT_ABI_DOUBLE",
+        elf::EF_RISCV_FLOAT_ABI,
+        elf::EF_RISCV_FLOAT_ABI_DOUBLE,
+    ),
+];
+
+/// ELF relocation types known by name.
+const RELOCATIONS: &[(u16, &str, u32)] = &[
+    (elf::EM_X86_64, "R_X86_64_64", elf::R_X86_64_64),
+    (elf::EM_X86_64, "R_X86_64_PC32", elf::R_X86_64_PC32),
+    (elf::EM_X86_64, "R_X86_64_GOTPCREL", elf::R_X86_64_GOTPCREL),
+    (elf::EM_X86_64, "R_X86_64_PLT32", elf::R_X86_64_PLT32),
+    (elf::EM_X86_64, "R_X86_64_COPY", elf::R_X86_64_COPY),
+    (elf::EM_X86_64, "R_X86_64_GLOB_DAT", elf::R_X86_64_GLOB_DAT),
+    (elf::EM_X86_64, "R_X86_64_JUMP_SLOT", elf::R_X86_64_JUMP_SLOT),
+    (elf::EM_X86_64, "R_X86_64_RELATIVE", elf::R_X86_64_RELATIVE),
+    (elf::EM_X86_64, "R_X86_64_TPOFF64", elf::R_X86_64_TPOFF64),
+    (elf::EM_X86_64, "R_X86_64_IRELATIVE", elf::R_X86_64_IRELATIVE),
+    (elf::EM_AARCH64, "R_AARCH64_ABS64", elf::R_AARCH64_ABS64),
+    (elf::EM_AARCH64, "R_AARCH64_COPY", elf::R_AARCH64_COPY),
+    (elf::EM_AARCH64, "R_AARCH64_GLOB_DAT", elf::R_AARCH64_GLOB_DAT),
+    (elf::EM_AARCH64, "R_AARCH64_JUMP_SLOT", elf::R_AARCH64_JUMP_SLOT),
+    (elf::EM_AARCH64, "R_AARCH64_RELATIVE", elf::R_AARCH64_RELATIVE),
+    (elf::EM_AARCH64, "R_AARCH64_TLS_TPREL", elf::R_AARCH64_TLS_TPREL),
+    (elf::EM_AARCH64, "R_AARCH64_IRELATIVE", elf::R_AARCH64_IRELATIVE),
+    (elf::EM_ARM, "R_ARM_ABS32", elf::R_ARM_ABS32),
+    (elf::EM_ARM, "R_ARM_REL32", elf::R_ARM_REL32),
+    (elf::EM_ARM, "R_ARM_COPY", elf::R_ARM_COPY),
+    (elf::EM_ARM, "R_ARM_GLOB_DAT", elf::R_ARM_GLOB_DAT),
+    (elf::EM_ARM, "R_ARM_JUMP_SLOT", elf::R_ARM_JUMP_SLOT),
+    (elf::EM_ARM, "R_ARM_RELATIVE", elf::R_ARM_RELATIVE),
+    (elf::EM_ARM, "R_ARM_TLS_TPOFF32", elf::R_ARM_TLS_TPOFF32),
+    (elf::EM_ARM, "R_ARM_IRELATIVE", elf::R_ARM_IRELATIVE),
+    (elf::EM_RISCV, "R_RISCV_32", elf::R_RISCV_32),
+    (elf::EM_RISCV, "R_RISCV_64", elf::R_RISCV_64),
+    (elf::EM_RISCV, "R_RISCV_RELATIVE", elf::R_RISCV_RELATIVE),
+    (elf::EM_RISCV, "R_RISCV_COPY", elf::R_RISCV_COPY),
+    (elf::EM_RISCV, "R_RISCV_JUMP_SLOT", elf::R_RISCV_JUMP_SLOT),
+];
+
+/// Returns the mask and the expected value of an ELF header flag.
+pub(super) fn elf_flag(machine: u16, name: &str) -> Option<(u32, u32)> {
+    if let Some(value) = parse_number(name) {
+        return Some((value, value));
+    }
+    ELF_FLAGS
+        .iter()
+        .find(|(m, n, _, _)| *m == machine && *n == name)
+        .map(|(_, _, mask, value)| (*mask, *value))
+}
+
+pub(super) fn relocation_type(machine: u16, name: &str) -> Option<u32> {
+    if let Some(value) = parse_number(name) {
+        return Some(value);
+    }
+    RELOCATIONS.iter().find(|(m, n, _)| *m == machine && *n == name).map(|(_, _, r_type)| *r_type)
+}
+
+fn parse_number(value: &str) -> Option<u32> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
@@ -48,3 +48,184 @@ fn normalize_platform_differences() {
         r#"println!("test\ntest")"#,
     );
 }
+
+#[test]
+fn object_check_names() {
+    use object::elf;
+
+    assert_eq!(
+        object_check::elf_flag(elf::EM_ARM, "EF_ARM_EABI_VER5"),
+        Some((elf::EF_ARM_EABIMASK, elf::EF_ARM_EABI_VER5))
+    );
+    assert_eq!(object_check::elf_flag(elf::EM_RISCV, "0x4"), Some((4, 4)));
+    assert_eq!(object_check::elf_flag(elf::EM_X86_64, "EF_ARM_EABI_VER5"), None);
+
+    assert_eq!(
+        object_check::relocation_type(elf::EM_X86_64, "R_X86_64_COPY"),
+        Some(elf::R_X86_64_COPY)
+    );
+    assert_eq!(object_check::relocation_type(elf::EM_AARCH64, "1025"), Some(1025));
+    assert_eq!(object_check::relocation_type(elf::EM_AARCH64, "R_X86_64_COPY"), None);
+}
+
+/// Builds a relocatable ARM object with 16 bytes of `.text`, referencing an undefined
+/// `core::panicking::panic` through a `R_ARM_ABS32` relocation.
+fn arm_object(flags: u32) -> Vec<u8> {
+    use object::elf::*;
+    use object::endian::{Endianness, U16, U32};
+    use object::pod::bytes_of;
+
+    let e = Endianness::Little;
+    let u16 = |value| U16::new(e, value);
+    let u32 = |value| U32::new(e, value);
+
+    let strtab = b"\0_ZN4core9panicking5panic17h0123456789abcdefE\0".to_vec();
+    let symbol = |st_name, st_info| Sym32 {
+        st_name: u32(st_name),
+        st_value: u32(0),
+        st_size: u32(0),
+        st_info,
+        st_other: 0,
+        st_shndx: u16(SHN_UNDEF),
+    };
+    let symtab = [symbol(0, 0), symbol(1, (STB_GLOBAL << 4) | STT_NOTYPE)]
+        .iter()
+        .flat_map(|sym| bytes_of(sym).to_vec())
+        .collect();
+    let rel = Rel32 { r_offset: u32(8), r_info: Rel32::r_info(e, 1, R_ARM_ABS32 as u8) };
+
+    // Name, type, link, info, entry size and contents of each section after the null one.
+    let sections = [
+        (".text", SHT_PROGBITS, 0, 0, 0, vec![0; 16]),
+        (".symtab", SHT_SYMTAB, 3, 1, 16, symtab),
+        (".strtab", SHT_STRTAB, 0, 0, 0, strtab),
+        (".rel.text", SHT_REL, 2, 1, 8, bytes_of(&rel).to_vec()),
+        (".shstrtab", SHT_STRTAB, 0, 0, 0, Vec::new()),
+    ];
+    let mut shstrtab = vec![0];
+    let mut names = Vec::new();
+    for (name, ..) in &sections {
+        names.push(shstrtab.len() as u32);
+        shstrtab.extend_from_slice(name.as_bytes());
+        shstrtab.push(0);
+    }
+
+    let header_size = std::mem::size_of::<FileHeader32<Endianness>>();
+    let mut data = vec![0; header_size];
+    let mut headers = vec![0; std::mem::size_of::<SectionHeader32<Endianness>>()];
+    for (index, (_, sh_type, link, info, entsize, contents)) in sections.iter().enumerate() {
+        let contents = if index == sections.len() - 1 { &shstrtab } else { contents };
+        let header = SectionHeader32 {
+            sh_name: u32(names[index]),
+            sh_type: u32(*sh_type),
+            sh_flags: u32(0),
+            sh_addr: u32(0),
+            sh_offset: u32(data.len() as u32),
+            sh_size: u32(contents.len() as u32),
+            sh_link: u32(*link),
+            sh_info: u32(*info),
+            sh_addralign: u32(1),
+            sh_entsize: u32(*entsize),
+        };
+        headers.extend_from_slice(bytes_of(&header));
+        data.extend_from_slice(contents);
+    }
+    data.resize(data.len().next_multiple_of(4), 0);
+
+    let header = FileHeader32 {
+        e_ident: Ident {
+            magic: ELFMAG,
+            class: ELFCLASS32,
+            data: ELFDATA2LSB,
+            version: EV_CURRENT,
+            os_abi: ELFOSABI_NONE,
+            abi_version: 0,
+            padding: [0; 7],
+        },
+        e_type: u16(ET_REL),
+        e_machine: u16(EM_ARM),
+        e_version: u32(EV_CURRENT.into()),
+        e_entry: u32(0),
+        e_phoff: u32(0),
+        e_shoff: u32(data.len() as u32),
+        e_flags: u32(flags),
+        e_ehsize: u16(header_size as u16),
+        e_phentsize: u16(0),
+        e_phnum: u16(0),
+        e_shentsize: u16(std::mem::size_of::<SectionHeader32<Endianness>>() as u16),
+        e_shnum: u16(sections.len() as u16 + 1),
+        e_shstrndx: u16(sections.len() as u16),
+    };
+    data[..header_size].copy_from_slice(bytes_of(&header));
+    data.extend_from_slice(&headers);
+    data
+}
+
+#[test]
+fn object_check_passing() {
+    let props = TestProps {
+        no_symbols: vec!["std::*".to_string()],
+        section_max_sizes: vec![(".text".to_string(), 16)],
+        elf_flags: vec!["EF_ARM_EABI_VER5".to_string(), "EF_ARM_ABI_FLOAT_HARD".to_string()],
+        no_relocations: vec!["R_ARM_REL32".to_string()],
+        ..TestProps::new()
+    };
+    let object = arm_object(object::elf::EF_ARM_EABI_VER5 | object::elf::EF_ARM_ABI_FLOAT_HARD);
+    assert_eq!(object_check::check(&props, &object).unwrap(), Vec::<String>::new());
+}
+
+#[test]
+fn object_check_violations() {
+    let object = arm_object(object::elf::EF_ARM_EABI_VER5 | object::elf::EF_ARM_ABI_FLOAT_SOFT);
+    let check = |props: TestProps| object_check::check(&props, &object).unwrap();
+
+    assert_eq!(
+        check(TestProps { no_symbols: vec!["core::panicking::*".to_string()], ..TestProps::new() }),
+        ["symbol `core::panicking::panic` matches forbidden pattern `core::panicking::*`"]
+    );
+    assert_eq!(
+        check(TestProps { section_max_sizes: vec![(".text".to_string(), 8)], ..TestProps::new() }),
+        ["section `.text` is 16 bytes, more than 8"]
+    );
+    assert_eq!(
+        check(TestProps {
+            elf_flags: vec!["EF_ARM_ABI_FLOAT_HARD".to_string()],
+            ..TestProps::new()
+        }),
+        ["ELF flag `EF_ARM_ABI_FLOAT_HARD` is not set (flags are 0x5000200)"]
+    );
+    assert_eq!(
+        check(TestProps { no_relocations: vec!["R_ARM_ABS32".to_string()], ..TestProps::new() }),
+        ["found 1 forbidden `R_ARM_ABS32` relocations"]
+    );
+}
+
+#[test]
+fn differential_minimize() {
+    use std::time::Duration;
+
+    let lines = ["fn main() {", "    let a = 1;", "    let b = 2;", "    print(a);", "}"];
+    let time = Duration::from_secs(3600);
+    // The mismatch reproduces as long as the program prints `a`.
+    let reproduces =
+        |lines: &[&str]| lines.contains(&"    let a = 1;") && lines.contains(&"    print(a);");
+    assert_eq!(
+        differential::minimize(&lines, 100, time, reproduces),
+        ["    let a = 1;", "    print(a);"]
+    );
+
+    // Nothing is removed when every line is needed.
+    assert_eq!(differential::minimize(&lines, 100, time, |lines| lines.len() == 5), lines);
+
+    // The number of attempts is bounded.
+    let mut attempts = 0;
+    differential::minimize(&lines, 3, time, |_| {
+        attempts += 1;
+        false
+    });
+    assert_eq!(attempts, 3);
+
+    // So is the time spent minimizing.
+    let minimized = differential::minimize(&lines, 100, Duration::ZERO, |_| unreachable!());
+    assert_eq!(minimized, lines);
+}
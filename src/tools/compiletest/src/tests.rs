@@ -76,3 +76,19 @@ fn test_extract_llvm_version() {
     assert_eq!(extract_llvm_version("12.0.0-rc3"), Some(120000));
     assert_eq!(extract_llvm_version("13.0.0git"), Some(130000));
 }
+
+#[test]
+fn test_shard() {
+    use build_helper::shard::Shard;
+
+    assert_eq!("2/4".parse(), Ok(Shard { index: 2, count: 4 }));
+    assert!("0/4".parse::<Shard>().is_err());
+    assert!("5/4".parse::<Shard>().is_err());
+    assert!("4".parse::<Shard>().is_err());
+
+    // Every test belongs to exactly one shard.
+    let shards = (1..=3).map(|index| Shard { index, count: 3 }).collect::<Vec<_>>();
+    for test in ["[ui] tests/ui/foo.rs", "[ui] tests/ui/foo.rs#bar", "[ui] tests/ui/baz.rs"] {
+        assert_eq!(1, shards.iter().filter(|shard| shard.contains(test)).count());
+    }
+}
@@ -69,6 +69,11 @@ pub fn check_crate(&mut self) {
         for (id, item_info) in &self.krate.paths {
             self.check_item_info(id, item_info);
         }
+        for (requirement, ids) in &self.krate.requirements {
+            for id in ids {
+                self.check_requirement(requirement, id);
+            }
+        }
     }
 
     fn check_items(&mut self, id: &Id, items: &[Id]) {
@@ -405,6 +410,18 @@ fn check_item_info(&mut self, id: &Id, item_info: &ItemSummary) {
         }
     }
 
+    fn check_requirement(&mut self, requirement: &str, id: &Id) {
+        let implements = self.krate.index.get(id).map(|item| item.requirements.as_slice());
+        if !implements.unwrap_or_default().iter().any(|r| r == requirement) {
+            self.fail(
+                id,
+                ErrorKind::Custom(format!(
+                    "Id in `requirements` for `{requirement}` doesn't implement it in `index`"
+                )),
+            );
+        }
+    }
+
     fn add_id_checked(&mut self, id: &'a Id, valid: fn(Kind) -> bool, expected: &str) {
         if let Some(kind) = self.kind_of(id) {
             if valid(kind) {
@@ -38,6 +38,7 @@ fn errors_on_missing_links() {
                 links: FxHashMap::from_iter([("Not Found".to_owned(), id("1"))]),
                 attrs: vec![],
                 deprecation: None,
+                requirements: Vec::new(),
                 inner: ItemEnum::Module(Module {
                     is_crate: true,
                     items: vec![],
@@ -47,6 +48,7 @@ fn errors_on_missing_links() {
         )]),
         paths: FxHashMap::default(),
         external_crates: FxHashMap::default(),
+        requirements: FxHashMap::default(),
         format_version: rustdoc_json_types::FORMAT_VERSION,
     };
 
@@ -85,6 +87,7 @@ fn errors_on_local_in_paths_and_not_index() {
                     links: FxHashMap::from_iter([(("prim@i32".to_owned(), id("0:1:1571")))]),
                     attrs: Vec::new(),
                     deprecation: None,
+                    requirements: Vec::new(),
                     inner: ItemEnum::Module(Module {
                         is_crate: true,
                         items: vec![id("0:1:717")],
@@ -104,6 +107,7 @@ fn errors_on_local_in_paths_and_not_index() {
                     links: FxHashMap::default(),
                     attrs: Vec::new(),
                     deprecation: None,
+                    requirements: Vec::new(),
                     inner: ItemEnum::Primitive(Primitive { name: "i32".to_owned(), impls: vec![] }),
                 },
             ),
@@ -117,6 +121,7 @@ fn errors_on_local_in_paths_and_not_index() {
             },
         )]),
         external_crates: FxHashMap::default(),
+        requirements: FxHashMap::default(),
         format_version: rustdoc_json_types::FORMAT_VERSION,
     };
 
@@ -148,6 +153,7 @@ fn checks_local_crate_id_is_correct() {
                 links: FxHashMap::default(),
                 attrs: Vec::new(),
                 deprecation: None,
+                requirements: Vec::new(),
                 inner: ItemEnum::Module(Module {
                     is_crate: true,
                     items: vec![],
@@ -157,7 +163,54 @@ fn checks_local_crate_id_is_correct() {
         )]),
         paths: FxHashMap::default(),
         external_crates: FxHashMap::default(),
+        requirements: FxHashMap::default(),
         format_version: FORMAT_VERSION,
     };
     check(&krate, &[]);
 }
+
+#[test]
+fn errors_on_requirements_not_in_items() {
+    let krate = Crate {
+        root: id("0"),
+        crate_version: None,
+        includes_private: false,
+        index: FxHashMap::from_iter([(
+            id("0"),
+            Item {
+                id: id("0"),
+                crate_id: 0,
+                name: Some("root".to_owned()),
+                span: None,
+                visibility: Visibility::Public,
+                docs: None,
+                links: FxHashMap::default(),
+                attrs: Vec::new(),
+                deprecation: None,
+                requirements: vec!["SWR-1".to_owned()],
+                inner: ItemEnum::Module(Module {
+                    is_crate: true,
+                    items: vec![],
+                    is_stripped: false,
+                }),
+            },
+        )]),
+        paths: FxHashMap::default(),
+        external_crates: FxHashMap::default(),
+        requirements: FxHashMap::from_iter([
+            ("SWR-1".to_owned(), vec![id("0")]),
+            ("SWR-2".to_owned(), vec![id("0")]),
+        ]),
+        format_version: FORMAT_VERSION,
+    };
+
+    check(
+        &krate,
+        &[Error {
+            id: id("0"),
+            kind: ErrorKind::Custom(
+                "Id in `requirements` for `SWR-2` doesn't implement it in `index`".to_owned(),
+            ),
+        }],
+    );
+}
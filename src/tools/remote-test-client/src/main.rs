@@ -3,7 +3,8 @@
 //! push artifacts and run tests on the server instead of locally.
 //!
 //! Here is also where we bake in the support to spawn the QEMU emulator as
-//! well.
+//! well, and to run bare-metal test binaries directly under QEMU system
+//! emulation, with the test output reported over Arm semihosting.
 
 use std::env;
 use std::fs::{self, File};
@@ -13,11 +14,14 @@
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const REMOTE_ADDR_ENV: &str = "TEST_DEVICE_ADDR";
 const DEFAULT_ADDR: &str = "127.0.0.1:12345";
 
+const SEMIHOSTING_TIMEOUT_ENV: &str = "TEST_SEMIHOSTING_TIMEOUT";
+const DEFAULT_SEMIHOSTING_TIMEOUT: u64 = 300;
+
 macro_rules! t {
     ($e:expr) => {
         match $e {
@@ -49,6 +53,13 @@ fn main() {
             args.next().unwrap(),
             args.collect(),
         ),
+        "run-semihosting" => run_semihosting(
+            &args.next().unwrap(),
+            // the last required parameter must remain the executable
+            // path so that the client works as a cargo runner
+            args.next().unwrap(),
+            args.collect(),
+        ),
         "help" | "-h" | "--help" => help(),
         cmd => {
             println!("unknown command: {}", cmd);
@@ -344,6 +355,93 @@ fn run(support_lib_count: usize, exe: String, all_args: Vec<String>) {
     }
 }
 
+fn run_semihosting(target: &str, exe: String, args: Vec<String>) {
+    // The guest program receives its command line through semihosting's
+    // SYS_GET_CMDLINE, which QEMU builds out of the `arg=` options. Commas
+    // are the option separator, and have to be doubled to be escaped.
+    let mut semihosting_config = String::from("enable=on,target=native");
+    let program_name = Path::new(&exe).file_name().unwrap().to_str().unwrap();
+    for arg in std::iter::once(program_name).chain(args.iter().map(|s| s.as_str())) {
+        semihosting_config.push_str(",arg=");
+        semihosting_config.push_str(&arg.replace(',', ",,"));
+    }
+
+    let mut cmd = match target {
+        "aarch64-unknown-none" | "aarch64-unknown-none-softfloat" => {
+            let mut cmd = Command::new("qemu-system-aarch64");
+            cmd.arg("-machine").arg("virt").arg("-cpu").arg("cortex-a57").arg("-m").arg("1024");
+            cmd
+        }
+        "thumbv7em-none-eabi" | "thumbv7em-none-eabihf" => {
+            let mut cmd = Command::new("qemu-system-arm");
+            cmd.arg("-machine").arg("mps2-an386");
+            cmd
+        }
+        _ => panic!("cannot run semihosting tests for: {}", target),
+    };
+    cmd.arg("-nographic")
+        // Turn resets into shutdowns, and pause the emulator on shutdowns,
+        // so that the only way for the guest to make QEMU exit is reporting
+        // its exit status through semihosting.
+        .arg("-no-reboot")
+        .arg("-no-shutdown")
+        .arg("-monitor")
+        .arg("none")
+        .arg("-serial")
+        .arg("none")
+        .arg("-semihosting-config")
+        .arg(&semihosting_config)
+        .arg("-kernel")
+        .arg(&exe)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped());
+
+    let timeout = env::var(SEMIHOSTING_TIMEOUT_ENV)
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .unwrap_or(DEFAULT_SEMIHOSTING_TIMEOUT);
+
+    let mut child = t!(cmd.spawn());
+
+    // The semihosted console is forwarded to QEMU's stdout: relay it to our
+    // own stdout as it arrives.
+    let mut guest_stdout = child.stdout.take().unwrap();
+    let relay = thread::spawn(move || {
+        let mut stdout = io::stdout();
+        let mut buf = [0; 4096];
+        loop {
+            let amt = t!(guest_stdout.read(&mut buf));
+            if amt == 0 {
+                break;
+            }
+            t!(stdout.write_all(&buf[..amt]));
+            t!(stdout.flush());
+        }
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = t!(child.try_wait()) {
+            break status;
+        }
+        if start.elapsed() > Duration::from_secs(timeout) {
+            let _ = child.kill();
+            let _ = child.wait();
+            println!("semihosted test timed out after {} seconds", timeout);
+            std::process::exit(3);
+        }
+        thread::sleep(Duration::from_millis(100));
+    };
+    relay.join().unwrap();
+
+    // QEMU propagates the status passed to SYS_EXIT(_EXTENDED) with the
+    // ADP_Stopped_ApplicationExit reason as its own exit status, and exits
+    // with 1 for any other reason. As QEMU doesn't exit when the guest shuts
+    // down or resets, a guest that faults or stops without reporting its
+    // status times out instead of passing.
+    std::process::exit(status.code().unwrap_or(3));
+}
+
 fn send(path: &Path, dst: &mut dyn Write) {
     t!(dst.write_all(path.file_name().unwrap().to_str().unwrap().as_bytes()));
     t!(dst.write_all(&[0]));
@@ -363,6 +461,8 @@ fn help() {
     push <path>                                          Copy <path> to emulator
     run <support_lib_count> <file> [support_libs...] [args...]
                                                          Run program on emulator
+    run-semihosting <target> <file> [args...]
+                                                         Run bare-metal program under QEMU
     help                                                 Display help message
 
 Spawning an emulator:
@@ -384,9 +484,19 @@ fn help() {
 the <file> and any specified support libs are pushed to the target. Finally, the
 <file> is executed in the emulator, preserving the current environment.
 That command's status code is returned.
+
+Executing bare-metal programs with semihosting:
+
+The <file> is booted as the kernel of a QEMU system emulator for <target>, with
+Arm semihosting enabled and [args...] provided as the semihosted command line.
+The semihosted output is forwarded to stdout, and the exit code reported through
+semihosting is returned. The emulator is killed after the number of seconds in
+the {3} environment variable, or {4} seconds if this isn't specified.
 ",
         env::args().next().unwrap(),
         REMOTE_ADDR_ENV,
-        DEFAULT_ADDR
+        DEFAULT_ADDR,
+        SEMIHOSTING_TIMEOUT_ENV,
+        DEFAULT_SEMIHOSTING_TIMEOUT,
     );
 }
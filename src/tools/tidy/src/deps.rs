@@ -129,7 +129,8 @@
 
 /// These are the root crates that are part of the runtime. The licenses for
 /// these and all their dependencies *must not* be in the exception list.
-const RUNTIME_CRATES: &[&str] = &["std", "core", "alloc", "test", "panic_abort", "panic_unwind"];
+const RUNTIME_CRATES: &[&str] =
+    &["std", "core", "alloc", "test", "test_semihosting", "panic_abort", "panic_unwind"];
 
 const PERMITTED_DEPS_LOCATION: &str = concat!(file!(), ":", line!());
 
@@ -40,6 +40,7 @@
     "library/unwind",
     "library/rtstartup", // Not sure what to do about this. magic stuff for mingw
     "library/test",      // Probably should defer to unstable `std::sys` APIs.
+    "library/test_semihosting", // Semihosting calls are specific to the architecture.
     // The `VaList` implementation must have platform specific code.
     // The Windows implementation of a `va_list` is always a character
     // pointer regardless of the target architecture. As a result,
@@ -0,0 +1,36 @@
+// Functions are only inlined into callers with the same overflow checks setting, as codegen keeps
+// or removes the overflow checks of the inlined body according to the setting of the caller.
+//
+// compile-flags: -O -C overflow-checks=on -Zmir-enable-passes=+Inline --crate-type lib
+
+#![feature(item_level_checks, rustc_attrs)]
+
+#[inline]
+fn checked_add(x: u8, y: u8) -> u8 {
+    x + y
+}
+
+#[inline]
+#[overflow_checks(off)]
+#[rustc_inherit_overflow_checks]
+fn wrapping_add(x: u8, y: u8) -> u8 {
+    x + y
+}
+
+// The checks of the callee must be kept, either by calling it or by inlining it in LLVM.
+// CHECK-LABEL: define{{.*}} @unchecked_caller
+// CHECK: {{(call|invoke).*(checked_add|panicking)}}
+#[no_mangle]
+#[overflow_checks(off)]
+pub fn unchecked_caller(x: u8, y: u8) -> u8 {
+    checked_add(x, y)
+}
+
+// The checks the callee inherits from the caller must be removed.
+// CHECK-LABEL: define{{.*}} @checked_caller
+// CHECK-NOT: panicking
+// CHECK: ret i8
+#[no_mangle]
+pub fn checked_caller(x: u8, y: u8) -> u8 {
+    wrapping_add(x, y)
+}
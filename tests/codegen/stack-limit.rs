@@ -0,0 +1,43 @@
+// Check the checks inserted by `-Z stack-limit` at the start of functions.
+
+// revisions: symbol register
+// compile-flags: -C no-prepopulate-passes -C opt-level=0
+//[symbol] compile-flags: -Z stack-limit=symbol:__stack_limit
+//[register] compile-flags: -Z stack-limit=register:x18
+
+#![crate_type = "lib"]
+#![feature(stack_overflow_handler)]
+
+// symbol: @__stack_limit = external global i8
+
+// CHECK-LABEL: @checked
+#[no_mangle]
+pub fn checked(x: u32) -> u32 {
+    // CHECK: start:
+    // CHECK: [[SP:%.*]] = call ptr @llvm.stacksave()
+    // CHECK: [[SP_INT:%.*]] = ptrtoint ptr [[SP]] to i{{32|64}}
+    // symbol: [[LIMIT:%.*]] = ptrtoint ptr @__stack_limit to i{{32|64}}
+    // register: [[LIMIT:%.*]] = call {{.*}} @llvm.read_register.{{.*}}(metadata ![[REG:[0-9]+]])
+    // CHECK: [[RESERVED:%.*]] = add i{{32|64}} [[LIMIT]], 512
+    // CHECK: [[EXCEEDED:%.*]] = icmp ult i{{32|64}} [[SP_INT]], [[RESERVED]]
+    // CHECK: [[EXPECT:%.*]] = call i1 @llvm.expect.i1(i1 [[EXCEEDED]], i1 false)
+    // CHECK: br i1 [[EXPECT]], label %stack_overflow, label %stack_ok
+    // CHECK: stack_overflow:
+    // CHECK-NEXT: call void @__rust_stack_overflow() #[[COLD:[0-9]+]]
+    // CHECK-NEXT: unreachable
+    // CHECK: stack_ok:
+    // CHECK: ret i32
+    x
+}
+
+// The handler itself is not checked.
+// CHECK-LABEL: @__rust_stack_overflow()
+// CHECK-NOT: @llvm.stacksave
+// CHECK: }
+#[stack_overflow_handler]
+pub extern "C" fn handler() -> ! {
+    loop {}
+}
+
+// CHECK: attributes #[[COLD]] = { cold }
+// register: ![[REG]] = !{!"x18"}
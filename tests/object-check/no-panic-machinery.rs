@@ -0,0 +1,23 @@
+// Checks that a `no_std` library using only infallible arithmetic pulls in neither the panic
+// machinery nor the allocator.
+// compile-flags: -O -C panic=abort
+// only-x86_64
+// only-linux
+// no-symbol: core::panicking::*
+// no-symbol: __rust_alloc*
+// section-max-size: .text 4096
+// no-relocation: R_X86_64_COPY
+
+#![crate_type = "cdylib"]
+#![no_std]
+
+#[no_mangle]
+pub extern "C" fn wrapping_sum(values: *const u32, len: usize) -> u32 {
+    let values = unsafe { core::slice::from_raw_parts(values, len) };
+    values.iter().fold(0, |sum, value| sum.wrapping_add(*value))
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo<'_>) -> ! {
+    loop {}
+}
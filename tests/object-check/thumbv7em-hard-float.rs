@@ -0,0 +1,24 @@
+// Checks that a bare-metal object for a hard-float target uses the EABI version 5 and the floating
+// point unit, without calls to the soft-float helpers. The object is not linked, so the test can
+// run without a linker for the target.
+// compile-flags: --target thumbv7em-none-eabihf --emit=obj
+// compile-flags: -O -C panic=abort -C codegen-units=1
+// needs-llvm-components: arm
+// elf-flag: EF_ARM_EABI_VER5
+// no-symbol: __aeabi_*
+// section-max-size: .text.select 64
+// no-relocation: R_ARM_ABS32
+
+#![feature(no_core, lang_items)]
+#![crate_type = "lib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+#[no_mangle]
+pub extern "C" fn select(first: bool, a: f32, b: f32) -> f32 {
+    if first { a } else { b }
+}
@@ -0,0 +1,46 @@
+#![no_std]
+#![feature(allocator_api)]
+#![feature(fallible_collection_ops)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, TryReserveError};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub fn build_vec(data: &[u32]) -> Result<Vec<u32>, TryReserveError> {
+    let mut vec = Vec::try_from_iter(data.iter().map(|x| x * 2))?;
+    vec.try_push(1)?;
+    vec.try_insert(0, 2)?;
+    vec.try_extend_from_slice(data)?;
+    Ok(vec)
+}
+
+pub fn build_string(name: &str) -> Result<String, TryReserveError> {
+    let mut string = String::new();
+    string.try_push_str("hello, ")?;
+    string.try_push_str(name)?;
+    string.try_push('!')?;
+    Ok(string)
+}
+
+pub fn build_box(value: u64) -> Option<Box<u64>> {
+    Box::try_new(value).ok()
+}
+
+pub fn build_map(data: &[u32]) -> Result<BTreeMap<u32, usize>, TryReserveError> {
+    let mut map = BTreeMap::new();
+    for (i, &key) in data.iter().enumerate() {
+        map.fallible_insert(key, i)?;
+    }
+    Ok(map)
+}
+
+pub fn build_set(data: &[u32]) -> Result<BTreeSet<u32>, TryReserveError> {
+    let mut set = BTreeSet::new();
+    for &value in data {
+        set.fallible_insert(value)?;
+    }
+    Ok(set)
+}
@@ -0,0 +1,37 @@
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    old = json.load(f)
+with open(sys.argv[2]) as f:
+    new = json.load(f)
+with open(sys.argv[3]) as f:
+    lto = json.load(f)
+
+sections = {section["name"]: section["size"] for section in old["sections"]}
+assert sections[".text"] > 0, sections
+assert old["size"] == sum(sections.values())
+
+crates = {crate["name"]: crate for crate in old["crates"]}
+assert "std" in crates, crates.keys()
+assert crates["main"]["sections"][".text"] > 0, crates["main"]
+
+items = {item["name"]: item for item in old["items"]}
+generic = items["main::generic"]
+assert generic["monomorphizations"] == 2, generic
+assert all(instance["crate"] == "main" for instance in generic["instances"]), generic
+assert generic["size"] == sum(instance["size"] for instance in generic["instances"])
+assert "diff" not in old
+
+diff = new["diff"]
+assert diff["old_size"] == old["size"] and diff["new_size"] == new["size"]
+changes = {change["name"]: change for change in diff["items"]}
+generic = changes["main::generic"]
+assert (generic["old_monomorphizations"], generic["new_monomorphizations"]) == (2, 3), generic
+assert generic["delta"] == generic["new_size"] - generic["old_size"] > 0, generic
+
+# With LTO, all the code is in the object file of the final crate, but is still attributed to the
+# crate its symbol belongs to.
+crates = {crate["name"]: crate for crate in lto["crates"]}
+assert crates["std"]["sections"][".text"] > 0, crates.keys()
+assert crates["core"]["sections"][".text"] > 0, crates.keys()
@@ -0,0 +1,11 @@
+#[inline(never)]
+fn generic<T: std::fmt::Debug>(value: T) {
+    println!("{value:?}");
+}
+
+fn main() {
+    generic(1u8);
+    generic("a");
+    #[cfg(more)]
+    generic(1u16);
+}
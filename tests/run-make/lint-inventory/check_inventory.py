@@ -0,0 +1,50 @@
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    inventory = json.load(f)
+
+assert inventory["crate_name"] == "lib"
+assert inventory["cap_lints"] is None
+NONSTANDARD_STYLE = ["non_camel_case_types", "non_snake_case", "non_upper_case_globals"]
+
+assert inventory["command_line"] == [
+    {"level": "allow", "name": "unused_imports", "lints": ["unused_imports"]},
+    {"level": "warn", "name": "nonstandard_style", "lints": NONSTANDARD_STYLE},
+], inventory["command_line"]
+
+attributes = {attr["location"]["line"]: attr for attr in inventory["attributes"]}
+assert sorted(attributes) == [2, 4, 9, 12, 15, 20, 29], attributes
+
+assert attributes[2]["level"] == "allow"
+assert attributes[2]["lints"] == ["dead_code"]
+assert attributes[2]["reason"] == "kept for the tests"
+assert attributes[2]["suppression"]
+assert attributes[2]["fulfilled"] is None
+
+assert attributes[4]["level"] == "expect"
+assert attributes[4]["reason"] == "documents the value"
+assert attributes[4]["fulfilled"] is True
+
+assert attributes[9]["level"] == "expect"
+assert attributes[9]["reason"] is None
+assert attributes[9]["fulfilled"] is False
+
+# Downgrading a lint denied by default is a suppression, denying lints is not.
+assert attributes[12]["level"] == "warn"
+assert attributes[12]["suppression"]
+assert attributes[15]["level"] == "deny"
+assert attributes[15]["lints"] == NONSTANDARD_STYLE
+assert not attributes[15]["suppression"]
+
+assert attributes[20]["name"] == "unused_mut"
+assert attributes[20]["location"]["file"] == "lib.rs"
+assert attributes[20]["location"]["column"] == 17
+assert attributes[20]["macro_call"]["line"] == 25
+assert not attributes[20]["external_macro"]
+
+# Lints of tools not running in this compilation are recorded as written.
+assert attributes[29]["name"] == "clippy::needless_return"
+assert attributes[29]["lints"] == ["clippy::needless_return"]
+assert attributes[29]["suppression"]
+assert attributes[29]["fulfilled"] is None
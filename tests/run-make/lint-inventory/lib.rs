@@ -0,0 +1,30 @@
+#![feature(lint_reasons)]
+#![allow(dead_code, reason = "kept for the tests")]
+
+#[expect(unused_variables, reason = "documents the value")]
+pub fn fulfilled() {
+    let x = 1;
+}
+
+#[expect(unused_mut)]
+pub fn unfulfilled() {}
+
+#[warn(arithmetic_overflow)]
+pub fn downgraded() {}
+
+#[deny(nonstandard_style)]
+pub mod strict {}
+
+macro_rules! allowing {
+    ($item:item) => {
+        #[allow(unused_mut)]
+        $item
+    };
+}
+
+allowing! {
+    pub fn expanded() {}
+}
+
+#[allow(clippy::needless_return)]
+pub fn tool() {}
@@ -0,0 +1,37 @@
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    inventory = json.load(f)
+
+assert inventory["crate_name"] == "lib"
+items = {item["location"]["line"]: item for item in inventory["items"]}
+assert sorted(items) == [2, 3, 16, 18, 31, 38], items
+
+assert items[2]["kind"] == "extern_fn"
+assert items[2]["item"] == "abs"
+assert items[3]["kind"] == "extern_static"
+
+block = items[16]
+assert block["kind"] == "unsafe_block"
+assert block["item"] == "read"
+assert block["location"]["column"] == 17
+assert block["safety_comment"] == (
+    "the caller passes a valid pointer,\nand the union is only used with plain integers."
+)
+operations = [op["description"] for op in block["operations"]]
+assert operations == ["dereference of raw pointer", "access to union field"], operations
+
+assert items[18]["safety_comment"] is None
+assert [op["description"] for op in items[18]["operations"]] == ["use of mutable static"]
+
+function = items[31]
+assert function["kind"] == "unsafe_fn"
+assert function["safety_comment"] == "calling `abs` has no preconditions."
+assert function["operations"][0]["description"] == "call to unsafe function"
+assert function["operations"][0]["callee"] == "abs"
+assert function["operations"][1]["description"] == "use of extern static"
+
+assert items[38]["kind"] == "unsafe_impl"
+assert items[38]["safety_comment"] == "`Token` has no data."
+assert items[38]["operations"] == []
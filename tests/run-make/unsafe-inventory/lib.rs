@@ -0,0 +1,38 @@
+extern "C" {
+    fn abs(x: i32) -> i32;
+    static errno: i32;
+}
+
+pub union Bits {
+    pub int: u32,
+    pub float: f32,
+}
+
+pub static mut COUNTER: u32 = 0;
+
+pub fn read(ptr: *const u32, bits: Bits) -> u32 {
+    // SAFETY: the caller passes a valid pointer,
+    // and the union is only used with plain integers.
+    let value = unsafe { *ptr + bits.int };
+
+    unsafe {
+        COUNTER += 1;
+    }
+    value
+}
+
+/// Returns the absolute value of `x`.
+///
+/// # Safety
+///
+/// Nothing, this is a test.
+// SAFETY: calling `abs` has no preconditions.
+#[inline]
+pub unsafe fn absolute(x: i32) -> i32 {
+    abs(x) + errno
+}
+
+pub struct Token;
+
+// SAFETY: `Token` has no data.
+unsafe impl Send for Token {}
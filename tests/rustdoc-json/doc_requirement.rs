@@ -0,0 +1,21 @@
+#![feature(doc_requirement)]
+#![feature(no_core)]
+#![no_core]
+
+// @is "$.index[*][?(@.name=='both')].requirements" '["SWR-1", "SWR-2"]'
+// @set both = "$.index[*][?(@.name=='both')].id"
+#[doc(requirement = "SWR-1")]
+#[doc(requirement = "SWR-2")]
+pub fn both() {}
+
+// @is "$.index[*][?(@.name=='Only')].requirements" '["SWR-2"]'
+// @set only = "$.index[*][?(@.name=='Only')].id"
+#[doc(requirement = "SWR-2")]
+pub struct Only;
+
+// @is "$.index[*][?(@.name=='none')].requirements" []
+pub fn none() {}
+
+// @ismany "$.requirements['SWR-1'][*]" $both
+// @ismany "$.requirements['SWR-2'][*]" $both $only
+// @!has "$.requirements['SWR-3']"
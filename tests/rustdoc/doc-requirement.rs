@@ -0,0 +1,24 @@
+#![feature(doc_requirement)]
+#![crate_name = "foo"]
+
+// @has foo/fn.implements.html
+// @has - '//*[@class="item-info"]/*[@class="stab requirements"]' 'Requirements: SWR-1, SWR-2'
+// @count - '//*[@class="stab requirements"]/code' 2
+#[doc(requirement = "SWR-1")]
+#[doc(requirement = "SWR-2")]
+pub fn implements() {}
+
+// @has foo/fn.nothing.html
+// @count - '//*[@class="stab requirements"]' 0
+pub fn nothing() {}
+
+pub struct Foo;
+
+impl Foo {
+    // @has foo/struct.Foo.html
+    // @count - '//*[@class="item-info"]/*[@class="stab requirements"]' 1
+    #[doc(requirement = "SWR-3")]
+    pub fn implements(&self) {}
+
+    pub fn nothing(&self) {}
+}
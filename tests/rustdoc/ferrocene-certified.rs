@@ -0,0 +1,23 @@
+#![crate_name = "foo"]
+#![feature(ferrocene_certified)]
+
+// @has foo/fn.certified.html
+// @has - '//*[@class="item-info"]/*[@class="stab certified"]' \
+//      'This item is part of the Ferrocene certified subset.'
+#[ferrocene::certified]
+pub fn certified() {}
+
+// @has foo/fn.uncertified.html
+// @count - '//*[@class="stab certified"]' 0
+pub fn uncertified() {}
+
+pub struct Foo;
+
+impl Foo {
+    // @has foo/struct.Foo.html
+    // @count - '//*[@class="item-info"]/*[@class="stab certified"]' 1
+    #[ferrocene::certified]
+    pub fn certified(&self) {}
+
+    pub fn uncertified(&self) {}
+}
@@ -0,0 +1,37 @@
+#![feature(item_level_checks)]
+#![overflow_checks(on)]
+#![crate_type = "lib"]
+
+// Test that `#[overflow_checks]` and `#[debug_assertions]` are set to `on` or `off`, and only
+// applied to items which can contain code.
+
+#[overflow_checks(maybe)]
+//~^ ERROR malformed `#[overflow_checks]` attribute
+pub fn a() {}
+
+#[debug_assertions(on, off)]
+//~^ ERROR malformed `#[debug_assertions]` attribute
+pub fn b() {}
+
+#[overflow_checks]
+//~^ ERROR malformed `overflow_checks` attribute input
+pub fn c() {}
+
+#[overflow_checks(off)]
+//~^ ERROR `#[overflow_checks]` should be applied to a function, closure, impl, trait or module
+pub struct S;
+
+#[debug_assertions(on)]
+//~^ ERROR `#[debug_assertions]` should be applied to a function, closure, impl, trait or module
+pub static X: u32 = 0;
+
+pub trait T {
+    #[overflow_checks(on)]
+    fn d(&self) {}
+}
+
+#[overflow_checks(off)]
+impl T for S {
+    #[debug_assertions(off)]
+    fn d(&self) {}
+}
@@ -0,0 +1,20 @@
+#![feature(stack_overflow_handler)]
+#![crate_type = "lib"]
+
+// Test that `#[stack_overflow_handler]` is only applied to functions with the signature
+// `extern "C" fn() -> !`.
+
+#[stack_overflow_handler] //~ ERROR attribute should be applied to a function definition
+pub static HANDLER: u8 = 0;
+
+#[stack_overflow_handler] //~ ERROR function must have the signature
+pub fn rust_abi() -> ! { loop {} }
+
+#[stack_overflow_handler] //~ ERROR function must have the signature
+pub extern "C" fn returns() {}
+
+#[stack_overflow_handler] //~ ERROR function must have the signature
+pub extern "C" fn arguments(_: u32) -> ! { loop {} }
+
+#[stack_overflow_handler] //~ ERROR function must have the signature
+pub extern "C" fn generic<T>() -> ! { loop {} }
@@ -0,0 +1,5 @@
+// A log without results is written when there are no diagnostics.
+// compile-flags: --error-format=sarif -Z unstable-options
+// check-pass
+
+fn main() {}
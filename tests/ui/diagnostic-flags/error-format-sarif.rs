@@ -0,0 +1,6 @@
+// compile-flags: --error-format=sarif -Z unstable-options
+// ignore-windows
+
+fn main() {
+    let _x: i32 = "a"; //~ ERROR mismatched types
+}
@@ -0,0 +1,4 @@
+#[doc(requirement = "SWR-1")] //~ ERROR: `#[doc(requirement)]` is experimental
+pub fn foo() {}
+
+fn main() {}
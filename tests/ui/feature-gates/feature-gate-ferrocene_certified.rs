@@ -0,0 +1,7 @@
+// The `ferrocene` tool is only known to crates enabling the feature.
+
+#![deny(ferrocene::uncertified)]
+//~^ ERROR unknown tool name `ferrocene` found in scoped lint: `ferrocene::uncertified`
+//~| ERROR unknown tool name `ferrocene` found in scoped lint: `ferrocene::uncertified`
+
+fn main() {}
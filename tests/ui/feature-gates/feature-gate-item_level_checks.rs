@@ -0,0 +1,7 @@
+#![crate_type = "lib"]
+
+#[overflow_checks(on)] //~ ERROR the `#[overflow_checks]` attribute is an experimental feature
+pub fn foo() {}
+
+#[debug_assertions(off)] //~ ERROR the `#[debug_assertions]` attribute is an experimental feature
+pub fn bar() {}
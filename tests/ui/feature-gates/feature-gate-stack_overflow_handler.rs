@@ -0,0 +1,6 @@
+#![crate_type = "lib"]
+
+#[stack_overflow_handler] //~ ERROR the `#[stack_overflow_handler]` attribute is an experimental
+pub extern "C" fn handler() -> ! {
+    loop {}
+}
@@ -0,0 +1,37 @@
+#![feature(ferrocene_certified)]
+#![deny(ferrocene::uncertified)]
+
+use std::num::Wrapping;
+
+fn main() {
+    let mut a = 1;
+    let mut b = 2;
+    std::mem::swap(&mut a, &mut b);
+    let _ = std::cmp::max(a, b);
+    let _ = Some(a).is_some();
+    let _ = std::mem::take(&mut a);
+    //~^ ERROR call to `std::mem::take`, which is not part of the certified subset
+    let _ = std::ptr::null::<u8>();
+
+    // References to functions are checked like calls.
+    let _: fn(&Option<i32>) -> bool = Option::is_some;
+    let _: fn(&mut i32) -> i32 = std::mem::take;
+    //~^ ERROR call to `std::mem::take`, which is not part of the certified subset
+
+    // Calls through traits are checked against the method they resolve to.
+    let _ = std::cmp::Ord::max(a, b);
+    //~^ ERROR call to `std::cmp::Ord::max`, which is not part of the certified subset
+}
+
+// Overloaded operators are checked against the trait method they call.
+#[allow(dead_code)]
+fn operators(a: Wrapping<i32>, s: &String) -> &str {
+    let mut b = a + a;
+    //~^ ERROR call to `<std::num::Wrapping<i32> as std::ops::Add>::add`, which is not part
+    b += a;
+    //~^ ERROR call to `<std::num::Wrapping<i32> as std::ops::AddAssign>::add_assign`, which is
+    let _ = -b;
+    //~^ ERROR call to `<std::num::Wrapping<i32> as std::ops::Neg>::neg`, which is not part
+    &s[1..]
+    //~^ ERROR `<std::string::String as std::ops::Index<std::ops::RangeFrom<usize>>>::index`
+}
@@ -0,0 +1,26 @@
+// compile-flags: -Z require-lint-reasons
+
+#![feature(lint_reasons)]
+#![allow(dead_code, reason = "the functions are never called")]
+
+#[allow(unused_variables)]
+//~^ ERROR `allow(unused_variables)` has no reason
+fn allowed() {
+    let x = 1;
+}
+
+#[expect(unused_variables)]
+//~^ ERROR `expect(unused_variables)` has no reason
+fn expected() {
+    let x = 1;
+}
+
+#[warn(arithmetic_overflow)]
+//~^ ERROR `warn(arithmetic_overflow)` has no reason
+fn downgraded() {}
+
+#[warn(unused_mut)]
+#[deny(unused_imports)]
+fn not_suppressed() {}
+
+fn main() {}
@@ -0,0 +1,40 @@
+// run-pass
+// needs-unwind
+// compile-flags: -C debug-assertions=off
+
+// `debug_assert!` honors `#[debug_assertions(on|off)]` on the enclosing items rather than only
+// the `-C debug-assertions` setting of the crate.
+
+#![feature(core_intrinsics, item_level_checks)]
+
+use std::hint::black_box;
+use std::intrinsics::debug_assertions;
+
+#[debug_assertions(on)]
+mod audited {
+    pub fn check(x: u32) {
+        debug_assert!(x < 10, "x is too large");
+    }
+
+    #[debug_assertions(off)]
+    pub fn check_off(x: u32) {
+        debug_assert_eq!(x, 0);
+    }
+
+    pub fn enabled() -> bool {
+        super::debug_assertions()
+    }
+}
+
+fn main() {
+    assert!(!debug_assertions());
+    assert!(audited::enabled());
+
+    debug_assert!(black_box(false));
+    audited::check(black_box(1));
+    audited::check_off(black_box(1));
+    assert!(std::panic::catch_unwind(|| audited::check(black_box(10))).is_err());
+
+    const ENABLED: bool = debug_assertions();
+    assert!(!ENABLED);
+}
@@ -0,0 +1,25 @@
+// run-pass
+// revisions: on off
+// [on] compile-flags: -C debug-assertions=on
+// [off] compile-flags: -C debug-assertions=off
+
+// The `debug_assertions` intrinsic follows the `-C debug-assertions` setting of the crate when it
+// is called through a binding rather than by its path, both at runtime and in constants.
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::debug_assertions;
+
+const ENABLED: bool = {
+    let f = debug_assertions;
+    f()
+};
+
+fn main() {
+    let expected = cfg!(debug_assertions);
+
+    let f = debug_assertions;
+    assert_eq!(f(), expected);
+    assert_eq!((debug_assertions)(), expected);
+    assert_eq!(ENABLED, expected);
+}
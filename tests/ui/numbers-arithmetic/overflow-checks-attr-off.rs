@@ -0,0 +1,32 @@
+// run-pass
+// needs-unwind
+// compile-flags: -C overflow-checks=on
+
+// `#[overflow_checks(off)]` removes the overflow checks of the item it is written on and of the
+// items nested in it, even though the crate enables them.
+
+#![feature(item_level_checks)]
+
+use std::hint::black_box;
+
+#[overflow_checks(off)]
+fn wrapping_add(x: u8, y: u8) -> u8 {
+    x + y
+}
+
+#[overflow_checks(off)]
+impl Wrapping {
+    fn mul(self, y: u8) -> u8 {
+        self.0 * y
+    }
+}
+
+struct Wrapping(u8);
+
+fn main() {
+    assert_eq!(wrapping_add(black_box(200), black_box(100)), 44);
+    assert_eq!(Wrapping(black_box(16)).mul(black_box(17)), 16);
+
+    let checked = std::panic::catch_unwind(|| black_box(200u8) + black_box(100u8));
+    assert!(checked.is_err());
+}
@@ -0,0 +1,29 @@
+// run-fail
+// error-pattern:thread 'main' panicked
+// error-pattern:attempt to add with overflow
+// compile-flags: -C overflow-checks=off
+// ignore-emscripten no processes
+
+// Overflow checks enabled with `#[overflow_checks(on)]` are kept even though the crate disables
+// them, including in closures nested in the item carrying the attribute.
+
+#![feature(item_level_checks)]
+
+use std::hint::black_box;
+
+fn unchecked(x: u8) -> u8 {
+    x + 200
+}
+
+#[overflow_checks(on)]
+mod checked {
+    pub fn add(x: u8) -> u8 {
+        let add = |y: u8| y + 200;
+        add(x)
+    }
+}
+
+fn main() {
+    assert_eq!(unchecked(black_box(100)), 44);
+    checked::add(black_box(100));
+}
@@ -0,0 +1,5 @@
+#![feature(no_panic)]
+#![crate_type = "lib"]
+
+#[no_panic] //~ ERROR attribute should be applied to a function definition
+pub struct Foo;
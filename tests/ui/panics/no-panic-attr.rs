@@ -0,0 +1,50 @@
+// build-fail
+// compile-flags: -O
+// error-pattern: marked `#[no_panic]`, can panic after optimization
+
+#![feature(no_panic)]
+#![crate_type = "rlib"]
+#![no_std]
+
+// The bounds check is optimized away, so no panic can be reached.
+#[no_panic]
+pub fn in_bounds(a: &[u32; 4], i: usize) -> u32 {
+    a[i & 3]
+}
+
+#[no_panic]
+pub fn out_of_bounds(a: &[u32], i: usize) -> u32 {
+    a[i]
+}
+
+#[no_panic]
+pub fn unwrap(r: Result<u32, u32>) -> u32 {
+    r.unwrap()
+}
+
+#[no_panic]
+pub fn expect(o: Option<u32>) -> u32 {
+    o.expect("no value")
+}
+
+#[no_panic]
+pub fn slice_range(a: &[u32], n: usize) -> &[u32] {
+    &a[..n]
+}
+
+#[inline(never)]
+fn helper(a: &[u32], i: usize) -> u32 {
+    a[i]
+}
+
+// The panic is not in the function itself, but in a function it calls.
+#[no_panic]
+pub fn through_helper(a: &[u32], i: usize) -> u32 {
+    helper(a, i)
+}
+
+// Functions can also be reached through function pointers.
+#[no_panic]
+pub fn helper_pointer() -> fn(&[u32], usize) -> u32 {
+    helper
+}
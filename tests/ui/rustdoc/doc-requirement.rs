@@ -0,0 +1,15 @@
+#![feature(doc_requirement)]
+#![crate_type = "lib"]
+
+#[doc(requirement = "SWR-1")]
+#[doc(requirement = "SWR-2")]
+pub fn valid() {}
+
+#[doc(requirement)] //~ ERROR doc requirement attribute expects a string
+pub fn missing_value() {}
+
+#[doc(requirement = "")] //~ ERROR doc requirement attribute expects a string
+pub fn empty() {}
+
+#[doc(requirement = "SWR 3")] //~ ERROR `SWR 3` is not a valid requirement ID
+pub fn whitespace() {}
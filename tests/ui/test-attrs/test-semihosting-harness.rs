@@ -0,0 +1,52 @@
+// Runs a test binary with the `no_std` harness of `library/test_semihosting` from start to finish
+// in QEMU, including `#[should_panic]` tests and tests that run after a panic.
+//
+// run-fail
+// check-run-results
+// compile-flags: --test
+// needs-qemu-semihosting
+
+#![no_std]
+
+#[test]
+fn a_passing_test() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[test]
+#[should_panic]
+fn b_should_panic() {
+    panic!("expected");
+}
+
+#[test]
+#[should_panic(expected = "the message")]
+fn c_should_panic_with_message() {
+    panic!("panicked with the message");
+}
+
+#[test]
+#[should_panic(expected = "another message")]
+fn d_should_panic_with_wrong_message() {
+    panic!("panicked with the message");
+}
+
+#[test]
+#[should_panic]
+fn e_should_panic_without_panicking() {}
+
+#[test]
+fn f_failing_test() {
+    assert_eq!(1 + 1, 3);
+}
+
+#[test]
+fn g_test_after_a_panic() {
+    assert_eq!(2 + 2, 4);
+}
+
+#[test]
+#[ignore = "not run"]
+fn h_ignored_test() {}
+
+// ferrocene-annotations: um_rustc_test